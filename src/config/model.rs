@@ -24,6 +24,8 @@ pub(crate) struct AppConfig {
     pub security: SecuritySettings,
     #[serde(default)]
     pub updates: UpdatesConfig,
+    pub links: LinksConfig,
+    pub bell: BellConfig,
     pub language: crate::i18n::Locale,
 }
 
@@ -56,6 +58,8 @@ impl FontConfig {
 pub(crate) struct TerminalConfig {
     pub max_scrollback: usize,
     pub cursor_blink_interval_ms: u64,
+    /// Distance between the default tab stops.
+    pub tab_width: usize,
 }
 
 impl Default for TerminalConfig {
@@ -63,6 +67,7 @@ impl Default for TerminalConfig {
         Self {
             max_scrollback: 30_000,
             cursor_blink_interval_ms: 500,
+            tab_width: 8,
         }
     }
 }
@@ -96,6 +101,43 @@ impl Default for LayoutConfig {
     }
 }
 
+/// How Ctrl/Cmd+click opens detected file locations.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct LinksConfig {
+    /// Editor command template, e.g. `code --goto {file}:{line}:{column}`.
+    ///
+    /// `{file}`, `{line}` and `{column}` are substituted (line and column
+    /// default to 1); the file is appended when `{file}` is absent.  Empty
+    /// opens files with the system default application.
+    pub editor_command: String,
+}
+
+/// How the GUI reacts to BEL from a pane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct BellConfig {
+    /// Briefly flash the pane that rang.
+    pub visual_flash: bool,
+    /// Mark background tabs that rang in the tab bar until they are selected.
+    pub tab_badge: bool,
+    /// Ask the window manager for attention when the window is unfocused.
+    pub urgency_hint: bool,
+    /// Play the system alert sound.
+    pub audible: bool,
+}
+
+impl Default for BellConfig {
+    fn default() -> Self {
+        Self {
+            visual_flash: true,
+            tab_badge: true,
+            urgency_hint: true,
+            audible: false,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub(crate) enum FontFamily {
     #[default]
@@ -159,6 +201,19 @@ pub(crate) struct SecuritySettings {
     pub block_title_query: bool,
     pub limit_cursor_jumps: bool,
     pub clear_mouse_on_reset: bool,
+    /// Lets programs post desktop notifications (OSC 9, OSC 777).
+    ///
+    /// Not a protection, so it does not count towards the inferred mode.
+    pub allow_notifications: bool,
+    /// Lets programs read the clipboard with OSC 52 once the user agrees
+    /// for their pane.
+    ///
+    /// Opt-in in every mode, and like `allow_notifications` not part of the
+    /// inferred mode.
+    pub allow_clipboard_read: bool,
+    /// Lets programs show images from local files with the Kitty graphics
+    /// protocol. Opt-in capability toggle, not part of the inferred mode.
+    pub allow_graphics_files: bool,
 }
 
 impl Default for SecuritySettings {
@@ -169,6 +224,9 @@ impl Default for SecuritySettings {
             block_title_query: true,
             limit_cursor_jumps: true,
             clear_mouse_on_reset: true,
+            allow_notifications: true,
+            allow_clipboard_read: false,
+            allow_graphics_files: false,
         }
     }
 }
@@ -177,7 +235,9 @@ impl SecuritySettings {
     /// Converts settings into a runtime `SecurityConfig`.
     ///
     /// When mode is `Disabled`, all checks are turned off regardless of
-    /// individual toggle values. `Standard` and `Custom` use individual toggles.
+    /// individual toggle values. `Standard` and `Custom` use individual
+    /// toggles. Notifications, clipboard reads and graphics files have their
+    /// own settings controls and follow their toggles in every mode.
     pub(crate) fn to_runtime(&self) -> crate::core::SecurityConfig {
         match self.mode {
             SecurityMode::Disabled => crate::core::SecurityConfig {
@@ -185,12 +245,18 @@ impl SecuritySettings {
                 block_title_query: false,
                 limit_cursor_jumps: false,
                 clear_mouse_on_reset: false,
+                allow_notifications: self.allow_notifications,
+                allow_clipboard_read: self.allow_clipboard_read,
+                allow_graphics_files: self.allow_graphics_files,
             },
             SecurityMode::Standard | SecurityMode::Custom => crate::core::SecurityConfig {
                 paste_protection: self.paste_protection,
                 block_title_query: self.block_title_query,
                 limit_cursor_jumps: self.limit_cursor_jumps,
                 clear_mouse_on_reset: self.clear_mouse_on_reset,
+                allow_notifications: self.allow_notifications,
+                allow_clipboard_read: self.allow_clipboard_read,
+                allow_graphics_files: self.allow_graphics_files,
             },
         }
     }
@@ -253,6 +319,7 @@ mod tests {
         assert_eq!(config.font.line_padding, 0);
         assert_eq!(config.terminal.max_scrollback, 30_000);
         assert_eq!(config.terminal.cursor_blink_interval_ms, 500);
+        assert_eq!(config.terminal.tab_width, 8);
         assert_eq!(config.layout.window_padding, 8);
         assert_eq!(config.layout.tab_bar_height, 36);
         assert_eq!(config.layout.pane_inner_padding, 4);
@@ -262,6 +329,14 @@ mod tests {
         assert!(config.security.block_title_query);
         assert!(config.security.limit_cursor_jumps);
         assert!(config.security.clear_mouse_on_reset);
+        assert!(config.security.allow_notifications);
+        assert!(!config.security.allow_clipboard_read);
+        assert!(!config.security.allow_graphics_files);
+        assert!(config.links.editor_command.is_empty());
+        assert!(config.bell.visual_flash);
+        assert!(config.bell.tab_badge);
+        assert!(config.bell.urgency_hint);
+        assert!(!config.bell.audible);
     }
 
     #[test]
@@ -272,12 +347,18 @@ mod tests {
             block_title_query: true,
             limit_cursor_jumps: true,
             clear_mouse_on_reset: true,
+            allow_notifications: false,
+            allow_clipboard_read: false,
+            allow_graphics_files: false,
         };
         let runtime = settings.to_runtime();
         assert!(!runtime.paste_protection);
         assert!(!runtime.block_title_query);
         assert!(!runtime.limit_cursor_jumps);
         assert!(!runtime.clear_mouse_on_reset);
+        assert!(!runtime.allow_notifications);
+        assert!(!runtime.allow_clipboard_read);
+        assert!(!runtime.allow_graphics_files);
     }
 
     #[test]
@@ -288,12 +369,16 @@ mod tests {
             block_title_query: true,
             limit_cursor_jumps: false,
             clear_mouse_on_reset: true,
+            allow_notifications: false,
+            allow_clipboard_read: false,
+            allow_graphics_files: false,
         };
         let runtime = settings.to_runtime();
         assert!(!runtime.paste_protection);
         assert!(runtime.block_title_query);
         assert!(!runtime.limit_cursor_jumps);
         assert!(runtime.clear_mouse_on_reset);
+        assert!(!runtime.allow_notifications);
     }
 
     #[test]
@@ -304,12 +389,16 @@ mod tests {
             block_title_query: false,
             limit_cursor_jumps: true,
             clear_mouse_on_reset: false,
+            allow_notifications: true,
+            allow_clipboard_read: true,
+            allow_graphics_files: true,
         };
         let runtime = settings.to_runtime();
         assert!(runtime.paste_protection);
         assert!(!runtime.block_title_query);
         assert!(runtime.limit_cursor_jumps);
         assert!(!runtime.clear_mouse_on_reset);
+        assert!(runtime.allow_notifications);
     }
 
     #[test]
@@ -326,6 +415,9 @@ mod tests {
             block_title_query: false,
             limit_cursor_jumps: false,
             clear_mouse_on_reset: false,
+            allow_notifications: true,
+            allow_clipboard_read: true,
+            allow_graphics_files: true,
         };
         assert_eq!(s.inferred_mode(), SecurityMode::Disabled);
     }
@@ -338,6 +430,9 @@ mod tests {
             block_title_query: false,
             limit_cursor_jumps: true,
             clear_mouse_on_reset: true,
+            allow_notifications: false,
+            allow_clipboard_read: false,
+            allow_graphics_files: false,
         };
         assert_eq!(s.inferred_mode(), SecurityMode::Custom);
     }
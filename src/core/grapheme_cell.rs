@@ -8,6 +8,33 @@ pub enum UnderlineStyle {
     None,
     Single,
     Double,
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+/// Rarely used cell attributes packed into one byte.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CellEffects(u8);
+
+impl CellEffects {
+    pub const BLINK: u8 = 1;
+    pub const HIDDEN: u8 = 2;
+    pub const OVERLINE: u8 = 4;
+    /// Guarded against selective erase (DECSCA); not a visual attribute.
+    pub const PROTECTED: u8 = 8;
+
+    pub fn contains(self, flag: u8) -> bool {
+        self.0 & flag == flag
+    }
+
+    pub fn set(&mut self, flag: u8, value: bool) {
+        if value {
+            self.0 |= flag;
+        } else {
+            self.0 &= !flag;
+        }
+    }
 }
 
 /// Storage for a grapheme cluster — inline (≤8 UTF-8 bytes) or heap (rare ZWJ sequences).
@@ -66,6 +93,10 @@ pub struct GraphemeCell {
     pub reverse: bool,
     pub underline_style: UnderlineStyle,
     pub strikethrough: bool,
+    /// Blink, conceal and overline flags.
+    pub effects: CellEffects,
+    /// Underline colour set by SGR 58; `None` draws the underline in `fg`.
+    pub underline_color: Option<Color>,
     /// Index into the terminal's hyperlink URL table (0 = no link; n ≥ 1 = URL at index n-1).
     pub hyperlink_id: u16,
 }
@@ -130,6 +161,8 @@ impl Default for GraphemeCell {
             reverse: false,
             underline_style: UnderlineStyle::None,
             strikethrough: false,
+            effects: CellEffects::default(),
+            underline_color: None,
             hyperlink_id: 0,
         }
     }
@@ -174,6 +207,23 @@ mod tests {
         assert!(!styled.is_default());
     }
 
+    #[test]
+    fn cell_stays_compact() {
+        assert!(std::mem::size_of::<GraphemeCell>() <= 48);
+    }
+
+    #[test]
+    fn effects_set_and_clear_flags() {
+        let mut effects = CellEffects::default();
+        effects.set(CellEffects::BLINK, true);
+        effects.set(CellEffects::OVERLINE, true);
+        assert!(effects.contains(CellEffects::BLINK));
+        assert!(!effects.contains(CellEffects::HIDDEN));
+        effects.set(CellEffects::BLINK, false);
+        assert!(!effects.contains(CellEffects::BLINK));
+        assert!(effects.contains(CellEffects::OVERLINE));
+    }
+
     #[test]
     fn from_char_emoji_is_wide() {
         let cell = GraphemeCell::from_char('🚀');
@@ -0,0 +1,133 @@
+use std::rc::Rc;
+
+/// A decoded inline image (Sixel or Kitty graphics) shared by every row strip that displays it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TerminalImage {
+    /// Terminal-assigned id, unique per `Terminal`.
+    pub id: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Row-major RGBA8 pixels (`width * height * 4` bytes, straight alpha).
+    pub pixels: Vec<u8>,
+}
+
+impl TerminalImage {
+    /// Returns the RGBA bytes of source pixel row `y`.
+    pub fn row_pixels(&self, y: u32) -> &[u8] {
+        let stride = self.width as usize * 4;
+        let start = y as usize * stride;
+        &self.pixels[start..start + stride]
+    }
+}
+
+/// One cell-row tall strip of a [`TerminalImage`], attached to a `PageRow`.
+///
+/// Images are anchored by storing a strip on every row they cover rather than
+/// an absolute position, so they scroll into scrollback, are evicted together
+/// with their rows and follow rows through reflow without extra bookkeeping.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageSlice {
+    pub image: Rc<TerminalImage>,
+    /// Grid column of the image's left edge.
+    pub col: usize,
+    /// Number of grid columns the image covers.
+    pub cols: usize,
+    /// Source rectangle shown in this cell row.
+    pub src_x: u32,
+    pub src_y: u32,
+    pub src_width: u32,
+    pub src_height: u32,
+    /// Destination offset from the top-left corner of the anchor cell, in pixels.
+    pub x_offset: u32,
+    pub y_offset: u32,
+    /// Destination size in pixels; differs from the source size when scaled.
+    pub width: u32,
+    pub height: u32,
+    /// Kitty placement id (0 for Sixel and unnamed placements).
+    pub placement_id: u32,
+    /// Stacking order; negative values are drawn beneath the text.
+    pub z_index: i32,
+}
+
+impl ImageSlice {
+    /// Creates an unscaled strip showing source rows `src_y..src_y + src_height`
+    /// at the top-left corner of cell `col`.
+    pub fn new(image: Rc<TerminalImage>, col: usize, cols: usize, src_y: u32, src_height: u32) -> Self {
+        let width = image.width;
+        Self {
+            image,
+            col,
+            cols,
+            src_x: 0,
+            src_y,
+            src_width: width,
+            src_height,
+            x_offset: 0,
+            y_offset: 0,
+            width,
+            height: src_height,
+            placement_id: 0,
+            z_index: 0,
+        }
+    }
+
+    /// Returns `true` when this strip lies entirely within `[col, col + cols)`.
+    pub fn is_covered_by(&self, col: usize, cols: usize) -> bool {
+        self.col >= col && self.col + self.cols <= col + cols
+    }
+
+    /// Returns `true` when this strip covers grid column `col`.
+    pub fn covers_col(&self, col: usize) -> bool {
+        col >= self.col && col < self.col + self.cols
+    }
+
+    /// Returns the RGBA bytes of the source pixel drawn at destination offset
+    /// `(dx, dy)` inside this strip (nearest-neighbour scaling).
+    pub fn source_pixel(&self, dx: u32, dy: u32) -> [u8; 4] {
+        let sx = self.src_x + (dx as u64 * self.src_width as u64 / self.width.max(1) as u64) as u32;
+        let sy = self.src_y + (dy as u64 * self.src_height as u64 / self.height.max(1) as u64) as u32;
+        let sx = sx.min(self.image.width.saturating_sub(1)) as usize;
+        let sy = sy.min(self.image.height.saturating_sub(1));
+        let row = self.image.row_pixels(sy);
+        [row[sx * 4], row[sx * 4 + 1], row[sx * 4 + 2], row[sx * 4 + 3]]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(width: u32, height: u32) -> Rc<TerminalImage> {
+        Rc::new(TerminalImage {
+            id: 1,
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        })
+    }
+
+    #[test]
+    fn row_pixels_returns_one_stride() {
+        let img = image(3, 2);
+        assert_eq!(img.row_pixels(1).len(), 12);
+    }
+
+    #[test]
+    fn slice_covered_by_wider_span() {
+        let slice = ImageSlice::new(image(1, 1), 2, 3, 0, 1);
+        assert!(slice.is_covered_by(1, 5));
+        assert!(!slice.is_covered_by(3, 5));
+    }
+
+    #[test]
+    fn scaled_slice_samples_nearest_source_pixel() {
+        let mut pixels = vec![0; 2 * 2 * 4];
+        pixels[4..8].copy_from_slice(&[1, 2, 3, 4]); // (1, 0)
+        let img = Rc::new(TerminalImage { id: 1, width: 2, height: 2, pixels });
+        let mut slice = ImageSlice::new(img, 0, 1, 0, 2);
+        slice.width = 4;
+        slice.height = 4;
+        assert_eq!(slice.source_pixel(2, 0), [1, 2, 3, 4]);
+        assert_eq!(slice.source_pixel(1, 0), [0, 0, 0, 0]);
+    }
+}
@@ -1,8 +1,10 @@
 mod color;
 mod grapheme_cell;
+mod image;
 mod page;
 mod page_list;
 mod position;
+mod search;
 mod security;
 mod selection;
 mod tracked_pin;
@@ -10,13 +12,17 @@ mod tracked_pin;
 pub mod terminal;
 
 pub use color::Color;
-pub use grapheme_cell::{GraphemeCell, UnderlineStyle};
-pub use page::{Page, PageRow, PAGE_SIZE};
+pub use grapheme_cell::{CellEffects, GraphemeCell, UnderlineStyle};
+pub use image::{ImageSlice, TerminalImage};
+pub use page::{CommandStatus, Page, PageRow, PromptMarkKind, PAGE_SIZE};
 pub use page_list::PageList;
 pub use position::Position;
+pub use search::{DetectedLink, LinkKind, Regex, SearchMatches, link_at, search_screen};
 pub use security::{SecurityConfig, SecurityEventKind, SecurityGuard};
-pub use selection::Selection;
-pub use terminal::{CursorStyle, MouseMode};
+pub use selection::{Selection, SelectionKind};
+pub use terminal::{
+    ClipboardTarget, CursorStyle, KeyboardFlags, MouseEncoding, MouseMode, Notification,
+};
 pub use tracked_pin::{PageCoord, TrackedPin};
 
 #[cfg(test)]
@@ -1,4 +1,7 @@
-use crate::core::GraphemeCell;
+use std::fmt;
+use std::time::Duration;
+
+use crate::core::{GraphemeCell, ImageSlice};
 
 /// A single row of terminal cells.
 #[derive(Clone, Debug)]
@@ -16,6 +19,10 @@ pub struct PageRow {
     /// Intentionally not `pub`: all writes to `cells` must go through
     /// `PageList::viewport_set`, which keeps this field in sync.
     pub(crate) written_cols: usize,
+    /// Inline image strips drawn over this row (see [`ImageSlice`]).
+    pub images: Vec<ImageSlice>,
+    /// Shell-integration (OSC 133) marks placed on this row.
+    pub marks: PromptMarks,
 }
 
 impl PageRow {
@@ -25,6 +32,8 @@ impl PageRow {
             cells: vec![GraphemeCell::default(); cols],
             wrapped: false,
             written_cols: 0,
+            images: Vec::new(),
+            marks: PromptMarks::default(),
         }
     }
 
@@ -34,7 +43,7 @@ impl PageRow {
     /// computed them intentionally), so `written_cols = cells.len()`.
     pub fn from_cells(cells: Vec<GraphemeCell>, wrapped: bool) -> Self {
         let written_cols = cells.len();
-        Self { cells, wrapped, written_cols }
+        Self { cells, wrapped, written_cols, images: Vec::new(), marks: PromptMarks::default() }
     }
 
     /// Resets all cells to default, clears the wrapped flag, and zeros `written_cols`.
@@ -42,7 +51,7 @@ impl PageRow {
         self.clear_with(GraphemeCell::default());
     }
 
-    /// Fills every cell with `blank`, clears the wrapped flag, and zeros `written_cols`.
+    /// Fills every cell with `blank`, clears the wrapped flag, images and marks, and zeros `written_cols`.
     ///
     /// Unlike [`clear`], this preserves the blank's SGR attributes (e.g. background
     /// color set by `\x1b[48;…m` before an erase sequence).
@@ -50,6 +59,93 @@ impl PageRow {
         self.cells.fill(blank);
         self.wrapped = false;
         self.written_cols = 0;
+        self.images.clear();
+        self.marks = PromptMarks::default();
+    }
+}
+
+/// Kind of an OSC 133 semantic prompt mark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PromptMarkKind {
+    /// `A` — the prompt starts.
+    Prompt,
+    /// `B` — the prompt ends and command input starts.
+    Input,
+    /// `C` — the command was submitted; its output starts.
+    Output,
+    /// `D` — the command finished.
+    CommandEnd,
+}
+
+impl PromptMarkKind {
+    pub const ALL: [Self; 4] = [Self::Prompt, Self::Input, Self::Output, Self::CommandEnd];
+}
+
+/// OSC 133 marks on a row, each stored as the column the cursor was at.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PromptMarks {
+    pub prompt: Option<u16>,
+    pub input: Option<u16>,
+    pub output: Option<u16>,
+    pub command_end: Option<u16>,
+    /// Result of the command started from this row's prompt, once it finished.
+    pub status: Option<CommandStatus>,
+}
+
+impl PromptMarks {
+    pub fn get(&self, kind: PromptMarkKind) -> Option<u16> {
+        match kind {
+            PromptMarkKind::Prompt => self.prompt,
+            PromptMarkKind::Input => self.input,
+            PromptMarkKind::Output => self.output,
+            PromptMarkKind::CommandEnd => self.command_end,
+        }
+    }
+
+    pub fn set(&mut self, kind: PromptMarkKind, col: Option<u16>) {
+        let slot = match kind {
+            PromptMarkKind::Prompt => &mut self.prompt,
+            PromptMarkKind::Input => &mut self.input,
+            PromptMarkKind::Output => &mut self.output,
+            PromptMarkKind::CommandEnd => &mut self.command_end,
+        };
+        *slot = col;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How a shell command finished, as reported by OSC 133 `D`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommandStatus {
+    /// Exit code from `D;<code>`; `None` when the shell did not report one.
+    pub exit_code: Option<i32>,
+    /// Time between the `C` and `D` marks.
+    pub duration: Duration,
+}
+
+impl CommandStatus {
+    /// A missing exit code counts as success.
+    pub fn succeeded(&self) -> bool {
+        self.exit_code.is_none_or(|code| code == 0)
+    }
+}
+
+impl fmt::Display for CommandStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.exit_code {
+            Some(code) => write!(f, "exit {code}")?,
+            None => f.write_str("done")?,
+        }
+        let secs = self.duration.as_secs_f64();
+        if secs < 60.0 {
+            write!(f, " in {secs:.1}s")
+        } else {
+            let secs = self.duration.as_secs();
+            write!(f, " in {}m {:02}s", secs / 60, secs % 60)
+        }
     }
 }
 
@@ -164,4 +260,14 @@ mod tests {
         page.row_mut(0).cells[1] = GraphemeCell::from_char('Z');
         assert_eq!(page.row(0).cells[1].grapheme(), "Z");
     }
+
+    #[test]
+    fn command_status_display() {
+        let ok = CommandStatus { exit_code: Some(0), duration: Duration::from_millis(1250) };
+        assert!(ok.succeeded());
+        assert_eq!(ok.to_string(), "exit 0 in 1.2s");
+        let failed = CommandStatus { exit_code: Some(2), duration: Duration::from_secs(125) };
+        assert!(!failed.succeeded());
+        assert_eq!(failed.to_string(), "exit 2 in 2m 05s");
+    }
 }
@@ -1,7 +1,9 @@
 use std::collections::VecDeque;
 
-use crate::core::tracked_pin::{PageCoord, TrackedPin};
-use crate::core::{GraphemeCell, Page, PageRow, PAGE_SIZE};
+use crate::core::tracked_pin::{PageCoord, TrackedPin, WeakPin};
+use crate::core::{
+    CommandStatus, GraphemeCell, ImageSlice, Page, PageRow, PromptMarkKind, PAGE_SIZE,
+};
 
 pub struct PageList {
     /// Scrollback ring buffer: oldest row at the front, newest at the back.
@@ -12,6 +14,19 @@ pub struct PageList {
     viewport_rows: usize,
     cols: usize,
     max_scrollback: usize,
+    /// Pins created by `track_pin`, kept in step with scrollback eviction.
+    tracked_pins: Vec<WeakPin>,
+    /// A cell carrying a hyperlink may have been overwritten or dropped
+    /// since the last `take_hyperlinks_dropped`.
+    hyperlinks_dropped: bool,
+    /// Command statuses of scrollback rows, oldest first, keyed by
+    /// `evicted_rows` plus the row's scrollback index so that eviction does
+    /// not renumber them. Spares `command_statuses` a scan of the scrollback.
+    scrollback_statuses: VecDeque<(usize, CommandStatus)>,
+    /// Rows evicted from the front of the scrollback since it was rebuilt.
+    evicted_rows: usize,
+    /// Bumped whenever a resize or reflow renumbers every row.
+    rebuilds: u64,
 }
 
 impl PageList {
@@ -22,6 +37,11 @@ impl PageList {
             viewport_rows,
             cols,
             max_scrollback,
+            tracked_pins: Vec::new(),
+            hyperlinks_dropped: false,
+            scrollback_statuses: VecDeque::new(),
+            evicted_rows: 0,
+            rebuilds: 0,
         };
         for _ in 0..viewport_rows {
             list.append_row(PageRow::new(cols));
@@ -37,6 +57,18 @@ impl PageList {
         self.viewport_rows
     }
 
+    /// Rows evicted from the front of the scrollback since the last rebuild.
+    /// Adding it to an absolute row gives an index that eviction leaves alone.
+    pub fn evicted_rows(&self) -> usize {
+        self.evicted_rows
+    }
+
+    /// Changes whenever a resize or reflow renumbers the rows, so indices
+    /// taken before it no longer point at the same text.
+    pub fn rebuilds(&self) -> u64 {
+        self.rebuilds
+    }
+
     pub fn scrollback_len(&self) -> usize {
         self.scrollback.len()
     }
@@ -66,6 +98,10 @@ impl PageList {
     pub fn viewport_set(&mut self, row: usize, col: usize, cell: GraphemeCell) {
         let (pi, ri) = Self::vrow_to_page(row);
         let page_row = self.pages[pi].row_mut(ri);
+        let old_link = page_row.cells[col].hyperlink_id;
+        if old_link != 0 && old_link != cell.hyperlink_id {
+            self.hyperlinks_dropped = true;
+        }
         page_row.cells[col] = cell;
         // Track the highest column written so reflow can distinguish real content
         // from unwritten padding at the end of soft-wrapped rows.
@@ -79,7 +115,14 @@ impl PageList {
         self.pages[pi].row(ri)
     }
 
+    /// Mutable access to a viewport row. The caller may overwrite its cells,
+    /// so a row carrying hyperlinks counts as dropping them.
     pub fn viewport_row_mut(&mut self, row: usize) -> &mut PageRow {
+        self.note_dropped_row(row);
+        self.row_mut(row)
+    }
+
+    fn row_mut(&mut self, row: usize) -> &mut PageRow {
         let (pi, ri) = Self::vrow_to_page(row);
         self.pages[pi].row_mut(ri)
     }
@@ -89,7 +132,34 @@ impl PageList {
     }
 
     pub fn viewport_set_wrapped(&mut self, row: usize, wrapped: bool) {
-        self.viewport_row_mut(row).wrapped = wrapped;
+        self.row_mut(row).wrapped = wrapped;
+    }
+
+    // ── Hyperlink bookkeeping ─────────────────────────────────────────────────
+
+    /// Whether a cell carrying a hyperlink may have been overwritten or
+    /// dropped since the last call; the hyperlink table is only worth
+    /// scanning for unused links after that.
+    pub fn take_hyperlinks_dropped(&mut self) -> bool {
+        std::mem::take(&mut self.hyperlinks_dropped)
+    }
+
+    fn note_dropped(&mut self, row: &PageRow) {
+        if row.cells.iter().any(|cell| cell.hyperlink_id != 0) {
+            self.hyperlinks_dropped = true;
+        }
+    }
+
+    fn note_dropped_row(&mut self, row: usize) {
+        if !self.hyperlinks_dropped {
+            let (pi, ri) = Self::vrow_to_page(row);
+            let dropped = self.pages[pi]
+                .row(ri)
+                .cells
+                .iter()
+                .any(|cell| cell.hyperlink_id != 0);
+            self.hyperlinks_dropped = dropped;
+        }
     }
 
     // ── Scrollback access ─────────────────────────────────────────────────────
@@ -101,10 +171,10 @@ impl PageList {
     // ── Copy / scroll within viewport ─────────────────────────────────────────
 
     /// Copy a viewport row to another viewport row.
-    pub fn viewport_copy_row(&mut self, src_row: usize, dst_row: usize) {
+    fn viewport_copy_row(&mut self, src_row: usize, dst_row: usize) {
         debug_assert_ne!(src_row, dst_row);
         let src = self.viewport_row(src_row).clone();
-        *self.viewport_row_mut(dst_row) = src;
+        *self.row_mut(dst_row) = src;
     }
 
     /// Scroll up within a viewport region [top..=bottom].
@@ -116,13 +186,14 @@ impl PageList {
         let evicted = if top == 0 && to_scrollback {
             Some(self.viewport_row(top).clone())
         } else {
+            self.note_dropped_row(top);
             None
         };
         for row in (top + 1)..=bottom {
             self.viewport_copy_row(row, row - 1);
         }
         // Clear the bottom row.
-        self.viewport_row_mut(bottom).clear();
+        self.row_mut(bottom).clear();
         if let Some(evicted_row) = evicted {
             self.push_to_scrollback(evicted_row);
         }
@@ -130,10 +201,11 @@ impl PageList {
 
     /// Scroll down within a viewport region [top..=bottom].
     pub fn scroll_down_region(&mut self, top: usize, bottom: usize) {
+        self.note_dropped_row(bottom);
         for row in (top..bottom).rev() {
             self.viewport_copy_row(row, row + 1);
         }
-        self.viewport_row_mut(top).clear();
+        self.row_mut(top).clear();
     }
 
     /// Push a row into the scrollback ring buffer.  O(1) amortized.
@@ -142,7 +214,19 @@ impl PageList {
     /// new row is inserted — no data is shifted.
     pub fn push_to_scrollback(&mut self, row: PageRow) {
         if self.scrollback.len() >= self.max_scrollback {
-            self.scrollback.pop_front(); // evict oldest — O(1)
+            // evict oldest — O(1)
+            if let Some(evicted) = self.scrollback.pop_front() {
+                self.note_dropped(&evicted);
+                self.evicted_rows += 1;
+                if evicted.marks.status.is_some() {
+                    self.scrollback_statuses.pop_front();
+                }
+            }
+            self.shift_tracked_pins();
+        }
+        if let Some(status) = row.marks.status {
+            let key = self.evicted_rows + self.scrollback.len();
+            self.scrollback_statuses.push_back((key, status));
         }
         self.scrollback.push_back(row); // O(1) amortized
     }
@@ -168,6 +252,158 @@ impl PageList {
         }
     }
 
+    // ── Row iteration ─────────────────────────────────────────────────────────
+
+    /// Visit every row, scrollback first, then the viewport.
+    pub fn for_each_row<F: FnMut(&PageRow)>(&self, mut f: F) {
+        for row in &self.scrollback {
+            f(row);
+        }
+        for vrow in 0..self.viewport_rows {
+            f(self.viewport_row(vrow));
+        }
+    }
+
+    /// Apply a function to every row, scrollback first, then the viewport.
+    pub fn for_each_row_mut<F: FnMut(&mut PageRow)>(&mut self, mut f: F) {
+        for row in &mut self.scrollback {
+            f(row);
+        }
+        for vrow in 0..self.viewport_rows {
+            f(self.row_mut(vrow));
+        }
+    }
+
+    // ── Prompt marks ──────────────────────────────────────────────────────────
+
+    /// Absolute index of the nearest row above `abs_row` carrying an OSC 133 prompt mark.
+    pub fn prompt_before(&self, abs_row: usize) -> Option<usize> {
+        (0..abs_row.min(self.total_rows()))
+            .rev()
+            .find(|&abs| self.abs_row(abs).marks.prompt.is_some())
+    }
+
+    /// Absolute index of the nearest row below `abs_row` carrying an OSC 133 prompt mark.
+    pub fn prompt_after(&self, abs_row: usize) -> Option<usize> {
+        (abs_row.saturating_add(1)..self.total_rows())
+            .find(|&abs| self.abs_row(abs).marks.prompt.is_some())
+    }
+
+    /// Attaches `status` to the prompt row of the command whose output
+    /// started at or before `abs_row`. A prompt that already has a status is
+    /// left alone, so a stray `D` cannot relabel an older command.
+    pub fn record_command_status(&mut self, abs_row: usize, status: CommandStatus) -> bool {
+        let Some(last) = self.total_rows().checked_sub(1) else {
+            return false;
+        };
+        let Some(output_row) = (0..=abs_row.min(last))
+            .rev()
+            .find(|&abs| self.abs_row(abs).marks.output.is_some())
+        else {
+            return false;
+        };
+        let Some(prompt_row) = (0..=output_row)
+            .rev()
+            .find(|&abs| self.abs_row(abs).marks.prompt.is_some())
+        else {
+            return false;
+        };
+        let marks = &mut self.abs_row_mut(prompt_row).marks;
+        if marks.status.is_some() {
+            return false;
+        }
+        marks.status = Some(status);
+        if prompt_row < self.scrollback.len() {
+            let key = self.evicted_rows + prompt_row;
+            let at = self.scrollback_statuses.partition_point(|&(k, _)| k < key);
+            self.scrollback_statuses.insert(at, (key, status));
+        }
+        true
+    }
+
+    /// Finished commands as `(prompt abs_row, status)`, oldest first.
+    ///
+    /// Scrollback rows come from an index kept up to date as rows are pushed
+    /// and evicted; only the viewport is scanned.
+    pub fn command_statuses(&self) -> impl Iterator<Item = (usize, CommandStatus)> + '_ {
+        let scrollback = self
+            .scrollback_statuses
+            .iter()
+            .map(|&(key, status)| (key - self.evicted_rows, status));
+        let viewport = (self.scrollback.len()..self.total_rows())
+            .filter_map(|abs| self.abs_row(abs).marks.status.map(|status| (abs, status)));
+        scrollback.chain(viewport)
+    }
+
+    /// Rebuilds the scrollback status index from the rows themselves.
+    fn index_scrollback_statuses(&mut self) {
+        self.evicted_rows = 0;
+        self.scrollback_statuses = self
+            .scrollback
+            .iter()
+            .enumerate()
+            .filter_map(|(key, row)| row.marks.status.map(|status| (key, status)))
+            .collect();
+    }
+
+    /// Output block (inclusive cell range) of the command whose output
+    /// contains `abs_row`, bounded by its `C` mark and the next mark after it.
+    pub fn command_output_zone(&self, abs_row: usize) -> Option<(PageCoord, PageCoord)> {
+        let start_row = (0..=abs_row.min(self.total_rows().checked_sub(1)?))
+            .rev()
+            .find(|&abs| self.abs_row(abs).marks.output.is_some())?;
+        let zone = self.output_zone_from(start_row)?;
+        (zone.1.abs_row >= abs_row).then_some(zone)
+    }
+
+    /// Output block of the most recent finished command.
+    pub fn last_command_output(&self) -> Option<(PageCoord, PageCoord)> {
+        (0..self.total_rows())
+            .rev()
+            .filter(|&abs| self.abs_row(abs).marks.output.is_some())
+            .find_map(|abs| self.output_zone_from(abs))
+    }
+
+    /// Zone starting at the `C` mark on `start_row` and ending just before the
+    /// next mark of any kind. `None` while the command is still running or
+    /// when it printed nothing.
+    fn output_zone_from(&self, start_row: usize) -> Option<(PageCoord, PageCoord)> {
+        let start_col = usize::from(self.abs_row(start_row).marks.output?);
+        let (end_row, end_col) = (start_row..self.total_rows()).find_map(|abs| {
+            let marks = self.abs_row(abs).marks;
+            PromptMarkKind::ALL
+                .into_iter()
+                .filter(|&kind| abs > start_row || kind != PromptMarkKind::Output)
+                .filter_map(|kind| marks.get(kind).map(usize::from))
+                .filter(|&col| abs > start_row || col >= start_col)
+                .min()
+                .map(|col| (abs, col))
+        })?;
+        let end = if end_col > 0 {
+            PageCoord {
+                abs_row: end_row,
+                col: end_col - 1,
+            }
+        } else {
+            PageCoord {
+                abs_row: end_row.checked_sub(1)?,
+                col: self.cols.saturating_sub(1),
+            }
+        };
+        let start = if start_col >= self.cols {
+            PageCoord {
+                abs_row: start_row + 1,
+                col: 0,
+            }
+        } else {
+            PageCoord {
+                abs_row: start_row,
+                col: start_col,
+            }
+        };
+        ((end.abs_row, end.col) >= (start.abs_row, start.col)).then_some((start, end))
+    }
+
     // ── Internal: append a viewport row at the end ────────────────────────────
 
     pub fn append_row(&mut self, row: PageRow) {
@@ -193,6 +429,8 @@ impl PageList {
     // ── Simple resize ─────────────────────────────────────────────────────────
 
     pub fn simple_resize(&mut self, new_rows: usize, new_cols: usize) {
+        self.invalidate_tracked_pins();
+        self.hyperlinks_dropped = true;
         if new_rows > self.viewport_rows {
             let extra = new_rows - self.viewport_rows;
             self.append_blank_rows(extra, new_cols);
@@ -214,9 +452,10 @@ impl PageList {
         }
     }
 
-    // ── Abs-row access (for reflow) ───────────────────────────────────────────
+    // ── Abs-row access ───────────────────────────────────────────────────────
 
-    fn abs_row(&self, abs: usize) -> &PageRow {
+    /// Row at absolute index `abs` (scrollback first, then viewport).
+    pub fn abs_row(&self, abs: usize) -> &PageRow {
         let sb_len = self.scrollback.len();
         if abs < sb_len {
             &self.scrollback[abs]
@@ -227,6 +466,15 @@ impl PageList {
         }
     }
 
+    fn abs_row_mut(&mut self, abs: usize) -> &mut PageRow {
+        let sb_len = self.scrollback.len();
+        if abs < sb_len {
+            &mut self.scrollback[abs]
+        } else {
+            self.row_mut(abs - sb_len)
+        }
+    }
+
     // ── Pin management ────────────────────────────────────────────────────────
 
     /// Creates a new pin at the given coordinate.
@@ -235,6 +483,45 @@ impl PageList {
         TrackedPin::new(coord)
     }
 
+    /// Creates a pin that follows its row as old scrollback is evicted.
+    ///
+    /// The pin is invalidated when its row is evicted, when the buffer is
+    /// resized or reflowed, or when the list is dropped.
+    pub fn track_pin(&mut self, coord: PageCoord) -> TrackedPin {
+        // Forget dropped pins before the list grows, so repeated searches
+        // without eviction do not accumulate dead handles.
+        if self.tracked_pins.len() == self.tracked_pins.capacity() {
+            self.tracked_pins.retain(|weak| weak.upgrade().is_some());
+        }
+        let pin = TrackedPin::new(coord);
+        self.tracked_pins.push(pin.downgrade());
+        pin
+    }
+
+    /// Moves tracked pins up one row after the oldest scrollback row was
+    /// evicted, dropping pins that pointed at it.
+    fn shift_tracked_pins(&mut self) {
+        self.tracked_pins.retain(|weak| {
+            let Some(pin) = weak.upgrade() else {
+                return false;
+            };
+            let coord = pin.coord();
+            if coord.abs_row == 0 {
+                pin.invalidate();
+                return false;
+            }
+            pin.set_abs_row(coord.abs_row - 1);
+            true
+        });
+    }
+
+    fn invalidate_tracked_pins(&mut self) {
+        self.rebuilds += 1;
+        for pin in self.tracked_pins.drain(..).filter_map(|weak| weak.upgrade()) {
+            pin.invalidate();
+        }
+    }
+
     // ── Reflow ────────────────────────────────────────────────────────────────
 
     /// Reflow all content (scrollback + viewport) to new dimensions.
@@ -243,7 +530,11 @@ impl PageList {
     /// reflowed buffer.  The column is preserved so the terminal stays in sync
     /// with the shell's internal cursor tracking (readline / zsh compute relative
     /// movements from their own state; forcing col=0 would desynchronise them).
+    ///
+    /// Pins from `track_pin` are invalidated; their owners search again.
     pub fn reflow(&mut self, new_rows: usize, new_cols: usize, cursor_pin: &TrackedPin) {
+        self.invalidate_tracked_pins();
+        self.hyperlinks_dropped = true;
         // Save the cursor's physical row before rebuilding the buffer.
         // It is placed at the bottom of the rebuilt viewport (truncated to
         // new_cols) so the active input line stays visible during resize —
@@ -297,6 +588,9 @@ impl PageList {
         let cursor_col = cursor_pin.coord().col;
         let mut lines: Vec<LogicalLine> = Vec::new();
         let mut current: Vec<GraphemeCell> = Vec::new();
+        let mut current_images: Vec<ImageSlice> = Vec::new();
+        let mut current_marks: Vec<(PromptMarkKind, usize)> = Vec::new();
+        let mut current_status: Option<CommandStatus> = None;
         let mut cursor_info: Option<CursorLineInfo> = None;
         let mut cursor_in_current = false;
         let mut cursor_col_in_line = 0usize;
@@ -319,6 +613,15 @@ impl PageList {
                 // trailing-default trimming at the logical-line level.
                 current.extend_from_slice(&row.cells);
             }
+            current_images.extend(row.images.iter().cloned());
+            if !row.marks.is_empty() {
+                for kind in PromptMarkKind::ALL {
+                    if let Some(col) = row.marks.get(kind) {
+                        current_marks.push((kind, line_start + col as usize));
+                    }
+                }
+                current_status = current_status.or(row.marks.status);
+            }
             if abs_row == cursor_abs {
                 cursor_in_current = true;
                 // Record the cursor's column offset within this logical line so
@@ -337,6 +640,9 @@ impl PageList {
                 lines.push(LogicalLine {
                     cells: std::mem::take(&mut current),
                     min_len: current_min_len,
+                    images: std::mem::take(&mut current_images),
+                    marks: std::mem::take(&mut current_marks),
+                    status: current_status.take(),
                 });
                 current_min_len = 0;
             }
@@ -348,7 +654,13 @@ impl PageList {
                     col_in_line: cursor_col_in_line,
                 });
             }
-            lines.push(LogicalLine { cells: current, min_len: current_min_len });
+            lines.push(LogicalLine {
+                cells: current,
+                min_len: current_min_len,
+                images: current_images,
+                marks: current_marks,
+                status: current_status,
+            });
         }
         (lines, cursor_info)
     }
@@ -391,6 +703,7 @@ impl PageList {
         for row in rows_iter.by_ref().take(scrollback_count) {
             self.scrollback.push_back(row);
         }
+        self.index_scrollback_statuses();
 
         // Viewport rows: take exactly new_rows rows.
         // Using .take(new_rows) ensures rows that follow the cursor (e.g. blank
@@ -405,11 +718,25 @@ impl PageList {
     }
 }
 
+impl Drop for PageList {
+    fn drop(&mut self) {
+        self.invalidate_tracked_pins();
+    }
+}
+
 // ── Reflow helpers ────────────────────────────────────────────────────────────
 
 struct LogicalLine {
     cells: Vec<GraphemeCell>,
     min_len: usize,
+    /// Image strips from every physical row of the line; re-attached to the
+    /// first rewrapped row so images stay with the line they were drawn on.
+    images: Vec<ImageSlice>,
+    /// OSC 133 marks as offsets into the logical line; re-placed on the
+    /// rewrapped row that holds the same offset.
+    marks: Vec<(PromptMarkKind, usize)>,
+    /// Command status; re-attached to the row that receives the prompt mark.
+    status: Option<CommandStatus>,
 }
 
 /// Cursor location within the logical-line representation produced by
@@ -427,10 +754,14 @@ fn rewrap_lines(lines: &[LogicalLine], new_cols: usize) -> Vec<PageRow> {
         let len = line_content_len(line);
 
         if len == 0 {
-            rewrapped.push(PageRow::new(new_cols));
+            let mut row = PageRow::new(new_cols);
+            row.images = line.images.clone();
+            place_marks(std::slice::from_mut(&mut row), line, new_cols);
+            rewrapped.push(row);
             continue;
         }
 
+        let first_row = rewrapped.len();
         let content = &line.cells[..len];
         let mut pos = 0;
 
@@ -484,10 +815,35 @@ fn rewrap_lines(lines: &[LogicalLine], new_cols: usize) -> Vec<PageRow> {
                 pos += 1;
             }
         }
+        if let Some(row) = rewrapped.get_mut(first_row) {
+            row.images = line.images.clone();
+        }
+        place_marks(&mut rewrapped[first_row..], line, new_cols);
     }
     rewrapped
 }
 
+/// Puts logical-line mark offsets onto the rows `line_rows` the line was rewrapped into.
+fn place_marks(line_rows: &mut [PageRow], line: &LogicalLine, new_cols: usize) {
+    if line_rows.is_empty() || new_cols == 0 {
+        return;
+    }
+    let last = line_rows.len() - 1;
+    let mut status_row = 0;
+    for &(kind, offset) in &line.marks {
+        let (row, col) = if offset / new_cols > last {
+            (last, new_cols - 1)
+        } else {
+            (offset / new_cols, offset % new_cols)
+        };
+        line_rows[row].marks.set(kind, Some(col.min(u16::MAX as usize) as u16));
+        if kind == PromptMarkKind::Prompt {
+            status_row = row;
+        }
+    }
+    line_rows[status_row].marks.status = line.status;
+}
+
 fn line_content_len(line: &LogicalLine) -> usize {
     let len = line
         .cells
@@ -559,6 +915,27 @@ mod tests {
         assert_eq!(list.scrollback_row(2).cells[0].grapheme(), "E");
     }
 
+    #[test]
+    fn command_statuses_follow_scrollback_eviction() {
+        let status = |code| CommandStatus {
+            exit_code: Some(code),
+            duration: Default::default(),
+        };
+        let mut list = PageList::new(2, 5, 3);
+        for code in 0..5 {
+            let mut row = PageRow::new(5);
+            row.marks.status = (code % 2 == 0).then(|| status(code));
+            list.push_to_scrollback(row);
+        }
+        list.viewport_row_mut(1).marks.status = Some(status(9));
+        let codes: Vec<_> = list
+            .command_statuses()
+            .map(|(abs, status)| (abs, status.exit_code))
+            .collect();
+        // Rows 0 and 1 were evicted; the rest moved up by two.
+        assert_eq!(codes, vec![(0, Some(2)), (2, Some(4)), (4, Some(9))]);
+    }
+
     #[test]
     fn scroll_up_region_pushes_to_scrollback() {
         let mut list = PageList::new(3, 5, 100);
@@ -814,4 +1191,92 @@ mod tests {
             "cursor must be at last viewport row"
         );
     }
+
+    #[test]
+    fn reflow_keeps_image_strips_on_their_line() {
+        use crate::core::{ImageSlice, TerminalImage};
+        use std::rc::Rc;
+
+        let mut list = PageList::new(3, 10, 100);
+        fill_viewport_row(&mut list, 0, "ABCDEFGH");
+        let image = Rc::new(TerminalImage { id: 1, width: 2, height: 2, pixels: vec![0; 16] });
+        list.viewport_row_mut(1).images.push(ImageSlice::new(image, 0, 1, 0, 2));
+        let cursor_abs = list.viewport_start_abs() + 2;
+        let pin = PageList::pin_at(PageCoord { abs_row: cursor_abs, col: 0 });
+        list.reflow(4, 4, &pin);
+        // "ABCDEFGH" now spans two rows; the image row follows it.
+        assert!(list.viewport_row(0).images.is_empty());
+        assert!(list.viewport_row(1).images.is_empty());
+        assert_eq!(list.viewport_row(2).images.len(), 1);
+    }
+
+    #[test]
+    fn reflow_keeps_prompt_marks_on_their_line() {
+        let mut list = PageList::new(4, 10, 100);
+        fill_viewport_row(&mut list, 0, "$ ls");
+        list.viewport_row_mut(0).marks.prompt = Some(0);
+        list.viewport_row_mut(0).marks.input = Some(2);
+        let status = CommandStatus { exit_code: Some(1), duration: Default::default() };
+        list.viewport_row_mut(0).marks.status = Some(status);
+        fill_viewport_row(&mut list, 1, "ABCDEFGH");
+        list.viewport_row_mut(1).marks.output = Some(0);
+        list.viewport_row_mut(2).marks.command_end = Some(0);
+        let cursor_abs = list.viewport_start_abs() + 3;
+        let pin = PageList::pin_at(PageCoord { abs_row: cursor_abs, col: 0 });
+        list.reflow(5, 4, &pin);
+        assert_eq!(list.viewport_row(0).marks.prompt, Some(0));
+        assert_eq!(list.viewport_row(0).marks.input, Some(2));
+        assert_eq!(list.viewport_row(0).marks.status, Some(status));
+        assert_eq!(list.viewport_row(1).marks.output, Some(0));
+        assert!(list.viewport_row(2).marks.is_empty());
+        assert_eq!(list.viewport_row(3).marks.command_end, Some(0));
+    }
+
+    #[test]
+    fn prompt_lookup_finds_nearest_marked_rows() {
+        let mut list = PageList::new(6, 10, 100);
+        list.viewport_row_mut(1).marks.prompt = Some(0);
+        list.viewport_row_mut(4).marks.prompt = Some(0);
+        let start = list.viewport_start_abs();
+        assert_eq!(list.prompt_before(start + 4), Some(start + 1));
+        assert_eq!(list.prompt_before(start + 1), None);
+        assert_eq!(list.prompt_after(start + 1), Some(start + 4));
+        assert_eq!(list.prompt_after(start + 4), None);
+    }
+
+    fn coord(abs_row: usize, col: usize) -> PageCoord {
+        PageCoord { abs_row, col }
+    }
+
+    #[test]
+    fn output_zone_spans_from_output_mark_to_command_end() {
+        let mut list = PageList::new(8, 10, 100);
+        list.viewport_row_mut(0).marks.prompt = Some(0);
+        list.viewport_row_mut(0).marks.input = Some(2);
+        list.viewport_row_mut(1).marks.output = Some(0);
+        list.viewport_row_mut(4).marks.command_end = Some(0);
+        list.viewport_row_mut(4).marks.prompt = Some(0);
+        list.viewport_row_mut(4).marks.input = Some(2);
+        let zone = Some((coord(1, 0), coord(3, 9)));
+        assert_eq!(list.command_output_zone(2), zone);
+        assert_eq!(list.command_output_zone(3), zone);
+        assert_eq!(list.command_output_zone(0), None);
+        assert_eq!(list.command_output_zone(4), None);
+        assert_eq!(list.last_command_output(), zone);
+    }
+
+    #[test]
+    fn running_or_silent_commands_have_no_output_zone() {
+        let mut list = PageList::new(8, 10, 100);
+        list.viewport_row_mut(1).marks.output = Some(0);
+        list.viewport_row_mut(3).marks.command_end = Some(4);
+        // Silent command: output and end marks at the same cell.
+        list.viewport_row_mut(4).marks.output = Some(2);
+        list.viewport_row_mut(4).marks.command_end = Some(2);
+        // Still running.
+        list.viewport_row_mut(6).marks.output = Some(0);
+        assert_eq!(list.command_output_zone(6), None);
+        assert_eq!(list.command_output_zone(4), None);
+        assert_eq!(list.last_command_output(), Some((coord(1, 0), coord(3, 3))));
+    }
 }
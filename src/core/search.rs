@@ -0,0 +1,342 @@
+//! Scrollback search: finds pattern matches across the whole buffer,
+//! treating soft-wrapped rows as one logical line.
+
+mod links;
+mod regex;
+
+pub use links::{DetectedLink, LinkKind, link_at};
+pub use regex::Regex;
+
+use regex::Scratch;
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::core::{PageCoord, PageList, PageRow};
+
+/// One match, from its first cell to its last cell (inclusive).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub start: PageCoord,
+    pub end: PageCoord,
+}
+
+/// The matches of one search, oldest first.
+///
+/// Rows are stored offset by the buffer's eviction count at search time, so
+/// output that evicts scrollback rows moves every match at once instead of
+/// one pin per match having to be shifted.
+#[derive(Clone, Debug, Default)]
+pub struct SearchMatches {
+    matches: VecDeque<SearchMatch>,
+    /// `PageList::rebuilds` when the search ran.
+    rebuilds: u64,
+}
+
+impl SearchMatches {
+    pub fn len(&self) -> usize {
+        self.matches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matches.is_empty()
+    }
+
+    /// `true` once `screen` was resized or reflowed after the search; none of
+    /// the matches point at their text any more.
+    pub fn is_stale(&self, screen: &PageList) -> bool {
+        self.rebuilds != screen.rebuilds()
+    }
+
+    /// Drops the matches whose first row has been evicted and returns how
+    /// many were dropped. Indices of the remaining matches drop by as much.
+    pub fn prune(&mut self, screen: &PageList) -> usize {
+        if self.is_stale(screen) {
+            let dropped = self.matches.len();
+            self.matches.clear();
+            return dropped;
+        }
+        let dropped = self
+            .matches
+            .partition_point(|m| m.start.abs_row < screen.evicted_rows());
+        self.matches.drain(..dropped);
+        dropped
+    }
+
+    /// The match at `index` in current coordinates, `None` if its text has
+    /// been evicted or the buffer rebuilt.
+    pub fn get(&self, index: usize, screen: &PageList) -> Option<SearchMatch> {
+        if self.is_stale(screen) {
+            return None;
+        }
+        Self::locate(self.matches.get(index)?, screen.evicted_rows())
+    }
+
+    /// Matches still in the buffer with their indices, oldest first.
+    pub fn iter<'a>(
+        &'a self,
+        screen: &PageList,
+    ) -> impl Iterator<Item = (usize, SearchMatch)> + 'a {
+        self.in_rows(screen, 0..usize::MAX)
+    }
+
+    /// Matches touching the absolute rows `rows`, with their indices.
+    pub fn in_rows<'a>(
+        &'a self,
+        screen: &PageList,
+        rows: Range<usize>,
+    ) -> impl Iterator<Item = (usize, SearchMatch)> + 'a {
+        let evicted = screen.evicted_rows();
+        // Matches never overlap, so their ends are sorted like their starts.
+        let first = if self.is_stale(screen) {
+            self.matches.len()
+        } else {
+            self.matches
+                .partition_point(|m| m.end.abs_row < rows.start.saturating_add(evicted))
+        };
+        let end = rows.end.saturating_add(evicted);
+        self.matches
+            .range(first..)
+            .take_while(move |m| m.start.abs_row < end)
+            .enumerate()
+            .filter_map(move |(i, m)| Some((first + i, Self::locate(m, evicted)?)))
+    }
+
+    fn locate(m: &SearchMatch, evicted: usize) -> Option<SearchMatch> {
+        let row = |coord: PageCoord| PageCoord {
+            abs_row: coord.abs_row - evicted,
+            col: coord.col,
+        };
+        (m.start.abs_row >= evicted).then(|| SearchMatch {
+            start: row(m.start),
+            end: row(m.end),
+        })
+    }
+}
+
+/// Returns every non-empty match of `regex` in `screen`, oldest first.
+///
+/// Matches never span a hard line break; a match that continues onto a
+/// soft-wrapped row ends on that row.
+pub fn search_screen(screen: &PageList, regex: &Regex) -> SearchMatches {
+    let mut spans = Vec::new();
+    let mut line = LineText::default();
+    for abs_row in 0..screen.total_rows() {
+        let row = screen.abs_row(abs_row);
+        line.push_row(row, abs_row);
+        if !row.wrapped {
+            line.find_all(regex, &mut spans);
+            line.clear();
+        }
+    }
+    line.find_all(regex, &mut spans);
+
+    let evicted = screen.evicted_rows();
+    let row = |coord: PageCoord| PageCoord {
+        abs_row: coord.abs_row + evicted,
+        col: coord.col,
+    };
+    SearchMatches {
+        matches: spans
+            .into_iter()
+            .map(|(start, end)| SearchMatch {
+                start: row(start),
+                end: row(end),
+            })
+            .collect(),
+        rebuilds: screen.rebuilds(),
+    }
+}
+
+/// Text of one logical line with the cell each character came from.
+#[derive(Default)]
+struct LineText {
+    chars: Vec<char>,
+    /// First and last column of the source cell, per character.
+    cells: Vec<(usize, usize, usize)>,
+    /// Regex buffers, reused for every line of a search.
+    scratch: Scratch,
+}
+
+impl LineText {
+    fn push_row(&mut self, row: &PageRow, abs_row: usize) {
+        // Soft-wrapped rows end at the last written column; unwritten padding
+        // (left when a wide char moved to the next row) is not text.
+        let len = if row.wrapped {
+            row.written_cols.min(row.cells.len())
+        } else {
+            row.cells.len()
+        };
+        for (col, cell) in row.cells[..len].iter().enumerate() {
+            if cell.width == 0 {
+                continue; // spacer behind a wide char
+            }
+            let last_col = col + usize::from(cell.width) - 1;
+            for ch in cell.grapheme().chars() {
+                self.chars.push(ch);
+                self.cells.push((abs_row, col, last_col));
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.chars.clear();
+        self.cells.clear();
+    }
+
+    fn find_all(&mut self, regex: &Regex, spans: &mut Vec<(PageCoord, PageCoord)>) {
+        spans.extend(self.ranges(regex).into_iter().map(|range| self.span(&range)));
+    }
+
+    /// Character ranges of the non-empty matches of `regex`, left to right.
+    fn ranges(&mut self, regex: &Regex) -> Vec<Range<usize>> {
+        // Trailing blanks are row padding, not content.
+        let len = self.chars.len() - self.chars.iter().rev().take_while(|c| **c == ' ').count();
+        let text = &self.chars[..len];
+        let mut ranges = Vec::new();
+        let mut at = 0;
+        while at < len {
+            let Some(range) = regex.find_at(text, at, &mut self.scratch) else {
+                break;
+            };
+            if range.is_empty() {
+                at = range.start + 1;
+                continue;
+            }
+            at = range.end;
+            ranges.push(range);
+        }
+        ranges
+    }
+
+    /// First and last cell of a non-empty character range.
+    fn span(&self, range: &Range<usize>) -> (PageCoord, PageCoord) {
+        let (start_row, start_col, _) = self.cells[range.start];
+        let (end_row, _, end_col) = self.cells[range.end - 1];
+        (
+            PageCoord {
+                abs_row: start_row,
+                col: start_col,
+            },
+            PageCoord {
+                abs_row: end_row,
+                col: end_col,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GraphemeCell;
+
+    fn write_row(screen: &mut PageList, row: usize, text: &str, wrapped: bool) {
+        for (col, ch) in text.chars().enumerate() {
+            screen.viewport_set(row, col, GraphemeCell::from_char(ch));
+        }
+        let row = screen.viewport_row_mut(row);
+        row.written_cols = text.chars().count();
+        row.wrapped = wrapped;
+    }
+
+    fn ends(screen: &PageList, matches: &SearchMatches) -> Vec<((usize, usize), (usize, usize))> {
+        matches
+            .iter(screen)
+            .map(|(_, m)| ((m.start.abs_row, m.start.col), (m.end.abs_row, m.end.col)))
+            .collect()
+    }
+
+    #[test]
+    fn finds_matches_across_soft_wrapped_rows() {
+        let mut screen = PageList::new(3, 5, 10);
+        write_row(&mut screen, 0, "hello", true);
+        write_row(&mut screen, 1, " worl", true);
+        write_row(&mut screen, 2, "d", false);
+        let matches = search_screen(&screen, &Regex::literal("world", true));
+        assert_eq!(ends(&screen, &matches), vec![((1, 1), (2, 0))]);
+    }
+
+    #[test]
+    fn hard_line_breaks_split_matches() {
+        let mut screen = PageList::new(2, 5, 10);
+        write_row(&mut screen, 0, "ab", false);
+        write_row(&mut screen, 1, "cab", false);
+        let matches = search_screen(&screen, &Regex::new("a?b", true).unwrap());
+        assert_eq!(
+            ends(&screen, &matches),
+            vec![((0, 0), (0, 1)), ((1, 1), (1, 2))]
+        );
+        assert!(search_screen(&screen, &Regex::literal("bc", true)).is_empty());
+        assert!(search_screen(&screen, &Regex::new("b$", true).unwrap()).len() == 2);
+    }
+
+    #[test]
+    fn wide_chars_cover_both_columns() {
+        let mut screen = PageList::new(1, 6, 10);
+        screen.viewport_set(0, 0, GraphemeCell::from_char('日'));
+        screen.viewport_set(0, 1, GraphemeCell::spacer());
+        screen.viewport_set(0, 2, GraphemeCell::from_char('x'));
+        let matches = search_screen(&screen, &Regex::literal("日x", true));
+        assert_eq!(ends(&screen, &matches), vec![((0, 0), (0, 2))]);
+    }
+
+    #[test]
+    fn matches_follow_their_text_through_eviction() {
+        let mut screen = PageList::new(2, 5, 2);
+        write_row(&mut screen, 0, "one", false);
+        screen.scroll_up_region(0, 1, true);
+        write_row(&mut screen, 1, "two", false);
+        let mut matches = search_screen(&screen, &Regex::new("one|two", true).unwrap());
+        assert_eq!(
+            ends(&screen, &matches),
+            vec![((0, 0), (0, 2)), ((2, 0), (2, 2))]
+        );
+
+        // Scrollback holds two rows; the third push evicts the `one` row.
+        screen.scroll_up_region(0, 1, true);
+        screen.scroll_up_region(0, 1, true);
+        assert_eq!(matches.get(0, &screen), None);
+        assert_eq!(ends(&screen, &matches), vec![((1, 0), (1, 2))]);
+        assert_eq!(matches.prune(&screen), 1);
+        assert_eq!(ends(&screen, &matches), vec![((1, 0), (1, 2))]);
+    }
+
+    #[test]
+    fn searches_a_full_scrollback() {
+        let mut screen = PageList::new(2, 80, 10_000);
+        for i in 0..12_000 {
+            write_row(&mut screen, 1, &format!("line {i} ok"), false);
+            screen.scroll_up_region(0, 1, true);
+        }
+        // The scrollback and the top viewport row hold lines 1999..12_000.
+        let matches = search_screen(&screen, &Regex::new(r"LINE \d*7 OK", false).unwrap());
+        assert_eq!(matches.len(), 1000);
+        let last = matches.get(matches.len() - 1, &screen).unwrap();
+        assert_eq!((last.start.abs_row, last.end.col), (9_998, 12));
+    }
+
+    #[test]
+    fn in_rows_yields_only_matches_touching_the_rows() {
+        let mut screen = PageList::new(4, 5, 10);
+        write_row(&mut screen, 0, "ab", false);
+        write_row(&mut screen, 1, "ab a", true);
+        write_row(&mut screen, 2, "b", false);
+        write_row(&mut screen, 3, "x", false);
+        let matches = search_screen(&screen, &Regex::literal("ab", true));
+        let indices: Vec<_> = matches.in_rows(&screen, 2..3).map(|(i, _)| i).collect();
+        assert_eq!(indices, vec![2]);
+        assert_eq!(matches.in_rows(&screen, 3..4).count(), 0);
+    }
+
+    #[test]
+    fn reflow_invalidates_matches() {
+        let mut screen = PageList::new(2, 5, 10);
+        write_row(&mut screen, 0, "abc", false);
+        let matches = search_screen(&screen, &Regex::literal("b", true));
+        let cursor = PageList::pin_at(PageCoord { abs_row: 1, col: 0 });
+        screen.reflow(2, 4, &cursor);
+        assert!(matches.is_stale(&screen));
+        assert_eq!(matches.iter(&screen).count(), 0);
+    }
+}
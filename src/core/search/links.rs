@@ -0,0 +1,252 @@
+//! Link detection in screen text: URLs, `path:line:column` locations and git
+//! commit hashes, including ones continued on soft-wrapped rows.
+
+use std::sync::OnceLock;
+
+use super::{LineText, Regex};
+use crate::core::{PageCoord, PageList};
+
+/// Rows followed in each direction along a soft-wrapped line.
+const MAX_WRAPPED_ROWS: usize = 32;
+
+/// What a detected link points at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkKind {
+    /// A `scheme://` URL.
+    Url,
+    /// A file path, optionally followed by `:line` and `:column`.
+    File {
+        path: String,
+        line: Option<u32>,
+        column: Option<u32>,
+    },
+    /// An abbreviated or full git commit hash.
+    Commit,
+}
+
+/// A link found in the screen text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetectedLink {
+    pub kind: LinkKind,
+    /// The link as displayed, without trailing punctuation.
+    pub text: String,
+    /// First and last cell of the link (inclusive).
+    pub start: PageCoord,
+    pub end: PageCoord,
+}
+
+impl DetectedLink {
+    /// Whether the cell at `abs_row`/`col` is part of the link.
+    pub fn contains(&self, abs_row: usize, col: usize) -> bool {
+        let cell = (abs_row, col);
+        cell >= (self.start.abs_row, self.start.col) && cell <= (self.end.abs_row, self.end.col)
+    }
+}
+
+/// Accepts or rejects a pattern match, returning the part that is the link.
+type Classifier = fn(&str) -> Option<(LinkKind, &str)>;
+
+/// Detection patterns, tried in order; the first one covering the cell wins.
+struct Patterns {
+    url: Regex,
+    file: Regex,
+    commit: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        let compile = |pattern| Regex::new(pattern, true).expect("built-in link pattern");
+        Patterns {
+            url: compile(r#"(?:https?|ftp|file)://[^\s<>"'`]+"#),
+            file: compile(r"(?:~|\.{1,2})?/?[\w.+@\-]+(?:/[\w.+@\-]+)*(?::\d+(?::\d+)?)?"),
+            commit: compile(r"\b[0-9a-f]{7,40}\b"),
+        }
+    })
+}
+
+/// Returns the link covering the cell at `at`, if any.
+pub fn link_at(screen: &PageList, at: PageCoord) -> Option<DetectedLink> {
+    let total = screen.total_rows();
+    if at.abs_row >= total {
+        return None;
+    }
+    let mut first = at.abs_row;
+    while first > 0 && at.abs_row - first < MAX_WRAPPED_ROWS && screen.abs_row(first - 1).wrapped {
+        first -= 1;
+    }
+    let mut last = at.abs_row;
+    while last + 1 < total && last - at.abs_row < MAX_WRAPPED_ROWS && screen.abs_row(last).wrapped {
+        last += 1;
+    }
+    let mut line = LineText::default();
+    for abs_row in first..=last {
+        line.push_row(screen.abs_row(abs_row), abs_row);
+    }
+
+    let patterns = patterns();
+    let classifiers: [(&Regex, Classifier); 3] = [
+        (&patterns.url, classify_url),
+        (&patterns.file, classify_file),
+        (&patterns.commit, classify_commit),
+    ];
+    for (regex, classify) in classifiers {
+        for range in line.ranges(regex) {
+            let text: String = line.chars[range.clone()].iter().collect();
+            let Some((kind, kept)) = classify(&text) else {
+                continue;
+            };
+            let range = range.start..range.start + kept.chars().count();
+            let (start, end) = line.span(&range);
+            let link = DetectedLink {
+                kind,
+                text: kept.to_owned(),
+                start,
+                end,
+            };
+            if link.contains(at.abs_row, at.col) {
+                return Some(link);
+            }
+        }
+    }
+    None
+}
+
+/// Drops sentence punctuation and closing brackets the URL did not open.
+fn classify_url(text: &str) -> Option<(LinkKind, &str)> {
+    let unopened = |url: &str, open, close| url.matches(open).count() < url.matches(close).count();
+    let mut url = text;
+    while let Some(last) = url.chars().last() {
+        let trailing = match last {
+            '.' | ',' | ':' | ';' | '!' | '?' => true,
+            ')' => unopened(url, '(', ')'),
+            ']' => unopened(url, '[', ']'),
+            '}' => unopened(url, '{', '}'),
+            _ => false,
+        };
+        if !trailing {
+            break;
+        }
+        url = &url[..url.len() - 1];
+    }
+    let (_, rest) = url.split_once("://")?;
+    (!rest.is_empty()).then_some((LinkKind::Url, url))
+}
+
+/// Accepts paths with a directory part, and bare file names with a line.
+fn classify_file(text: &str) -> Option<(LinkKind, &str)> {
+    let mut parts = text.splitn(3, ':');
+    let path = parts.next()?;
+    let line = parts.next().and_then(|n| n.parse().ok());
+    let column = parts.next().and_then(|n| n.parse().ok());
+    let (path, kept) = if line.is_some() {
+        (path, text)
+    } else {
+        let trimmed = text.trim_end_matches('.');
+        (trimmed, trimmed)
+    };
+    let has_name = path.chars().any(|c| c.is_alphabetic());
+    let plausible = path.contains('/') || (line.is_some() && path.contains('.'));
+    (has_name && plausible && path != "/").then(|| {
+        let kind = LinkKind::File {
+            path: path.to_owned(),
+            line,
+            column,
+        };
+        (kind, kept)
+    })
+}
+
+/// Hex words need both a digit and a letter so plain numbers and words
+/// such as `deadbeef` or `1234567` are not taken for hashes.
+fn classify_commit(text: &str) -> Option<(LinkKind, &str)> {
+    let has_digit = text.chars().any(|c| c.is_ascii_digit());
+    let has_letter = text.chars().any(|c| c.is_ascii_alphabetic());
+    (has_digit && has_letter).then_some((LinkKind::Commit, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GraphemeCell;
+
+    fn screen_with_rows(cols: usize, rows: &[(&str, bool)]) -> PageList {
+        let mut screen = PageList::new(rows.len(), cols, 10);
+        for (row, (text, wrapped)) in rows.iter().enumerate() {
+            for (col, ch) in text.chars().enumerate() {
+                screen.viewport_set(row, col, GraphemeCell::from_char(ch));
+            }
+            let page_row = screen.viewport_row_mut(row);
+            page_row.written_cols = text.chars().count();
+            page_row.wrapped = *wrapped;
+        }
+        screen
+    }
+
+    fn at(abs_row: usize, col: usize) -> PageCoord {
+        PageCoord { abs_row, col }
+    }
+
+    #[test]
+    fn finds_url_continued_on_a_wrapped_row() {
+        let screen = screen_with_rows(12, &[("see https://", true), ("ferrum.dev).", false)]);
+        let link = link_at(&screen, at(1, 3)).expect("url");
+        assert_eq!(link.kind, LinkKind::Url);
+        assert_eq!(link.text, "https://ferrum.dev");
+        assert_eq!((link.start, link.end), (at(0, 4), at(1, 9)));
+        assert!(link_at(&screen, at(1, 10)).is_none());
+        assert!(link_at(&screen, at(0, 1)).is_none());
+    }
+
+    #[test]
+    fn keeps_brackets_the_url_opened() {
+        let screen = screen_with_rows(40, &[("(https://en.wikipedia.org/wiki/X_(y))", false)]);
+        let link = link_at(&screen, at(0, 5)).expect("url");
+        assert_eq!(link.text, "https://en.wikipedia.org/wiki/X_(y)");
+    }
+
+    #[test]
+    fn parses_file_locations() {
+        let screen = screen_with_rows(30, &[("--> src/gui/mod.rs:12:5 and", false)]);
+        let link = link_at(&screen, at(0, 8)).expect("file");
+        assert_eq!(
+            link.kind,
+            LinkKind::File {
+                path: "src/gui/mod.rs".into(),
+                line: Some(12),
+                column: Some(5),
+            }
+        );
+        assert_eq!((link.start.col, link.end.col), (4, 22));
+
+        let screen = screen_with_rows(30, &[("at main.rs:7 in ./x/y.", false)]);
+        let bare = link_at(&screen, at(0, 4)).expect("bare file name with a line");
+        assert!(matches!(bare.kind, LinkKind::File { line: Some(7), .. }));
+        let relative = link_at(&screen, at(0, 17)).expect("relative path");
+        assert_eq!(relative.text, "./x/y");
+        assert!(link_at(&screen, at(0, 14)).is_none());
+    }
+
+    #[test]
+    fn paths_need_a_directory_or_a_line_number() {
+        let screen = screen_with_rows(30, &[("and/or 10:30 1.5:2", false)]);
+        assert!(link_at(&screen, at(0, 1)).is_some());
+        assert!(link_at(&screen, at(0, 8)).is_none());
+        assert!(link_at(&screen, at(0, 14)).is_none());
+    }
+
+    #[test]
+    fn detects_commit_hashes() {
+        let screen = screen_with_rows(40, &[("3ad8464 fix; deadbeef 1234567 9bc5021f", false)]);
+        assert_eq!(
+            link_at(&screen, at(0, 2)).map(|l| l.kind),
+            Some(LinkKind::Commit)
+        );
+        assert!(link_at(&screen, at(0, 15)).is_none());
+        assert!(link_at(&screen, at(0, 24)).is_none());
+        assert_eq!(
+            link_at(&screen, at(0, 31)).map(|l| l.text),
+            Some("9bc5021f".into())
+        );
+    }
+}
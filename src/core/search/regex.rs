@@ -0,0 +1,764 @@
+//! Minimal regular expressions for scrollback search.
+//!
+//! Supports literals, `.`, character classes (`[a-z]`, `[^0-9]`), the
+//! escapes `\d \w \s \D \W \S \b \B`, anchors `^ $`, groups `(…)` and
+//! `(?:…)`, alternation `|`, and the quantifiers `* + ? {n} {n,} {n,m}`
+//! (each optionally lazy with a trailing `?`).  Matching runs a Pike VM,
+//! so time is linear in the haystack length regardless of the pattern.
+
+use std::fmt;
+use std::ops::Range;
+
+/// Upper bound for `{n,m}` counts; keeps the compiled program small.
+const MAX_REPEAT: u32 = 1000;
+
+/// Upper bound for the compiled program of a pattern.  Nested counted
+/// repeats such as `(a{1000}){1000}` multiply, and matching time grows with
+/// the program size.
+const MAX_PROGRAM_LEN: usize = 10_000;
+
+/// A pattern that failed to parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegexError {
+    message: &'static str,
+    position: usize,
+}
+
+impl fmt::Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+/// A compiled pattern, matched against text as a slice of `char`s.
+#[derive(Debug)]
+pub struct Regex {
+    program: Vec<Inst>,
+    case_sensitive: bool,
+}
+
+impl Regex {
+    /// Compiles `pattern`.
+    pub fn new(pattern: &str, case_sensitive: bool) -> Result<Self, RegexError> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            pos: 0,
+        };
+        let node = parser.parse_alternation()?;
+        if parser.pos < parser.chars.len() {
+            return Err(parser.error("unmatched ')'"));
+        }
+        if program_len(&node) >= MAX_PROGRAM_LEN {
+            return Err(parser.error("pattern too large"));
+        }
+        Ok(Self::compile(&node, case_sensitive))
+    }
+
+    /// Builds a pattern matching `text` literally.
+    pub fn literal(text: &str, case_sensitive: bool) -> Self {
+        let node = Node::Concat(text.chars().map(Node::Char).collect());
+        Self::compile(&node, case_sensitive)
+    }
+
+    fn compile(node: &Node, case_sensitive: bool) -> Self {
+        let mut program = Vec::new();
+        emit(node, &mut program);
+        program.push(Inst::Match);
+        Self {
+            program,
+            case_sensitive,
+        }
+    }
+
+    /// Finds the leftmost match starting at or after `start`.
+    ///
+    /// Among matches starting at the same position the one preferred by the
+    /// pattern wins (greedy quantifiers longest, lazy ones shortest, earlier
+    /// alternatives first).  The match may be empty.  `scratch` holds the
+    /// thread lists, so repeated calls do not allocate.
+    pub fn find_at(
+        &self,
+        haystack: &[char],
+        start: usize,
+        scratch: &mut Scratch,
+    ) -> Option<Range<usize>> {
+        let Scratch {
+            current,
+            next,
+            stack,
+        } = scratch;
+        current.reset(self.program.len());
+        next.reset(self.program.len());
+        let mut found = None;
+
+        for pos in start..=haystack.len() {
+            // Seed a new attempt at this position with the lowest priority.
+            if found.is_none() {
+                self.add_thread(current, stack, 0, pos, pos, haystack);
+            }
+            if current.list.is_empty() && found.is_some() {
+                break;
+            }
+            let ch = haystack.get(pos).copied();
+            for i in 0..current.list.len() {
+                let (pc, origin) = current.list[i];
+                match &self.program[pc] {
+                    Inst::Match => {
+                        found = Some(origin..pos);
+                        // Lower-priority threads can no longer win.
+                        break;
+                    }
+                    inst => {
+                        if ch.is_some_and(|c| self.step_matches(inst, c)) {
+                            self.add_thread(next, stack, pc + 1, pos + 1, origin, haystack);
+                        }
+                    }
+                }
+            }
+            std::mem::swap(current, next);
+            next.clear();
+        }
+        found
+    }
+
+    fn step_matches(&self, inst: &Inst, c: char) -> bool {
+        match inst {
+            Inst::Char(expected) => {
+                *expected == c || (!self.case_sensitive && fold(*expected) == fold(c))
+            }
+            Inst::Any => true,
+            Inst::Class(class) => {
+                let hit = class.contains(c)
+                    || (!self.case_sensitive
+                        && (class.contains(fold(c))
+                            || c.to_uppercase().any(|u| class.contains(u))));
+                hit != class.negated
+            }
+            _ => false,
+        }
+    }
+
+    /// Follows jumps, splits and assertions from `pc`, queueing every
+    /// consuming instruction reached in priority order.
+    fn add_thread(
+        &self,
+        threads: &mut Threads,
+        stack: &mut Vec<usize>,
+        pc: usize,
+        pos: usize,
+        origin: usize,
+        haystack: &[char],
+    ) {
+        stack.clear();
+        stack.push(pc);
+        while let Some(pc) = stack.pop() {
+            if !threads.visit(pc) {
+                continue;
+            }
+            match &self.program[pc] {
+                Inst::Jump(target) => stack.push(*target),
+                Inst::Split(first, second) => {
+                    stack.push(*second);
+                    stack.push(*first);
+                }
+                Inst::Assert(assertion) => {
+                    if assertion.holds(haystack, pos) {
+                        stack.push(pc + 1);
+                    }
+                }
+                _ => threads.list.push((pc, origin)),
+            }
+        }
+    }
+}
+
+/// Simple case folding: the first lowercase mapping of `c`.
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// ── Program ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Assert(Assertion),
+    /// Try the first target before the second.
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Assertion {
+    LineStart,
+    LineEnd,
+    WordBoundary,
+    NotWordBoundary,
+}
+
+impl Assertion {
+    fn holds(self, haystack: &[char], pos: usize) -> bool {
+        let before = pos
+            .checked_sub(1)
+            .and_then(|i| haystack.get(i))
+            .is_some_and(|c| is_word_char(*c));
+        let after = haystack.get(pos).is_some_and(|c| is_word_char(*c));
+        match self {
+            Assertion::LineStart => pos == 0,
+            Assertion::LineEnd => pos == haystack.len(),
+            Assertion::WordBoundary => before != after,
+            Assertion::NotWordBoundary => before == after,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl ClassItem {
+    fn matches(self, c: char) -> bool {
+        match self {
+            ClassItem::Range(lo, hi) => lo <= c && c <= hi,
+            ClassItem::Digit(negated) => c.is_ascii_digit() != negated,
+            ClassItem::Word(negated) => is_word_char(c) != negated,
+            ClassItem::Space(negated) => c.is_whitespace() != negated,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Class {
+    items: Vec<ClassItem>,
+    negated: bool,
+}
+
+impl Class {
+    fn single(item: ClassItem) -> Self {
+        Self {
+            items: vec![item],
+            negated: false,
+        }
+    }
+
+    /// Whether `c` is listed by the class, ignoring negation.
+    fn contains(&self, c: char) -> bool {
+        self.items.iter().any(|item| item.matches(c))
+    }
+}
+
+/// Buffers `Regex::find_at` reuses between calls.
+#[derive(Default)]
+pub struct Scratch {
+    current: Threads,
+    next: Threads,
+    /// Pending program counters of `add_thread`.
+    stack: Vec<usize>,
+}
+
+/// Thread list for one VM step, deduplicated by program counter.
+#[derive(Default)]
+struct Threads {
+    list: Vec<(usize, usize)>,
+    /// Per program counter, the `step` it was last visited in; clearing
+    /// moves to a new step instead of touching every entry.
+    seen: Vec<u32>,
+    step: u32,
+}
+
+impl Threads {
+    /// Empties the list and sizes it for a program of `len` instructions.
+    fn reset(&mut self, len: usize) {
+        if self.seen.len() != len {
+            self.seen = vec![0; len];
+            self.step = 0;
+        }
+        self.clear();
+    }
+
+    /// Marks `pc` as visited; returns `false` if it already was.
+    fn visit(&mut self, pc: usize) -> bool {
+        std::mem::replace(&mut self.seen[pc], self.step) != self.step
+    }
+
+    fn clear(&mut self) {
+        self.list.clear();
+        self.step = self.step.wrapping_add(1);
+        if self.step == 0 {
+            self.seen.fill(0);
+            self.step = 1;
+        }
+    }
+}
+
+// ── Syntax tree ─────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug)]
+enum Node {
+    Char(char),
+    Any,
+    Class(Class),
+    Assert(Assertion),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    },
+}
+
+/// Number of instructions `emit` produces for `node`, saturating instead of
+/// overflowing.
+fn program_len(node: &Node) -> usize {
+    match node {
+        Node::Char(_) | Node::Any | Node::Class(_) | Node::Assert(_) => 1,
+        Node::Concat(nodes) => nodes
+            .iter()
+            .fold(0, |len, node| len.saturating_add(program_len(node))),
+        Node::Alternation(branches) => branches
+            .iter()
+            .fold(2 * branches.len().saturating_sub(1), |len, branch| {
+                len.saturating_add(program_len(branch))
+            }),
+        Node::Repeat { node, min, max, .. } => {
+            let body = program_len(node);
+            let optional = match max {
+                None => body.saturating_add(2),
+                Some(max) => ((max - min) as usize).saturating_mul(body.saturating_add(1)),
+            };
+            (*min as usize)
+                .saturating_mul(body)
+                .saturating_add(optional)
+        }
+    }
+}
+
+fn emit(node: &Node, program: &mut Vec<Inst>) {
+    match node {
+        Node::Char(c) => program.push(Inst::Char(*c)),
+        Node::Any => program.push(Inst::Any),
+        Node::Class(class) => program.push(Inst::Class(class.clone())),
+        Node::Assert(assertion) => program.push(Inst::Assert(*assertion)),
+        Node::Concat(nodes) => {
+            for node in nodes {
+                emit(node, program);
+            }
+        }
+        Node::Alternation(branches) => {
+            let mut exits = Vec::new();
+            for (i, branch) in branches.iter().enumerate() {
+                if i + 1 < branches.len() {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    emit(branch, program);
+                    exits.push(program.len());
+                    program.push(Inst::Jump(0));
+                    let next = program.len();
+                    program[split] = Inst::Split(split + 1, next);
+                } else {
+                    emit(branch, program);
+                }
+            }
+            let end = program.len();
+            for exit in exits {
+                program[exit] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat {
+            node,
+            min,
+            max,
+            greedy,
+        } => {
+            for _ in 0..*min {
+                emit(node, program);
+            }
+            match max {
+                None => {
+                    let split = program.len();
+                    program.push(Inst::Split(0, 0));
+                    emit(node, program);
+                    program.push(Inst::Jump(split));
+                    let body = split + 1;
+                    program[split] = ordered_split(body, program.len(), *greedy);
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Inst::Split(0, 0));
+                        emit(node, program);
+                    }
+                    let end = program.len();
+                    for split in splits {
+                        program[split] = ordered_split(split + 1, end, *greedy);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn ordered_split(body: usize, skip: usize, greedy: bool) -> Inst {
+    if greedy {
+        Inst::Split(body, skip)
+    } else {
+        Inst::Split(skip, body)
+    }
+}
+
+// ── Parser ──────────────────────────────────────────────────────────────────
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &'static str) -> RegexError {
+        RegexError {
+            message,
+            position: self.pos,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, RegexError> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.eat('|') {
+            branches.push(self.parse_concat()?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.remove(0)
+        } else {
+            Node::Alternation(branches)
+        })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, RegexError> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_quantifiers(atom)?);
+        }
+        Ok(Node::Concat(nodes))
+    }
+
+    fn parse_quantifiers(&mut self, mut node: Node) -> Result<Node, RegexError> {
+        loop {
+            let (min, max) = match self.peek() {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                Some('{') => match self.counted_repeat()? {
+                    Some(bounds) => bounds,
+                    None => return Ok(node),
+                },
+                _ => return Ok(node),
+            };
+            if matches!(node, Node::Assert(_)) {
+                return Err(self.error("nothing to repeat"));
+            }
+            // Step past the quantifier character or the closing brace.
+            self.pos += 1;
+            let greedy = !self.eat('?');
+            node = Node::Repeat {
+                node: Box::new(node),
+                min,
+                max,
+                greedy,
+            };
+        }
+    }
+
+    /// Parses `{n}`, `{n,}` or `{n,m}` at the cursor, leaving it on the
+    /// closing brace.  Returns `None` (cursor unmoved) when the brace does
+    /// not start a valid count, so it is read as a literal.
+    fn counted_repeat(&mut self) -> Result<Option<(u32, Option<u32>)>, RegexError> {
+        let start = self.pos;
+        self.pos += 1;
+        let Some(min) = self.number() else {
+            self.pos = start;
+            return Ok(None);
+        };
+        let max = if self.eat(',') {
+            if self.peek() == Some('}') {
+                None
+            } else {
+                match self.number() {
+                    Some(max) => Some(max),
+                    None => {
+                        self.pos = start;
+                        return Ok(None);
+                    }
+                }
+            }
+        } else {
+            Some(min)
+        };
+        if self.peek() != Some('}') {
+            self.pos = start;
+            return Ok(None);
+        }
+        if min > MAX_REPEAT || max.is_some_and(|max| max > MAX_REPEAT) {
+            return Err(self.error("repetition count too large"));
+        }
+        if max.is_some_and(|max| max < min) {
+            return Err(self.error("invalid repetition range"));
+        }
+        Ok(Some((min, max)))
+    }
+
+    fn number(&mut self) -> Option<u32> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return None;
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+            .or(Some(u32::MAX))
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, RegexError> {
+        let Some(c) = self.peek() else {
+            return Err(self.error("unexpected end of pattern"));
+        };
+        self.pos += 1;
+        match c {
+            '(' => {
+                if self.eat('?') && !self.eat(':') {
+                    return Err(self.error("unsupported group flag"));
+                }
+                let inner = self.parse_alternation()?;
+                if !self.eat(')') {
+                    return Err(self.error("unclosed group"));
+                }
+                Ok(inner)
+            }
+            '[' => self.parse_class(),
+            '.' => Ok(Node::Any),
+            '^' => Ok(Node::Assert(Assertion::LineStart)),
+            '$' => Ok(Node::Assert(Assertion::LineEnd)),
+            '*' | '+' | '?' => {
+                self.pos -= 1;
+                Err(self.error("nothing to repeat"))
+            }
+            '\\' => self.parse_escape(),
+            c => Ok(Node::Char(c)),
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<Node, RegexError> {
+        let Some(c) = self.peek() else {
+            return Err(self.error("trailing backslash"));
+        };
+        self.pos += 1;
+        Ok(match c {
+            'b' => Node::Assert(Assertion::WordBoundary),
+            'B' => Node::Assert(Assertion::NotWordBoundary),
+            _ => match self.class_escape(c)? {
+                EscapeItem::Char(c) => Node::Char(c),
+                EscapeItem::Class(item) => Node::Class(Class::single(item)),
+            },
+        })
+    }
+
+    /// Escapes valid both inside and outside brackets; the cursor is past `c`.
+    fn class_escape(&self, c: char) -> Result<EscapeItem, RegexError> {
+        Ok(match c {
+            'd' => EscapeItem::Class(ClassItem::Digit(false)),
+            'D' => EscapeItem::Class(ClassItem::Digit(true)),
+            'w' => EscapeItem::Class(ClassItem::Word(false)),
+            'W' => EscapeItem::Class(ClassItem::Word(true)),
+            's' => EscapeItem::Class(ClassItem::Space(false)),
+            'S' => EscapeItem::Class(ClassItem::Space(true)),
+            'n' => EscapeItem::Char('\n'),
+            't' => EscapeItem::Char('\t'),
+            'r' => EscapeItem::Char('\r'),
+            c if c.is_ascii_alphanumeric() => return Err(self.error("unknown escape")),
+            c => EscapeItem::Char(c),
+        })
+    }
+
+    fn parse_class(&mut self) -> Result<Node, RegexError> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        let mut first = true;
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("unclosed character class"));
+            };
+            self.pos += 1;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+            let lo = if c == '\\' {
+                let Some(escaped) = self.peek() else {
+                    return Err(self.error("trailing backslash"));
+                };
+                self.pos += 1;
+                match self.class_escape(escaped)? {
+                    EscapeItem::Char(c) => c,
+                    EscapeItem::Class(item) => {
+                        items.push(item);
+                        continue;
+                    }
+                }
+            } else {
+                c
+            };
+            // `a-z` range, unless the dash is the last character.
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|c| *c != ']') {
+                self.pos += 1;
+                let mut hi = self.chars[self.pos];
+                self.pos += 1;
+                if hi == '\\' {
+                    let Some(escaped) = self.peek() else {
+                        return Err(self.error("trailing backslash"));
+                    };
+                    self.pos += 1;
+                    match self.class_escape(escaped)? {
+                        EscapeItem::Char(c) => hi = c,
+                        EscapeItem::Class(_) => return Err(self.error("invalid class range")),
+                    }
+                }
+                if hi < lo {
+                    return Err(self.error("invalid class range"));
+                }
+                items.push(ClassItem::Range(lo, hi));
+            } else {
+                items.push(ClassItem::Range(lo, lo));
+            }
+        }
+        Ok(Node::Class(Class { items, negated }))
+    }
+}
+
+enum EscapeItem {
+    Char(char),
+    Class(ClassItem),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, text: &str) -> Option<String> {
+        let haystack: Vec<char> = text.chars().collect();
+        let regex = Regex::new(pattern, true).unwrap();
+        let range = regex.find_at(&haystack, 0, &mut Scratch::default())?;
+        Some(haystack[range].iter().collect())
+    }
+
+    #[test]
+    fn literals_classes_and_escapes() {
+        assert_eq!(find("b.d", "abcde").as_deref(), Some("bcd"));
+        assert_eq!(find("[0-9]+", "abc 1234 x").as_deref(), Some("1234"));
+        assert_eq!(
+            find(r"\w+@\w+\.com", "mail me@host.com now").as_deref(),
+            Some("me@host.com")
+        );
+        assert_eq!(find(r"[^a-c\s]+", "abc xyz").as_deref(), Some("xyz"));
+        assert_eq!(find(r"\.", "a.b").as_deref(), Some("."));
+        assert_eq!(find("x", "abc"), None);
+    }
+
+    #[test]
+    fn leftmost_match_with_greedy_and_lazy_quantifiers() {
+        assert_eq!(find("a+", "baaa").as_deref(), Some("aaa"));
+        assert_eq!(find("a+?", "baaa").as_deref(), Some("a"));
+        assert_eq!(find("<.*>", "<a><b>").as_deref(), Some("<a><b>"));
+        assert_eq!(find("<.*?>", "<a><b>").as_deref(), Some("<a>"));
+        assert_eq!(find("a{2,3}", "a aa aaaa").as_deref(), Some("aa"));
+        assert_eq!(find("x{2}", "xxx").as_deref(), Some("xx"));
+        assert_eq!(find("a{,", "a{,").as_deref(), Some("a{,"));
+    }
+
+    #[test]
+    fn alternation_groups_and_anchors() {
+        assert_eq!(find("cat|dog", "hotdog").as_deref(), Some("dog"));
+        assert_eq!(find("(?:ab)+c", "xababc").as_deref(), Some("ababc"));
+        assert_eq!(find("^ab", "cab"), None);
+        assert_eq!(find("ab$", "abab").as_deref(), Some("ab"));
+        assert_eq!(find(r"\bis\b", "this is").as_deref(), Some("is"));
+    }
+
+    #[test]
+    fn case_insensitive_matching() {
+        let haystack: Vec<char> = "Error ERROR".chars().collect();
+        let scratch = &mut Scratch::default();
+        let regex = Regex::new("error", false).unwrap();
+        assert_eq!(regex.find_at(&haystack, 0, scratch), Some(0..5));
+        assert_eq!(regex.find_at(&haystack, 1, scratch), Some(6..11));
+        let class = Regex::new("[a-z]+", false).unwrap();
+        assert_eq!(class.find_at(&haystack, 0, scratch), Some(0..5));
+        let negated = Regex::new("[^e ]+", false).unwrap();
+        assert_eq!(negated.find_at(&haystack, 0, scratch), Some(1..5));
+        let literal = Regex::literal("err", true);
+        assert_eq!(literal.find_at(&haystack, 0, scratch), None);
+        let dot = Regex::literal("a.b", false);
+        assert_eq!(dot.find_at(&['a', 'x', 'b'], 0, scratch), None);
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        for pattern in ["(ab", "ab)", "[a-", "*a", r"\q", "a{3,1}", "(?i)a", "\\"] {
+            assert!(Regex::new(pattern, true).is_err(), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn nested_repeats_are_limited_by_program_size() {
+        assert!(Regex::new("a{1000}", true).is_ok());
+        for pattern in ["(a{1000}){1000}", "((a{100}){100}){100}", "(a|b{999}){50}"] {
+            let error = Regex::new(pattern, true).unwrap_err();
+            assert_eq!(error.message, "pattern too large", "{pattern}");
+        }
+        // The estimate matches what `emit` produces (plus the final `Match`).
+        for pattern in ["(ab{2}){3}", "a|b|c", "x*?y{1,3}", "(?:a|bc)+$"] {
+            let node = Parser {
+                chars: pattern.chars().collect(),
+                pos: 0,
+            }
+            .parse_alternation()
+            .unwrap();
+            let regex = Regex::new(pattern, true).unwrap();
+            assert_eq!(regex.program.len(), program_len(&node) + 1, "{pattern}");
+        }
+    }
+}
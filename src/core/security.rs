@@ -12,6 +12,12 @@ pub struct SecurityConfig {
     pub block_title_query: bool,
     pub limit_cursor_jumps: bool,
     pub clear_mouse_on_reset: bool,
+    pub allow_notifications: bool,
+    /// Lets OSC 52 queries read the clipboard, after the user agrees.
+    pub allow_clipboard_read: bool,
+    /// Lets Kitty graphics commands load images from files (`t=f`, `t=t`).
+    /// Off by default: a program could otherwise show any readable file.
+    pub allow_graphics_files: bool,
 }
 
 impl Default for SecurityConfig {
@@ -21,6 +27,9 @@ impl Default for SecurityConfig {
             block_title_query: true,
             limit_cursor_jumps: true,
             clear_mouse_on_reset: true,
+            allow_notifications: true,
+            allow_clipboard_read: false,
+            allow_graphics_files: false,
         }
     }
 }
@@ -32,6 +41,8 @@ pub enum SecurityEventKind {
     TitleQuery,
     CursorRewrite,
     MouseLeak,
+    /// An OSC 52 clipboard read was refused.
+    ClipboardRead,
 }
 
 impl SecurityEventKind {
@@ -44,6 +55,7 @@ impl SecurityEventKind {
             Self::TitleQuery => t.security_event_title_query_blocked,
             Self::CursorRewrite => t.security_event_cursor_rewrite,
             Self::MouseLeak => t.security_event_mouse_leak_prevented,
+            Self::ClipboardRead => t.security_event_clipboard_read_denied,
         }
     }
 }
@@ -87,6 +99,8 @@ impl SecurityGuard {
             SecurityEventKind::TitleQuery => self.config.block_title_query,
             SecurityEventKind::CursorRewrite => self.config.limit_cursor_jumps,
             SecurityEventKind::MouseLeak => self.config.clear_mouse_on_reset,
+            // Refused reads are always worth telling the user about.
+            SecurityEventKind::ClipboardRead => true,
         }
     }
 
@@ -1,9 +1,21 @@
 use crate::core::PageCoord;
 
+/// Which cells between the two selection ends are selected.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SelectionKind {
+    /// Reading order: the rest of the first row, whole rows in between and
+    /// the start of the last row.
+    #[default]
+    Stream,
+    /// The rectangle with the two ends as opposite corners.
+    Block,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct Selection {
     pub start: PageCoord,
     pub end: PageCoord,
+    pub kind: SelectionKind,
 }
 
 impl Selection {
@@ -24,6 +36,10 @@ impl Selection {
         if row < start.abs_row || row > end.abs_row {
             return false;
         }
+        if self.kind == SelectionKind::Block {
+            let (left, right) = self.block_columns();
+            return col >= left && col <= right;
+        }
         if row == start.abs_row && row == end.abs_row {
             return col >= start.col && col <= end.col;
         }
@@ -36,6 +52,10 @@ impl Selection {
         true
     }
 
+    /// Leftmost and rightmost column of a block selection.
+    pub fn block_columns(&self) -> (usize, usize) {
+        (self.start.col.min(self.end.col), self.start.col.max(self.end.col))
+    }
 }
 
 #[cfg(test)]
@@ -47,6 +67,7 @@ mod tests {
         let sel = Selection {
             start: PageCoord { abs_row: 5, col: 2 },
             end: PageCoord { abs_row: 5, col: 7 },
+            kind: SelectionKind::Stream,
         };
         assert!(sel.contains(5, 2));
         assert!(sel.contains(5, 4));
@@ -62,6 +83,7 @@ mod tests {
         let sel = Selection {
             start: PageCoord { abs_row: 10, col: 3 },
             end: PageCoord { abs_row: 12, col: 5 },
+            kind: SelectionKind::Stream,
         };
         assert!(sel.contains(10, 3));
         assert!(sel.contains(10, 79));
@@ -78,6 +100,7 @@ mod tests {
         let sel = Selection {
             start: PageCoord { abs_row: 2, col: 3 },
             end: PageCoord { abs_row: 5, col: 7 },
+            kind: SelectionKind::Stream,
         };
         let (s, e) = sel.normalized();
         assert_eq!(s, sel.start);
@@ -89,6 +112,7 @@ mod tests {
         let sel = Selection {
             start: PageCoord { abs_row: 5, col: 7 },
             end: PageCoord { abs_row: 2, col: 3 },
+            kind: SelectionKind::Stream,
         };
         let (s, e) = sel.normalized();
         assert_eq!(s.abs_row, 2);
@@ -97,4 +121,21 @@ mod tests {
         assert_eq!(e.col, 7);
     }
 
+    #[test]
+    fn contains_block_clips_every_row_to_the_columns() {
+        // Ends given bottom-left then top-right.
+        let sel = Selection {
+            start: PageCoord { abs_row: 4, col: 2 },
+            end: PageCoord { abs_row: 2, col: 6 },
+            kind: SelectionKind::Block,
+        };
+        assert_eq!(sel.block_columns(), (2, 6));
+        for row in 2..=4 {
+            assert!(sel.contains(row, 2));
+            assert!(sel.contains(row, 6));
+            assert!(!sel.contains(row, 1));
+            assert!(!sel.contains(row, 7));
+        }
+        assert!(!sel.contains(5, 4));
+    }
 }
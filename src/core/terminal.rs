@@ -1,22 +1,29 @@
-use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::time::Instant;
 
-use base64::Engine as _;
 use crate::config::ThemeChoice;
 use super::{
-    Color, GraphemeCell, PageCoord, PageList, SecurityConfig,
+    CellEffects, Color, GraphemeCell, PageCoord, PageList, SecurityConfig,
     SecurityEventKind, TrackedPin, UnderlineStyle,
 };
 use unicode_width::UnicodeWidthChar;
 use vte::{Params, Parser, Perform};
 
-/// Maximum number of unique hyperlink URLs stored per terminal session.
-const HYPERLINK_URL_TABLE_MAX: usize = 4096;
+/// Cell size assumed for image placement until the GUI reports real metrics.
+const DEFAULT_CELL_PIXEL_WIDTH: u32 = 10;
+const DEFAULT_CELL_PIXEL_HEIGHT: u32 = 20;
 
 mod alt_screen;
+mod apc;
+mod charsets;
 mod grid_ops;
 mod handlers;
+mod hyperlinks;
+mod images;
+mod kitty_graphics;
 mod resize;
+mod sixel;
+mod tab_stops;
 
 /// Cursor style reported by DECSCUSR.
 #[derive(Default, Clone, Copy, PartialEq, Debug)]
@@ -44,18 +51,135 @@ impl CursorStyle {
 pub enum MouseMode {
     #[default]
     Off,
+    X10,         // ?9: press only, no modifiers
     Normal,      // ?1000: press + release
     ButtonEvent, // ?1002: press + release + drag
     AnyEvent,    // ?1003: report all mouse motion
 }
 
+/// How mouse reports encode the button and position.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MouseEncoding {
+    #[default]
+    Default,   // CSI M with one byte per value, positions up to 223
+    Utf8,      // ?1005: CSI M with UTF-8 encoded values, positions up to 2015
+    Sgr,       // ?1006: CSI < b ; x ; y M/m
+    Urxvt,     // ?1015: CSI b ; x ; y M with decimal values
+    SgrPixels, // ?1016: like SGR, but x/y are pixels
+}
+
+/// Clipboard addressed by an OSC 52 selection parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClipboardTarget {
+    /// `c`: the clipboard used by copy and paste.
+    Clipboard,
+    /// `p`, `s`: the primary selection (X11/Wayland); the clipboard elsewhere.
+    Primary,
+}
+
+/// An OSC 52 clipboard query waiting for the GUI to answer it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClipboardRead {
+    pub target: ClipboardTarget,
+    /// Selection parameter echoed in the reply.
+    selection: u8,
+    /// The reply ends like the query did.
+    bell_terminated: bool,
+}
+
+/// A desktop notification requested with OSC 9 or OSC 777.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Notification {
+    /// Summary line; empty when the sequence carries none (OSC 9).
+    pub title: String,
+    pub body: String,
+}
+
+/// Kitty keyboard protocol progressive-enhancement flags (`CSI > flags u`).
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyboardFlags(u8);
+
+impl KeyboardFlags {
+    pub const DISAMBIGUATE: u8 = 1;
+    pub const REPORT_EVENT_TYPES: u8 = 2;
+    pub const REPORT_ALTERNATE_KEYS: u8 = 4;
+    pub const REPORT_ALL_KEYS: u8 = 8;
+    pub const REPORT_TEXT: u8 = 16;
+    const ALL: u8 = 31;
+
+    /// Builds flags from a raw value, dropping undefined bits.
+    pub fn from_bits(bits: u16) -> Self {
+        Self((bits & u16::from(Self::ALL)) as u8)
+    }
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, flag: u8) -> bool {
+        self.0 & flag == flag
+    }
+}
+
+/// Default and ANSI colours that SGR colours resolve against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Palette {
+    fg: Color,
+    bg: Color,
+    ansi: [Color; 16],
+}
+
+impl Palette {
+    /// Maps `color` to the matching slot of `to`; colours that are not part
+    /// of this palette (custom SGR colours) are returned unchanged.
+    fn remap(&self, to: &Palette, color: Color) -> Color {
+        if color == self.fg {
+            return to.fg;
+        }
+        if color == self.bg {
+            return to.bg;
+        }
+        match self.ansi.iter().position(|&c| c == color) {
+            Some(i) => to.ansi[i],
+            None => color,
+        }
+    }
+}
+
+/// Cursor state saved by DECSC and restored by DECRC.
+#[derive(Clone, Copy, Debug)]
+struct SavedCursor {
+    coord: PageCoord,
+    origin_mode: bool,
+    autowrap: bool,
+    charsets: charsets::Charsets,
+}
+
+impl Default for SavedCursor {
+    fn default() -> Self {
+        Self {
+            coord: PageCoord { abs_row: 0, col: 0 },
+            origin_mode: false,
+            autowrap: true,
+            charsets: charsets::Charsets::default(),
+        }
+    }
+}
+
 pub struct Terminal {
     // ── Primary state (PageList + TrackedPin) ────────────────────────────────
     pub screen: PageList,
     cursor_pin: TrackedPin,
-    saved_cursor: PageCoord,
+    saved_cursor: SavedCursor,
     alt_saved_cursor: PageCoord,
     alt_screen: Option<PageList>,
+    /// Alternate screen contents kept while the main screen is shown, so
+    /// `?47h` and `?1047h` find them again.
+    parked_alt_screen: Option<PageList>,
 
     // ── Colour and attribute state ───────────────────────────────────────────
     current_fg: Color,
@@ -63,12 +187,20 @@ pub struct Terminal {
     pub default_fg: Color,
     pub default_bg: Color,
     pub ansi_palette: [Color; 16],
+    /// Colours of the config theme; OSC 104/110/111 restore these.
+    theme_palette: Palette,
+    /// Cursor colour set by OSC 12 (`None` = default foreground).
+    cursor_color: Option<Color>,
     current_bold: bool,
     current_dim: bool,
     current_italic: bool,
     current_reverse: bool,
     current_underline_style: UnderlineStyle,
     current_strikethrough: bool,
+    current_effects: CellEffects,
+    current_underline_color: Option<Color>,
+    /// Last printed graphic character, repeated by REP.
+    last_graphic: Option<char>,
 
     // ── Scroll region ────────────────────────────────────────────────────────
     scroll_top: usize,
@@ -76,23 +208,53 @@ pub struct Terminal {
     saved_scroll_top: usize,
     saved_scroll_bottom: usize,
 
+    // ── Tab stops ────────────────────────────────────────────────────────────
+    /// Tab stops of the active screen.
+    tab_stops: tab_stops::TabStops,
+    /// Tab stops of the inactive screen (main or alternate).
+    saved_tab_stops: tab_stops::TabStops,
+
+    // ── Character sets ───────────────────────────────────────────────────────
+    charsets: charsets::Charsets,
+
     // ── Limits ───────────────────────────────────────────────────────────────
     pub max_scrollback: usize,
 
     // ── Mode flags ───────────────────────────────────────────────────────────
     pub decckm: bool,               // Application Cursor Key Mode (ESC[?1h/l)
+    pub application_keypad: bool,   // DECKPAM/DECKPNM (ESC = / ESC >, mode 66)
     pub cursor_visible: bool,       // DECTCEM (mode 25)
     pub pending_responses: Vec<u8>, // Bytes queued for PTY replies.
     pub mouse_mode: MouseMode,
-    pub sgr_mouse: bool,
+    pub mouse_encoding: MouseEncoding,
     pub bracketed_paste: bool,
     pub focus_reporting: bool,
+    /// DECOM (`?6`): cursor addressing is relative to the scroll region.
+    origin_mode: bool,
+    /// DECAWM (`?7`): printing past the right margin wraps to the next line.
+    autowrap: bool,
+    /// IRM (`4`): printed characters shift the rest of the line right.
+    insert_mode: bool,
+    /// LNM (`20`): LF, VT and FF also return the cursor to column 0.
+    linefeed_mode: bool,
+    /// Mode `?2026`: when the open synchronized update began, if any.
+    synchronized_output: Option<Instant>,
     pub security_config: SecurityConfig,
     pending_security_events: Vec<SecurityEventKind>,
+    /// BELs received since the GUI last took them.
+    pending_bells: u32,
+    /// Notifications posted by OSC 9/777, waiting for the GUI.
+    pending_notifications: Vec<Notification>,
     pub cursor_style: CursorStyle,
     pub resize_at: Option<Instant>,
     /// modifyOtherKeys level set by `ESC [ > 4 ; <n> m` (0 = off, 1 = level 1, 2 = level 2).
     pub modify_other_keys: u8,
+    /// Kitty keyboard flag stack of the active screen (top = current flags).
+    keyboard_flags: Vec<KeyboardFlags>,
+    /// Kitty keyboard flag stack of the inactive screen (main or alternate).
+    saved_keyboard_flags: Vec<KeyboardFlags>,
+    /// When the running command's output started (OSC 133 `C`).
+    command_started_at: Option<Instant>,
 
     // ── Selection pins ───────────────────────────────────────────────────────
     pub selection_start_pin: Option<TrackedPin>,
@@ -104,15 +266,32 @@ pub struct Terminal {
     /// Window/icon title set by OSC 0/1/2.
     pub title: Option<String>,
     /// Decoded clipboard text queued by OSC 52 for the GUI to write to the system clipboard.
-    pub pending_clipboard_write: Option<String>,
+    pub pending_clipboard_write: Option<(ClipboardTarget, String)>,
+    /// OSC 52 queries allowed by the security config, oldest first, each
+    /// with the replies queued after it; those wait so replies keep the
+    /// order of the queries.
+    pending_clipboard_reads: VecDeque<(ClipboardRead, Vec<u8>)>,
 
     // ── OSC 8 hyperlinks ─────────────────────────────────────────────────────
-    /// URL table: index 0 corresponds to hyperlink_id 1.
-    hyperlink_urls: Vec<String>,
-    /// Companion index for O(1) URL deduplication (url → hyperlink_id).
-    hyperlink_url_index: HashMap<String, u16>,
+    /// Links referenced by the cells' hyperlink ids.
+    hyperlinks: hyperlinks::Hyperlinks,
     /// Currently active hyperlink id (0 = none).
     current_hyperlink_id: u16,
+
+    // ── DCS and inline images ────────────────────────────────────────────────
+    /// Handler for the DCS sequence currently being received, if recognised.
+    dcs_handler: Option<handlers::dcs::DcsHandler>,
+    /// Cell size in pixels, used to map image pixels onto grid cells.
+    cell_pixel_width: u32,
+    cell_pixel_height: u32,
+    /// Id of the most recently created inline image.
+    next_image_id: u32,
+    /// Splits APC strings (Kitty graphics) out of the byte stream before `parser`.
+    apc: apc::ApcScanner,
+    /// Images transmitted with the Kitty graphics protocol.
+    graphics: kitty_graphics::GraphicsStore,
+    /// Placed Sixel images, kept within a byte budget.
+    sixel_images: images::SixelImages,
 }
 
 impl Terminal {
@@ -134,46 +313,77 @@ impl Terminal {
         Self {
             screen,
             cursor_pin,
-            saved_cursor: PageCoord { abs_row: 0, col: 0 },
+            saved_cursor: SavedCursor::default(),
             alt_saved_cursor: PageCoord { abs_row: 0, col: 0 },
             alt_screen: None,
+            parked_alt_screen: None,
             current_fg: default_fg,
             current_bg: default_bg,
             default_fg,
             default_bg,
             ansi_palette,
+            theme_palette: Palette {
+                fg: default_fg,
+                bg: default_bg,
+                ansi: ansi_palette,
+            },
+            cursor_color: None,
             current_bold: false,
             current_dim: false,
             current_italic: false,
             current_reverse: false,
             current_underline_style: UnderlineStyle::None,
             current_strikethrough: false,
+            current_effects: CellEffects::default(),
+            current_underline_color: None,
+            last_graphic: None,
             scroll_top: 0,
             scroll_bottom: rows - 1,
             saved_scroll_top: 0,
             saved_scroll_bottom: rows - 1,
+            tab_stops: tab_stops::TabStops::new(cols, tab_stops::DEFAULT_TAB_WIDTH),
+            saved_tab_stops: tab_stops::TabStops::new(cols, tab_stops::DEFAULT_TAB_WIDTH),
+            charsets: charsets::Charsets::default(),
             max_scrollback,
             decckm: false,
+            application_keypad: false,
             cursor_visible: true,
             pending_responses: Vec::new(),
             mouse_mode: MouseMode::Off,
-            sgr_mouse: false,
+            mouse_encoding: MouseEncoding::Default,
             bracketed_paste: false,
             focus_reporting: false,
+            origin_mode: false,
+            autowrap: true,
+            insert_mode: false,
+            linefeed_mode: false,
+            synchronized_output: None,
             security_config: SecurityConfig::default(),
             pending_security_events: Vec::new(),
+            pending_bells: 0,
+            pending_notifications: Vec::new(),
             cursor_style: CursorStyle::default(),
             resize_at: None,
             modify_other_keys: 0,
+            keyboard_flags: Vec::new(),
+            saved_keyboard_flags: Vec::new(),
+            command_started_at: None,
             selection_start_pin: None,
             selection_end_pin: None,
             parser: Parser::new(),
             cwd: None,
             title: None,
             pending_clipboard_write: None,
-            hyperlink_urls: Vec::new(),
-            hyperlink_url_index: HashMap::new(),
+            pending_clipboard_reads: VecDeque::new(),
+            hyperlinks: hyperlinks::Hyperlinks::default(),
             current_hyperlink_id: 0,
+            dcs_handler: None,
+            cell_pixel_width: DEFAULT_CELL_PIXEL_WIDTH,
+            cell_pixel_height: DEFAULT_CELL_PIXEL_HEIGHT,
+            next_image_id: 0,
+            apc: apc::ApcScanner::default(),
+            graphics: kitty_graphics::GraphicsStore::default(),
+            sixel_images: images::SixelImages::default(),
         }
     }
 
@@ -188,10 +398,32 @@ impl Terminal {
 
     pub fn process(&mut self, bytes: &[u8]) {
         let mut parser = std::mem::replace(&mut self.parser, Parser::new());
-        parser.advance(self, bytes);
+        let mut rest = bytes;
+        while !rest.is_empty() {
+            match self.apc.step(rest) {
+                apc::ApcStep::Forward(n) => {
+                    parser.advance(self, &rest[..n]);
+                    rest = &rest[n..];
+                }
+                apc::ApcStep::ForwardEsc => parser.advance(self, b"\x1b"),
+                apc::ApcStep::Consumed(n) => rest = &rest[n..],
+                apc::ApcStep::Complete(n) => {
+                    rest = &rest[n..];
+                    let payload = self.apc.take_payload();
+                    self.handle_apc(&payload);
+                }
+            }
+        }
         self.parser = parser;
     }
 
+    /// Dispatches a complete APC string; only Kitty graphics (`G`) is understood.
+    fn handle_apc(&mut self, payload: &[u8]) {
+        if let Some(body) = payload.strip_prefix(b"G") {
+            handlers::kitty_graphics::handle_kitty_graphics(self, body);
+        }
+    }
+
     fn param(&self, params: &Params, default: u16) -> u16 {
         params
             .iter()
@@ -200,9 +432,13 @@ impl Terminal {
             .unwrap_or(default)
     }
 
-    /// Queues a response that GUI will flush back to PTY.
+    /// Queues a response that GUI will flush back to PTY, behind any
+    /// clipboard query still waiting for an answer.
     fn respond(&mut self, data: &[u8]) {
-        self.pending_responses.extend_from_slice(data);
+        match self.pending_clipboard_reads.back_mut() {
+            Some((_, held)) => held.extend_from_slice(data),
+            None => self.pending_responses.extend_from_slice(data),
+        }
     }
 
     /// Creates a blank `GraphemeCell` that inherits the current fg/bg.
@@ -220,6 +456,7 @@ impl Terminal {
         let max_sb = self.max_scrollback;
         self.screen = PageList::new(rows, cols, max_sb);
         self.cursor_pin = PageList::pin_at(PageCoord { abs_row: 0, col: 0 });
+        self.hyperlinks.note_dropped();
     }
 
     /// Clears the screen and scrollback, resetting cursor to (0,0).
@@ -230,22 +467,54 @@ impl Terminal {
         self.reset_scroll_region();
     }
 
+    /// Changes the default tab stop interval, discarding custom stops.
+    pub fn set_tab_width(&mut self, width: usize) {
+        self.tab_stops.set_width(width);
+        self.saved_tab_stops.set_width(width);
+    }
+
+    /// When the application began the synchronized update (mode `?2026`)
+    /// that is still open, if any; the GUI holds back frames meanwhile.
+    pub fn synchronized_output_since(&self) -> Option<Instant> {
+        self.synchronized_output
+    }
+
+    /// Kitty keyboard protocol flags currently in effect for the active screen.
+    pub fn keyboard_flags(&self) -> KeyboardFlags {
+        self.keyboard_flags.last().copied().unwrap_or_default()
+    }
+
     /// Drains all pending PTY response bytes.
     pub fn drain_responses(&mut self) -> Vec<u8> {
         std::mem::take(&mut self.pending_responses)
     }
 
     /// Drains a pending clipboard write queued by OSC 52.
-    pub fn drain_clipboard_write(&mut self) -> Option<String> {
+    pub fn drain_clipboard_write(&mut self) -> Option<(ClipboardTarget, String)> {
         self.pending_clipboard_write.take()
     }
 
+    /// OSC 52 queries waiting for an answer, oldest first.
+    pub fn pending_clipboard_reads(&self) -> impl Iterator<Item = &ClipboardRead> {
+        self.pending_clipboard_reads.iter().map(|(read, _)| read)
+    }
+
+    /// Answers the oldest pending OSC 52 query with `text`, or with an empty
+    /// selection when the read was refused, and releases the replies queued
+    /// after it.
+    pub fn answer_clipboard_read(&mut self, text: Option<&str>) {
+        let Some((read, held)) = self.pending_clipboard_reads.pop_front() else {
+            return;
+        };
+        let reply = handlers::clipboard::reply_bytes(&read, text.unwrap_or_default());
+        // Everything up to the next pending query can go out now.
+        self.pending_responses.extend_from_slice(&reply);
+        self.pending_responses.extend_from_slice(&held);
+    }
+
     /// Returns the URL associated with the given hyperlink id, or `None` if the id is 0 or out of range.
     pub fn hyperlink_url(&self, id: u16) -> Option<&str> {
-        if id == 0 {
-            return None;
-        }
-        self.hyperlink_urls.get((id - 1) as usize).map(String::as_str)
+        self.hyperlinks.url(id)
     }
 
     /// Registers a tracked selection-start pin at the given absolute row/col.
@@ -293,6 +562,16 @@ impl Terminal {
         std::mem::take(&mut self.pending_security_events)
     }
 
+    /// Returns how many times BEL rang since the last call, resetting the count.
+    pub fn take_bells(&mut self) -> u32 {
+        std::mem::take(&mut self.pending_bells)
+    }
+
+    /// Drains desktop notifications posted since the last call.
+    pub fn drain_notifications(&mut self) -> Vec<Notification> {
+        std::mem::take(&mut self.pending_notifications)
+    }
+
     /// Clears transient mouse-tracking state when the PTY process exits.
     pub fn cleanup_after_process_exit(&mut self) {
         self.clear_mouse_tracking(self.security_config.clear_mouse_on_reset);
@@ -336,10 +615,47 @@ impl Terminal {
         self.current_strikethrough = value;
     }
 
+    fn set_effect(&mut self, flag: u8, value: bool) {
+        self.current_effects.set(flag, value);
+    }
+
+    fn set_underline_color(&mut self, color: Option<Color>) {
+        self.current_underline_color = color;
+    }
+
     fn set_decckm(&mut self, enabled: bool) {
         self.decckm = enabled;
     }
 
+    fn set_application_keypad(&mut self, enabled: bool) {
+        self.application_keypad = enabled;
+    }
+
+    fn set_origin_mode(&mut self, enabled: bool) {
+        self.origin_mode = enabled;
+        self.cursor_home();
+    }
+
+    fn set_autowrap(&mut self, enabled: bool) {
+        self.autowrap = enabled;
+    }
+
+    fn set_insert_mode(&mut self, enabled: bool) {
+        self.insert_mode = enabled;
+    }
+
+    fn set_linefeed_mode(&mut self, enabled: bool) {
+        self.linefeed_mode = enabled;
+    }
+
+    fn set_synchronized_output(&mut self, enabled: bool) {
+        if !enabled {
+            self.synchronized_output = None;
+        } else if self.synchronized_output.is_none() {
+            self.synchronized_output = Some(Instant::now());
+        }
+    }
+
     fn set_cursor_visible(&mut self, visible: bool) {
         self.cursor_visible = visible;
     }
@@ -348,8 +664,13 @@ impl Terminal {
         self.mouse_mode = mode;
     }
 
-    fn set_sgr_mouse(&mut self, enabled: bool) {
-        self.sgr_mouse = enabled;
+    /// Selects `encoding`; resetting it only has an effect while it is active.
+    fn set_mouse_encoding(&mut self, encoding: MouseEncoding, enabled: bool) {
+        if enabled {
+            self.mouse_encoding = encoding;
+        } else if self.mouse_encoding == encoding {
+            self.mouse_encoding = MouseEncoding::Default;
+        }
     }
 
     fn set_bracketed_paste(&mut self, enabled: bool) {
@@ -369,9 +690,10 @@ impl Terminal {
     }
 
     fn clear_mouse_tracking(&mut self, emit_event: bool) {
-        let had_mouse_tracking = self.mouse_mode != MouseMode::Off || self.sgr_mouse;
+        let had_mouse_tracking =
+            self.mouse_mode != MouseMode::Off || self.mouse_encoding != MouseEncoding::Default;
         self.mouse_mode = MouseMode::Off;
-        self.sgr_mouse = false;
+        self.mouse_encoding = MouseEncoding::Default;
         if emit_event && had_mouse_tracking {
             self.emit_security_event(SecurityEventKind::MouseLeak);
         }
@@ -411,6 +733,22 @@ impl Terminal {
         self.set_cursor_col(col);
     }
 
+    /// Rows reachable by absolute cursor addressing: the scroll region in
+    /// origin mode, the whole screen otherwise.
+    fn addressable_rows(&self) -> (usize, usize) {
+        if self.origin_mode {
+            (self.scroll_top, self.scroll_bottom)
+        } else {
+            (0, self.screen.viewport_rows().saturating_sub(1))
+        }
+    }
+
+    /// Moves the cursor to the top-left corner of the addressable area.
+    fn cursor_home(&mut self) {
+        let (top, _) = self.addressable_rows();
+        self.set_cursor(top, 0);
+    }
+
     fn maybe_record_cursor_rewrite(&mut self, from_row: usize, to_row: usize) {
         if !self.security_config.limit_cursor_jumps || self.is_alt_screen() || to_row >= from_row {
             return;
@@ -445,6 +783,7 @@ impl Terminal {
     /// Recolors all cells when the theme changes.
     ///
     /// Maps old default/ANSI colors to new ones; leaves custom SGR colors untouched.
+    /// Colours changed through OSC 4/10/11 stay in effect.
     pub fn recolor(
         &mut self,
         old_fg: Color,
@@ -454,62 +793,113 @@ impl Terminal {
         new_bg: Color,
         new_ansi: &[Color; 16],
     ) {
-        let remap = |color: Color| -> Color {
-            if color == old_fg {
-                return new_fg;
-            }
-            if color == old_bg {
-                return new_bg;
-            }
-            for (i, old_c) in old_ansi.iter().enumerate() {
-                if color == *old_c {
-                    return new_ansi[i];
-                }
-            }
-            color
+        let old = Palette {
+            fg: old_fg,
+            bg: old_bg,
+            ansi: *old_ansi,
+        };
+        let new = Palette {
+            fg: new_fg,
+            bg: new_bg,
+            ansi: *new_ansi,
         };
+        let remap = |color: Color| old.remap(&new, color);
+        self.remap_colors(remap);
+        self.default_fg = remap(self.default_fg);
+        self.default_bg = remap(self.default_bg);
+        self.ansi_palette = self.ansi_palette.map(remap);
+        self.theme_palette = new;
+    }
+
+    /// The default and ANSI colours currently in effect.
+    fn palette(&self) -> Palette {
+        Palette {
+            fg: self.default_fg,
+            bg: self.default_bg,
+            ansi: self.ansi_palette,
+        }
+    }
 
+    /// Switches to `palette`, recoloring cells drawn with the current one.
+    fn set_palette(&mut self, palette: Palette) {
+        let old = self.palette();
+        if old == palette {
+            return;
+        }
+        self.remap_colors(|color| old.remap(&palette, color));
+        self.default_fg = palette.fg;
+        self.default_bg = palette.bg;
+        self.ansi_palette = palette.ansi;
+    }
+
+    /// Applies `remap` to every cell colour and to the current SGR colours.
+    fn remap_colors(&mut self, remap: impl Fn(Color) -> Color) {
         let recolor_cell = |gc: &mut GraphemeCell| {
             gc.fg = remap(gc.fg);
             gc.bg = remap(gc.bg);
+            gc.underline_color = gc.underline_color.map(&remap);
         };
         self.screen.viewport_recolor(recolor_cell);
         self.screen.scrollback_recolor(recolor_cell);
-        if let Some(ref mut alt) = self.alt_screen {
-            alt.viewport_recolor(recolor_cell);
+        for other in self.alt_screen.iter_mut().chain(self.parked_alt_screen.iter_mut()) {
+            other.viewport_recolor(recolor_cell);
         }
 
         self.current_fg = remap(self.current_fg);
         self.current_bg = remap(self.current_bg);
-        self.default_fg = new_fg;
-        self.default_bg = new_bg;
-        self.ansi_palette = *new_ansi;
+        self.current_underline_color = self.current_underline_color.map(&remap);
+    }
+
+    /// Cursor colour set by OSC 12, if any.
+    pub fn cursor_color(&self) -> Option<Color> {
+        self.cursor_color
     }
 
     pub fn full_reset(&mut self) {
         let rows = self.screen.viewport_rows();
 
         self.alt_screen = None;
-        self.saved_cursor = PageCoord { abs_row: 0, col: 0 };
+        self.parked_alt_screen = None;
+        self.saved_cursor = SavedCursor::default();
         self.alt_saved_cursor = PageCoord { abs_row: 0, col: 0 };
         self.reset_scroll_region();
         self.saved_scroll_top = 0;
         self.saved_scroll_bottom = rows.saturating_sub(1);
+        self.tab_stops.reset();
+        self.saved_tab_stops.reset();
+        self.charsets = charsets::Charsets::default();
         self.decckm = false;
+        self.application_keypad = false;
+        self.origin_mode = false;
+        self.autowrap = true;
+        self.insert_mode = false;
+        self.linefeed_mode = false;
+        self.synchronized_output = None;
         self.cursor_visible = true;
         self.cursor_style = CursorStyle::default();
         self.pending_responses.clear();
         self.pending_clipboard_write = None;
+        self.pending_clipboard_reads.clear();
         self.bracketed_paste = false;
         self.modify_other_keys = 0;
+        self.keyboard_flags.clear();
+        self.saved_keyboard_flags.clear();
+        self.command_started_at = None;
         if self.security_config.clear_mouse_on_reset {
             self.clear_mouse_tracking(true);
         }
         self.cwd = None;
-        self.hyperlink_urls.clear();
-        self.hyperlink_url_index.clear();
+        self.hyperlinks.clear();
         self.current_hyperlink_id = 0;
+        self.dcs_handler = None;
+        self.graphics.clear();
+        self.sixel_images.clear();
+        self.cursor_color = None;
+        let theme = self.theme_palette;
+        self.set_palette(theme);
         self.reset_attributes();
+        self.set_effect(CellEffects::PROTECTED, false);
+        self.last_graphic = None;
         self.parser = Parser::new();
         self.reset_screen_buffer();
     }
@@ -518,6 +908,10 @@ impl Terminal {
         handlers::private_modes::handle_private_mode(self, params, intermediates, action)
     }
 
+    fn handle_ansi_mode(&mut self, params: &Params, intermediates: &[u8], action: char) -> bool {
+        handlers::private_modes::handle_ansi_mode(self, params, intermediates, action)
+    }
+
     fn handle_cursor_style_csi(
         &mut self,
         params: &Params,
@@ -548,34 +942,43 @@ impl Terminal {
         handlers::scroll::handle_scroll_csi(self, action, params)
     }
 
-    fn handle_erase_csi(&mut self, action: char, params: &Params) -> bool {
-        handlers::erase::handle_erase_csi(self, action, params)
+    fn handle_erase_csi(&mut self, action: char, params: &Params, intermediates: &[u8]) -> bool {
+        handlers::erase::handle_erase_csi(self, action, params, intermediates)
     }
 
     fn handle_device_csi(&mut self, action: char, params: &Params, intermediates: &[u8]) -> bool {
         handlers::device::handle_device_csi(self, action, params, intermediates)
     }
-}
 
-impl Perform for Terminal {
-    fn print(&mut self, c: char) {
+    /// Writes `c`, already mapped through the character sets, at the cursor.
+    fn print_graphic(&mut self, c: char) {
+        self.last_graphic = Some(c);
         let mut width = UnicodeWidthChar::width(c).unwrap_or(1);
         if width == 0 {
             // Keep combining marks visible as standalone glyphs instead of dropping them.
             width = 1;
         }
 
-        if self.cursor_col() + width > self.screen.cols() {
-            // Mark current row as soft-wrapped before moving to next row.
-            let cr = self.cursor_row();
-            self.screen.viewport_set_wrapped(cr, true);
-            self.set_cursor_col(0);
-            let next_row = self.cursor_row() + 1;
-            self.advance_cursor_row(next_row);
+        let cols = self.screen.cols();
+        if self.cursor_col() + width > cols {
+            if self.autowrap {
+                // Mark current row as soft-wrapped before moving to next row.
+                let cr = self.cursor_row();
+                self.screen.viewport_set_wrapped(cr, true);
+                self.set_cursor_col(0);
+                let next_row = self.cursor_row() + 1;
+                self.advance_cursor_row(next_row);
+            } else {
+                // Without DECAWM the last column is overwritten in place.
+                self.set_cursor_col(cols.saturating_sub(width));
+            }
         }
 
         let cr = self.cursor_row();
         let cc = self.cursor_col();
+        if self.insert_mode {
+            handlers::edit::insert_blank_cells(self, cr, cc, width);
+        }
         let mut gc = GraphemeCell::from_char(c);
         gc.fg = self.current_fg;
         gc.bg = self.current_bg;
@@ -585,6 +988,8 @@ impl Perform for Terminal {
         gc.reverse = self.current_reverse;
         gc.underline_style = self.current_underline_style;
         gc.strikethrough = self.current_strikethrough;
+        gc.effects = self.current_effects;
+        gc.underline_color = self.current_underline_color;
         gc.hyperlink_id = self.current_hyperlink_id;
         self.screen.viewport_set(cr, cc, gc);
 
@@ -594,7 +999,19 @@ impl Perform for Terminal {
             self.screen.viewport_set(cr, cc + 1, spacer_gc);
         }
 
-        self.set_cursor_col(cc + width);
+        let next_col = cc + width;
+        if self.autowrap {
+            self.set_cursor_col(next_col);
+        } else {
+            self.set_cursor_col(next_col.min(cols.saturating_sub(1)));
+        }
+    }
+}
+
+impl Perform for Terminal {
+    fn print(&mut self, c: char) {
+        let c = self.charsets.translate(c);
+        self.print_graphic(c);
     }
 
     fn execute(&mut self, byte: u8) {
@@ -606,6 +1023,9 @@ impl Perform for Terminal {
                 self.screen.viewport_set_wrapped(cr, false);
                 let next_row = cr + 1;
                 self.advance_cursor_row(next_row);
+                if self.linefeed_mode {
+                    self.set_cursor_col(0);
+                }
             }
             13 => {
                 self.set_cursor_col(0);
@@ -615,21 +1035,27 @@ impl Perform for Terminal {
                     self.set_cursor_col(self.cursor_col() - 1);
                 }
             }
-            9 => {
-                const DEFAULT_TAB_WIDTH: usize = 8;
-                let new_col =
-                    (self.cursor_col() + DEFAULT_TAB_WIDTH) & !(DEFAULT_TAB_WIDTH - 1);
-                self.set_cursor_col(new_col.min(self.screen.cols() - 1));
-            }
+            7 => self.pending_bells = self.pending_bells.saturating_add(1),
+            9 => handlers::tabs::horizontal_tab(self),
+            // SO/SI: invoke G1/G0 into GL.
+            14 => self.charsets.lock_shift(1),
+            15 => self.charsets.lock_shift(0),
             _ => {}
         }
     }
 
-    fn hook(&mut self, _params: &Params, _intermediates: &[u8], _ignore: bool, _action: char) {}
+    fn hook(&mut self, params: &Params, intermediates: &[u8], _ignore: bool, action: char) {
+        handlers::dcs::handle_dcs_hook(self, params, intermediates, action);
+    }
 
-    fn put(&mut self, _byte: u8) {}
-    fn unhook(&mut self) {}
-    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+    fn put(&mut self, byte: u8) {
+        handlers::dcs::handle_dcs_put(self, byte);
+    }
+
+    fn unhook(&mut self) {
+        handlers::dcs::handle_dcs_unhook(self);
+    }
+    fn osc_dispatch(&mut self, params: &[&[u8]], bell_terminated: bool) {
         if params.is_empty() {
             return;
         }
@@ -651,48 +1077,18 @@ impl Perform for Terminal {
                 let uri = String::from_utf8_lossy(params[1]);
                 self.cwd = parse_osc7_uri(&uri);
             }
-            // OSC 52: clipboard write — params[1] is selection (ignored), params[2] is base64 data
-            b"52" => {
-                if params.len() < 3 {
-                    return;
-                }
-                let payload = params[2];
-                // A payload of "?" is a clipboard read query — ignore it.
-                if payload == b"?" {
-                    return;
-                }
-                if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(payload)
-                    && let Ok(text) = String::from_utf8(bytes)
-                {
-                    self.pending_clipboard_write = Some(text);
-                }
+            // OSC 52: clipboard write and query
+            b"52" => handlers::clipboard::handle_clipboard_osc(self, params, bell_terminated),
+            // OSC 4/10/11/12: set or query colours; OSC 104/110/111/112: reset them
+            b"4" | b"10" | b"11" | b"12" | b"104" | b"110" | b"111" | b"112" => {
+                handlers::colors::handle_color_osc(self, params, bell_terminated);
             }
+            // OSC 9 (iTerm2) and OSC 777 (rxvt-unicode): desktop notifications
+            b"9" | b"777" => handlers::notifications::handle_notification_osc(self, params),
+            // OSC 133: semantic prompt marks (FinalTerm shell integration)
+            b"133" => handlers::semantic_prompt::handle_semantic_prompt(self, params),
             // OSC 8: hyperlinks — OSC 8 ; params ; uri ST
-            // params[1] may contain link attributes (e.g. `id=foo`) per spec; we ignore them
-            // since most tools do not use them and URL-based deduplication is sufficient.
-            b"8" => {
-                let uri = params.get(2).copied().unwrap_or(b"");
-                if uri.is_empty() {
-                    self.current_hyperlink_id = 0;
-                } else {
-                    use std::collections::hash_map::Entry;
-                    let url = String::from_utf8_lossy(uri).into_owned();
-                    self.current_hyperlink_id = match self.hyperlink_url_index.entry(url) {
-                        Entry::Occupied(e) => *e.get(),
-                        Entry::Vacant(e) => {
-                            if self.hyperlink_urls.len() >= HYPERLINK_URL_TABLE_MAX {
-                                // Table full — treat as no hyperlink rather than growing unboundedly.
-                                eprintln!("[ferrum] OSC 8: hyperlink URL table full ({HYPERLINK_URL_TABLE_MAX} entries); ignoring new URL");
-                                return;
-                            }
-                            let id = (self.hyperlink_urls.len() + 1) as u16;
-                            self.hyperlink_urls.push(e.key().clone());
-                            e.insert(id);
-                            id
-                        }
-                    };
-                }
-            }
+            b"8" => handlers::hyperlinks::handle_hyperlink_osc(self, params),
             _ => {}
         }
     }
@@ -704,9 +1100,15 @@ impl Perform for Terminal {
         if self.handle_private_mode(params, intermediates, action) {
             return;
         }
+        if self.handle_ansi_mode(params, intermediates, action) {
+            return;
+        }
         if self.handle_cursor_style_csi(params, intermediates, action) {
             return;
         }
+        if handlers::keyboard::handle_keyboard_csi(self, params, intermediates, action) {
+            return;
+        }
         if action == 'm' {
             if intermediates == b">" {
                 // modifyOtherKeys: ESC [ > 4 ; <level> m
@@ -724,6 +1126,16 @@ impl Perform for Terminal {
             self.handle_sgr(params);
             return;
         }
+        if handlers::reports::handle_mode_report_csi(self, params, intermediates, action) {
+            return;
+        }
+        if intermediates == b"!" && action == 'p' {
+            handlers::private_modes::soft_reset(self);
+            return;
+        }
+        if handlers::tabs::handle_tab_csi(self, params, intermediates, action) {
+            return;
+        }
         if self.handle_cursor_csi(action, params) {
             return;
         }
@@ -733,29 +1145,30 @@ impl Perform for Terminal {
         if self.handle_scroll_csi(action, params) {
             return;
         }
-        if self.handle_erase_csi(action, params) {
+        if self.handle_erase_csi(action, params, intermediates) {
             return;
         }
         self.handle_device_csi(action, params, intermediates);
     }
 
-    fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, byte: u8) {
-        match byte {
-            b'7' => {
-                self.saved_cursor = PageCoord {
-                    abs_row: self.cursor_pin.coord().abs_row,
-                    col: self.cursor_col(),
-                };
+    fn esc_dispatch(&mut self, intermediates: &[u8], _ignore: bool, byte: u8) {
+        if intermediates == b"#" {
+            if byte == b'8' {
+                handlers::erase::screen_alignment_test(self); // DECALN
             }
-            b'8' => {
-                let from_row = self.cursor_row();
-                let vstart = self.screen.viewport_start_abs();
-                let row = self.saved_cursor.abs_row.saturating_sub(vstart)
-                    .min(self.screen.viewport_rows().saturating_sub(1));
-                let col = self.saved_cursor.col.min(self.screen.cols().saturating_sub(1));
-                self.set_cursor(row, col);
-                self.maybe_record_cursor_rewrite(from_row, self.cursor_row());
+            return;
+        }
+        // SCS — designate a character set into G0–G3.
+        if let [designator @ (b'(' | b')' | b'*' | b'+')] = intermediates {
+            if let Some(charset) = charsets::Charset::from_final(byte) {
+                let slot = usize::from(designator - b'(');
+                self.charsets.designate(slot, charset);
             }
+            return;
+        }
+        match byte {
+            b'7' => self.save_cursor(),    // DECSC
+            b'8' => self.restore_cursor(), // DECRC
             b'M' => {
                 let from_row = self.cursor_row();
                 // Reverse Index: cursor up, scroll down if at top of region
@@ -766,7 +1179,14 @@ impl Perform for Terminal {
                 }
                 self.maybe_record_cursor_rewrite(from_row, self.cursor_row());
             }
+            b'H' if intermediates.is_empty() => handlers::tabs::set_tab_stop(self), // HTS
+            b'=' => self.set_application_keypad(true), // DECKPAM
+            b'>' => self.set_application_keypad(false), // DECKPNM
             b'c' => self.full_reset(), // RIS - full terminal reset
+            b'N' => self.charsets.single_shift(2), // SS2
+            b'O' => self.charsets.single_shift(3), // SS3
+            b'n' => self.charsets.lock_shift(2), // LS2
+            b'o' => self.charsets.lock_shift(3), // LS3
             _ => {}
         }
     }
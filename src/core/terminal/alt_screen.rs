@@ -1,60 +1,223 @@
-//! Alternate screen buffer management (used by vim, htop, less, etc.).
+//! Alternate screen buffer management (used by vim, htop, less, etc.) and
+//! cursor save/restore.
+//!
+//! The modes follow xterm:
+//! - `?47` switches buffers and nothing else; the alternate screen keeps its
+//!   contents between visits.
+//! - `?1047` is the same, but clears the alternate screen when leaving it.
+//! - `?1048` saves (`h`) or restores (`l`) the cursor like DECSC/DECRC.
+//! - `?1049` saves the cursor and switches to a cleared alternate screen;
+//!   leaving restores the cursor.
 
 use super::super::{PageCoord, PageList};
 
-use super::CursorStyle;
+use super::{CursorStyle, SavedCursor};
 
 impl super::Terminal {
-    /// Enters the alternate screen buffer.
+    /// DECSC (`ESC 7`, `?1048h`): saves the cursor position, origin mode,
+    /// autowrap and character sets.
+    pub(super) fn save_cursor(&mut self) {
+        self.saved_cursor = SavedCursor {
+            coord: PageCoord {
+                abs_row: self.cursor_pin.coord().abs_row,
+                col: self.cursor_col(),
+            },
+            origin_mode: self.origin_mode,
+            autowrap: self.autowrap,
+            charsets: self.charsets,
+        };
+    }
+
+    /// DECRC (`ESC 8`, `?1048l`): restores what `save_cursor` saved.
+    pub(super) fn restore_cursor(&mut self) {
+        let from_row = self.cursor_row();
+        let saved = self.saved_cursor;
+        self.origin_mode = saved.origin_mode;
+        self.autowrap = saved.autowrap;
+        self.charsets = saved.charsets;
+        let vstart = self.screen.viewport_start_abs();
+        let row = saved.coord.abs_row.saturating_sub(vstart)
+            .min(self.screen.viewport_rows().saturating_sub(1));
+        let col = saved.coord.col.min(self.screen.cols().saturating_sub(1));
+        self.set_cursor(row, col);
+        self.maybe_record_cursor_rewrite(from_row, self.cursor_row());
+    }
+
+    /// Enters the alternate screen buffer (`?1049h`).
     ///
-    /// Saves the current screen, cursor position, and scroll region,
-    /// then replaces the screen with a blank one.
+    /// Saves the cursor position, then switches to a cleared alternate
+    /// screen with the cursor at the top-left corner.
     pub(super) fn enter_alt_screen(&mut self) {
         if self.alt_screen.is_none() {
-            let rows = self.screen.viewport_rows();
-            let cols = self.screen.cols();
-
-            let alt_screen = PageList::new(rows, cols, 0); // alt screen has no scrollback
-            let abs_start = alt_screen.viewport_start_abs();
-            // Save cursor coord before swapping.
             self.alt_saved_cursor = PageCoord {
                 abs_row: self.cursor_pin.coord().abs_row,
                 col: self.cursor_col(),
             };
-            let main_screen = std::mem::replace(&mut self.screen, alt_screen);
-            self.alt_screen = Some(main_screen);
-            // Register a new cursor pin on the alt screen at (0, 0).
-            let new_cursor_coord = PageCoord { abs_row: abs_start, col: 0 };
-            self.cursor_pin = PageList::pin_at(new_cursor_coord);
-
-            self.saved_scroll_top = self.scroll_top;
-            self.saved_scroll_bottom = self.scroll_bottom;
-            // cursor_row() == 0, cursor_col() == 0 automatically (pin at abs_start, col 0).
-            self.cursor_style = CursorStyle::BlinkingBlock;
-            self.scroll_top = 0;
-            self.scroll_bottom = rows - 1;
+            self.switch_to_alt_screen(true);
+            self.set_cursor(0, 0);
         }
     }
 
-    /// Leaves the alternate screen and restores the main buffer.
+    /// Leaves the alternate screen and restores the main buffer and the
+    /// cursor saved by `enter_alt_screen` (`?1049l`).
     pub(super) fn leave_alt_screen(&mut self) {
-        if let Some(main_screen) = self.alt_screen.take() {
-            self.screen = main_screen;
-            // Restore cursor position from saved coord.
+        if self.alt_screen.is_some() {
+            self.switch_to_main_screen(false);
             let saved = self.alt_saved_cursor;
             let vstart = self.screen.viewport_start_abs();
-            let rows = self.screen.viewport_rows();
-            let cols = self.screen.cols();
-            let row = saved.abs_row.saturating_sub(vstart).min(rows.saturating_sub(1));
-            let col = saved.col.min(cols.saturating_sub(1));
-            self.cursor_pin = PageList::pin_at(PageCoord {
-                abs_row: vstart + row,
-                col,
-            });
-
-            self.scroll_top = self.saved_scroll_top;
-            self.scroll_bottom = self.saved_scroll_bottom;
-            self.cursor_style = CursorStyle::default();
+            let row = saved.abs_row.saturating_sub(vstart)
+                .min(self.screen.viewport_rows().saturating_sub(1));
+            let col = saved.col.min(self.screen.cols().saturating_sub(1));
+            self.set_cursor(row, col);
         }
     }
+
+    /// Swaps the alternate screen in, blank when `clear` or on first use
+    /// (`?47h`, `?1047h`). The cursor keeps its position.
+    pub(super) fn switch_to_alt_screen(&mut self, clear: bool) {
+        if self.alt_screen.is_some() {
+            return;
+        }
+        let rows = self.screen.viewport_rows();
+        let cols = self.screen.cols();
+        let (row, col) = (self.cursor_row(), self.cursor_col());
+
+        let alt_screen = match self.parked_alt_screen.take() {
+            Some(parked) if !clear => parked,
+            parked => {
+                if parked.is_some() {
+                    self.hyperlinks.note_dropped();
+                }
+                PageList::new(rows, cols, 0) // alt screen has no scrollback
+            }
+        };
+        let main_screen = std::mem::replace(&mut self.screen, alt_screen);
+        self.alt_screen = Some(main_screen);
+        // Register a new cursor pin on the alt screen at the same grid position.
+        let abs_start = self.screen.viewport_start_abs();
+        self.cursor_pin = PageList::pin_at(PageCoord { abs_row: abs_start + row, col });
+
+        self.saved_scroll_top = self.scroll_top;
+        self.saved_scroll_bottom = self.scroll_bottom;
+        self.cursor_style = CursorStyle::BlinkingBlock;
+        // The alternate screen starts with its own, empty keyboard flag stack.
+        self.saved_keyboard_flags = std::mem::take(&mut self.keyboard_flags);
+        // ...and with default tab stops, so the shell's survive what the app sets.
+        let mut alt_tab_stops = self.tab_stops.clone();
+        alt_tab_stops.reset();
+        self.saved_tab_stops = std::mem::replace(&mut self.tab_stops, alt_tab_stops);
+        self.scroll_top = 0;
+        self.scroll_bottom = rows - 1;
+    }
+
+    /// Swaps the main screen back in, parking the alternate screen unless
+    /// `clear` discards it (`?47l`, and `?1047l` with `clear`).
+    pub(super) fn switch_to_main_screen(&mut self, clear: bool) {
+        let Some(main_screen) = self.alt_screen.take() else {
+            return;
+        };
+        let (row, col) = (self.cursor_row(), self.cursor_col());
+        let alt_screen = std::mem::replace(&mut self.screen, main_screen);
+        if clear {
+            self.hyperlinks.note_dropped();
+        }
+        self.parked_alt_screen = (!clear).then_some(alt_screen);
+        let vstart = self.screen.viewport_start_abs();
+        let row = row.min(self.screen.viewport_rows().saturating_sub(1));
+        let col = col.min(self.screen.cols().saturating_sub(1));
+        self.cursor_pin = PageList::pin_at(PageCoord {
+            abs_row: vstart + row,
+            col,
+        });
+
+        self.scroll_top = self.saved_scroll_top;
+        self.scroll_bottom = self.saved_scroll_bottom;
+        self.cursor_style = CursorStyle::default();
+        self.keyboard_flags = std::mem::take(&mut self.saved_keyboard_flags);
+        std::mem::swap(&mut self.tab_stops, &mut self.saved_tab_stops);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::terminal::Terminal;
+
+    fn row_text(term: &Terminal, row: usize) -> String {
+        (0..term.screen.cols())
+            .map(|col| term.screen.viewport_get(row, col).first_char())
+            .collect::<String>()
+            .trim_end()
+            .to_owned()
+    }
+
+    #[test]
+    fn mode_47_keeps_alt_contents_and_cursor() {
+        let mut term = Terminal::new(3, 10);
+        term.process(b"main\x1b[2;3H\x1b[?47h");
+        assert!(term.is_alt_screen());
+        assert_eq!((term.cursor_row(), term.cursor_col()), (1, 2));
+        assert_eq!(row_text(&term, 0), "");
+        term.process(b"alt");
+
+        term.process(b"\x1b[?47l");
+        assert!(!term.is_alt_screen());
+        assert_eq!(row_text(&term, 0), "main");
+        assert_eq!((term.cursor_row(), term.cursor_col()), (1, 5));
+
+        // Switching back finds the alternate screen as it was left.
+        term.process(b"\x1b[?47h");
+        assert_eq!(row_text(&term, 1), "  alt");
+    }
+
+    #[test]
+    fn mode_1047_clears_alt_screen_on_exit() {
+        let mut term = Terminal::new(3, 10);
+        term.process(b"\x1b[?1047halt\x1b[?1047l");
+        assert!(!term.is_alt_screen());
+        assert_eq!(term.cursor_col(), 3);
+        term.process(b"\x1b[?1047h");
+        assert_eq!(row_text(&term, 0), "");
+    }
+
+    #[test]
+    fn mode_1049_clears_alt_screen_on_entry() {
+        let mut term = Terminal::new(3, 10);
+        term.process(b"\x1b[?47halt\x1b[?47l\x1b[2;4H\x1b[?1049h");
+        assert_eq!(row_text(&term, 0), "");
+        assert_eq!((term.cursor_row(), term.cursor_col()), (0, 0));
+        term.process(b"\x1b[?1049l");
+        assert_eq!((term.cursor_row(), term.cursor_col()), (1, 3));
+    }
+
+    #[test]
+    fn mode_1048_saves_and_restores_cursor() {
+        let mut term = Terminal::new(4, 10);
+        term.process(b"\x1b[3;5H\x1b[?1048h\x1b[H\x1b[?1048l");
+        assert!(!term.is_alt_screen());
+        assert_eq!((term.cursor_row(), term.cursor_col()), (2, 4));
+        // Shares the DECSC slot.
+        term.process(b"\x1b[1;2H\x1b7\x1b[4;4H\x1b[?1048l");
+        assert_eq!((term.cursor_row(), term.cursor_col()), (0, 1));
+    }
+
+    #[test]
+    fn parked_alt_screen_follows_resize() {
+        let mut term = Terminal::new(3, 10);
+        term.process(b"\x1b[?47hx\x1b[?47l");
+        term.resize(5, 6);
+        term.process(b"\x1b[?47h");
+        assert_eq!(term.screen.viewport_rows(), 5);
+        assert_eq!(term.screen.cols(), 6);
+        assert_eq!(row_text(&term, 0), "x");
+    }
+
+    #[test]
+    fn alt_screen_tab_stops_do_not_leak_into_main() {
+        let mut term = Terminal::new(3, 20);
+        term.process(b"\x1b[3G\x1bH\r");
+        term.process(b"\x1b[?1049h\x1b[3g\x1b[5G\x1bH\r\t");
+        assert_eq!(term.cursor_col(), 4);
+        term.process(b"\x1b[?1049l\r\t");
+        assert_eq!(term.cursor_col(), 2);
+    }
 }
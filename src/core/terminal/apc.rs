@@ -0,0 +1,169 @@
+//! APC (`ESC _ ... ESC \`) extraction in front of the VT parser.
+//!
+//! `vte` consumes APC strings without reporting them, so `Terminal::process`
+//! runs every chunk through this scanner first: ordinary bytes are forwarded
+//! to the parser in contiguous runs, APC payloads are collected here.  State
+//! is kept between calls, so sequences split across PTY reads still work.
+
+/// Upper bound on a single APC payload; longer strings are discarded.
+const MAX_APC_BYTES: usize = 16 * 1024 * 1024;
+
+const ESC: u8 = 0x1b;
+const CAN: u8 = 0x18;
+const SUB: u8 = 0x1a;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+enum State {
+    #[default]
+    Ground,
+    /// An ESC was seen in ground state and has not been forwarded yet.
+    Escape,
+    /// Inside the APC string.
+    String,
+    /// An ESC was seen inside the APC string (possible ST).
+    StringEscape,
+}
+
+/// What `Terminal::process` should do with the bytes the scanner looked at.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(super) enum ApcStep {
+    /// Feed the first `n` bytes to the VT parser.
+    Forward(usize),
+    /// Feed the ESC held back by the previous step to the VT parser; no input consumed.
+    ForwardEsc,
+    /// The first `n` bytes were consumed by the scanner.
+    Consumed(usize),
+    /// The first `n` bytes completed an APC string; take it with `take_payload`.
+    Complete(usize),
+}
+
+#[derive(Default)]
+pub(super) struct ApcScanner {
+    state: State,
+    payload: Vec<u8>,
+    overflowed: bool,
+}
+
+impl ApcScanner {
+    /// Examines the start of `bytes` (which must not be empty).
+    pub(super) fn step(&mut self, bytes: &[u8]) -> ApcStep {
+        match self.state {
+            State::Ground => match bytes.iter().position(|&b| b == ESC) {
+                Some(0) => {
+                    self.state = State::Escape;
+                    ApcStep::Consumed(1)
+                }
+                Some(pos) => ApcStep::Forward(pos),
+                None => ApcStep::Forward(bytes.len()),
+            },
+            State::Escape => {
+                if bytes[0] == b'_' {
+                    self.state = State::String;
+                    self.payload.clear();
+                    self.overflowed = false;
+                    ApcStep::Consumed(1)
+                } else {
+                    self.state = State::Ground;
+                    ApcStep::ForwardEsc
+                }
+            }
+            State::String => {
+                let end = bytes
+                    .iter()
+                    .position(|&b| matches!(b, ESC | CAN | SUB))
+                    .unwrap_or(bytes.len());
+                self.push_payload(&bytes[..end]);
+                match bytes.get(end) {
+                    Some(&ESC) => {
+                        self.state = State::StringEscape;
+                        ApcStep::Consumed(end + 1)
+                    }
+                    // CAN / SUB abort the string.
+                    Some(_) => {
+                        self.state = State::Ground;
+                        self.payload.clear();
+                        ApcStep::Consumed(end + 1)
+                    }
+                    None => ApcStep::Consumed(end),
+                }
+            }
+            State::StringEscape => {
+                if bytes[0] == b'\\' {
+                    self.state = State::Ground;
+                    if self.overflowed {
+                        self.payload.clear();
+                        ApcStep::Consumed(1)
+                    } else {
+                        ApcStep::Complete(1)
+                    }
+                } else {
+                    // Any other escape aborts the string and starts a new sequence.
+                    self.payload.clear();
+                    self.state = State::Escape;
+                    ApcStep::Consumed(0)
+                }
+            }
+        }
+    }
+
+    /// Takes the payload of the APC string completed by the last step.
+    pub(super) fn take_payload(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.payload)
+    }
+
+    fn push_payload(&mut self, bytes: &[u8]) {
+        if self.overflowed {
+            return;
+        }
+        if self.payload.len() + bytes.len() > MAX_APC_BYTES {
+            self.overflowed = true;
+            self.payload = Vec::new();
+        } else {
+            self.payload.extend_from_slice(bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::terminal::Terminal;
+
+    fn printed(term: &Terminal) -> String {
+        (0..term.screen.cols())
+            .map(|c| term.screen.viewport_get(0, c).first_char())
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+
+    #[test]
+    fn apc_string_is_not_printed() {
+        let mut term = Terminal::new(2, 20);
+        term.process(b"a\x1b_Xjunk\x1b\\b");
+        assert_eq!(printed(&term), "ab");
+    }
+
+    #[test]
+    fn apc_split_across_chunks() {
+        let mut term = Terminal::new(2, 20);
+        for chunk in [&b"a\x1b"[..], b"_Xju", b"nk\x1b", b"\\b"] {
+            term.process(chunk);
+        }
+        assert_eq!(printed(&term), "ab");
+    }
+
+    #[test]
+    fn escape_sequences_still_reach_parser() {
+        let mut term = Terminal::new(2, 20);
+        term.process(b"abc\x1b");
+        term.process(b"[2Dx");
+        assert_eq!(printed(&term), "axc");
+    }
+
+    #[test]
+    fn escape_inside_apc_aborts_it() {
+        let mut term = Terminal::new(2, 20);
+        term.process(b"\x1b_junk\x1b[1Cx");
+        assert_eq!(printed(&term), " x");
+    }
+}
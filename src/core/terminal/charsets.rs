@@ -0,0 +1,128 @@
+//! Character sets G0–G3 (ISO 2022 designation and shifts, as in VT220).
+
+/// A 94-character set a G slot can hold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum Charset {
+    #[default]
+    Ascii,
+    /// DEC Special Graphics: line drawing and symbols in place of `_`–`~`.
+    DecSpecialGraphics,
+    /// United Kingdom: `#` is the pound sign.
+    Uk,
+}
+
+impl Charset {
+    /// The set selected by the final byte of `ESC ( F` and friends.
+    pub(super) fn from_final(byte: u8) -> Option<Self> {
+        match byte {
+            b'B' => Some(Self::Ascii),
+            b'0' => Some(Self::DecSpecialGraphics),
+            b'A' => Some(Self::Uk),
+            _ => None,
+        }
+    }
+
+    fn map(self, c: char) -> char {
+        match self {
+            Self::Ascii => c,
+            Self::Uk if c == '#' => '£',
+            Self::Uk => c,
+            Self::DecSpecialGraphics => match c {
+                '_' => ' ',
+                '`' => '◆',
+                'a' => '▒',
+                'b' => '␉',
+                'c' => '␌',
+                'd' => '␍',
+                'e' => '␊',
+                'f' => '°',
+                'g' => '±',
+                'h' => '␤',
+                'i' => '␋',
+                'j' => '┘',
+                'k' => '┐',
+                'l' => '┌',
+                'm' => '└',
+                'n' => '┼',
+                'o' => '⎺',
+                'p' => '⎻',
+                'q' => '─',
+                'r' => '⎼',
+                's' => '⎽',
+                't' => '├',
+                'u' => '┤',
+                'v' => '┴',
+                'w' => '┬',
+                'x' => '│',
+                'y' => '≤',
+                'z' => '≥',
+                '{' => 'π',
+                '|' => '≠',
+                '}' => '£',
+                '~' => '·',
+                _ => c,
+            },
+        }
+    }
+}
+
+/// Designated sets, the set invoked into GL and a pending single shift.
+///
+/// Saved and restored with the cursor by DECSC/DECRC.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) struct Charsets {
+    slots: [Charset; 4],
+    gl: usize,
+    single_shift: Option<usize>,
+}
+
+impl Charsets {
+    /// Designates `charset` into G`slot` (`ESC (`, `)`, `*`, `+`).
+    pub(super) fn designate(&mut self, slot: usize, charset: Charset) {
+        if let Some(g) = self.slots.get_mut(slot) {
+            *g = charset;
+        }
+    }
+
+    /// Invokes G`slot` into GL until the next locking shift (SI, SO, LS2, LS3).
+    pub(super) fn lock_shift(&mut self, slot: usize) {
+        self.gl = slot.min(3);
+    }
+
+    /// Uses G`slot` for the next printed character only (SS2, SS3).
+    pub(super) fn single_shift(&mut self, slot: usize) {
+        self.single_shift = Some(slot.min(3));
+    }
+
+    /// Maps a printed character through the active set.
+    pub(super) fn translate(&mut self, c: char) -> char {
+        let slot = self.single_shift.take().unwrap_or(self.gl);
+        self.slots[slot].map(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shifts_select_the_designated_set() {
+        let mut sets = Charsets::default();
+        sets.designate(1, Charset::DecSpecialGraphics);
+        assert_eq!(sets.translate('q'), 'q');
+        sets.lock_shift(1);
+        assert_eq!(sets.translate('q'), '─');
+        assert_eq!(sets.translate('A'), 'A');
+        sets.lock_shift(0);
+        assert_eq!(sets.translate('x'), 'x');
+    }
+
+    #[test]
+    fn single_shift_applies_to_one_character() {
+        let mut sets = Charsets::default();
+        sets.designate(2, Charset::Uk);
+        sets.single_shift(2);
+        assert_eq!(sets.translate('#'), '£');
+        assert_eq!(sets.translate('#'), '#');
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::UnderlineStyle;
+use crate::core::{CellEffects, UnderlineStyle};
 use crate::core::terminal::Terminal;
 
 pub(in super::super) fn reset_attributes(term: &mut Terminal) {
@@ -12,4 +12,6 @@ pub(in super::super) fn reset_attributes(term: &mut Terminal) {
     term.set_reverse(false);
     term.set_underline_style(UnderlineStyle::None);
     term.set_strikethrough(false);
+    term.set_effect(CellEffects::BLINK | CellEffects::HIDDEN | CellEffects::OVERLINE, false);
+    term.set_underline_color(None);
 }
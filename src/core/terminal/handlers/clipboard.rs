@@ -0,0 +1,159 @@
+//! OSC 52 clipboard access: `52 ; Pc ; Pd` writes base64 data `Pd` to the
+//! selection named by `Pc`, and `Pd` = `?` queries it.
+//!
+//! Only the first of the selection characters in `Pc` is honoured; cut
+//! buffers (`0`-`7`) are not supported.
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+
+use crate::core::SecurityEventKind;
+use crate::core::terminal::{ClipboardRead, ClipboardTarget, Terminal};
+
+/// Queries that may wait for an answer at once; later ones are refused.
+const MAX_PENDING_READS: usize = 16;
+
+pub(in super::super) fn handle_clipboard_osc(
+    term: &mut Terminal,
+    params: &[&[u8]],
+    bell_terminated: bool,
+) {
+    let (Some(&selection_param), Some(&payload)) = (params.get(1), params.get(2)) else {
+        return;
+    };
+    let Some((selection, target)) = parse_selection(selection_param) else {
+        return;
+    };
+
+    if payload == b"?" {
+        let read = ClipboardRead {
+            target,
+            selection,
+            bell_terminated,
+        };
+        if term.security_config.allow_clipboard_read
+            && term.pending_clipboard_reads.len() < MAX_PENDING_READS
+        {
+            term.pending_clipboard_reads.push_back((read, Vec::new()));
+        } else {
+            term.emit_security_event(SecurityEventKind::ClipboardRead);
+            term.respond(&reply_bytes(&read, ""));
+        }
+        return;
+    }
+
+    if let Ok(bytes) = STANDARD.decode(payload)
+        && let Ok(text) = String::from_utf8(bytes)
+    {
+        term.pending_clipboard_write = Some((target, text));
+    }
+}
+
+/// The answer to a clipboard query: `text` base64-encoded.
+pub(in super::super) fn reply_bytes(read: &ClipboardRead, text: &str) -> Vec<u8> {
+    let terminator = if read.bell_terminated {
+        "\x07"
+    } else {
+        "\x1b\\"
+    };
+    format!(
+        "\x1b]52;{};{}{terminator}",
+        read.selection as char,
+        STANDARD.encode(text)
+    )
+    .into_bytes()
+}
+
+/// Picks the first supported selection in `Pc`; an empty `Pc` means the
+/// clipboard.
+fn parse_selection(param: &[u8]) -> Option<(u8, ClipboardTarget)> {
+    if param.is_empty() {
+        return Some((b'c', ClipboardTarget::Clipboard));
+    }
+    param.iter().find_map(|&c| match c {
+        b'c' => Some((c, ClipboardTarget::Clipboard)),
+        b'p' | b's' => Some((c, ClipboardTarget::Primary)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(term: &mut Terminal, query: &[u8]) -> Option<ClipboardRead> {
+        term.process(query);
+        term.pending_clipboard_reads().next().cloned()
+    }
+
+    #[test]
+    fn write_honours_selection_parameter() {
+        let mut term = Terminal::new(2, 10);
+        term.process(b"\x1b]52;p;aGVsbG8=\x07");
+        assert_eq!(
+            term.drain_clipboard_write(),
+            Some((ClipboardTarget::Primary, "hello".to_owned()))
+        );
+        term.process(b"\x1b]52;;aGk=\x1b\\");
+        assert_eq!(
+            term.drain_clipboard_write(),
+            Some((ClipboardTarget::Clipboard, "hi".to_owned()))
+        );
+        // Cut buffers only: nothing to write to.
+        term.process(b"\x1b]52;0;aGk=\x1b\\");
+        assert_eq!(term.drain_clipboard_write(), None);
+    }
+
+    #[test]
+    fn query_is_refused_unless_allowed() {
+        let mut term = Terminal::new(2, 10);
+        assert_eq!(read(&mut term, b"\x1b]52;c;?\x07"), None);
+        assert_eq!(
+            String::from_utf8(term.drain_responses()).unwrap(),
+            "\x1b]52;c;\x07"
+        );
+        assert_eq!(
+            term.drain_security_events(),
+            vec![SecurityEventKind::ClipboardRead]
+        );
+    }
+
+    #[test]
+    fn allowed_query_is_answered_in_base64() {
+        let mut term = Terminal::new(2, 10);
+        term.security_config.allow_clipboard_read = true;
+        let query = read(&mut term, b"\x1b]52;s;?\x1b\\").unwrap();
+        assert_eq!(query.target, ClipboardTarget::Primary);
+        assert!(term.drain_responses().is_empty());
+
+        term.answer_clipboard_read(Some("hello"));
+        assert_eq!(
+            String::from_utf8(term.drain_responses()).unwrap(),
+            "\x1b]52;s;aGVsbG8=\x1b\\"
+        );
+    }
+
+    #[test]
+    fn replies_after_a_pending_query_keep_their_order() {
+        let mut term = Terminal::new(2, 10);
+        term.security_config.allow_clipboard_read = true;
+        term.process(b"\x1b[5n\x1b]52;c;?\x07\x1b[c\x1b]52;p;?\x07\x1b[5n");
+        assert_eq!(term.pending_clipboard_reads().count(), 2);
+        assert_eq!(
+            String::from_utf8(term.drain_responses()).unwrap(),
+            "\x1b[0n"
+        );
+
+        term.answer_clipboard_read(Some("a"));
+        let after_first = String::from_utf8(term.drain_responses()).unwrap();
+        assert!(after_first.starts_with("\x1b]52;c;YQ==\x07\x1b[?"));
+        assert!(!after_first.contains("0n"));
+
+        term.answer_clipboard_read(None);
+        assert_eq!(
+            String::from_utf8(term.drain_responses()).unwrap(),
+            "\x1b]52;p;\x07\x1b[0n"
+        );
+        assert_eq!(term.pending_clipboard_reads().count(), 0);
+    }
+}
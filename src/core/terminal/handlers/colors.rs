@@ -0,0 +1,293 @@
+//! Dynamic colours: OSC 4/10/11/12 set and query the ANSI palette, default
+//! foreground, default background and cursor colour; OSC 104/110/111/112
+//! restore them from the theme.
+
+use crate::core::Color;
+use crate::core::terminal::{Palette, Terminal};
+
+/// OSC numbers of the dynamic colours, in the order OSC 10 assigns them.
+const FOREGROUND: u16 = 10;
+const BACKGROUND: u16 = 11;
+const CURSOR: u16 = 12;
+
+pub(in super::super) fn handle_color_osc(
+    term: &mut Terminal,
+    params: &[&[u8]],
+    bell_terminated: bool,
+) {
+    // Replies end the same way as the query, as in xterm.
+    let terminator = if bell_terminated { "\x07" } else { "\x1b\\" };
+    let Some(code) = parse_number(params[0]) else {
+        return;
+    };
+    // Changes are collected and applied once, so the cells are recoloured in
+    // a single pass from the palette before the sequence to the one after it.
+    let mut palette = term.palette();
+    match code {
+        4 => {
+            for pair in params[1..].chunks(2) {
+                let [index, spec] = pair else {
+                    break;
+                };
+                let Some(index) = parse_number(index).filter(|&i| i <= 255) else {
+                    continue;
+                };
+                if *spec == b"?" {
+                    let color = match palette.ansi.get(index as usize) {
+                        Some(&color) => color,
+                        None => term.color_from_256(index),
+                    };
+                    let reply = format!("\x1b]4;{index};{}{terminator}", format_color(color));
+                    term.respond(reply.as_bytes());
+                } else if let Some(color) = parse_color_spec(spec)
+                    && index < 16
+                {
+                    // Only the 16 ANSI colours are configurable; 16-255 are fixed.
+                    palette.ansi[index as usize] = color;
+                }
+            }
+        }
+        // Each further parameter sets the next dynamic colour.
+        FOREGROUND..=CURSOR => {
+            for (slot, spec) in (code..=CURSOR).zip(&params[1..]) {
+                if *spec == b"?" {
+                    let color = dynamic_color(term, &palette, slot);
+                    let reply = format!("\x1b]{slot};{}{terminator}", format_color(color));
+                    term.respond(reply.as_bytes());
+                } else if let Some(color) = parse_color_spec(spec) {
+                    set_dynamic_color(term, &mut palette, slot, Some(color));
+                }
+            }
+        }
+        104 => {
+            let theme = term.theme_palette;
+            if params.len() < 2 || params[1..].iter().all(|p| p.is_empty()) {
+                palette.ansi = theme.ansi;
+            } else {
+                for index in params[1..].iter().filter_map(|p| parse_number(p)) {
+                    if let Some(slot) = palette.ansi.get_mut(index as usize) {
+                        *slot = theme.ansi[index as usize];
+                    }
+                }
+            }
+        }
+        110..=112 => set_dynamic_color(term, &mut palette, code - 100, None),
+        _ => {}
+    }
+    term.set_palette(palette);
+}
+
+fn dynamic_color(term: &Terminal, palette: &Palette, slot: u16) -> Color {
+    match slot {
+        FOREGROUND => palette.fg,
+        BACKGROUND => palette.bg,
+        _ => term.cursor_color.unwrap_or(palette.fg),
+    }
+}
+
+/// Sets dynamic colour `slot` in `palette`, or the cursor colour; `None`
+/// restores the theme colour.
+fn set_dynamic_color(
+    term: &mut Terminal,
+    palette: &mut Palette,
+    slot: u16,
+    color: Option<Color>,
+) {
+    let theme = term.theme_palette;
+    match slot {
+        FOREGROUND => palette.fg = color.unwrap_or(theme.fg),
+        BACKGROUND => palette.bg = color.unwrap_or(theme.bg),
+        _ => term.cursor_color = color,
+    }
+}
+
+fn parse_number(param: &[u8]) -> Option<u16> {
+    std::str::from_utf8(param).ok()?.parse().ok()
+}
+
+/// Parses an X11 colour spec: `rgb:R/G/B` with 1-4 hex digits per
+/// component, or `#RGB` with 1-4 digits per component.
+fn parse_color_spec(spec: &[u8]) -> Option<Color> {
+    let spec = std::str::from_utf8(spec).ok()?;
+    let (r, g, b) = if let Some(rgb) = spec.strip_prefix("rgb:") {
+        let mut parts = rgb.split('/');
+        let color = (
+            scaled_component(parts.next()?)?,
+            scaled_component(parts.next()?)?,
+            scaled_component(parts.next()?)?,
+        );
+        if parts.next().is_some() {
+            return None;
+        }
+        color
+    } else {
+        let hex = spec.strip_prefix('#')?;
+        let digits = hex.len() / 3;
+        if hex.len() % 3 != 0 || !(1..=4).contains(&digits) {
+            return None;
+        }
+        (
+            leading_component(&hex[..digits])?,
+            leading_component(&hex[digits..2 * digits])?,
+            leading_component(&hex[2 * digits..])?,
+        )
+    };
+    Some(Color { r, g, b })
+}
+
+fn hex_value(hex: &str) -> Option<u32> {
+    if !(1..=4).contains(&hex.len()) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// `rgb:` components are fractions of their maximum (`f` = `ff` = `ffff`).
+fn scaled_component(hex: &str) -> Option<u8> {
+    let value = hex_value(hex)?;
+    let max = (1u32 << (4 * hex.len())) - 1;
+    Some(((value * 255 + max / 2) / max) as u8)
+}
+
+/// `#` components keep their most significant bits (`#f00` = `#f00000`).
+fn leading_component(hex: &str) -> Option<u8> {
+    let value = hex_value(hex)?;
+    let bits = 4 * hex.len() as u32;
+    Some(if bits >= 8 {
+        (value >> (bits - 8)) as u8
+    } else {
+        (value << (8 - bits)) as u8
+    })
+}
+
+/// Formats `color` the way xterm reports it: `rgb:rrrr/gggg/bbbb`.
+fn format_color(color: Color) -> String {
+    let wide = |c: u8| u16::from(c) * 0x101;
+    format!(
+        "rgb:{:04x}/{:04x}/{:04x}",
+        wide(color.r),
+        wide(color.g),
+        wide(color.b)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(term: &mut Terminal, query: &[u8]) -> String {
+        term.process(query);
+        String::from_utf8(term.drain_responses()).unwrap()
+    }
+
+    #[test]
+    fn parses_x11_color_specs() {
+        let color = |r, g, b| Some(Color { r, g, b });
+        assert_eq!(parse_color_spec(b"rgb:ff/80/00"), color(255, 128, 0));
+        assert_eq!(parse_color_spec(b"rgb:f/8/0"), color(255, 136, 0));
+        assert_eq!(parse_color_spec(b"rgb:ffff/0000/8080"), color(255, 0, 128));
+        assert_eq!(parse_color_spec(b"#f80"), color(240, 128, 0));
+        assert_eq!(parse_color_spec(b"#1a2b3c"), color(26, 43, 60));
+        assert_eq!(parse_color_spec(b"#123456789"), color(18, 69, 120));
+        assert_eq!(parse_color_spec(b"rgb:ff/80"), None);
+        assert_eq!(parse_color_spec(b"#12345"), None);
+        assert_eq!(parse_color_spec(b"red"), None);
+    }
+
+    #[test]
+    fn sets_and_queries_default_colors() {
+        let mut term = Terminal::new(2, 10);
+        term.process(b"A\x1b]11;#102030\x1b\\");
+        let bg = Color {
+            r: 16,
+            g: 32,
+            b: 48,
+        };
+        assert_eq!(term.default_bg, bg);
+        assert_eq!(term.screen.viewport_get(0, 0).bg, bg);
+        assert_eq!(
+            reply(&mut term, b"\x1b]11;?\x1b\\"),
+            "\x1b]11;rgb:1010/2020/3030\x1b\\"
+        );
+        // BEL-terminated queries get BEL-terminated replies.
+        term.process(b"\x1b]10;rgb:ff/ff/ff;?\x07");
+        assert_eq!(
+            term.default_fg,
+            Color {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+        assert_eq!(
+            String::from_utf8(term.drain_responses()).unwrap(),
+            "\x1b]11;rgb:1010/2020/3030\x07"
+        );
+    }
+
+    #[test]
+    fn osc4_recolors_palette_cells() {
+        let mut term = Terminal::new(2, 10);
+        term.process(b"\x1b[31mR\x1b]4;1;rgb:00/ff/00\x1b\\");
+        let green = Color { r: 0, g: 255, b: 0 };
+        assert_eq!(term.ansi_palette[1], green);
+        assert_eq!(term.screen.viewport_get(0, 0).fg, green);
+        assert_eq!(
+            reply(&mut term, b"\x1b]4;1;?;196;?\x1b\\"),
+            "\x1b]4;1;rgb:0000/ffff/0000\x1b\\\x1b]4;196;rgb:ffff/0000/0000\x1b\\"
+        );
+    }
+
+    #[test]
+    fn resets_restore_theme_colors() {
+        let mut term = Terminal::new(2, 10);
+        let theme = term.palette();
+        term.process(b"\x1b]4;1;#fff;2;#fff\x1b\\\x1b]10;#000;#111;#222\x1b\\");
+        assert_eq!(
+            term.cursor_color(),
+            Some(Color {
+                r: 32,
+                g: 32,
+                b: 32
+            })
+        );
+        term.process(b"\x1b]104;1\x1b\\");
+        assert_eq!(term.ansi_palette[1], theme.ansi[1]);
+        assert_ne!(term.ansi_palette[2], theme.ansi[2]);
+        term.process(b"\x1b]104\x1b\\\x1b]110\x1b\\\x1b]111\x1b\\\x1b]112\x1b\\");
+        assert_eq!(term.palette(), theme);
+        assert_eq!(term.cursor_color(), None);
+    }
+
+    #[test]
+    fn theme_change_keeps_osc_overrides() {
+        let mut term = Terminal::new(2, 10);
+        let old = term.palette();
+        term.process(b"\x1b]11;#102030\x1b\\");
+        let new_fg = Color { r: 1, g: 2, b: 3 };
+        term.recolor(old.fg, old.bg, &old.ansi, new_fg, old.bg, &old.ansi);
+        assert_eq!(term.default_fg, new_fg);
+        assert_eq!(
+            term.default_bg,
+            Color {
+                r: 16,
+                g: 32,
+                b: 48
+            }
+        );
+        term.process(b"\x1b]111\x1b\\");
+        assert_eq!(term.default_bg, old.bg);
+    }
+
+    #[test]
+    fn palette_entries_of_one_sequence_recolor_together() {
+        let mut term = Terminal::new(2, 10);
+        term.process(b"\x1b]4;1;#110000;2;#002200\x1b\\\x1b[31mA\x1b[32mB");
+        // Colour 1 takes colour 2's old value in the same sequence that
+        // changes colour 2; cells of each keep following their own entry.
+        term.process(b"\x1b]4;1;#002200;2;#000033\x1b\\");
+        let fg = |col| term.screen.viewport_get(0, col).fg;
+        assert_eq!(fg(0), Color { r: 0, g: 0x22, b: 0 });
+        assert_eq!(fg(1), Color { r: 0, g: 0, b: 0x33 });
+    }
+}
@@ -13,20 +13,31 @@ pub(in super::super) fn handle_cursor_csi(
             let col = iter.next().and_then(|p| p.first().copied()).unwrap_or(1);
 
             // Accept ConPTY's cursor position as authoritative.
+            let (top, bottom) = term.addressable_rows();
             term.set_cursor(
-                (row as usize).saturating_sub(1).min(term.screen.viewport_rows() - 1),
+                (top + (row as usize).saturating_sub(1)).min(bottom),
                 (col as usize).saturating_sub(1).min(term.screen.cols().saturating_sub(1)),
             );
             true
         }
         'A' => {
+            // CUU stops at the top margin when starting inside the scroll region.
             let n = term.param(params, 1).max(1) as usize;
-            term.set_cursor_row(term.cursor_row().saturating_sub(n));
+            let cr = term.cursor_row();
+            let top = if cr >= term.scroll_top { term.scroll_top } else { 0 };
+            term.set_cursor_row(cr.saturating_sub(n).max(top));
             true
         }
         'B' => {
+            // CUD stops at the bottom margin when starting inside the scroll region.
             let n = term.param(params, 1).max(1) as usize;
-            term.set_cursor_row((term.cursor_row() + n).min(term.screen.viewport_rows() - 1));
+            let cr = term.cursor_row();
+            let bottom = if cr <= term.scroll_bottom {
+                term.scroll_bottom
+            } else {
+                term.screen.viewport_rows() - 1
+            };
+            term.set_cursor_row((cr + n).min(bottom));
             true
         }
         'C' => {
@@ -46,7 +57,8 @@ pub(in super::super) fn handle_cursor_csi(
         }
         'd' => {
             let row = term.param(params, 1) as usize;
-            term.set_cursor_row(row.saturating_sub(1).min(term.screen.viewport_rows() - 1));
+            let (top, bottom) = term.addressable_rows();
+            term.set_cursor_row((top + row.saturating_sub(1)).min(bottom));
             true
         }
         _ => false,
@@ -0,0 +1,120 @@
+use super::reports;
+use crate::core::terminal::Terminal;
+use crate::core::terminal::sixel::SixelDecoder;
+use vte::Params;
+
+/// Consumer of the data string of the DCS sequence currently being received.
+pub(in super::super) enum DcsHandler {
+    /// `DCS P1 ; P2 ; P3 q <sixel data> ST`
+    Sixel(Box<SixelDecoder>),
+    /// `DCS $ q Pt ST` — DECRQSS, the setting name being collected.
+    StatusRequest(Vec<u8>),
+}
+
+pub(in super::super) fn handle_dcs_hook(
+    term: &mut Terminal,
+    params: &Params,
+    intermediates: &[u8],
+    action: char,
+) {
+    term.dcs_handler = match (intermediates, action) {
+        ([], 'q') => Some(DcsHandler::Sixel(Box::new(SixelDecoder::new(params)))),
+        (b"$", 'q') => Some(DcsHandler::StatusRequest(Vec::new())),
+        _ => None,
+    };
+}
+
+pub(in super::super) fn handle_dcs_put(term: &mut Terminal, byte: u8) {
+    match term.dcs_handler.as_mut() {
+        Some(DcsHandler::Sixel(decoder)) => decoder.put(byte),
+        Some(DcsHandler::StatusRequest(request))
+            if request.len() < reports::MAX_STATUS_REQUEST_LEN =>
+        {
+            request.push(byte)
+        }
+        Some(DcsHandler::StatusRequest(_)) | None => {}
+    }
+}
+
+pub(in super::super) fn handle_dcs_unhook(term: &mut Terminal) {
+    match term.dcs_handler.take() {
+        Some(DcsHandler::Sixel(decoder)) => {
+            if let Some((width, height, pixels)) = decoder.finish() {
+                let image = term.new_image(width, height, pixels);
+                term.place_sixel_image(image);
+            }
+        }
+        Some(DcsHandler::StatusRequest(request)) => reports::reply_status_string(term, &request),
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::terminal::Terminal;
+
+    /// One red 2x6 sixel column pair: colour 1 defined as 100% red.
+    const RED_SIXEL: &[u8] = b"\x1bP0;1;0q#1;2;100;0;0#1~~\x1b\\";
+
+    #[test]
+    fn sixel_image_is_anchored_to_cursor_row() {
+        let mut term = Terminal::new(4, 10);
+        term.set_cell_pixel_size(2, 6);
+        term.process(b"ab");
+        term.process(RED_SIXEL);
+
+        let images = &term.screen.viewport_row(0).images;
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].col, 2);
+        assert_eq!(images[0].image.width, 2);
+        assert_eq!(images[0].image.height, 6);
+        assert_eq!(&images[0].image.pixels[..4], &[255, 0, 0, 255]);
+        // Cursor moves below the image, back to its left column.
+        assert_eq!(term.cursor_row(), 1);
+        assert_eq!(term.cursor_col(), 2);
+    }
+
+    #[test]
+    fn tall_sixel_spans_rows_and_scrolls_into_scrollback() {
+        let mut term = Terminal::new(2, 10);
+        term.set_cell_pixel_size(2, 6);
+        // Three sixel bands (18 px) = three cell rows on a two-row screen.
+        term.process(b"\x1bPq#0;2;0;0;100~~-~~-~~\x1b\\");
+
+        assert_eq!(term.screen.scrollback_len(), 2);
+        let first = &term.screen.scrollback_row(0).images[0];
+        assert_eq!((first.src_y, first.src_height), (0, 6));
+        let last = &term.screen.viewport_row(0).images[0];
+        assert_eq!((last.src_y, last.src_height), (12, 6));
+    }
+
+    #[test]
+    fn sixel_repeat_and_raster_attributes() {
+        let mut term = Terminal::new(4, 10);
+        term.set_cell_pixel_size(8, 16);
+        term.process(b"\x1bPq\"1;1;20;12#2!5~\x1b\\");
+
+        let image = &term.screen.viewport_row(0).images[0].image;
+        assert_eq!((image.width, image.height), (20, 12));
+        // Unpainted area is filled with register 0 (opaque) when P2 != 1.
+        let last = image.row_pixels(11);
+        assert_eq!(last[last.len() - 1], 255);
+    }
+
+    #[test]
+    fn erasing_the_row_removes_the_image() {
+        let mut term = Terminal::new(4, 10);
+        term.set_cell_pixel_size(2, 6);
+        term.process(RED_SIXEL);
+        term.process(b"\x1b[2J");
+        assert!(term.screen.viewport_row(0).images.is_empty());
+    }
+
+    #[test]
+    fn unknown_dcs_is_ignored() {
+        let mut term = Terminal::new(4, 10);
+        term.process(b"\x1bP+q544e\x1b\\");
+        assert!(term.screen.viewport_row(0).images.is_empty());
+        assert_eq!(term.cursor_row(), 0);
+    }
+}
@@ -1,6 +1,18 @@
 use crate::core::terminal::Terminal;
 use vte::Params;
 
+/// Name reported by XTVERSION.
+const TERMINAL_NAME: &str = "Ferrum";
+
+/// Firmware version for DA2: `major * 10000 + minor * 100 + patch`.
+fn version_number() -> u32 {
+    env!("CARGO_PKG_VERSION")
+        .split('.')
+        .take(3)
+        .map(|part| part.parse::<u32>().unwrap_or(0))
+        .fold(0, |acc, part| acc * 100 + part)
+}
+
 pub(in super::super) fn handle_device_csi(
     term: &mut Terminal,
     action: char,
@@ -11,9 +23,16 @@ pub(in super::super) fn handle_device_csi(
         'n' => {
             match term.param(params, 0) {
                 6 => {
-                    // CPR — Cursor Position Report (1-indexed)
-                    let response =
-                        format!("\x1b[{};{}R", term.cursor_row() + 1, term.cursor_col() + 1,);
+                    // CPR — Cursor Position Report (1-indexed, relative to the origin);
+                    // DECXCPR (`CSI ? 6 n`) adds the page number.
+                    let (top, _) = term.addressable_rows();
+                    let row = term.cursor_row().saturating_sub(top) + 1;
+                    let col = term.cursor_col() + 1;
+                    let response = if intermediates == b"?" {
+                        format!("\x1b[?{row};{col};1R")
+                    } else {
+                        format!("\x1b[{row};{col}R")
+                    };
                     term.respond(response.as_bytes());
                 }
                 5 => {
@@ -25,13 +44,101 @@ pub(in super::super) fn handle_device_csi(
             true
         }
         'c' => {
-            if intermediates.is_empty() && term.param(params, 0) == 0 {
-                // DA1 — Primary Device Attributes (VT220-level).
-                // ?62: VT220; 22: ANSI color; 29: ANSI text locator.
-                term.respond(b"\x1b[?62;22;29c");
+            if term.param(params, 0) == 0 {
+                match intermediates {
+                    // DA1 — Primary Device Attributes (VT220-level).
+                    // ?62: VT220; 4: Sixel graphics; 22: ANSI color; 29: ANSI text locator.
+                    b"" => term.respond(b"\x1b[?62;4;22;29c"),
+                    // DA2 — Secondary Device Attributes: terminal type 1 (VT220),
+                    // firmware version, ROM cartridge 0.
+                    b">" => {
+                        let response = format!("\x1b[>1;{};0c", version_number());
+                        term.respond(response.as_bytes());
+                    }
+                    // DA3 — Tertiary Device Attributes: a zero unit id.
+                    b"=" => term.respond(b"\x1bP!|00000000\x1b\\"),
+                    _ => {}
+                }
+            }
+            true
+        }
+        'q' if intermediates == b">" => {
+            // XTVERSION — name and version of the terminal.
+            if term.param(params, 0) == 0 {
+                let version = env!("CARGO_PKG_VERSION");
+                let response = format!("\x1bP>|{TERMINAL_NAME} {version}\x1b\\");
+                term.respond(response.as_bytes());
+            }
+            true
+        }
+        't' if intermediates.is_empty() => {
+            // XTWINOPS size reports. Only the read-only ones are answered; window
+            // manipulation is left to the user and title reports (20/21) are
+            // gated by `is_blocked_title_query` before reaching here.
+            let rows = term.screen.viewport_rows() as u32;
+            let cols = term.screen.cols() as u32;
+            let (cell_w, cell_h) = (term.cell_pixel_width, term.cell_pixel_height);
+            let response = match term.param(params, 0) {
+                // Text area size in pixels.
+                14 => Some(format!("\x1b[4;{};{}t", rows * cell_h, cols * cell_w)),
+                // Character cell size in pixels.
+                16 => Some(format!("\x1b[6;{cell_h};{cell_w}t")),
+                // Text area size in characters.
+                18 => Some(format!("\x1b[8;{rows};{cols}t")),
+                _ => None,
+            };
+            if let Some(response) = response {
+                term.respond(response.as_bytes());
             }
             true
         }
         _ => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::core::terminal::Terminal;
+
+    fn reply(term: &mut Terminal, query: &[u8]) -> String {
+        term.process(query);
+        String::from_utf8(term.drain_responses()).unwrap()
+    }
+
+    #[test]
+    fn answers_secondary_and_tertiary_device_attributes() {
+        let mut term = Terminal::new(4, 10);
+        assert_eq!(reply(&mut term, b"\x1b[c"), "\x1b[?62;4;22;29c");
+        let version = super::version_number();
+        assert_eq!(
+            reply(&mut term, b"\x1b[>c"),
+            format!("\x1b[>1;{version};0c")
+        );
+        assert_eq!(
+            reply(&mut term, b"\x1b[>0c"),
+            format!("\x1b[>1;{version};0c")
+        );
+        assert_eq!(reply(&mut term, b"\x1b[=c"), "\x1bP!|00000000\x1b\\");
+    }
+
+    #[test]
+    fn xtversion_reports_name_and_version() {
+        let mut term = Terminal::new(4, 10);
+        let expected = format!("\x1bP>|Ferrum {}\x1b\\", env!("CARGO_PKG_VERSION"));
+        assert_eq!(reply(&mut term, b"\x1b[>q"), expected);
+        // DECSCUSR shares the final byte and must not answer.
+        assert_eq!(reply(&mut term, b"\x1b[2 q"), "");
+    }
+
+    #[test]
+    fn xtwinops_reports_sizes_from_cell_metrics() {
+        let mut term = Terminal::new(24, 80);
+        term.set_cell_pixel_size(9, 18);
+        assert_eq!(reply(&mut term, b"\x1b[14t"), "\x1b[4;432;720t");
+        assert_eq!(reply(&mut term, b"\x1b[16t"), "\x1b[6;18;9t");
+        assert_eq!(reply(&mut term, b"\x1b[18t"), "\x1b[8;24;80t");
+        // Window manipulation is ignored.
+        assert_eq!(reply(&mut term, b"\x1b[8;10;10t"), "");
+        assert_eq!(term.screen.viewport_rows(), 24);
+    }
+}
@@ -27,18 +27,9 @@ pub(in super::super) fn handle_inline_edit_csi(
         '@' => {
             // ICH: insert N blank cells and shift remainder right.
             let n = term.param(params, 1).max(1) as usize;
-            let blank = term.make_blank_grapheme_cell();
-            let cols = term.screen.cols();
             let cr = term.cursor_row();
             let cc = term.cursor_col();
-            for col in (cc..cols).rev() {
-                if col >= cc + n {
-                    let cell = term.screen.viewport_get(cr, col - n).clone();
-                    term.screen.viewport_set(cr, col, cell);
-                } else {
-                    term.screen.viewport_set(cr, col, blank.clone());
-                }
-            }
+            insert_blank_cells(term, cr, cc, n);
             true
         }
         'X' => {
@@ -53,10 +44,34 @@ pub(in super::super) fn handle_inline_edit_csi(
             }
             true
         }
+        'b' => {
+            // REP: repeat the preceding graphic character N times.
+            let n = term.param(params, 1).max(1);
+            if let Some(c) = term.last_graphic {
+                for _ in 0..n {
+                    term.print_graphic(c);
+                }
+            }
+            true
+        }
         _ => false,
     }
 }
 
+/// Shifts the cells from `cc` on right by `n`, blanking the gap (ICH, IRM).
+pub(in super::super) fn insert_blank_cells(term: &mut Terminal, cr: usize, cc: usize, n: usize) {
+    let blank = term.make_blank_grapheme_cell();
+    let cols = term.screen.cols();
+    for col in (cc..cols).rev() {
+        if col >= cc + n {
+            let cell = term.screen.viewport_get(cr, col - n).clone();
+            term.screen.viewport_set(cr, col, cell);
+        } else {
+            term.screen.viewport_set(cr, col, blank.clone());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::GraphemeCell;
@@ -145,4 +160,15 @@ mod tests {
         assert_eq!(term.screen.viewport_get(0, 1).bg, green);
         assert_eq!(term.screen.viewport_get(0, 2).bg, green);
     }
+
+    #[test]
+    fn rep_repeats_the_last_printed_character() {
+        let mut term = Terminal::new(4, 8);
+        term.process(b"ab\x1b[3b");
+        assert_eq!(read_row(&term), "abbbb   ");
+        assert_eq!(term.cursor_col(), 5);
+        // Repeats the translated character, not the raw byte.
+        term.process(b"\r\x1b(0q\x1b(B\x1b[b");
+        assert_eq!(read_row(&term), "──bbb   ");
+    }
 }
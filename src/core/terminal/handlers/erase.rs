@@ -1,11 +1,27 @@
 use crate::core::terminal::Terminal;
+use crate::core::{CellEffects, GraphemeCell};
+use std::ops::Range;
 use vte::Params;
 
 pub(in super::super) fn handle_erase_csi(
     term: &mut Terminal,
     action: char,
     params: &Params,
+    intermediates: &[u8],
 ) -> bool {
+    if intermediates == b"?" && matches!(action, 'J' | 'K') {
+        selective_erase(term, action, term.param(params, 0));
+        return true;
+    }
+    if intermediates == b"\"" && action == 'q' {
+        // DECSCA — 1 protects subsequently printed cells from DECSED/DECSEL.
+        match term.param(params, 0) {
+            0 | 2 => term.set_effect(CellEffects::PROTECTED, false),
+            1 => term.set_effect(CellEffects::PROTECTED, true),
+            _ => {}
+        }
+        return true;
+    }
     match action {
         'J' => {
             let cr = term.cursor_row();
@@ -48,12 +64,15 @@ pub(in super::super) fn handle_erase_csi(
                             new_screen.viewport_set(r, c, gc);
                         }
                         new_screen.viewport_set_wrapped(r, term.screen.viewport_is_wrapped(r));
+                        new_screen.viewport_row_mut(r).images = term.screen.viewport_row(r).images.clone();
+                        new_screen.viewport_row_mut(r).marks = term.screen.viewport_row(r).marks;
                     }
                     let abs = new_screen.viewport_start_abs() + cr;
                     let new_cursor_pin =
                         crate::core::PageList::pin_at(crate::core::PageCoord { abs_row: abs, col: cc });
                     term.cursor_pin = new_cursor_pin;
                     term.screen = new_screen;
+                    term.hyperlinks.note_dropped();
                 }
                 _ => {}
             }
@@ -87,6 +106,50 @@ pub(in super::super) fn handle_erase_csi(
     }
 }
 
+/// DECSED (`CSI ? Ps J`) and DECSEL (`CSI ? Ps K`): like ED and EL, but
+/// cells written under DECSCA protection are kept.
+fn selective_erase(term: &mut Terminal, action: char, mode: u16) {
+    let cr = term.cursor_row();
+    let cc = term.cursor_col();
+    let cols = term.screen.cols();
+    let rows = term.screen.viewport_rows();
+    let mut spans: Vec<(usize, Range<usize>)> = Vec::new();
+    match (action, mode) {
+        ('J', 0) => {
+            spans.push((cr, cc..cols));
+            spans.extend((cr + 1..rows).map(|row| (row, 0..cols)));
+        }
+        ('J', 1) => {
+            spans.extend((0..cr).map(|row| (row, 0..cols)));
+            spans.push((cr, 0..(cc + 1).min(cols)));
+        }
+        ('J', 2) => spans.extend((0..rows).map(|row| (row, 0..cols))),
+        ('K', 0) => spans.push((cr, cc..cols)),
+        ('K', 1) => spans.push((cr, 0..(cc + 1).min(cols))),
+        ('K', 2) => spans.push((cr, 0..cols)),
+        _ => {}
+    }
+    let blank = term.make_blank_grapheme_cell();
+    for (row, span) in spans {
+        for col in span {
+            if !term.screen.viewport_get(row, col).effects.contains(CellEffects::PROTECTED) {
+                term.screen.viewport_set(row, col, blank.clone());
+            }
+        }
+    }
+}
+
+/// DECALN (`ESC # 8`): fills the screen with `E`, resets the margins and
+/// homes the cursor.
+pub(in super::super) fn screen_alignment_test(term: &mut Terminal) {
+    let fill = GraphemeCell::from_char('E');
+    for row in 0..term.screen.viewport_rows() {
+        term.screen.viewport_row_mut(row).clear_with(fill.clone());
+    }
+    term.reset_scroll_region();
+    term.set_cursor(0, 0);
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::GraphemeCell;
@@ -391,4 +454,45 @@ mod tests {
         assert_eq!(term.screen.viewport_get(0, 0).bg, blue);
         assert_eq!(term.screen.viewport_get(0, 9).bg, blue);
     }
+
+    #[test]
+    fn selective_erase_keeps_protected_cells() {
+        let mut term = Terminal::new(3, 6);
+        term.process(b"ab\x1b[1\"qCD\x1b[0\"qef\r\n\x1b[1\"qX\x1b[\"qyz");
+        term.process(b"\x1b[?2J");
+        let row = |term: &Terminal, r| -> String {
+            (0..6).map(|c| get_char(term, r, c)).collect()
+        };
+        assert_eq!(row(&term, 0), "  CD  ");
+        assert_eq!(row(&term, 1), "X     ");
+
+        // SGR 0 leaves the protection in place; plain ED ignores it.
+        term.process(b"\x1b[1\"q\x1b[0m\x1b[2;2HP\x1b[?K");
+        assert_eq!(row(&term, 1), "XP    ");
+        term.process(b"\x1b[2J");
+        assert_eq!(row(&term, 0), "      ");
+    }
+
+    #[test]
+    fn decsel_erases_unprotected_cells_around_cursor() {
+        let mut term = Terminal::new(2, 6);
+        term.process(b"a\x1b[1\"qB\x1b[0\"qcdef\x1b[4G\x1b[?1K");
+        assert_eq!(
+            (0..6).map(|c| get_char(&term, 0, c)).collect::<String>(),
+            " B  ef"
+        );
+    }
+
+    #[test]
+    fn decaln_fills_screen_and_resets_margins() {
+        let mut term = Terminal::new(3, 4);
+        term.process(b"\x1b[2;3r\x1b[3;3Hx\x1b#8");
+        for r in 0..3 {
+            for c in 0..4 {
+                assert_eq!(get_char(&term, r, c), 'E');
+            }
+        }
+        assert_eq!((term.cursor_row(), term.cursor_col()), (0, 0));
+        assert_eq!((term.scroll_top, term.scroll_bottom), (0, 2));
+    }
 }
@@ -0,0 +1,166 @@
+//! OSC 8 hyperlinks: `8 ; params ; URI` starts a link on the cells printed
+//! next and an empty URI ends it. `params` is a `:`-separated list of
+//! `key=value` pairs, of which only `id` is used.
+
+use crate::core::PageList;
+use crate::core::terminal::Terminal;
+use crate::core::terminal::hyperlinks::MAX_HYPERLINKS;
+
+pub(in super::super) fn handle_hyperlink_osc(term: &mut Terminal, params: &[&[u8]]) {
+    let uri = params.get(2).copied().unwrap_or(b"");
+    if uri.is_empty() {
+        term.current_hyperlink_id = 0;
+        return;
+    }
+    let link_id = params.get(1).and_then(|p| link_id(p));
+    let url = String::from_utf8_lossy(uri);
+    term.current_hyperlink_id = intern(term, link_id.as_deref(), &url);
+}
+
+/// The `id=` value among the link parameters.
+fn link_id(params: &[u8]) -> Option<String> {
+    params
+        .split(|&b| b == b':')
+        .find_map(|pair| pair.strip_prefix(b"id="))
+        .filter(|id| !id.is_empty())
+        .map(|id| String::from_utf8_lossy(id).into_owned())
+}
+
+/// Cell id for the link, freeing links no longer shown anywhere when the
+/// table is full. Returns 0 (no link) if every stored link is still in use.
+fn intern(term: &mut Terminal, link_id: Option<&str>, url: &str) -> u16 {
+    if let Some(id) = term.hyperlinks.find(link_id, url) {
+        return id;
+    }
+    // Scanning every screen is only worth it once cells carrying links
+    // have been overwritten or dropped since the last scan.
+    if term.hyperlinks.is_full() && take_hyperlinks_dropped(term) {
+        let live = live_hyperlinks(term);
+        term.hyperlinks.retain(&live);
+    }
+    term.hyperlinks.insert(link_id, url).unwrap_or_else(|| {
+        if term.hyperlinks.first_overflow() {
+            eprintln!(
+                "[ferrum] OSC 8: all {MAX_HYPERLINKS} hyperlinks are in use; ignoring new links"
+            );
+        }
+        0
+    })
+}
+
+/// Whether cells carrying links were overwritten or dropped on any screen
+/// since the last call.
+fn take_hyperlinks_dropped(term: &mut Terminal) -> bool {
+    // Every flag is taken, so the next scan starts afresh.
+    let mut dropped = term.hyperlinks.take_dropped();
+    dropped |= term.screen.take_hyperlinks_dropped();
+    for screen in [term.alt_screen.as_mut(), term.parked_alt_screen.as_mut()]
+        .into_iter()
+        .flatten()
+    {
+        dropped |= screen.take_hyperlinks_dropped();
+    }
+    dropped
+}
+
+/// Marks, by cell id, the links referenced by a row of any screen or by the
+/// link being printed.
+fn live_hyperlinks(term: &Terminal) -> Vec<bool> {
+    let mut live = vec![false; MAX_HYPERLINKS + 1];
+    live[usize::from(term.current_hyperlink_id)] = true;
+    let screens: [Option<&PageList>; 3] = [
+        Some(&term.screen),
+        term.alt_screen.as_ref(),
+        term.parked_alt_screen.as_ref(),
+    ];
+    for screen in screens.into_iter().flatten() {
+        screen.for_each_row(|row| {
+            for cell in &row.cells {
+                if let Some(flag) = live.get_mut(usize::from(cell.hyperlink_id)) {
+                    *flag = true;
+                }
+            }
+        });
+    }
+    live
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link_at(term: &Terminal, row: usize, col: usize) -> u16 {
+        term.screen.viewport_get(row, col).hyperlink_id
+    }
+
+    #[test]
+    fn cells_sharing_an_id_form_one_link() {
+        let mut term = Terminal::new(3, 20);
+        term.process(b"\x1b]8;id=1;https://ferrum.dev\x1b\\ab\x1b]8;;\x1b\\ ");
+        term.process(b"\x1b]8;id=2;https://ferrum.dev\x1b\\cd\x1b]8;;\x1b\\\r\n");
+        term.process(b"\x1b]8;id=1;https://ferrum.dev\x1b\\ef\x1b]8;;\x1b\\");
+
+        let first = link_at(&term, 0, 0);
+        assert_ne!(first, 0);
+        assert_eq!(link_at(&term, 1, 1), first);
+        assert_ne!(link_at(&term, 0, 3), first);
+        assert_eq!(
+            term.hyperlink_url(link_at(&term, 0, 3)),
+            Some("https://ferrum.dev")
+        );
+    }
+
+    #[test]
+    fn link_id_is_read_from_the_parameter_list() {
+        assert_eq!(link_id(b"foo=bar:id=x1"), Some("x1".to_owned()));
+        assert_eq!(link_id(b"id="), None);
+        assert_eq!(link_id(b""), None);
+    }
+
+    #[test]
+    fn full_table_drops_links_no_longer_on_screen() {
+        let mut term = Terminal::new(2, 10);
+        term.process(b"\x1b]8;;https://kept\x1b\\k\x1b]8;;\x1b\\");
+        let kept = link_at(&term, 0, 0);
+        // Each link overwrites the previous one in the same cell.
+        for i in 1..MAX_HYPERLINKS {
+            let link = format!("\x1b[2;1H\x1b]8;;https://{i}\x1b\\x\x1b]8;;\x1b\\");
+            term.process(link.as_bytes());
+        }
+        assert!(term.hyperlinks.is_full());
+
+        term.process(b"\x1b[2;2H\x1b]8;;https://new\x1b\\n\x1b]8;;\x1b\\");
+        let new = link_at(&term, 1, 1);
+        assert_ne!(new, 0);
+        assert_eq!(term.hyperlink_url(new), Some("https://new"));
+        assert_eq!(term.hyperlink_url(kept), Some("https://kept"));
+        assert_eq!(
+            term.hyperlink_url(link_at(&term, 1, 0)),
+            Some(format!("https://{}", MAX_HYPERLINKS - 1).as_str())
+        );
+    }
+
+    #[test]
+    fn full_table_is_only_rescanned_after_linked_cells_are_dropped() {
+        // 64 × 64 cells, each holding its own link.
+        let mut term = Terminal::new(64, 64);
+        for i in 0..MAX_HYPERLINKS {
+            let link = format!("\x1b]8;;https://{i}\x1b\\x");
+            term.process(link.as_bytes());
+        }
+        term.process(b"\x1b]8;;\x1b\\");
+        assert!(term.hyperlinks.is_full());
+
+        term.process(b"\x1b]8;;https://new\x1b\\");
+        assert_eq!(term.current_hyperlink_id, 0);
+        assert!(!take_hyperlinks_dropped(&mut term));
+        assert!(!term.hyperlinks.first_overflow(), "warning logged once");
+
+        term.process(b"\x1b]8;;\x1b\\\x1b[1;1Hy\x1b]8;;https://new\x1b\\");
+        assert_ne!(term.current_hyperlink_id, 0);
+        assert_eq!(
+            term.hyperlink_url(term.current_hyperlink_id),
+            Some("https://new")
+        );
+    }
+}
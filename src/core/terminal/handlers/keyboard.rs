@@ -0,0 +1,145 @@
+//! Kitty keyboard protocol flag stack: `CSI ? u`, `CSI > flags u`,
+//! `CSI < n u` and `CSI = flags ; mode u`.
+
+use crate::core::KeyboardFlags;
+use crate::core::terminal::Terminal;
+use vte::Params;
+
+/// Maximum depth of each screen's flag stack; pushing onto a full stack
+/// drops the oldest entry.
+const MAX_KEYBOARD_FLAG_STACK: usize = 8;
+
+pub(in super::super) fn handle_keyboard_csi(
+    term: &mut Terminal,
+    params: &Params,
+    intermediates: &[u8],
+    action: char,
+) -> bool {
+    if action != 'u' {
+        return false;
+    }
+    match intermediates {
+        b"?" => {
+            let response = format!("\x1b[?{}u", term.keyboard_flags().bits());
+            term.respond(response.as_bytes());
+        }
+        b">" => {
+            if term.keyboard_flags.len() >= MAX_KEYBOARD_FLAG_STACK {
+                term.keyboard_flags.remove(0);
+            }
+            let flags = KeyboardFlags::from_bits(term.param(params, 0));
+            term.keyboard_flags.push(flags);
+        }
+        b"<" => {
+            let count = usize::from(term.param(params, 1).max(1));
+            let len = term.keyboard_flags.len();
+            term.keyboard_flags.truncate(len.saturating_sub(count));
+        }
+        b"=" => {
+            let bits = term.param(params, 0);
+            let mode = params
+                .iter()
+                .nth(1)
+                .and_then(|p| p.first().copied())
+                .unwrap_or(1);
+            let current = term.keyboard_flags().bits() as u16;
+            let flags = match mode {
+                1 => KeyboardFlags::from_bits(bits),
+                2 => KeyboardFlags::from_bits(current | bits),
+                3 => KeyboardFlags::from_bits(current & !bits),
+                _ => return true,
+            };
+            match term.keyboard_flags.last_mut() {
+                Some(top) => *top = flags,
+                None => term.keyboard_flags.push(flags),
+            }
+        }
+        // Plain `CSI u` is SCORC (restore cursor); not handled here.
+        _ => return false,
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::KeyboardFlags;
+    use crate::core::terminal::Terminal;
+
+    fn query(term: &mut Terminal) -> Vec<u8> {
+        term.process(b"\x1b[?u");
+        term.drain_responses()
+    }
+
+    #[test]
+    fn query_reports_zero_by_default() {
+        let mut term = Terminal::new(4, 10);
+        assert_eq!(query(&mut term), b"\x1b[?0u");
+    }
+
+    #[test]
+    fn push_and_pop_restore_previous_flags() {
+        let mut term = Terminal::new(4, 10);
+        term.process(b"\x1b[>1u\x1b[>11u");
+        assert_eq!(query(&mut term), b"\x1b[?11u");
+        term.process(b"\x1b[<u");
+        assert_eq!(term.keyboard_flags().bits(), 1);
+        term.process(b"\x1b[<5u");
+        assert!(term.keyboard_flags().is_empty());
+    }
+
+    #[test]
+    fn set_modes_replace_or_and_clear_bits() {
+        let mut term = Terminal::new(4, 10);
+        term.process(b"\x1b[=1u");
+        assert_eq!(term.keyboard_flags().bits(), 1);
+        term.process(b"\x1b[=8;2u");
+        assert_eq!(term.keyboard_flags().bits(), 9);
+        term.process(b"\x1b[=1;3u");
+        assert_eq!(term.keyboard_flags().bits(), 8);
+        // `=` modifies the top entry rather than pushing.
+        term.process(b"\x1b[<u");
+        assert!(term.keyboard_flags().is_empty());
+    }
+
+    #[test]
+    fn undefined_bits_are_dropped() {
+        let mut term = Terminal::new(4, 10);
+        term.process(b"\x1b[>255u");
+        assert_eq!(term.keyboard_flags().bits(), 31);
+        assert!(term.keyboard_flags().contains(KeyboardFlags::REPORT_TEXT));
+    }
+
+    #[test]
+    fn full_stack_drops_oldest_entry() {
+        let mut term = Terminal::new(4, 10);
+        for flags in 1..=9 {
+            term.process(format!("\x1b[>{flags}u").as_bytes());
+        }
+        term.process(b"\x1b[<7u");
+        assert_eq!(term.keyboard_flags().bits(), 2);
+        term.process(b"\x1b[<u");
+        assert!(term.keyboard_flags().is_empty());
+    }
+
+    #[test]
+    fn alternate_screen_has_its_own_stack() {
+        let mut term = Terminal::new(4, 10);
+        term.process(b"\x1b[>1u");
+        term.process(b"\x1b[?1049h");
+        assert!(term.keyboard_flags().is_empty());
+        term.process(b"\x1b[>15u");
+        assert_eq!(term.keyboard_flags().bits(), 15);
+        term.process(b"\x1b[?1049l");
+        assert_eq!(term.keyboard_flags().bits(), 1);
+    }
+
+    #[test]
+    fn full_reset_clears_both_stacks() {
+        let mut term = Terminal::new(4, 10);
+        term.process(b"\x1b[>1u\x1b[?1049h\x1b[>3u");
+        term.process(b"\x1bc");
+        assert!(term.keyboard_flags().is_empty());
+        term.process(b"\x1b[?1049h");
+        assert!(term.keyboard_flags().is_empty());
+    }
+}
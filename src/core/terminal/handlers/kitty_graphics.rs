@@ -0,0 +1,427 @@
+use std::rc::Rc;
+
+use crate::core::terminal::Terminal;
+use crate::core::terminal::kitty_graphics::{GraphicsCommand, GraphicsError, decode_image};
+use crate::core::{ImageSlice, TerminalImage};
+
+/// Handles the body of an `ESC _ G ... ESC \` graphics command (without the `G`).
+pub(in super::super) fn handle_kitty_graphics(term: &mut Terminal, data: &[u8]) {
+    let (control, payload) = match data.iter().position(|&b| b == b';') {
+        Some(pos) => (&data[..pos], &data[pos + 1..]),
+        None => (data, &data[data.len()..]),
+    };
+    let command = GraphicsCommand::parse(control);
+
+    if term.graphics.has_pending_transfer() || matches!(command.action, b't' | b'T' | b'q') {
+        let Some((command, encoded)) = term.graphics.push_chunk(command, payload) else {
+            return;
+        };
+        let decoded = encoded.and_then(|encoded| {
+            if matches!(command.medium, b'f' | b't') && !term.security_config.allow_graphics_files {
+                return Err(GraphicsError::new("EPERM", "file transmission is disabled"));
+            }
+            decode_image(&command, &encoded)
+        });
+        if command.action == b'q' {
+            // Queries only report whether the image could be loaded.
+            respond(term, &command, command.image_id, decoded.map(|_| ()));
+        } else {
+            transmit(term, &command, decoded);
+        }
+        return;
+    }
+
+    match command.action {
+        b'p' => {
+            let result = resolve_image(term, &command).and_then(|(id, image)| {
+                place(term, &command, image)?;
+                Ok(id)
+            });
+            let id = *result.as_ref().unwrap_or(&command.image_id);
+            respond(term, &command, id, result.map(|_| ()));
+        }
+        b'd' => delete(term, &command),
+        _ => respond(
+            term,
+            &command,
+            command.image_id,
+            Err(GraphicsError { code: "EINVAL", reason: "unsupported action" }),
+        ),
+    }
+}
+
+/// Stores a decoded image and, for `a=T`, places it at the cursor.
+fn transmit(
+    term: &mut Terminal,
+    command: &GraphicsCommand,
+    decoded: Result<(u32, u32, Vec<u8>), GraphicsError>,
+) {
+    let (width, height, pixels) = match decoded {
+        Ok(decoded) => decoded,
+        Err(err) => return respond(term, command, command.image_id, Err(err)),
+    };
+    let id = term.graphics.resolve_id(command);
+    // Re-transmitting an id replaces the image and drops its placements.
+    if let Some(old) = term.graphics.remove(id) {
+        term.remove_image_strips(|slice| Rc::ptr_eq(&slice.image, &old));
+    }
+    let image = term.new_image(width, height, pixels);
+    for evicted in term.graphics.insert(id, command.image_number, Rc::clone(&image)) {
+        term.remove_image_strips(|slice| Rc::ptr_eq(&slice.image, &evicted));
+    }
+
+    let result = if command.action == b'T' { place(term, command, image) } else { Ok(()) };
+    respond(term, command, id, result);
+}
+
+/// Finds the image addressed by `i=` or `I=`.
+fn resolve_image(
+    term: &mut Terminal,
+    command: &GraphicsCommand,
+) -> Result<(u32, Rc<TerminalImage>), GraphicsError> {
+    let not_found = GraphicsError { code: "ENOENT", reason: "image not found" };
+    let id = if command.image_id != 0 {
+        command.image_id
+    } else if command.image_number != 0 {
+        term.graphics.id_for_number(command.image_number).ok_or(not_found)?
+    } else {
+        return Err(GraphicsError { code: "EINVAL", reason: "no image id" });
+    };
+    let image = term.graphics.get(id).ok_or(not_found)?;
+    Ok((id, image))
+}
+
+/// Places `image` with its top-left corner in the cursor cell.
+///
+/// The placement is split into one strip per covered row, each strip
+/// carrying its share of the (possibly scaled) source rectangle.
+fn place(term: &mut Terminal, command: &GraphicsCommand, image: Rc<TerminalImage>) -> Result<(), GraphicsError> {
+    let cell_w = term.cell_pixel_width;
+    let cell_h = term.cell_pixel_height;
+
+    let src_x = command.x.min(image.width);
+    let src_y = command.y.min(image.height);
+    let src_w = clamp_extent(command.width, image.width - src_x);
+    let src_h = clamp_extent(command.height, image.height - src_y);
+    if src_w == 0 || src_h == 0 {
+        return Err(GraphicsError { code: "EINVAL", reason: "source rectangle is empty" });
+    }
+
+    let x_offset = command.cell_x_offset.min(cell_w - 1);
+    let y_offset = command.cell_y_offset.min(cell_h - 1);
+    // `c` / `r` scale the image to fill that many cells; with only one of
+    // them given, the other side keeps the aspect ratio.
+    let scale = |cells: u32, cell: u32, offset: u32| (cells.saturating_mul(cell)).saturating_sub(offset).max(1);
+    let (width, height) = match (command.columns, command.rows) {
+        (0, 0) => (src_w, src_h),
+        (c, 0) => {
+            let width = scale(c, cell_w, x_offset);
+            (width, (src_h as u64 * width as u64 / src_w as u64).max(1) as u32)
+        }
+        (0, r) => {
+            let height = scale(r, cell_h, y_offset);
+            ((src_w as u64 * height as u64 / src_h as u64).max(1) as u32, height)
+        }
+        (c, r) => (scale(c, cell_w, x_offset), scale(r, cell_h, y_offset)),
+    };
+
+    if command.placement_id != 0 {
+        // Re-using a placement id moves that placement.
+        term.remove_image_strips(|slice| {
+            Rc::ptr_eq(&slice.image, &image) && slice.placement_id == command.placement_id
+        });
+    }
+
+    let col = term.cursor_col();
+    let cols = (x_offset + width).div_ceil(cell_w).max(1) as usize;
+    let rows = (y_offset + height).div_ceil(cell_h).max(1);
+    let strips = (0..rows)
+        .map(|row| {
+            let top = (row * cell_h).max(y_offset);
+            let bottom = ((row + 1) * cell_h).min(y_offset + height);
+            let src_top = src_y + ((top - y_offset) as u64 * src_h as u64 / height as u64) as u32;
+            let src_bottom = src_y + ((bottom - y_offset) as u64 * src_h as u64 / height as u64) as u32;
+            ImageSlice {
+                image: Rc::clone(&image),
+                col,
+                cols,
+                src_x,
+                src_y: src_top,
+                src_width: src_w,
+                src_height: src_bottom - src_top,
+                x_offset,
+                y_offset: top - row * cell_h,
+                width,
+                height: bottom - top,
+                placement_id: command.placement_id,
+                z_index: command.z_index,
+            }
+        })
+        .collect();
+
+    if command.no_cursor_move {
+        term.attach_image_strips(strips, false);
+        return Ok(());
+    }
+    // The cursor ends after the image's last column, on its last row.
+    term.attach_image_strips(strips, true);
+    let next_col = col + cols;
+    if next_col >= term.screen.cols() {
+        let next_row = term.cursor_row() + 1;
+        term.advance_cursor_row(next_row);
+        term.set_cursor_col(0);
+    } else {
+        term.set_cursor_col(next_col);
+    }
+    Ok(())
+}
+
+/// A requested extent of 0 means "to the edge of the image".
+fn clamp_extent(requested: u32, available: u32) -> u32 {
+    if requested == 0 { available } else { requested.min(available) }
+}
+
+/// Identifies one placement across the rows its strips are attached to.
+struct PlacementKey {
+    image: Rc<TerminalImage>,
+    placement_id: u32,
+    col: usize,
+}
+
+impl PlacementKey {
+    fn of(slice: &ImageSlice) -> Self {
+        Self { image: Rc::clone(&slice.image), placement_id: slice.placement_id, col: slice.col }
+    }
+
+    fn matches(&self, slice: &ImageSlice) -> bool {
+        Rc::ptr_eq(&self.image, &slice.image)
+            && self.placement_id == slice.placement_id
+            && self.col == slice.col
+    }
+}
+
+/// Handles `a=d`.  Lower-case specifiers delete placements only; upper-case
+/// ones also free the image data of affected images left without placements.
+fn delete(term: &mut Terminal, command: &GraphicsCommand) {
+    let free = command.delete.is_ascii_uppercase();
+    // Protocol cell coordinates are 1-based.
+    let cell_col = command.x.saturating_sub(1) as usize;
+    let cell_row = command.y.saturating_sub(1) as usize;
+    let cursor = (term.cursor_row(), term.cursor_col());
+
+    let mut affected_ids = Vec::new();
+    match command.delete.to_ascii_lowercase() {
+        b'i' | b'n' => {
+            let id = if command.delete.eq_ignore_ascii_case(&b'i') {
+                Some(command.image_id)
+            } else {
+                term.graphics.id_for_number(command.image_number)
+            };
+            let Some(image) = id.and_then(|id| term.graphics.get(id)) else {
+                return;
+            };
+            let placement_id = command.placement_id;
+            term.remove_image_strips(|slice| {
+                Rc::ptr_eq(&slice.image, &image) && (placement_id == 0 || slice.placement_id == placement_id)
+            });
+            affected_ids.extend(id);
+        }
+        b'r' => {
+            let range = command.x..=command.y;
+            affected_ids = term.graphics.ids_where(|id, _| range.contains(&id));
+            let images: Vec<_> = affected_ids.iter().filter_map(|&id| term.graphics.get(id)).collect();
+            term.remove_image_strips(|slice| images.iter().any(|image| Rc::ptr_eq(image, &slice.image)));
+        }
+        specifier => {
+            let z = command.z_index;
+            let keys = visible_placements(term, |row, slice| match specifier {
+                b'a' => true,
+                b'c' => row == cursor.0 && slice.covers_col(cursor.1),
+                b'p' => row == cell_row && slice.covers_col(cell_col),
+                b'q' => row == cell_row && slice.covers_col(cell_col) && slice.z_index == z,
+                b'x' => slice.covers_col(cell_col),
+                b'y' => row == cell_row,
+                b'z' => slice.z_index == z,
+                _ => false,
+            });
+            term.remove_image_strips(|slice| keys.iter().any(|key| key.matches(slice)));
+            if free {
+                affected_ids = term
+                    .graphics
+                    .ids_where(|_, image| keys.iter().any(|key| Rc::ptr_eq(&key.image, image)));
+            }
+        }
+    }
+    if free {
+        term.graphics.free_unplaced(&affected_ids);
+    }
+}
+
+/// Collects the placements with a strip on a viewport row matching `pred`.
+fn visible_placements(
+    term: &Terminal,
+    mut pred: impl FnMut(usize, &ImageSlice) -> bool,
+) -> Vec<PlacementKey> {
+    let mut keys: Vec<PlacementKey> = Vec::new();
+    for row in 0..term.screen.viewport_rows() {
+        for slice in &term.screen.viewport_row(row).images {
+            if pred(row, slice) && !keys.iter().any(|key| key.matches(slice)) {
+                keys.push(PlacementKey::of(slice));
+            }
+        }
+    }
+    keys
+}
+
+/// Sends the protocol reply for a command that addressed an image by id or
+/// number; `q=1` silences successes and `q=2` errors as well.
+fn respond(term: &mut Terminal, command: &GraphicsCommand, image_id: u32, result: Result<(), GraphicsError>) {
+    if command.image_id == 0 && command.image_number == 0 {
+        return;
+    }
+    let message = match result {
+        Ok(()) if command.quiet == 0 => "OK".to_string(),
+        Err(err) if command.quiet < 2 => format!("{}:{}", err.code, err.reason),
+        _ => return,
+    };
+    let mut reply = format!("\x1b_Gi={image_id}");
+    if command.image_number != 0 {
+        reply.push_str(&format!(",I={}", command.image_number));
+    }
+    if command.placement_id != 0 {
+        reply.push_str(&format!(",p={}", command.placement_id));
+    }
+    reply.push_str(&format!(";{message}\x1b\\"));
+    term.respond(reply.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::terminal::Terminal;
+    use base64::Engine as _;
+
+    /// A 4x4 opaque RGBA image, base64-encoded.
+    fn rgba_4x4() -> String {
+        base64::engine::general_purpose::STANDARD.encode([200u8; 4 * 4 * 4])
+    }
+
+    fn term() -> Terminal {
+        let mut term = Terminal::new(4, 10);
+        term.set_cell_pixel_size(2, 2);
+        term
+    }
+
+    fn image_rows(term: &Terminal) -> Vec<usize> {
+        (0..term.screen.viewport_rows())
+            .filter(|&r| !term.screen.viewport_row(r).images.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn transmit_and_display_places_image_and_replies_ok() {
+        let mut term = term();
+        term.process(format!("\x1b_Ga=T,f=32,s=4,v=4,i=5;{}\x1b\\", rgba_4x4()).as_bytes());
+
+        assert_eq!(image_rows(&term), vec![0, 1]);
+        let strip = &term.screen.viewport_row(1).images[0];
+        assert_eq!((strip.col, strip.cols, strip.src_y, strip.height), (0, 2, 2, 2));
+        // Cursor after the last column, on the last row.
+        assert_eq!((term.cursor_row(), term.cursor_col()), (1, 2));
+        assert_eq!(term.drain_responses(), b"\x1b_Gi=5;OK\x1b\\");
+    }
+
+    #[test]
+    fn chunked_transmission_across_process_calls() {
+        let mut term = term();
+        let data = rgba_4x4();
+        let (head, tail) = data.split_at(8);
+        term.process(format!("\x1b_Ga=t,f=32,s=4,v=4,i=1,m=1;{head}\x1b\\").as_bytes());
+        assert!(term.drain_responses().is_empty());
+        let last = format!("\x1b_Gm=0;{tail}\x1b\\");
+        let (a, b) = last.as_bytes().split_at(5);
+        term.process(a);
+        term.process(b);
+        assert_eq!(term.drain_responses(), b"\x1b_Gi=1;OK\x1b\\");
+
+        term.process(b"\x1b_Ga=p,i=1,p=3,c=1,r=1,C=1\x1b\\");
+        assert_eq!(term.drain_responses(), b"\x1b_Gi=1,p=3;OK\x1b\\");
+        let strip = &term.screen.viewport_row(0).images[0];
+        assert_eq!((strip.width, strip.height, strip.placement_id), (2, 2, 3));
+        assert_eq!((term.cursor_row(), term.cursor_col()), (0, 0));
+    }
+
+    #[test]
+    fn offsets_and_z_index_are_recorded() {
+        let mut term = term();
+        term.process(format!("\x1b_Ga=T,s=4,v=4,X=1,Y=1,z=-3,q=2;{}\x1b\\", rgba_4x4()).as_bytes());
+        // 1px offset + 4px image spans three 2px rows and columns.
+        assert_eq!(image_rows(&term), vec![0, 1, 2]);
+        let first = &term.screen.viewport_row(0).images[0];
+        assert_eq!((first.x_offset, first.y_offset, first.height, first.cols), (1, 1, 1, 3));
+        assert_eq!(first.z_index, -3);
+        let last = &term.screen.viewport_row(2).images[0];
+        assert_eq!((last.y_offset, last.height, last.src_y), (0, 1, 3));
+        assert!(term.drain_responses().is_empty());
+    }
+
+    #[test]
+    fn file_transmission_is_off_by_default() {
+        use base64::Engine as _;
+        let mut term = term();
+        let path = base64::engine::general_purpose::STANDARD.encode("/etc/hostname");
+        term.process(format!("\x1b_Ga=t,t=f,s=1,v=1,i=3;{path}\x1b\\").as_bytes());
+        assert_eq!(
+            term.drain_responses(),
+            b"\x1b_Gi=3;EPERM:file transmission is disabled\x1b\\"
+        );
+    }
+
+    #[test]
+    fn placement_scrolls_with_rows() {
+        let mut term = term();
+        term.process(format!("\x1b_Ga=T,s=4,v=4;{}\x1b\\", rgba_4x4()).as_bytes());
+        term.process(b"\r\n\n\n\n");
+        assert_eq!(term.screen.scrollback_len(), 2);
+        assert_eq!(term.screen.scrollback_row(0).images.len(), 1);
+        assert_eq!(term.screen.scrollback_row(1).images.len(), 1);
+    }
+
+    #[test]
+    fn delete_by_id_removes_every_strip() {
+        let mut term = term();
+        term.process(format!("\x1b_Ga=T,s=4,v=4,i=2,q=1;{}\x1b\\", rgba_4x4()).as_bytes());
+        term.process(b"\x1b_Ga=d,d=i,i=2\x1b\\");
+        assert!(image_rows(&term).is_empty());
+        // Lower-case delete keeps the data, so it can be placed again.
+        term.process(b"\x1b_Ga=p,i=2\x1b\\");
+        assert_eq!(term.drain_responses(), b"\x1b_Gi=2;OK\x1b\\");
+
+        term.process(b"\x1b_Ga=d,d=I,i=2\x1b\\");
+        term.process(b"\x1b_Ga=p,i=2\x1b\\");
+        assert_eq!(term.drain_responses(), b"\x1b_Gi=2;ENOENT:image not found\x1b\\");
+    }
+
+    #[test]
+    fn delete_at_cursor_removes_whole_placement() {
+        let mut term = term();
+        term.process(format!("\x1b_Ga=T,s=4,v=4,C=1;{}\x1b\\", rgba_4x4()).as_bytes());
+        term.process(b"\x1b[2;1H\x1b_Ga=d,d=c\x1b\\");
+        assert!(image_rows(&term).is_empty());
+    }
+
+    #[test]
+    fn query_does_not_store() {
+        let mut term = term();
+        term.process(format!("\x1b_Ga=q,s=4,v=4,i=31;{}\x1b\\", rgba_4x4()).as_bytes());
+        assert_eq!(term.drain_responses(), b"\x1b_Gi=31;OK\x1b\\");
+        term.process(b"\x1b_Ga=p,i=31\x1b\\");
+        assert_eq!(term.drain_responses(), b"\x1b_Gi=31;ENOENT:image not found\x1b\\");
+    }
+
+    #[test]
+    fn bad_data_reports_error() {
+        let mut term = term();
+        term.process(b"\x1b_Ga=t,s=4,v=4,i=9;AAAA\x1b\\");
+        assert_eq!(term.drain_responses(), b"\x1b_Gi=9;ENODATA:insufficient image data\x1b\\");
+    }
+}
@@ -1,8 +1,18 @@
 pub(super) mod attributes;
+pub(super) mod clipboard;
+pub(super) mod colors;
 pub(super) mod cursor;
+pub(super) mod dcs;
 pub(super) mod device;
 pub(super) mod edit;
 pub(super) mod erase;
+pub(super) mod hyperlinks;
+pub(super) mod keyboard;
+pub(super) mod kitty_graphics;
+pub(super) mod notifications;
 pub(super) mod private_modes;
+pub(super) mod reports;
 pub(super) mod scroll;
+pub(super) mod semantic_prompt;
 pub(super) mod sgr;
+pub(super) mod tabs;
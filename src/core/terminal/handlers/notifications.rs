@@ -0,0 +1,93 @@
+//! Desktop notifications: OSC 9 `9 ; body` (iTerm2) and OSC 777
+//! `777 ; notify ; title ; body` (rxvt-unicode).
+
+use crate::core::terminal::{Notification, Terminal};
+
+/// Notifications queued beyond this before the GUI drains them are dropped.
+const MAX_PENDING_NOTIFICATIONS: usize = 16;
+
+pub(in super::super) fn handle_notification_osc(term: &mut Terminal, params: &[&[u8]]) {
+    if !term.security_config.allow_notifications
+        || term.pending_notifications.len() >= MAX_PENDING_NOTIFICATIONS
+    {
+        return;
+    }
+    let notification = if params[0] == b"9" {
+        // ConEmu uses OSC 9 with numeric subcommands (`9;4;…` is progress).
+        let first = params.get(1).copied().unwrap_or_default();
+        if first.is_empty() || first.iter().all(u8::is_ascii_digit) {
+            return;
+        }
+        Notification {
+            title: String::new(),
+            body: join_text(&params[1..]),
+        }
+    } else {
+        if params.get(1).copied() != Some(b"notify") {
+            return;
+        }
+        Notification {
+            title: join_text(params.get(2..3).unwrap_or_default()),
+            body: join_text(params.get(3..).unwrap_or_default()),
+        }
+    };
+    if notification.title.is_empty() && notification.body.is_empty() {
+        return;
+    }
+    term.pending_notifications.push(notification);
+}
+
+/// Rejoins text the parser split at `;`.
+fn join_text(parts: &[&[u8]]) -> String {
+    String::from_utf8_lossy(&parts.join(&b';')).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::terminal::{Notification, Terminal};
+
+    fn notification(title: &str, body: &str) -> Notification {
+        Notification {
+            title: title.to_owned(),
+            body: body.to_owned(),
+        }
+    }
+
+    #[test]
+    fn osc_777_notify_has_title_and_body() {
+        let mut term = Terminal::new(2, 10);
+        term.process(b"\x1b]777;notify;Build;done; 3 warnings\x07");
+        assert_eq!(
+            term.drain_notifications(),
+            vec![notification("Build", "done; 3 warnings")]
+        );
+        assert!(term.drain_notifications().is_empty());
+        // Other OSC 777 commands are not notifications.
+        term.process(b"\x1b]777;preexec\x1b\\");
+        assert!(term.drain_notifications().is_empty());
+    }
+
+    #[test]
+    fn osc_9_posts_body_and_skips_conemu_commands() {
+        let mut term = Terminal::new(2, 10);
+        term.process(b"\x1b]9;Tests passed\x1b\\\x1b]9;4;1;50\x1b\\\x1b]9;\x1b\\");
+        assert_eq!(
+            term.drain_notifications(),
+            vec![notification("", "Tests passed")]
+        );
+    }
+
+    #[test]
+    fn notifications_follow_security_config() {
+        let mut term = Terminal::new(2, 10);
+        term.security_config.allow_notifications = false;
+        term.process(b"\x1b]9;hidden\x07");
+        assert!(term.drain_notifications().is_empty());
+
+        term.security_config.allow_notifications = true;
+        for _ in 0..20 {
+            term.process(b"\x1b]9;spam\x07");
+        }
+        assert_eq!(term.drain_notifications().len(), 16);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::core::terminal::Terminal;
-use crate::core::{CursorStyle, MouseMode};
+use crate::core::{CellEffects, CursorStyle, MouseEncoding, MouseMode};
 use vte::Params;
 
 pub(in super::super) fn handle_private_mode(
@@ -23,26 +23,84 @@ pub(in super::super) fn handle_private_mode(
         match (action, mode) {
             ('h', 1) => term.set_decckm(true), // DECCKM: application cursor keys
             ('l', 1) => term.set_decckm(false), // DECCKM: normal cursor keys
+            ('h', 6) => term.set_origin_mode(true), // DECOM: origin at the scroll region
+            ('l', 6) => term.set_origin_mode(false),
+            ('h', 7) => term.set_autowrap(true), // DECAWM: wrap at the right margin
+            ('l', 7) => term.set_autowrap(false),
             ('h', 25) => term.set_cursor_visible(true),
             ('l', 25) => term.set_cursor_visible(false),
+            ('h', 66) => term.set_application_keypad(true), // DECNKM: application keypad
+            ('l', 66) => term.set_application_keypad(false),
+            // Alternate screen: see alt_screen.rs for how the variants differ.
+            ('h', 47 | 1047) => term.switch_to_alt_screen(false),
+            ('l', 47) => term.switch_to_main_screen(false),
+            ('l', 1047) => term.switch_to_main_screen(true),
+            ('h', 1048) => term.save_cursor(),
+            ('l', 1048) => term.restore_cursor(),
             ('h', 1049) => term.enter_alt_screen(),
             ('l', 1049) => term.leave_alt_screen(),
             // Mouse tracking modes
+            ('h', 9) => term.set_mouse_mode(MouseMode::X10),
+            ('l', 9) => term.set_mouse_mode(MouseMode::Off),
             ('h', 1000) => term.set_mouse_mode(MouseMode::Normal),
             ('l', 1000) => term.set_mouse_mode(MouseMode::Off),
             ('h', 1002) => term.set_mouse_mode(MouseMode::ButtonEvent),
             ('l', 1002) => term.set_mouse_mode(MouseMode::Off),
             ('h', 1003) => term.set_mouse_mode(MouseMode::AnyEvent),
             ('l', 1003) => term.set_mouse_mode(MouseMode::Off),
-            // SGR extended mouse format
-            ('h', 1006) => term.set_sgr_mouse(true),
-            ('l', 1006) => term.set_sgr_mouse(false),
+            // Extended mouse encodings
+            (_, 1005) => term.set_mouse_encoding(MouseEncoding::Utf8, action == 'h'),
+            (_, 1006) => term.set_mouse_encoding(MouseEncoding::Sgr, action == 'h'),
+            (_, 1015) => term.set_mouse_encoding(MouseEncoding::Urxvt, action == 'h'),
+            (_, 1016) => term.set_mouse_encoding(MouseEncoding::SgrPixels, action == 'h'),
             // Focus reporting
             ('h', 1004) => term.set_focus_reporting(true),
             ('l', 1004) => term.set_focus_reporting(false),
             // Bracketed paste mode
             ('h', 2004) => term.set_bracketed_paste(true),
             ('l', 2004) => term.set_bracketed_paste(false),
+            // Synchronized output
+            ('h', 2026) => term.set_synchronized_output(true),
+            ('l', 2026) => term.set_synchronized_output(false),
+            _ => {}
+        }
+    }
+    true
+}
+
+/// DECSTR (`CSI ! p`): soft reset. Restores modes, margins, character sets,
+/// rendition and the saved cursor to their defaults, leaving the screen
+/// contents and cursor position alone (unlike RIS).
+pub(in super::super) fn soft_reset(term: &mut Terminal) {
+    term.set_cursor_visible(true);
+    term.set_insert_mode(false);
+    term.origin_mode = false;
+    term.set_autowrap(true);
+    term.set_decckm(false);
+    term.set_application_keypad(false);
+    term.reset_scroll_region();
+    term.charsets = Default::default();
+    term.reset_attributes();
+    term.set_effect(CellEffects::PROTECTED, false);
+    term.saved_cursor = Default::default();
+}
+
+/// ANSI modes (`CSI Pm h` / `CSI Pm l`, no `?`).
+pub(in super::super) fn handle_ansi_mode(
+    term: &mut Terminal,
+    params: &Params,
+    intermediates: &[u8],
+    action: char,
+) -> bool {
+    if !intermediates.is_empty() || (action != 'h' && action != 'l') {
+        return false;
+    }
+
+    let enabled = action == 'h';
+    for param in params.iter() {
+        match param.first().copied() {
+            Some(4) => term.set_insert_mode(enabled),    // IRM: insert/replace
+            Some(20) => term.set_linefeed_mode(enabled), // LNM: linefeed/new line
             _ => {}
         }
     }
@@ -76,7 +134,7 @@ pub(in super::super) fn handle_cursor_style_csi(
 #[cfg(test)]
 mod tests {
     use crate::core::terminal::Terminal;
-    use crate::core::{CursorStyle, MouseMode};
+    use crate::core::{CursorStyle, MouseEncoding, MouseMode};
 
     #[test]
     fn decckm_application_mode() {
@@ -173,11 +231,11 @@ mod tests {
         let mut term = Terminal::new(4, 10);
         term.process(b"\x1b[?1002;1006h");
         assert_eq!(term.mouse_mode, MouseMode::ButtonEvent);
-        assert!(term.sgr_mouse);
+        assert_eq!(term.mouse_encoding, MouseEncoding::Sgr);
 
         term.process(b"\x1b[?1002;1006l");
         assert_eq!(term.mouse_mode, MouseMode::Off);
-        assert!(!term.sgr_mouse);
+        assert_eq!(term.mouse_encoding, MouseEncoding::Default);
     }
 
     #[test]
@@ -186,7 +244,7 @@ mod tests {
         term.set_cursor(1, 2);
 
         term.process(b"\x1b[?6n");
-        assert_eq!(term.drain_responses(), b"\x1b[2;3R".to_vec());
+        assert_eq!(term.drain_responses(), b"\x1b[?2;3;1R".to_vec());
     }
 
     #[test]
@@ -202,9 +260,25 @@ mod tests {
     fn sgr_mouse_on_off() {
         let mut term = Terminal::new(4, 10);
         term.process(b"\x1b[?1006h");
-        assert!(term.sgr_mouse);
+        assert_eq!(term.mouse_encoding, MouseEncoding::Sgr);
         term.process(b"\x1b[?1006l");
-        assert!(!term.sgr_mouse);
+        assert_eq!(term.mouse_encoding, MouseEncoding::Default);
+    }
+
+    #[test]
+    fn mouse_encodings_replace_each_other() {
+        let mut term = Terminal::new(4, 10);
+        term.process(b"\x1b[?9h");
+        assert_eq!(term.mouse_mode, MouseMode::X10);
+        term.process(b"\x1b[?1006h\x1b[?1016h");
+        assert_eq!(term.mouse_encoding, MouseEncoding::SgrPixels);
+        // Resetting an inactive encoding leaves the current one alone.
+        term.process(b"\x1b[?1005l");
+        assert_eq!(term.mouse_encoding, MouseEncoding::SgrPixels);
+        term.process(b"\x1b[?1015h");
+        assert_eq!(term.mouse_encoding, MouseEncoding::Urxvt);
+        term.process(b"\x1b[?1015l");
+        assert_eq!(term.mouse_encoding, MouseEncoding::Default);
     }
 
     #[test]
@@ -227,6 +301,19 @@ mod tests {
         assert!(!term.focus_reporting);
     }
 
+    #[test]
+    fn synchronized_output_on_off() {
+        let mut term = Terminal::new(4, 10);
+        assert!(term.synchronized_output_since().is_none());
+        term.process(b"\x1b[?2026h");
+        let since = term.synchronized_output_since().expect("update open");
+        // Repeating the request keeps the original start.
+        term.process(b"\x1b[?2026h");
+        assert_eq!(term.synchronized_output_since(), Some(since));
+        term.process(b"\x1b[?2026l");
+        assert!(term.synchronized_output_since().is_none());
+    }
+
     #[test]
     fn cursor_style_steady_block() {
         let mut term = Terminal::new(4, 10);
@@ -240,4 +327,25 @@ mod tests {
         term.process(b"\x1b[5 q");
         assert_eq!(term.cursor_style, CursorStyle::BlinkingBar);
     }
+
+    #[test]
+    fn soft_reset_restores_modes_but_keeps_screen() {
+        let mut term = Terminal::new(6, 10);
+        term.process(b"Hi\x1b[?25l\x1b[4h\x1b[?7l\x1b[?1h\x1b[2;4r\x1b(0\x1b[1;31m\x1b[?6h");
+        term.process(b"\x1b[2;3H\x1b[!p");
+        assert!(term.cursor_visible);
+        assert!(!term.insert_mode);
+        assert!(!term.origin_mode);
+        assert!(term.autowrap);
+        assert!(!term.decckm);
+        assert_eq!((term.scroll_top, term.scroll_bottom), (0, 5));
+        assert_eq!((term.cursor_row(), term.cursor_col()), (2, 2));
+        assert_eq!(term.screen.viewport_get(0, 0).first_char(), 'H');
+
+        term.process(b"q");
+        let cell = term.screen.viewport_get(2, 2);
+        assert_eq!(cell.first_char(), 'q');
+        assert!(!cell.bold);
+        assert_eq!(cell.fg, term.default_fg);
+    }
 }
@@ -0,0 +1,230 @@
+//! Feature probes: DECRQM mode queries and DECRQSS status strings.
+
+use crate::core::terminal::Terminal;
+use crate::core::{CellEffects, Color, CursorStyle, MouseEncoding, MouseMode, UnderlineStyle};
+use vte::Params;
+
+/// Longest DECRQSS request accepted; real ones are one or two bytes.
+pub(in super::super) const MAX_STATUS_REQUEST_LEN: usize = 8;
+
+/// DECRPM `Ps` values.
+const MODE_NOT_RECOGNIZED: u8 = 0;
+const MODE_SET: u8 = 1;
+const MODE_RESET: u8 = 2;
+
+fn mode_state(enabled: bool) -> u8 {
+    if enabled { MODE_SET } else { MODE_RESET }
+}
+
+/// State of DEC private mode `mode`, as reported by DECRPM.
+fn private_mode_state(term: &Terminal, mode: u16) -> u8 {
+    match mode {
+        1 => mode_state(term.decckm),
+        6 => mode_state(term.origin_mode),
+        7 => mode_state(term.autowrap),
+        25 => mode_state(term.cursor_visible),
+        66 => mode_state(term.application_keypad),
+        9 => mode_state(term.mouse_mode == MouseMode::X10),
+        1000 => mode_state(term.mouse_mode == MouseMode::Normal),
+        1002 => mode_state(term.mouse_mode == MouseMode::ButtonEvent),
+        1003 => mode_state(term.mouse_mode == MouseMode::AnyEvent),
+        1004 => mode_state(term.focus_reporting),
+        1005 => mode_state(term.mouse_encoding == MouseEncoding::Utf8),
+        1006 => mode_state(term.mouse_encoding == MouseEncoding::Sgr),
+        1015 => mode_state(term.mouse_encoding == MouseEncoding::Urxvt),
+        1016 => mode_state(term.mouse_encoding == MouseEncoding::SgrPixels),
+        47 | 1047 | 1049 => mode_state(term.is_alt_screen()),
+        2004 => mode_state(term.bracketed_paste),
+        2026 => mode_state(term.synchronized_output.is_some()),
+        _ => MODE_NOT_RECOGNIZED,
+    }
+}
+
+/// State of ANSI mode `mode`, as reported by DECRPM.
+fn ansi_mode_state(term: &Terminal, mode: u16) -> u8 {
+    match mode {
+        4 => mode_state(term.insert_mode),
+        20 => mode_state(term.linefeed_mode),
+        _ => MODE_NOT_RECOGNIZED,
+    }
+}
+
+/// DECRQM — `CSI ? Pd $ p` (DEC private) and `CSI Pd $ p` (ANSI).
+pub(in super::super) fn handle_mode_report_csi(
+    term: &mut Terminal,
+    params: &Params,
+    intermediates: &[u8],
+    action: char,
+) -> bool {
+    if action != 'p' {
+        return false;
+    }
+    let mode = term.param(params, 0);
+    let response = match intermediates {
+        b"?$" => format!("\x1b[?{mode};{}$y", private_mode_state(term, mode)),
+        b"$" => format!("\x1b[{mode};{}$y", ansi_mode_state(term, mode)),
+        _ => return false,
+    };
+    term.respond(response.as_bytes());
+    true
+}
+
+/// SGR parameters for `color` as a foreground (`base` 30) or background
+/// (`base` 40) colour; `None` for the default colour.
+fn sgr_color(term: &Terminal, color: Color, default: Color, base: u16) -> Option<String> {
+    if color == default {
+        return None;
+    }
+    let code = match term.ansi_palette.iter().position(|&c| c == color) {
+        Some(i @ 0..=7) => (base + i as u16).to_string(),
+        Some(i) => (base + 60 + i as u16 - 8).to_string(),
+        None => format!("{};2;{};{};{}", base + 8, color.r, color.g, color.b),
+    };
+    Some(code)
+}
+
+/// The current graphic rendition as an SGR parameter string.
+fn current_sgr(term: &Terminal) -> String {
+    let mut codes = vec!["0".to_owned()];
+    let underline = term.current_underline_style;
+    let effects = term.current_effects;
+    let flags = [
+        (term.current_bold, "1"),
+        (term.current_dim, "2"),
+        (term.current_italic, "3"),
+        (underline == UnderlineStyle::Single, "4"),
+        (underline == UnderlineStyle::Curly, "4:3"),
+        (underline == UnderlineStyle::Dotted, "4:4"),
+        (underline == UnderlineStyle::Dashed, "4:5"),
+        (effects.contains(CellEffects::BLINK), "5"),
+        (term.current_reverse, "7"),
+        (effects.contains(CellEffects::HIDDEN), "8"),
+        (term.current_strikethrough, "9"),
+        (underline == UnderlineStyle::Double, "21"),
+        (effects.contains(CellEffects::OVERLINE), "53"),
+    ];
+    codes.extend(
+        flags
+            .iter()
+            .filter(|(on, _)| *on)
+            .map(|(_, code)| (*code).to_owned()),
+    );
+    codes.extend(sgr_color(term, term.current_fg, term.default_fg, 30));
+    codes.extend(sgr_color(term, term.current_bg, term.default_bg, 40));
+    codes.extend(
+        term.current_underline_color
+            .map(|c| format!("58:2::{}:{}:{}", c.r, c.g, c.b)),
+    );
+    codes.join(";")
+}
+
+fn cursor_style_param(style: CursorStyle) -> u8 {
+    match style {
+        CursorStyle::BlinkingBlock => 1,
+        CursorStyle::SteadyBlock => 2,
+        CursorStyle::BlinkingUnderline => 3,
+        CursorStyle::SteadyUnderline => 4,
+        CursorStyle::BlinkingBar => 5,
+        CursorStyle::SteadyBar => 6,
+    }
+}
+
+/// DECRQSS — answers `DCS $ q Pt ST` with `DCS 1 $ r <setting> ST`, or
+/// `DCS 0 $ r ST` when the setting is not supported.
+pub(in super::super) fn reply_status_string(term: &mut Terminal, request: &[u8]) {
+    let setting = match request {
+        b"m" => Some(format!("{}m", current_sgr(term))),
+        b" q" => Some(format!("{} q", cursor_style_param(term.cursor_style))),
+        b"\"q" => Some(format!(
+            "{}\"q",
+            u8::from(term.current_effects.contains(CellEffects::PROTECTED))
+        )),
+        b"r" => Some(format!(
+            "{};{}r",
+            term.scroll_top + 1,
+            term.scroll_bottom + 1
+        )),
+        _ => None,
+    };
+    let response = match setting {
+        Some(setting) => format!("\x1bP1$r{setting}\x1b\\"),
+        None => "\x1bP0$r\x1b\\".to_owned(),
+    };
+    term.respond(response.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::terminal::Terminal;
+
+    fn reply(term: &mut Terminal, query: &[u8]) -> String {
+        term.process(query);
+        String::from_utf8(term.drain_responses()).unwrap()
+    }
+
+    #[test]
+    fn decrqm_reports_private_modes() {
+        let mut term = Terminal::new(4, 10);
+        assert_eq!(reply(&mut term, b"\x1b[?2004$p"), "\x1b[?2004;2$y");
+        term.process(b"\x1b[?2004h\x1b[?1002h\x1b=");
+        assert_eq!(reply(&mut term, b"\x1b[?2004$p"), "\x1b[?2004;1$y");
+        assert_eq!(reply(&mut term, b"\x1b[?1002$p"), "\x1b[?1002;1$y");
+        assert_eq!(reply(&mut term, b"\x1b[?1000$p"), "\x1b[?1000;2$y");
+        term.process(b"\x1b[?1016h");
+        assert_eq!(reply(&mut term, b"\x1b[?1016$p"), "\x1b[?1016;1$y");
+        assert_eq!(reply(&mut term, b"\x1b[?1006$p"), "\x1b[?1006;2$y");
+        assert_eq!(reply(&mut term, b"\x1b[?66$p"), "\x1b[?66;1$y");
+        assert_eq!(reply(&mut term, b"\x1b[?7$p"), "\x1b[?7;1$y");
+        assert_eq!(reply(&mut term, b"\x1b[?9999$p"), "\x1b[?9999;0$y");
+        assert_eq!(reply(&mut term, b"\x1b[?2026$p"), "\x1b[?2026;2$y");
+        term.process(b"\x1b[?2026h");
+        assert_eq!(reply(&mut term, b"\x1b[?2026$p"), "\x1b[?2026;1$y");
+    }
+
+    #[test]
+    fn decrqm_reports_alt_screen_and_ansi_modes() {
+        let mut term = Terminal::new(4, 10);
+        term.process(b"\x1b[?1049h\x1b[4h");
+        assert_eq!(reply(&mut term, b"\x1b[?1049$p"), "\x1b[?1049;1$y");
+        assert_eq!(reply(&mut term, b"\x1b[4$p"), "\x1b[4;1$y");
+        assert_eq!(reply(&mut term, b"\x1b[20$p"), "\x1b[20;2$y");
+        assert_eq!(reply(&mut term, b"\x1b[3$p"), "\x1b[3;0$y");
+    }
+
+    #[test]
+    fn decrqss_reports_sgr() {
+        let mut term = Terminal::new(4, 10);
+        assert_eq!(reply(&mut term, b"\x1bP$qm\x1b\\"), "\x1bP1$r0m\x1b\\");
+        term.process(b"\x1b[1;4;31;104m");
+        assert_eq!(
+            reply(&mut term, b"\x1bP$qm\x1b\\"),
+            "\x1bP1$r0;1;4;31;104m\x1b\\"
+        );
+        term.process(b"\x1b[0;48;2;1;2;3m");
+        assert_eq!(
+            reply(&mut term, b"\x1bP$qm\x1b\\"),
+            "\x1bP1$r0;48;2;1;2;3m\x1b\\"
+        );
+        term.process(b"\x1b[0;4:3;5;53;58:2::9:8:7m");
+        assert_eq!(
+            reply(&mut term, b"\x1bP$qm\x1b\\"),
+            "\x1bP1$r0;4:3;5;53;58:2::9:8:7m\x1b\\"
+        );
+    }
+
+    #[test]
+    fn decrqss_reports_cursor_style_and_margins() {
+        let mut term = Terminal::new(10, 10);
+        term.process(b"\x1b[4 q\x1b[2;7r");
+        assert_eq!(reply(&mut term, b"\x1bP$q q\x1b\\"), "\x1bP1$r4 q\x1b\\");
+        assert_eq!(reply(&mut term, b"\x1bP$qr\x1b\\"), "\x1bP1$r2;7r\x1b\\");
+        term.process(b"\x1b[1\"q");
+        assert_eq!(reply(&mut term, b"\x1bP$q\"q\x1b\\"), "\x1bP1$r1\"q\x1b\\");
+    }
+
+    #[test]
+    fn decrqss_rejects_unknown_settings() {
+        let mut term = Terminal::new(4, 10);
+        assert_eq!(reply(&mut term, b"\x1bP$qx\x1b\\"), "\x1bP0$r\x1b\\");
+    }
+}
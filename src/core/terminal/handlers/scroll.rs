@@ -27,8 +27,8 @@ pub(in super::super) fn handle_scroll_csi(
 
             term.scroll_top = top_1_based - 1;
             term.scroll_bottom = bottom_1_based - 1;
-            // VT spec: DECSTBM resets cursor to home position (0,0).
-            term.set_cursor(0, 0);
+            // VT spec: DECSTBM moves the cursor home (the region's top in origin mode).
+            term.cursor_home();
             true
         }
         'S' => {
@@ -0,0 +1,106 @@
+//! OSC 133 semantic prompt marks: `A` prompt start, `B` command input start,
+//! `C` command output start, `D [; exit]` command finished.
+
+use std::time::Instant;
+
+use crate::core::terminal::Terminal;
+use crate::core::{CommandStatus, PromptMarkKind};
+
+pub(in super::super) fn handle_semantic_prompt(term: &mut Terminal, params: &[&[u8]]) {
+    let kind = match params.get(1).copied() {
+        Some(b"A") => PromptMarkKind::Prompt,
+        Some(b"B") => PromptMarkKind::Input,
+        Some(b"C") => PromptMarkKind::Output,
+        Some(b"D") => PromptMarkKind::CommandEnd,
+        _ => return,
+    };
+    // Full-screen programs own the alternate screen; it has no scrollback to navigate.
+    if term.is_alt_screen() {
+        return;
+    }
+    let row = term.cursor_row();
+    let col = term.cursor_col().min(u16::MAX as usize) as u16;
+    term.screen.viewport_row_mut(row).marks.set(kind, Some(col));
+
+    match kind {
+        PromptMarkKind::Output => term.command_started_at = Some(Instant::now()),
+        // A `D` without a preceding `C` (e.g. an empty command line) carries no result.
+        PromptMarkKind::CommandEnd => {
+            if let Some(started) = term.command_started_at.take() {
+                let status = CommandStatus {
+                    exit_code: params.get(2).and_then(|code| parse_exit_code(code)),
+                    duration: started.elapsed(),
+                };
+                let abs_row = term.screen.viewport_start_abs() + row;
+                term.screen.record_command_status(abs_row, status);
+            }
+        }
+        PromptMarkKind::Prompt | PromptMarkKind::Input => {}
+    }
+}
+
+/// Parses the exit code of `D;<code>`; extra `key=value` options are ignored.
+fn parse_exit_code(param: &[u8]) -> Option<i32> {
+    std::str::from_utf8(param).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::terminal::Terminal;
+
+    #[test]
+    fn marks_are_recorded_at_the_cursor() {
+        let mut term = Terminal::new(4, 20);
+        term.process(b"\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]133;C\x07out\r\n\x1b]133;D;0\x07");
+        let marks = |row| term.screen.viewport_row(row).marks;
+        assert_eq!(marks(0).prompt, Some(0));
+        assert_eq!(marks(0).input, Some(2));
+        assert_eq!(marks(1).output, Some(0));
+        assert_eq!(marks(2).command_end, Some(0));
+    }
+
+    #[test]
+    fn command_end_records_status_on_the_prompt_row() {
+        let mut term = Terminal::new(6, 20);
+        term.process(b"\x1b]133;A\x07$ \x1b]133;B\x07false\r\n\x1b]133;C\x07");
+        term.process(b"\x1b]133;D;1\x07\x1b]133;A\x07$ \x1b]133;B\x07true\r\n\x1b]133;C\x07");
+        term.process(b"\x1b]133;D;0\x07\x1b]133;A\x07$ ");
+        let status = |row| term.screen.viewport_row(row).marks.status;
+        assert_eq!(status(0).and_then(|s| s.exit_code), Some(1));
+        assert!(!status(0).unwrap().succeeded());
+        assert_eq!(status(1).and_then(|s| s.exit_code), Some(0));
+        assert_eq!(status(2), None);
+        assert_eq!(term.screen.command_statuses().count(), 2);
+    }
+
+    #[test]
+    fn command_end_without_output_mark_is_ignored() {
+        let mut term = Terminal::new(4, 20);
+        term.process(b"\x1b]133;A\x07$ \x1b]133;B\x07\r\n\x1b]133;D;0\x07");
+        assert_eq!(term.screen.command_statuses().count(), 0);
+        // A missing exit code still records the command as finished.
+        term.process(b"\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]133;C\x07\x1b]133;D\x07");
+        let (row, status) = term.screen.command_statuses().next().unwrap();
+        assert_eq!(row, 1);
+        assert_eq!(status.exit_code, None);
+        assert!(status.succeeded());
+    }
+
+    #[test]
+    fn unknown_marks_and_alt_screen_are_ignored() {
+        let mut term = Terminal::new(4, 20);
+        term.process(b"\x1b]133;Z\x07");
+        assert!(term.screen.viewport_row(0).marks.is_empty());
+        term.process(b"\x1b[?1049h\x1b]133;A\x07");
+        assert!(term.screen.viewport_row(0).marks.is_empty());
+    }
+
+    #[test]
+    fn marks_scroll_into_scrollback() {
+        let mut term = Terminal::new(2, 10);
+        term.process(b"\x1b]133;A\x07$ \r\n1\r\n2\r\n3");
+        assert_eq!(term.screen.scrollback_len(), 2);
+        assert_eq!(term.screen.scrollback_row(0).marks.prompt, Some(0));
+        assert_eq!(term.screen.prompt_before(term.screen.total_rows()), Some(0));
+    }
+}
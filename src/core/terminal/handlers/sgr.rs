@@ -1,14 +1,20 @@
-use crate::core::{Color, UnderlineStyle};
+use crate::core::{CellEffects, Color, UnderlineStyle};
 use crate::core::terminal::Terminal;
 use vte::Params;
 
-/// Parses an extended color (256-color or true-color) from the SGR parameter iterator.
+/// Parses an extended color (256-color or true-color) for SGR 38, 48 and 58.
 ///
-/// Used by both SGR 38 (foreground) and SGR 48 (background).
+/// `param` is the whole parameter: the colon form (`38:2::r:g:b`) carries
+/// the color in its sub-parameters, the semicolon form (`38;2;r;g;b`) in
+/// the parameters that follow.
 fn parse_extended_color<'a>(
     term: &Terminal,
+    param: &[u16],
     iter: &mut impl Iterator<Item = &'a [u16]>,
 ) -> Option<Color> {
+    if param.len() > 1 {
+        return color_from_subparams(term, &param[1..]);
+    }
     let sub_code = iter.next()?.first().copied().unwrap_or(0);
     match sub_code {
         5 => {
@@ -25,6 +31,36 @@ fn parse_extended_color<'a>(
     }
 }
 
+/// Color from the sub-parameters of `38:5:n`, `38:2:r:g:b` or
+/// `38:2:cs:r:g:b` (with a color space id, usually empty).
+fn color_from_subparams(term: &Terminal, sub: &[u16]) -> Option<Color> {
+    let rgb = |r: u16, g: u16, b: u16| Color {
+        r: r as u8,
+        g: g as u8,
+        b: b as u8,
+    };
+    match *sub {
+        [5, n, ..] => Some(term.color_from_256(n)),
+        [2, _, r, g, b, ..] => Some(rgb(r, g, b)),
+        [2, r, g, b] => Some(rgb(r, g, b)),
+        _ => None,
+    }
+}
+
+/// Underline style selected by `4:n`; `4` alone is a single underline.
+fn underline_style(param: &[u16]) -> Option<UnderlineStyle> {
+    let style = match param.get(1) {
+        None | Some(1) => UnderlineStyle::Single,
+        Some(0) => UnderlineStyle::None,
+        Some(2) => UnderlineStyle::Double,
+        Some(3) => UnderlineStyle::Curly,
+        Some(4) => UnderlineStyle::Dotted,
+        Some(5) => UnderlineStyle::Dashed,
+        Some(_) => return None,
+    };
+    Some(style)
+}
+
 pub(in super::super) fn handle_sgr(term: &mut Terminal, params: &Params) {
     // SGR with no params means reset all attributes.
     if params.is_empty() {
@@ -42,8 +78,14 @@ pub(in super::super) fn handle_sgr(term: &mut Terminal, params: &Params) {
             1 => term.set_bold(true),
             2 => term.set_dim(true),
             3 => term.set_italic(true),
-            4 => term.set_underline_style(UnderlineStyle::Single),
+            4 => {
+                if let Some(style) = underline_style(param) {
+                    term.set_underline_style(style);
+                }
+            }
+            5 | 6 => term.set_effect(CellEffects::BLINK, true),
             7 => term.set_reverse(true),
+            8 => term.set_effect(CellEffects::HIDDEN, true),
             9 => term.set_strikethrough(true),
             21 => term.set_underline_style(UnderlineStyle::Double),
             22 => {
@@ -52,22 +94,32 @@ pub(in super::super) fn handle_sgr(term: &mut Terminal, params: &Params) {
             }
             23 => term.set_italic(false),
             24 => term.set_underline_style(UnderlineStyle::None),
+            25 => term.set_effect(CellEffects::BLINK, false),
             27 => term.set_reverse(false),
+            28 => term.set_effect(CellEffects::HIDDEN, false),
             29 => term.set_strikethrough(false),
             30..=37 => term.set_fg(term.ansi_palette[(code - 30) as usize]),
             38 => {
-                if let Some(color) = parse_extended_color(term, &mut iter) {
+                if let Some(color) = parse_extended_color(term, param, &mut iter) {
                     term.set_fg(color);
                 }
             }
             39 => term.set_fg(term.default_fg),
             40..=47 => term.set_bg(term.ansi_palette[(code - 40) as usize]),
             48 => {
-                if let Some(color) = parse_extended_color(term, &mut iter) {
+                if let Some(color) = parse_extended_color(term, param, &mut iter) {
                     term.set_bg(color);
                 }
             }
             49 => term.set_bg(term.default_bg),
+            53 => term.set_effect(CellEffects::OVERLINE, true),
+            55 => term.set_effect(CellEffects::OVERLINE, false),
+            58 => {
+                if let Some(color) = parse_extended_color(term, param, &mut iter) {
+                    term.set_underline_color(Some(color));
+                }
+            }
+            59 => term.set_underline_color(None),
             90..=97 => term.set_fg(term.ansi_palette[(code - 90 + 8) as usize]),
             100..=107 => term.set_bg(term.ansi_palette[(code - 100 + 8) as usize]),
             _ => {}
@@ -78,7 +130,7 @@ pub(in super::super) fn handle_sgr(term: &mut Terminal, params: &Params) {
 #[cfg(test)]
 mod tests {
     use crate::core::terminal::Terminal;
-    use crate::core::{Color, UnderlineStyle};
+    use crate::core::{CellEffects, Color, UnderlineStyle};
 
     fn write_colored(seq: &[u8]) -> Terminal {
         let mut term = Terminal::new(4, 20);
@@ -317,4 +369,60 @@ mod tests {
         assert!(!b.italic);
         assert!(!b.strikethrough);
     }
+
+    #[test]
+    fn sgr_colon_underline_styles() {
+        let styles = [
+            (b"\x1b[4:3m", UnderlineStyle::Curly),
+            (b"\x1b[4:4m", UnderlineStyle::Dotted),
+            (b"\x1b[4:5m", UnderlineStyle::Dashed),
+            (b"\x1b[4:2m", UnderlineStyle::Double),
+        ];
+        for (seq, style) in styles {
+            let term = write_colored(seq);
+            assert_eq!(term.screen.viewport_get(0, 0).underline_style, style);
+        }
+        let term = write_colored(b"\x1b[4:3m\x1b[4:0m");
+        assert_eq!(term.screen.viewport_get(0, 0).underline_style, UnderlineStyle::None);
+    }
+
+    #[test]
+    fn sgr_underline_color() {
+        let term = write_colored(b"\x1b[4:3;58:2::255:0:10m");
+        let cell = term.screen.viewport_get(0, 0);
+        assert_eq!(cell.underline_color, Some(Color { r: 255, g: 0, b: 10 }));
+        let term = write_colored(b"\x1b[58;5;196m");
+        assert_eq!(term.screen.viewport_get(0, 0).underline_color, Some(Color::from_256(196)));
+        let term = write_colored(b"\x1b[58:2:1:2:3m\x1b[59m");
+        assert_eq!(term.screen.viewport_get(0, 0).underline_color, None);
+    }
+
+    #[test]
+    fn sgr_colon_colors_do_not_consume_following_params() {
+        let term = write_colored(b"\x1b[38:5:196;1m");
+        let cell = term.screen.viewport_get(0, 0);
+        assert_eq!(cell.fg, Color::from_256(196));
+        assert!(cell.bold);
+    }
+
+    #[test]
+    fn sgr_blink_conceal_overline() {
+        let mut term = Terminal::new(4, 20);
+        term.process(b"\x1b[5;8;53mA\x1b[25;28mB\x1b[55mC");
+        let a = term.screen.viewport_get(0, 0).effects;
+        assert!(a.contains(CellEffects::BLINK | CellEffects::HIDDEN | CellEffects::OVERLINE));
+        let b = term.screen.viewport_get(0, 1).effects;
+        assert!(!b.contains(CellEffects::BLINK));
+        assert!(!b.contains(CellEffects::HIDDEN));
+        assert!(b.contains(CellEffects::OVERLINE));
+        assert_eq!(term.screen.viewport_get(0, 2).effects, CellEffects::default());
+    }
+
+    #[test]
+    fn sgr_reset_clears_effects_and_underline_color() {
+        let term = write_colored(b"\x1b[6;8;53;58;5;1m\x1b[0m");
+        let cell = term.screen.viewport_get(0, 0);
+        assert_eq!(cell.effects, CellEffects::default());
+        assert_eq!(cell.underline_color, None);
+    }
 }
@@ -0,0 +1,124 @@
+use crate::core::terminal::Terminal;
+use vte::Params;
+
+/// HTS (`ESC H`): sets a tab stop at the cursor column.
+pub(in super::super) fn set_tab_stop(term: &mut Terminal) {
+    let col = term.cursor_col();
+    term.tab_stops.set(col);
+}
+
+/// HT: moves the cursor to the next tab stop, or the last column.
+pub(in super::super) fn horizontal_tab(term: &mut Terminal) {
+    let col = term.tab_stops.next(term.cursor_col(), 1);
+    term.set_cursor_col(col);
+}
+
+pub(in super::super) fn handle_tab_csi(
+    term: &mut Terminal,
+    params: &Params,
+    intermediates: &[u8],
+    action: char,
+) -> bool {
+    match (intermediates, action) {
+        // CHT — Cursor Horizontal Forward Tabulation
+        ([], 'I') => {
+            let n = term.param(params, 1).max(1) as usize;
+            let col = term.tab_stops.next(term.cursor_col(), n);
+            term.set_cursor_col(col);
+            true
+        }
+        // CBT — Cursor Backward Tabulation
+        ([], 'Z') => {
+            let n = term.param(params, 1).max(1) as usize;
+            let col = term.tab_stops.prev(term.cursor_col(), n);
+            term.set_cursor_col(col);
+            true
+        }
+        // TBC — Tab Clear: 0 at the cursor column, 3 everywhere.
+        ([], 'g') => {
+            match term.param(params, 0) {
+                0 => {
+                    let col = term.cursor_col();
+                    term.tab_stops.clear(col);
+                }
+                3 => term.tab_stops.clear_all(),
+                _ => {}
+            }
+            true
+        }
+        // DECRQPSR 2 — request DECTABSR, the tab stop report.
+        (b"$", 'w') => {
+            if term.param(params, 0) == 2 {
+                let stops: Vec<String> = term
+                    .tab_stops
+                    .columns()
+                    .map(|col| (col + 1).to_string())
+                    .collect();
+                let response = format!("\x1bP2$u{}\x1b\\", stops.join("/"));
+                term.respond(response.as_bytes());
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::terminal::Terminal;
+
+    #[test]
+    fn tab_moves_to_default_stops() {
+        let mut term = Terminal::new(2, 20);
+        term.process(b"ab\t");
+        assert_eq!(term.cursor_col(), 8);
+        term.process(b"\t\t");
+        assert_eq!(term.cursor_col(), 19);
+    }
+
+    #[test]
+    fn hts_and_tbc_edit_the_stops() {
+        let mut term = Terminal::new(2, 20);
+        term.process(b"\x1b[3g\x1b[4G\x1bH\x1b[11G\x1bH\r\t");
+        assert_eq!(term.cursor_col(), 3);
+        term.process(b"\t");
+        assert_eq!(term.cursor_col(), 10);
+        term.process(b"\x1b[g\r\t\t");
+        assert_eq!(term.cursor_col(), 19);
+    }
+
+    #[test]
+    fn cht_and_cbt_move_by_several_stops() {
+        let mut term = Terminal::new(2, 40);
+        term.process(b"\x1b[3I");
+        assert_eq!(term.cursor_col(), 24);
+        term.process(b"\x1b[2Z");
+        assert_eq!(term.cursor_col(), 8);
+        term.process(b"\x1b[5Z");
+        assert_eq!(term.cursor_col(), 0);
+    }
+
+    #[test]
+    fn dectabsr_reports_one_based_columns() {
+        let mut term = Terminal::new(2, 20);
+        term.process(b"\x1b[2$w");
+        assert_eq!(term.drain_responses(), b"\x1bP2$u9/17\x1b\\");
+        term.process(b"\x1b[3g\x1b[2$w");
+        assert_eq!(term.drain_responses(), b"\x1bP2$u\x1b\\");
+    }
+
+    #[test]
+    fn full_reset_restores_default_stops() {
+        let mut term = Terminal::new(2, 20);
+        term.process(b"\x1b[3g\x1bc\t");
+        assert_eq!(term.cursor_col(), 8);
+    }
+
+    #[test]
+    fn widening_adds_default_stops() {
+        let mut term = Terminal::new(2, 10);
+        term.resize(2, 30);
+        term.process(b"\t\t\t");
+        assert_eq!(term.cursor_col(), 24);
+    }
+}
@@ -0,0 +1,145 @@
+//! OSC 8 hyperlink table.
+//!
+//! Cells carry a small hyperlink id; the table maps it to the link's URL
+//! and its `id=` parameter. Cells opened with the same `id=` and URL are
+//! one link even when other output separates them, as with the wrapped or
+//! padded links of `ls --hyperlink` and compiler diagnostics. Links without
+//! an `id=` are grouped by URL.
+
+use std::collections::HashMap;
+
+/// Maximum number of hyperlinks stored at once per terminal session.
+pub(super) const MAX_HYPERLINKS: usize = 4096;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Hyperlink {
+    id: Option<String>,
+    url: String,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Hyperlinks {
+    /// Slot `n` holds cell id `n + 1`; `None` marks a freed slot.
+    links: Vec<Option<Hyperlink>>,
+    /// Companion index for O(1) deduplication (link → cell id).
+    index: HashMap<Hyperlink, u16>,
+    /// Freed cell ids, reused before the table grows.
+    free: Vec<u16>,
+    /// A whole screen or scrollback carrying links was discarded since the
+    /// last scan for unused links.
+    dropped: bool,
+    /// The table-full warning was logged; it is only logged once.
+    overflow_logged: bool,
+}
+
+impl Hyperlinks {
+    /// URL of the link with cell id `id`; `None` for 0 and unused ids.
+    pub(super) fn url(&self, id: u16) -> Option<&str> {
+        let slot = usize::from(id).checked_sub(1)?;
+        self.links.get(slot)?.as_ref().map(|link| link.url.as_str())
+    }
+
+    /// Cell id of an identical link already in the table.
+    pub(super) fn find(&self, id: Option<&str>, url: &str) -> Option<u16> {
+        let key = Hyperlink {
+            id: id.map(str::to_owned),
+            url: url.to_owned(),
+        };
+        self.index.get(&key).copied()
+    }
+
+    /// Whether adding a link needs a slot to be freed first.
+    pub(super) fn is_full(&self) -> bool {
+        self.free.is_empty() && self.links.len() >= MAX_HYPERLINKS
+    }
+
+    /// Adds a link and returns its cell id, or `None` when the table is full.
+    pub(super) fn insert(&mut self, id: Option<&str>, url: &str) -> Option<u16> {
+        let link = Hyperlink {
+            id: id.map(str::to_owned),
+            url: url.to_owned(),
+        };
+        let cell_id = match self.free.pop() {
+            Some(cell_id) => {
+                self.links[usize::from(cell_id) - 1] = Some(link.clone());
+                cell_id
+            }
+            None if self.links.len() < MAX_HYPERLINKS => {
+                self.links.push(Some(link.clone()));
+                self.links.len() as u16
+            }
+            None => return None,
+        };
+        self.index.insert(link, cell_id);
+        Some(cell_id)
+    }
+
+    /// Frees every link whose cell id is not marked in `live`, indexed by
+    /// cell id.
+    pub(super) fn retain(&mut self, live: &[bool]) {
+        for (slot, entry) in self.links.iter_mut().enumerate() {
+            let cell_id = slot + 1;
+            if live.get(cell_id).copied().unwrap_or(false) {
+                continue;
+            }
+            if let Some(link) = entry.take() {
+                self.index.remove(&link);
+                self.free.push(cell_id as u16);
+            }
+        }
+    }
+
+    /// Notes that cells carrying links may have been discarded other than
+    /// through the screens' own bookkeeping.
+    pub(super) fn note_dropped(&mut self) {
+        self.dropped = true;
+    }
+
+    /// Whether links may have been discarded since the last call.
+    pub(super) fn take_dropped(&mut self) -> bool {
+        std::mem::take(&mut self.dropped)
+    }
+
+    /// `true` the first time it is called, so a full table is reported once.
+    pub(super) fn first_overflow(&mut self) -> bool {
+        !std::mem::replace(&mut self.overflow_logged, true)
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.links.clear();
+        self.index.clear();
+        self.free.clear();
+        self.dropped = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_ids_keep_links_with_the_same_url_apart() {
+        let mut links = Hyperlinks::default();
+        let a = links.insert(Some("a"), "https://ferrum.dev").unwrap();
+        let b = links.insert(Some("b"), "https://ferrum.dev").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(links.find(Some("a"), "https://ferrum.dev"), Some(a));
+        assert_eq!(links.find(None, "https://ferrum.dev"), None);
+        assert_eq!(links.url(b), Some("https://ferrum.dev"));
+    }
+
+    #[test]
+    fn retain_frees_unmarked_links_for_reuse() {
+        let mut links = Hyperlinks::default();
+        let kept = links.insert(None, "https://a").unwrap();
+        let dropped = links.insert(None, "https://b").unwrap();
+        let mut live = vec![false; 3];
+        live[usize::from(kept)] = true;
+        links.retain(&live);
+
+        assert_eq!(links.url(kept), Some("https://a"));
+        assert_eq!(links.url(dropped), None);
+        assert_eq!(links.find(None, "https://b"), None);
+        assert_eq!(links.insert(None, "https://c"), Some(dropped));
+    }
+}
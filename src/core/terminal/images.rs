@@ -0,0 +1,169 @@
+//! Inline image placement: anchors decoded images to the rows they cover.
+
+use std::collections::VecDeque;
+use std::rc::{Rc, Weak};
+
+use super::super::{ImageSlice, PageRow, TerminalImage};
+
+/// Byte budget for decoded Sixel images still shown on some row.
+const SIXEL_STORAGE_LIMIT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Sixel images in placement order, with their decoded size.
+///
+/// Sixel images live only in the row strips showing them, so the list holds
+/// weak references: images whose rows are gone drop out on their own. When
+/// the live ones exceed `SIXEL_STORAGE_LIMIT_BYTES` the oldest are evicted.
+#[derive(Default)]
+pub(super) struct SixelImages {
+    images: VecDeque<(Weak<TerminalImage>, usize)>,
+    used_bytes: usize,
+}
+
+impl SixelImages {
+    /// Accounts for a newly placed image and returns the older images that
+    /// must be removed from the screen to stay within budget.
+    pub(super) fn track(&mut self, image: &Rc<TerminalImage>) -> Vec<Rc<TerminalImage>> {
+        let used_bytes = &mut self.used_bytes;
+        self.images.retain(|(weak, size)| {
+            let alive = weak.strong_count() > 0;
+            if !alive {
+                *used_bytes -= size;
+            }
+            alive
+        });
+
+        let size = image.pixels.len();
+        self.images.push_back((Rc::downgrade(image), size));
+        self.used_bytes += size;
+
+        let mut evicted = Vec::new();
+        while self.used_bytes > SIXEL_STORAGE_LIMIT_BYTES && self.images.len() > 1 {
+            let Some((weak, size)) = self.images.pop_front() else { break };
+            self.used_bytes -= size;
+            evicted.extend(weak.upgrade());
+        }
+        evicted
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.images.clear();
+        self.used_bytes = 0;
+    }
+}
+
+impl super::Terminal {
+    /// Updates the cell size in pixels used to map image pixels onto grid cells.
+    ///
+    /// The GUI calls this whenever font metrics change; the core has no other
+    /// way to know how many rows a Sixel image occupies.
+    pub fn set_cell_pixel_size(&mut self, width: u32, height: u32) {
+        self.cell_pixel_width = width.max(1);
+        self.cell_pixel_height = height.max(1);
+    }
+
+    /// Wraps decoded RGBA pixels in a shared image with a fresh id.
+    pub(super) fn new_image(&mut self, width: u32, height: u32, pixels: Vec<u8>) -> Rc<TerminalImage> {
+        self.next_image_id = self.next_image_id.wrapping_add(1);
+        Rc::new(TerminalImage { id: self.next_image_id, width, height, pixels })
+    }
+
+    /// Places a decoded Sixel image at the cursor, evicting the oldest Sixel
+    /// images once they exceed their byte budget.
+    pub(super) fn place_sixel_image(&mut self, image: Rc<TerminalImage>) {
+        for evicted in self.sixel_images.track(&image) {
+            self.remove_image_strips(|slice| Rc::ptr_eq(&slice.image, &evicted));
+        }
+        self.place_image_at_cursor(image);
+    }
+
+    /// Removes matching strips from every row of every screen, scrollback included.
+    pub(super) fn remove_image_strips(&mut self, mut pred: impl FnMut(&ImageSlice) -> bool) {
+        let mut prune = |row: &mut PageRow| row.images.retain(|slice| !pred(slice));
+        self.screen.for_each_row_mut(&mut prune);
+        for other in self.alt_screen.iter_mut().chain(self.parked_alt_screen.iter_mut()) {
+            other.for_each_row_mut(&mut prune);
+        }
+    }
+
+    /// Places `image` with its top-left corner at the cursor and attaches one
+    /// strip per covered row, scrolling the region when the image runs past
+    /// the bottom.  The cursor ends on the line below the image, at the
+    /// image's left column (xterm behaviour with Sixel scrolling enabled).
+    pub(super) fn place_image_at_cursor(&mut self, image: Rc<TerminalImage>) {
+        let cell_w = self.cell_pixel_width;
+        let cell_h = self.cell_pixel_height;
+        let col = self.cursor_col();
+        let cols = (image.width.div_ceil(cell_w) as usize).max(1);
+        let rows = image.height.div_ceil(cell_h).max(1);
+
+        let strips = (0..rows)
+            .map(|strip| {
+                let src_y = strip * cell_h;
+                ImageSlice::new(Rc::clone(&image), col, cols, src_y, cell_h.min(image.height - src_y))
+            })
+            .collect();
+        self.attach_image_strips(strips, true);
+
+        let next_row = self.cursor_row() + 1;
+        self.advance_cursor_row(next_row);
+        self.set_cursor_col(col.min(self.screen.cols().saturating_sub(1)));
+    }
+
+    /// Attaches `strips[k]` to the k-th row starting at the cursor row.
+    ///
+    /// With `scroll` the cursor walks down the strips (scrolling the region
+    /// as needed) and ends on the row of the last one.  Otherwise the cursor
+    /// stays put and strips that would fall below the screen are dropped.
+    pub(super) fn attach_image_strips(&mut self, strips: Vec<ImageSlice>, scroll: bool) {
+        let start_row = self.cursor_row();
+        let rows = self.screen.viewport_rows();
+        for (k, slice) in strips.into_iter().enumerate() {
+            let row = if scroll {
+                if k > 0 {
+                    let next_row = self.cursor_row() + 1;
+                    self.advance_cursor_row(next_row);
+                }
+                self.cursor_row()
+            } else if start_row + k < rows {
+                start_row + k
+            } else {
+                break;
+            };
+            let page_row = self.screen.viewport_row_mut(row);
+            // Drop strips the new image hides completely, so apps that redraw
+            // the same image in place do not pile up strips on the row.
+            page_row.images.retain(|existing| {
+                existing.z_index > slice.z_index || !existing.is_covered_by(slice.col, slice.cols)
+            });
+            page_row.images.push(slice);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(id: u32, bytes: usize) -> Rc<TerminalImage> {
+        Rc::new(TerminalImage { id, width: 1, height: 1, pixels: vec![0; bytes] })
+    }
+
+    #[test]
+    fn sixel_budget_evicts_the_oldest_live_images() {
+        let mut images = SixelImages::default();
+        let half = SIXEL_STORAGE_LIMIT_BYTES / 2;
+        let first = image(1, half);
+        let second = image(2, half);
+        assert!(images.track(&first).is_empty());
+        assert!(images.track(&second).is_empty());
+
+        let third = image(3, half);
+        let evicted = images.track(&third);
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].id, 1);
+
+        // Images whose rows are gone no longer count against the budget.
+        drop((first, second, evicted));
+        assert!(images.track(&image(4, half)).is_empty());
+    }
+}
@@ -0,0 +1,622 @@
+//! Kitty graphics protocol (`ESC _ G <control> ; <payload> ESC \`):
+//! command parsing, payload decoding and the bounded per-terminal image store.
+//!
+//! Applying commands to the screen lives in `handlers::kitty_graphics`.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use base64::Engine as _;
+
+use super::super::TerminalImage;
+
+/// Byte budget for decoded image data kept per terminal.
+const STORAGE_LIMIT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Upper bound on the base64 payload collected across chunks.
+const MAX_ENCODED_BYTES: usize = STORAGE_LIMIT_BYTES / 3 * 4;
+
+/// Largest image file read with `t=f` or `t=t`.  Files are read while
+/// output is parsed, so this stays well below the storage budget.
+const MAX_FILE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Largest accepted image side, in pixels.
+const MAX_IMAGE_DIMENSION: u32 = 10_000;
+
+/// Marker kitty requires in the name of files sent with `t=t`.
+const TEMP_FILE_MARKER: &str = "tty-graphics-protocol";
+
+/// Protocol error, reported to the client as `CODE:reason`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct GraphicsError {
+    pub code: &'static str,
+    pub reason: &'static str,
+}
+
+impl GraphicsError {
+    pub(super) const fn new(code: &'static str, reason: &'static str) -> Self {
+        Self { code, reason }
+    }
+}
+
+/// Parsed control data of one graphics command.
+///
+/// Keys that are unused by an action keep their protocol defaults.  The
+/// delete action reuses `x`, `y` and `z` as cell coordinates and z-index,
+/// exactly as the protocol does.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct GraphicsCommand {
+    /// `a`: t, T, p, d, q (f, a, c for animation are not supported).
+    pub action: u8,
+    /// `q`: 1 suppresses OK responses, 2 suppresses errors too.
+    pub quiet: u32,
+    /// `f`: 24 (RGB), 32 (RGBA) or 100 (PNG).
+    pub format: u32,
+    /// `t`: d (direct), f (file), t (temporary file), s (shared memory).
+    pub medium: u8,
+    /// `o`: z for zlib-compressed data.
+    pub compression: u8,
+    /// `m=1`: more chunks follow.
+    pub more: bool,
+    /// `i`, `I`, `p`.
+    pub image_id: u32,
+    pub image_number: u32,
+    pub placement_id: u32,
+    /// `s`, `v`: pixel size of raw RGB(A) data.
+    pub data_width: u32,
+    pub data_height: u32,
+    /// `S`, `O`: byte count and offset to read from a file.
+    pub data_size: u64,
+    pub data_offset: u64,
+    /// `x`, `y`, `w`, `h`: source rectangle (0 = whole image).
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// `X`, `Y`: pixel offset inside the first cell.
+    pub cell_x_offset: u32,
+    pub cell_y_offset: u32,
+    /// `c`, `r`: display size in cells (0 = derive from pixels).
+    pub columns: u32,
+    pub rows: u32,
+    /// `C=1`: leave the cursor where it is.
+    pub no_cursor_move: bool,
+    /// `z`: stacking order.
+    pub z_index: i32,
+    /// `d`: what to delete.
+    pub delete: u8,
+}
+
+impl Default for GraphicsCommand {
+    fn default() -> Self {
+        Self {
+            action: b't',
+            quiet: 0,
+            format: 32,
+            medium: b'd',
+            compression: 0,
+            more: false,
+            image_id: 0,
+            image_number: 0,
+            placement_id: 0,
+            data_width: 0,
+            data_height: 0,
+            data_size: 0,
+            data_offset: 0,
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            cell_x_offset: 0,
+            cell_y_offset: 0,
+            columns: 0,
+            rows: 0,
+            no_cursor_move: false,
+            z_index: 0,
+            delete: b'a',
+        }
+    }
+}
+
+impl GraphicsCommand {
+    /// Parses the comma-separated `key=value` control data.  Unknown keys and
+    /// malformed values are ignored.
+    pub(super) fn parse(control: &[u8]) -> Self {
+        let mut cmd = Self::default();
+        for pair in control.split(|&b| b == b',') {
+            let Some((&key, rest)) = pair.split_first() else {
+                continue;
+            };
+            let Some(value) = rest.strip_prefix(b"=") else {
+                continue;
+            };
+            let char_value = value.first().copied().unwrap_or(0);
+            let uint = || std::str::from_utf8(value).ok().and_then(|v| v.parse::<u32>().ok());
+            match key {
+                b'a' => cmd.action = char_value,
+                b't' => cmd.medium = char_value,
+                b'o' => cmd.compression = char_value,
+                b'd' => cmd.delete = char_value,
+                b'z' => {
+                    if let Some(z) = std::str::from_utf8(value).ok().and_then(|v| v.parse().ok()) {
+                        cmd.z_index = z;
+                    }
+                }
+                _ => {
+                    let Some(n) = uint() else { continue };
+                    match key {
+                        b'q' => cmd.quiet = n,
+                        b'f' => cmd.format = n,
+                        b'm' => cmd.more = n == 1,
+                        b'i' => cmd.image_id = n,
+                        b'I' => cmd.image_number = n,
+                        b'p' => cmd.placement_id = n,
+                        b's' => cmd.data_width = n,
+                        b'v' => cmd.data_height = n,
+                        b'S' => cmd.data_size = n.into(),
+                        b'O' => cmd.data_offset = n.into(),
+                        b'x' => cmd.x = n,
+                        b'y' => cmd.y = n,
+                        b'w' => cmd.width = n,
+                        b'h' => cmd.height = n,
+                        b'X' => cmd.cell_x_offset = n,
+                        b'Y' => cmd.cell_y_offset = n,
+                        b'c' => cmd.columns = n,
+                        b'r' => cmd.rows = n,
+                        b'C' => cmd.no_cursor_move = n == 1,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        cmd
+    }
+}
+
+/// A transmission whose chunks are still arriving (`m=1`).
+struct PendingTransfer {
+    command: GraphicsCommand,
+    /// Concatenated base64 chunks.
+    encoded: Vec<u8>,
+    too_large: bool,
+}
+
+struct StoredImage {
+    image: Rc<TerminalImage>,
+    number: u32,
+    /// Value of `GraphicsStore::clock` when last transmitted or placed.
+    last_used: u64,
+}
+
+/// Transmitted images of one terminal, keyed by client image id.
+///
+/// The store owns one reference to every image; placements hold the others
+/// via their row strips.  Decoded bytes are capped at `STORAGE_LIMIT_BYTES`:
+/// when a new image does not fit, the least recently used images are evicted,
+/// preferring ones that are no longer placed anywhere.
+#[derive(Default)]
+pub(super) struct GraphicsStore {
+    images: HashMap<u32, StoredImage>,
+    used_bytes: usize,
+    clock: u64,
+    /// Next id handed out for images transmitted without `i=`.
+    next_auto_id: u32,
+    pending: Option<PendingTransfer>,
+}
+
+impl GraphicsStore {
+    /// Adds a chunk of a transmission.
+    ///
+    /// Returns the first chunk's command and the complete base64 payload once
+    /// the last chunk arrives, or `None` while more chunks are expected.
+    /// Commands that arrive while a transfer is pending continue it, as the
+    /// protocol requires clients to finish a transfer before anything else.
+    pub(super) fn push_chunk(
+        &mut self,
+        command: GraphicsCommand,
+        payload: &[u8],
+    ) -> Option<(GraphicsCommand, Result<Vec<u8>, GraphicsError>)> {
+        let more = command.more;
+        let pending = self.pending.get_or_insert_with(|| PendingTransfer {
+            command,
+            encoded: Vec::new(),
+            too_large: false,
+        });
+        if pending.encoded.len() + payload.len() > MAX_ENCODED_BYTES {
+            pending.too_large = true;
+            pending.encoded = Vec::new();
+        } else if !pending.too_large {
+            pending.encoded.extend_from_slice(payload);
+        }
+        if more {
+            return None;
+        }
+        let pending = self.pending.take()?;
+        let result = if pending.too_large {
+            Err(GraphicsError::new("EFBIG", "image data is too large"))
+        } else {
+            Ok(pending.encoded)
+        };
+        Some((pending.command, result))
+    }
+
+    /// Returns `true` while a chunked transmission is in progress.
+    pub(super) fn has_pending_transfer(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Returns the id to store a transmission under: the client's `i=`, or a
+    /// fresh id from the top of the range when only `I=` or nothing was given.
+    pub(super) fn resolve_id(&mut self, command: &GraphicsCommand) -> u32 {
+        if command.image_id != 0 {
+            return command.image_id;
+        }
+        loop {
+            self.next_auto_id = self.next_auto_id.wrapping_sub(1);
+            if self.next_auto_id != 0 && !self.images.contains_key(&self.next_auto_id) {
+                return self.next_auto_id;
+            }
+        }
+    }
+
+    /// Stores `image` under `id`, replacing any previous image with that id.
+    ///
+    /// Returns the images evicted to stay within budget; their placements
+    /// must be removed by the caller.
+    pub(super) fn insert(&mut self, id: u32, number: u32, image: Rc<TerminalImage>) -> Vec<Rc<TerminalImage>> {
+        self.remove(id);
+        let size = image.pixels.len();
+        let mut evicted = Vec::new();
+        while self.used_bytes + size > STORAGE_LIMIT_BYTES {
+            let Some(victim) = self.eviction_candidate() else { break };
+            if let Some(old) = self.remove(victim) {
+                evicted.push(old);
+            }
+        }
+        self.clock += 1;
+        self.used_bytes += size;
+        self.images.insert(id, StoredImage { image, number, last_used: self.clock });
+        evicted
+    }
+
+    /// Least recently used image, preferring ones without placements.
+    fn eviction_candidate(&self) -> Option<u32> {
+        self.images
+            .iter()
+            .min_by_key(|(_, stored)| (Rc::strong_count(&stored.image) > 1, stored.last_used))
+            .map(|(&id, _)| id)
+    }
+
+    pub(super) fn remove(&mut self, id: u32) -> Option<Rc<TerminalImage>> {
+        let stored = self.images.remove(&id)?;
+        self.used_bytes -= stored.image.pixels.len();
+        Some(stored.image)
+    }
+
+    /// Looks up an image by id, marking it as recently used.
+    pub(super) fn get(&mut self, id: u32) -> Option<Rc<TerminalImage>> {
+        self.clock += 1;
+        let stored = self.images.get_mut(&id)?;
+        stored.last_used = self.clock;
+        Some(Rc::clone(&stored.image))
+    }
+
+    /// Returns the id of the newest image transmitted with number `number`.
+    pub(super) fn id_for_number(&self, number: u32) -> Option<u32> {
+        self.images
+            .iter()
+            .filter(|(_, stored)| stored.number == number)
+            .max_by_key(|(_, stored)| stored.last_used)
+            .map(|(&id, _)| id)
+    }
+
+    /// Ids of all stored images that satisfy `pred`.
+    pub(super) fn ids_where(&self, mut pred: impl FnMut(u32, &Rc<TerminalImage>) -> bool) -> Vec<u32> {
+        self.images
+            .iter()
+            .filter(|(id, stored)| pred(**id, &stored.image))
+            .map(|(&id, _)| id)
+            .collect()
+    }
+
+    /// Drops stored images that no placement refers to any more, among `ids`.
+    pub(super) fn free_unplaced(&mut self, ids: &[u32]) {
+        for id in ids {
+            if self.images.get(id).is_some_and(|stored| Rc::strong_count(&stored.image) == 1) {
+                self.remove(*id);
+            }
+        }
+    }
+
+    /// Forgets every image and any half-received transmission.
+    pub(super) fn clear(&mut self) {
+        self.images.clear();
+        self.used_bytes = 0;
+        self.pending = None;
+    }
+}
+
+/// Decodes a complete base64 payload into `(width, height, rgba)`
+/// according to the command's medium, compression and format.
+pub(super) fn decode_image(
+    command: &GraphicsCommand,
+    encoded: &[u8],
+) -> Result<(u32, u32, Vec<u8>), GraphicsError> {
+    let payload = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .or_else(|_| base64::engine::general_purpose::STANDARD_NO_PAD.decode(encoded))
+        .map_err(|_| GraphicsError::new("EINVAL", "payload is not valid base64"))?;
+
+    let data = match command.medium {
+        b'd' => payload,
+        b'f' | b't' => read_file(command, &payload)?,
+        b's' => return Err(GraphicsError::new("EINVAL", "shared memory transmission is not supported")),
+        _ => return Err(GraphicsError::new("EINVAL", "unknown transmission medium")),
+    };
+
+    let data = match command.compression {
+        0 => data,
+        b'z' => miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(&data, STORAGE_LIMIT_BYTES)
+            .map_err(|_| GraphicsError::new("EINVAL", "zlib decompression failed"))?,
+        _ => return Err(GraphicsError::new("EINVAL", "unknown compression")),
+    };
+
+    match command.format {
+        24 | 32 => decode_raw(command, data),
+        100 => decode_png(&data),
+        _ => Err(GraphicsError::new("EINVAL", "unknown image format")),
+    }
+}
+
+/// Reads the file named by `path_bytes`; temporary files are deleted afterwards.
+fn read_file(command: &GraphicsCommand, path_bytes: &[u8]) -> Result<Vec<u8>, GraphicsError> {
+    let bad_file = GraphicsError::new("EBADF", "cannot read file");
+    let path = std::str::from_utf8(path_bytes).map_err(|_| bad_file)?;
+    let is_temp = command.medium == b't';
+    let path = if is_temp {
+        graphics_temp_file(Path::new(path))
+            .ok_or(GraphicsError::new("EBADF", "not a graphics temporary file"))?
+    } else {
+        PathBuf::from(path)
+    };
+    // Only regular files: reading a FIFO or device could block the terminal.
+    let metadata = std::fs::metadata(&path).map_err(|_| bad_file)?;
+    if !metadata.is_file() {
+        return Err(bad_file);
+    }
+
+    let available = metadata.len().saturating_sub(command.data_offset);
+    let size = if command.data_size > 0 {
+        command.data_size.min(available)
+    } else {
+        available
+    };
+    if size > MAX_FILE_BYTES {
+        return Err(GraphicsError::new("EFBIG", "file is too large"));
+    }
+
+    let mut file = std::fs::File::open(&path).map_err(|_| bad_file)?;
+    if command.data_offset > 0 {
+        file.seek(SeekFrom::Start(command.data_offset)).map_err(|_| bad_file)?;
+    }
+    let mut data = Vec::new();
+    file.take(size).read_to_end(&mut data).map_err(|_| bad_file)?;
+    if is_temp {
+        let _ = std::fs::remove_file(&path);
+    }
+    Ok(data)
+}
+
+/// Resolves a temporary-file transfer to the file it may read and delete.
+///
+/// The file must carry the protocol marker in its name, must not be a
+/// symlink, and its canonical parent must be a temp directory, so a client
+/// cannot delete arbitrary files through `..` or links.
+fn graphics_temp_file(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?;
+    if !name.to_str()?.contains(TEMP_FILE_MARKER) {
+        return None;
+    }
+    let parent = path.parent()?.canonicalize().ok()?;
+    let in_temp_dir = [std::env::temp_dir(), "/tmp".into(), "/dev/shm".into()]
+        .iter()
+        .filter_map(|dir| dir.canonicalize().ok())
+        .any(|dir| parent.starts_with(dir));
+    if !in_temp_dir {
+        return None;
+    }
+    let resolved = parent.join(name);
+    let is_link = std::fs::symlink_metadata(&resolved)
+        .is_ok_and(|metadata| metadata.file_type().is_symlink());
+    (!is_link).then_some(resolved)
+}
+
+fn check_dimensions(width: u32, height: u32) -> Result<(), GraphicsError> {
+    if width == 0 || height == 0 {
+        return Err(GraphicsError::new("EINVAL", "image has no pixels"));
+    }
+    if width > MAX_IMAGE_DIMENSION
+        || height > MAX_IMAGE_DIMENSION
+        || width as usize * height as usize * 4 > STORAGE_LIMIT_BYTES
+    {
+        return Err(GraphicsError::new("EFBIG", "image is too large"));
+    }
+    Ok(())
+}
+
+fn decode_raw(command: &GraphicsCommand, data: Vec<u8>) -> Result<(u32, u32, Vec<u8>), GraphicsError> {
+    let (width, height) = (command.data_width, command.data_height);
+    check_dimensions(width, height)?;
+    let pixels = width as usize * height as usize;
+    let bytes_per_pixel = if command.format == 24 { 3 } else { 4 };
+    if data.len() < pixels * bytes_per_pixel {
+        return Err(GraphicsError::new("ENODATA", "insufficient image data"));
+    }
+    let rgba = if bytes_per_pixel == 4 {
+        let mut data = data;
+        data.truncate(pixels * 4);
+        data
+    } else {
+        data[..pixels * 3]
+            .chunks_exact(3)
+            .flat_map(|px| [px[0], px[1], px[2], 255])
+            .collect()
+    };
+    Ok((width, height, rgba))
+}
+
+fn decode_png(data: &[u8]) -> Result<(u32, u32, Vec<u8>), GraphicsError> {
+    let bad_png = GraphicsError::new("EBADPNG", "failed to decode PNG");
+    let mut decoder = png::Decoder::new_with_limits(
+        std::io::Cursor::new(data),
+        png::Limits { bytes: STORAGE_LIMIT_BYTES },
+    );
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|_| bad_png)?;
+    let (width, height) = reader.info().size();
+    check_dimensions(width, height)?;
+    let mut buf = vec![0; reader.output_buffer_size().ok_or(bad_png)?];
+    let info = reader.next_frame(&mut buf).map_err(|_| bad_png)?;
+    buf.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf.chunks_exact(3).flat_map(|px| [px[0], px[1], px[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => {
+            buf.chunks_exact(2).flat_map(|px| [px[0], px[0], px[0], px[1]]).collect()
+        }
+        png::ColorType::Grayscale => buf.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        png::ColorType::Indexed => return Err(bad_png),
+    };
+    Ok((info.width, info.height, rgba))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(id: u32, bytes: usize) -> Rc<TerminalImage> {
+        Rc::new(TerminalImage { id, width: 1, height: 1, pixels: vec![0; bytes] })
+    }
+
+    #[test]
+    fn parse_reads_keys_and_ignores_unknown() {
+        let cmd = GraphicsCommand::parse(b"a=T,f=100,i=7,p=2,c=10,r=3,z=-5,C=1,K=9,x=oops");
+        assert_eq!(cmd.action, b'T');
+        assert_eq!(cmd.format, 100);
+        assert_eq!((cmd.image_id, cmd.placement_id), (7, 2));
+        assert_eq!((cmd.columns, cmd.rows), (10, 3));
+        assert_eq!(cmd.z_index, -5);
+        assert!(cmd.no_cursor_move);
+        assert_eq!(cmd.x, 0);
+    }
+
+    #[test]
+    fn chunks_are_joined_under_first_command() {
+        let mut store = GraphicsStore::default();
+        let first = GraphicsCommand::parse(b"a=T,i=3,m=1");
+        assert!(store.push_chunk(first, b"AAAA").is_none());
+        assert!(store.has_pending_transfer());
+        let (cmd, payload) = store.push_chunk(GraphicsCommand::parse(b"m=0"), b"BBBB").unwrap();
+        assert_eq!((cmd.action, cmd.image_id), (b'T', 3));
+        assert_eq!(payload.unwrap(), b"AAAABBBB");
+        assert!(!store.has_pending_transfer());
+    }
+
+    #[test]
+    fn raw_rgb_is_expanded_to_rgba() {
+        let cmd = GraphicsCommand::parse(b"f=24,s=1,v=2");
+        let encoded = base64::engine::general_purpose::STANDARD.encode([1, 2, 3, 4, 5, 6]);
+        let (w, h, rgba) = decode_image(&cmd, encoded.as_bytes()).unwrap();
+        assert_eq!((w, h), (1, 2));
+        assert_eq!(rgba, vec![1, 2, 3, 255, 4, 5, 6, 255]);
+    }
+
+    #[test]
+    fn short_raw_data_is_rejected() {
+        let cmd = GraphicsCommand::parse(b"f=32,s=2,v=2");
+        let encoded = base64::engine::general_purpose::STANDARD.encode([0; 8]);
+        assert_eq!(decode_image(&cmd, encoded.as_bytes()).unwrap_err().code, "ENODATA");
+    }
+
+    #[test]
+    fn png_is_decoded() {
+        let mut png_bytes = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png_bytes, 2, 1);
+            encoder.set_color(png::ColorType::Rgb);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&[255, 0, 0, 0, 255, 0]).unwrap();
+        }
+        let cmd = GraphicsCommand::parse(b"f=100");
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&png_bytes);
+        let (w, h, rgba) = decode_image(&cmd, encoded.as_bytes()).unwrap();
+        assert_eq!((w, h), (2, 1));
+        assert_eq!(rgba, vec![255, 0, 0, 255, 0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn temp_file_outside_temp_dir_is_refused() {
+        assert!(graphics_temp_file(Path::new("/etc/tty-graphics-protocol-x")).is_none());
+        assert!(graphics_temp_file(Path::new("/tmp/passwd")).is_none());
+        assert!(graphics_temp_file(Path::new("/tmp/../etc/tty-graphics-protocol-x")).is_none());
+        let temp = std::env::temp_dir().canonicalize().unwrap();
+        assert_eq!(
+            graphics_temp_file(&temp.join("tty-graphics-protocol-x.rgba")),
+            Some(temp.join("tty-graphics-protocol-x.rgba"))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn temp_file_symlink_is_refused() {
+        let temp = std::env::temp_dir();
+        let link = temp.join(format!("tty-graphics-protocol-link-{}", std::process::id()));
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink("/etc/hostname", &link).unwrap();
+        assert!(graphics_temp_file(&link).is_none());
+        std::fs::remove_file(&link).unwrap();
+    }
+
+    #[test]
+    fn files_over_the_read_limit_are_refused() {
+        let path = std::env::temp_dir().join(format!("ferrum-graphics-big-{}", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        file.set_len(MAX_FILE_BYTES + 1).unwrap();
+        let encoded = base64::engine::general_purpose::STANDARD.encode(path.to_str().unwrap());
+        let whole = decode_image(&GraphicsCommand::parse(b"t=f"), encoded.as_bytes());
+        assert_eq!(whole.unwrap_err().code, "EFBIG");
+        // A slice of the file within the limit is read.
+        let slice = GraphicsCommand::parse(b"t=f,f=32,s=1,v=1,S=4");
+        assert_eq!(
+            decode_image(&slice, encoded.as_bytes()).unwrap(),
+            (1, 1, vec![0; 4])
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn store_evicts_unplaced_images_first() {
+        let mut store = GraphicsStore::default();
+        let half = STORAGE_LIMIT_BYTES / 2;
+        let placed = image(1, half);
+        let _placement = Rc::clone(&placed);
+        store.insert(1, 0, placed);
+        store.insert(2, 0, image(2, half));
+        let evicted = store.insert(3, 0, image(3, half));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].id, 2);
+        assert!(store.get(1).is_some());
+        assert!(store.get(3).is_some());
+    }
+
+    #[test]
+    fn auto_ids_do_not_collide_with_stored_ids() {
+        let mut store = GraphicsStore::default();
+        store.insert(u32::MAX, 0, image(1, 4));
+        let id = store.resolve_id(&GraphicsCommand::default());
+        assert_eq!(id, u32::MAX - 1);
+    }
+}
@@ -7,8 +7,8 @@ impl super::Terminal {
         }
 
         // Alt screen: simple resize (no reflow).
-        if let Some(ref mut alt) = self.alt_screen {
-            alt.simple_resize(rows, cols);
+        for other in self.alt_screen.iter_mut().chain(self.parked_alt_screen.iter_mut()) {
+            other.simple_resize(rows, cols);
         }
 
         let old_cols = self.screen.cols();
@@ -23,6 +23,9 @@ impl super::Terminal {
             self.simple_resize(rows, cols);
         }
 
+        self.tab_stops.resize(cols);
+        self.saved_tab_stops.resize(cols);
+
         // Reset scroll region to full screen.
         self.scroll_top = 0;
         self.scroll_bottom = rows - 1;
@@ -0,0 +1,266 @@
+//! Sixel graphics decoder, fed byte-by-byte from the DCS `put` hook.
+//!
+//! Decodes into an RGBA8 buffer that grows as sixels arrive; `finish()` crops
+//! it to the painted extent (or the raster attributes, when larger).
+
+use vte::Params;
+
+/// Upper bound on either image dimension, so a hostile stream cannot make us
+/// allocate an arbitrarily large buffer.
+const MAX_SIXEL_DIMENSION: usize = 4096;
+
+/// Number of colour registers (matches xterm's default `numColorRegisters`).
+const COLOR_REGISTERS: usize = 256;
+
+/// VT340 default colour map for registers 0..16, as 0xRRGGBB.
+const VT340_PALETTE: [u32; 16] = [
+    0x000000, 0x3333CC, 0xCC2121, 0x33CC33, 0xCC33CC, 0x33CCCC, 0xCCCC33, 0x878787,
+    0x424242, 0x545499, 0x994242, 0x549954, 0x995499, 0x549999, 0x999954, 0xCCCCCC,
+];
+
+/// Parser state for multi-byte sixel control functions.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Control {
+    /// Plain sixel data.
+    None,
+    /// `!Pn` — repeat introducer.
+    Repeat,
+    /// `#Pc;Pu;Px;Py;Pz` — colour introducer.
+    Color,
+    /// `"Pan;Pad;Ph;Pv` — raster attributes.
+    Raster,
+}
+
+pub(super) struct SixelDecoder {
+    /// Pixels as 0xAARRGGBB; alpha 0 marks a pixel no sixel has painted.
+    pixels: Vec<u32>,
+    buf_width: usize,
+    buf_height: usize,
+    /// Painted extent (exclusive).
+    width: usize,
+    height: usize,
+    /// Size declared by raster attributes.
+    raster_width: usize,
+    raster_height: usize,
+    x: usize,
+    y: usize,
+    palette: [u32; COLOR_REGISTERS],
+    color: usize,
+    /// `P2 = 1`: unpainted pixels stay transparent instead of taking register 0.
+    transparent_bg: bool,
+    control: Control,
+    args: Vec<u16>,
+}
+
+impl SixelDecoder {
+    /// Creates a decoder from the DCS parameters `P1 ; P2 ; P3`.
+    pub(super) fn new(params: &Params) -> Self {
+        let p2 = params.iter().nth(1).and_then(|p| p.first().copied()).unwrap_or(0);
+        let mut palette = [0xFF00_0000u32; COLOR_REGISTERS];
+        for (slot, rgb) in palette.iter_mut().zip(VT340_PALETTE) {
+            *slot = 0xFF00_0000 | rgb;
+        }
+        Self {
+            pixels: Vec::new(),
+            buf_width: 0,
+            buf_height: 0,
+            width: 0,
+            height: 0,
+            raster_width: 0,
+            raster_height: 0,
+            x: 0,
+            y: 0,
+            palette,
+            color: 0,
+            transparent_bg: p2 == 1,
+            control: Control::None,
+            args: Vec::new(),
+        }
+    }
+
+    pub(super) fn put(&mut self, byte: u8) {
+        if self.control != Control::None {
+            match byte {
+                b'0'..=b'9' => {
+                    if self.args.is_empty() {
+                        self.args.push(0);
+                    }
+                    if let Some(last) = self.args.last_mut() {
+                        *last = last.saturating_mul(10).saturating_add(u16::from(byte - b'0'));
+                    }
+                    return;
+                }
+                b';' => {
+                    if self.args.is_empty() {
+                        self.args.push(0);
+                    }
+                    self.args.push(0);
+                    return;
+                }
+                // A repeat count is consumed by the sixel character that follows it.
+                _ if self.control == Control::Repeat => {}
+                _ => self.finish_control(),
+            }
+        }
+
+        match byte {
+            b'?'..=b'~' => {
+                let repeat = if self.control == Control::Repeat {
+                    self.args.first().copied().unwrap_or(1).max(1) as usize
+                } else {
+                    1
+                };
+                self.control = Control::None;
+                self.args.clear();
+                self.paint(byte - b'?', repeat);
+            }
+            b'!' => self.begin_control(Control::Repeat),
+            b'#' => self.begin_control(Control::Color),
+            b'"' => self.begin_control(Control::Raster),
+            b'$' => self.x = 0,
+            b'-' => {
+                self.x = 0;
+                self.y += 6;
+            }
+            // Line breaks and other stray bytes inside the data string are ignored.
+            _ => {}
+        }
+    }
+
+    /// Crops the painted area and returns `(width, height, rgba)`, or `None`
+    /// when nothing was drawn.
+    pub(super) fn finish(mut self) -> Option<(u32, u32, Vec<u8>)> {
+        if self.control != Control::None {
+            self.finish_control();
+        }
+        let width = self.width.max(self.raster_width).min(MAX_SIXEL_DIMENSION);
+        let height = self.height.max(self.raster_height).min(MAX_SIXEL_DIMENSION);
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let fill = if self.transparent_bg { 0 } else { self.palette[0] };
+        let mut rgba = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let px = if x < self.buf_width && y < self.buf_height {
+                    self.pixels[y * self.buf_width + x]
+                } else {
+                    0
+                };
+                let px = if px >> 24 == 0 { fill } else { px };
+                rgba.extend_from_slice(&[(px >> 16) as u8, (px >> 8) as u8, px as u8, (px >> 24) as u8]);
+            }
+        }
+        Some((width as u32, height as u32, rgba))
+    }
+
+    fn begin_control(&mut self, control: Control) {
+        self.control = control;
+        self.args.clear();
+    }
+
+    /// Applies a completed `#` or `"` control function.
+    fn finish_control(&mut self) {
+        let arg = |i: usize| self.args.get(i).copied().unwrap_or(0) as usize;
+        match self.control {
+            Control::Color => {
+                let register = arg(0) % COLOR_REGISTERS;
+                if self.args.len() >= 5 {
+                    let rgb = match arg(1) {
+                        1 => hls_to_rgb(arg(2), arg(3), arg(4)),
+                        2 => percent_rgb(arg(2), arg(3), arg(4)),
+                        _ => self.palette[register],
+                    };
+                    self.palette[register] = 0xFF00_0000 | (rgb & 0x00FF_FFFF);
+                }
+                self.color = register;
+            }
+            Control::Raster => {
+                self.raster_width = arg(2).min(MAX_SIXEL_DIMENSION);
+                self.raster_height = arg(3).min(MAX_SIXEL_DIMENSION);
+            }
+            Control::Repeat | Control::None => {}
+        }
+        self.control = Control::None;
+        self.args.clear();
+    }
+
+    /// Paints `repeat` columns of the 6-pixel sixel `bits` at the current position.
+    fn paint(&mut self, bits: u8, repeat: usize) {
+        let x_end = (self.x + repeat).min(MAX_SIXEL_DIMENSION);
+        if bits != 0 && self.y < MAX_SIXEL_DIMENSION && self.x < x_end {
+            let y_end = (self.y + 6).min(MAX_SIXEL_DIMENSION);
+            self.ensure_capacity(x_end, y_end);
+            let color = self.palette[self.color];
+            for bit in 0..(y_end - self.y) {
+                if bits & (1 << bit) == 0 {
+                    continue;
+                }
+                let row = (self.y + bit) * self.buf_width;
+                self.pixels[row + self.x..row + x_end].fill(color);
+                self.height = self.height.max(self.y + bit + 1);
+            }
+            self.width = self.width.max(x_end);
+        }
+        self.x = x_end;
+    }
+
+    /// Grows the pixel buffer (doubling) so that `width x height` fits.
+    fn ensure_capacity(&mut self, width: usize, height: usize) {
+        if width <= self.buf_width && height <= self.buf_height {
+            return;
+        }
+        let new_width = grow(self.buf_width, width);
+        let new_height = grow(self.buf_height, height);
+        let mut pixels = vec![0u32; new_width * new_height];
+        for y in 0..self.buf_height {
+            let src = &self.pixels[y * self.buf_width..(y + 1) * self.buf_width];
+            pixels[y * new_width..y * new_width + self.buf_width].copy_from_slice(src);
+        }
+        self.pixels = pixels;
+        self.buf_width = new_width;
+        self.buf_height = new_height;
+    }
+}
+
+fn grow(current: usize, needed: usize) -> usize {
+    if needed <= current {
+        return current;
+    }
+    needed.max(current * 2).clamp(64, MAX_SIXEL_DIMENSION)
+}
+
+/// Converts sixel RGB percentages (0..=100) to 0xRRGGBB.
+fn percent_rgb(r: usize, g: usize, b: usize) -> u32 {
+    let scale = |v: usize| (v.min(100) * 255 / 100) as u32;
+    (scale(r) << 16) | (scale(g) << 8) | scale(b)
+}
+
+/// Converts sixel HLS (hue 0..360 with 0 = blue, lightness/saturation 0..=100) to 0xRRGGBB.
+fn hls_to_rgb(hue: usize, lightness: usize, saturation: usize) -> u32 {
+    // DEC hue is rotated: 0° blue, 120° red, 240° green.
+    let h = ((hue + 240) % 360) as f32 / 360.0;
+    let l = lightness.min(100) as f32 / 100.0;
+    let s = saturation.min(100) as f32 / 100.0;
+    if s == 0.0 {
+        let v = (l * 255.0).round() as u32;
+        return (v << 16) | (v << 8) | v;
+    }
+    let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+    let p = 2.0 * l - q;
+    let channel = |t: f32| {
+        let t = t.rem_euclid(1.0);
+        let v = if t < 1.0 / 6.0 {
+            p + (q - p) * 6.0 * t
+        } else if t < 0.5 {
+            q
+        } else if t < 2.0 / 3.0 {
+            p + (q - p) * (2.0 / 3.0 - t) * 6.0
+        } else {
+            p
+        };
+        (v * 255.0).round() as u32
+    };
+    (channel(h + 1.0 / 3.0) << 16) | (channel(h) << 8) | channel(h - 1.0 / 3.0)
+}
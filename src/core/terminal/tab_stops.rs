@@ -0,0 +1,130 @@
+//! Horizontal tab stops of a terminal screen.
+
+/// Default distance between tab stops.
+pub(super) const DEFAULT_TAB_WIDTH: usize = 8;
+
+/// One flag per column; `true` marks a tab stop.
+///
+/// Columns beyond the ones set explicitly get a stop every `width`
+/// columns, both initially and when the screen grows.
+#[derive(Debug, Clone)]
+pub(super) struct TabStops {
+    stops: Vec<bool>,
+    width: usize,
+}
+
+impl TabStops {
+    pub(super) fn new(cols: usize, width: usize) -> Self {
+        let mut tabs = Self {
+            stops: Vec::new(),
+            width: width.max(1),
+        };
+        tabs.resize(cols);
+        tabs
+    }
+
+    /// Restores the default stops (RIS, tab width change).
+    pub(super) fn reset(&mut self) {
+        let cols = self.stops.len();
+        self.stops.clear();
+        self.resize(cols);
+    }
+
+    /// Changes the default interval and restores the default stops.
+    pub(super) fn set_width(&mut self, width: usize) {
+        let width = width.max(1);
+        if width != self.width {
+            self.width = width;
+            self.reset();
+        }
+    }
+
+    /// Keeps the stops of surviving columns; new columns get default stops.
+    pub(super) fn resize(&mut self, cols: usize) {
+        let old = self.stops.len();
+        self.stops.truncate(cols);
+        let width = self.width;
+        self.stops
+            .extend((old..cols).map(|col| col > 0 && col % width == 0));
+    }
+
+    pub(super) fn set(&mut self, col: usize) {
+        if let Some(stop) = self.stops.get_mut(col) {
+            *stop = true;
+        }
+    }
+
+    pub(super) fn clear(&mut self, col: usize) {
+        if let Some(stop) = self.stops.get_mut(col) {
+            *stop = false;
+        }
+    }
+
+    pub(super) fn clear_all(&mut self) {
+        self.stops.fill(false);
+    }
+
+    /// Column of the `count`-th stop right of `col`, or the last column.
+    pub(super) fn next(&self, col: usize, count: usize) -> usize {
+        let last = self.stops.len().saturating_sub(1);
+        let mut col = col;
+        for _ in 0..count {
+            match (col + 1..self.stops.len()).find(|&c| self.stops[c]) {
+                Some(next) => col = next,
+                None => return last,
+            }
+        }
+        col.min(last)
+    }
+
+    /// Column of the `count`-th stop left of `col`, or column 0.
+    pub(super) fn prev(&self, col: usize, count: usize) -> usize {
+        let mut col = col.min(self.stops.len());
+        for _ in 0..count {
+            match (0..col).rev().find(|&c| self.stops[c]) {
+                Some(prev) => col = prev,
+                None => return 0,
+            }
+        }
+        col
+    }
+
+    /// Columns holding a stop, left to right.
+    pub(super) fn columns(&self) -> impl Iterator<Item = usize> + '_ {
+        self.stops
+            .iter()
+            .enumerate()
+            .filter_map(|(col, &stop)| stop.then_some(col))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_every_width_columns() {
+        let tabs = TabStops::new(20, 8);
+        assert_eq!(tabs.columns().collect::<Vec<_>>(), vec![8, 16]);
+        assert_eq!(tabs.next(0, 1), 8);
+        assert_eq!(tabs.next(8, 1), 16);
+        assert_eq!(tabs.next(16, 1), 19);
+        assert_eq!(tabs.next(3, 2), 16);
+        assert_eq!(tabs.prev(17, 1), 16);
+        assert_eq!(tabs.prev(16, 1), 8);
+        assert_eq!(tabs.prev(9, 5), 0);
+    }
+
+    #[test]
+    fn resize_keeps_custom_stops_and_fills_new_columns() {
+        let mut tabs = TabStops::new(12, 4);
+        tabs.clear_all();
+        tabs.set(3);
+        tabs.resize(20);
+        assert_eq!(tabs.columns().collect::<Vec<_>>(), vec![3, 12, 16]);
+        tabs.resize(10);
+        assert_eq!(tabs.columns().collect::<Vec<_>>(), vec![3]);
+        tabs.set_width(5);
+        assert_eq!(tabs.columns().collect::<Vec<_>>(), vec![5]);
+    }
+}
@@ -1,5 +1,5 @@
 use std::cell::Cell;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 /// A coordinate in the `PageList` address space.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -17,30 +17,64 @@ pub struct PageCoord {
 /// `Arc<Mutex<>>` on every cursor movement.
 #[derive(Clone, Debug)]
 pub struct TrackedPin {
-    inner: Rc<Cell<PageCoord>>,
+    inner: Rc<PinSlot>,
+}
+
+#[derive(Debug)]
+struct PinSlot {
+    coord: Cell<PageCoord>,
+    /// Cleared when the row under the pin leaves the buffer.
+    valid: Cell<bool>,
+}
+
+/// Non-owning handle kept by `PageList` for pins created with
+/// `PageList::track_pin`.
+pub(crate) struct WeakPin(Weak<PinSlot>);
+
+impl WeakPin {
+    pub(crate) fn upgrade(&self) -> Option<TrackedPin> {
+        self.0.upgrade().map(|inner| TrackedPin { inner })
+    }
 }
 
 impl TrackedPin {
     pub(crate) fn new(coord: PageCoord) -> Self {
         Self {
-            inner: Rc::new(Cell::new(coord)),
+            inner: Rc::new(PinSlot {
+                coord: Cell::new(coord),
+                valid: Cell::new(true),
+            }),
         }
     }
 
+    pub(crate) fn downgrade(&self) -> WeakPin {
+        WeakPin(Rc::downgrade(&self.inner))
+    }
+
     pub fn coord(&self) -> PageCoord {
-        self.inner.get()
+        self.inner.coord.get()
     }
 
     pub fn set_coord(&self, coord: PageCoord) {
-        self.inner.set(coord);
+        self.inner.coord.set(coord);
     }
 
     pub fn set_col(&self, col: usize) {
-        self.inner.set(PageCoord { col, ..self.inner.get() });
+        self.set_coord(PageCoord { col, ..self.coord() });
     }
 
     pub fn set_abs_row(&self, abs_row: usize) {
-        self.inner.set(PageCoord { abs_row, ..self.inner.get() });
+        self.set_coord(PageCoord { abs_row, ..self.coord() });
+    }
+
+    /// Returns `false` once the row under the pin has been evicted or the
+    /// buffer it points into has been rebuilt.
+    pub fn is_valid(&self) -> bool {
+        self.inner.valid.get()
+    }
+
+    pub(crate) fn invalidate(&self) {
+        self.inner.valid.set(false);
     }
 }
 
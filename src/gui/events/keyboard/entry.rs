@@ -9,15 +9,16 @@ impl FerrumWindow {
         tx: &mpsc::Sender<PtyEvent>,
         config: &AppConfig,
     ) {
+        let key = Self::normalize_non_text_key(&event.logical_key, &event.physical_key);
+
         if event.state != ElementState::Pressed {
+            self.forward_kitty_key_event(&key, event);
             return;
         }
 
         // Reset blink phase so the cursor is immediately visible after keypress.
         self.cursor_blink_start = std::time::Instant::now();
 
-        let key = Self::normalize_non_text_key(&event.logical_key, &event.physical_key);
-
         // Escape cancels tab drag.
         #[cfg(not(target_os = "macos"))]
         if matches!(key, Key::Named(NamedKey::Escape)) && self.dragging_tab.is_some() {
@@ -32,6 +33,16 @@ impl FerrumWindow {
             return; // Do not forward rename keystrokes to PTY.
         }
 
+        // An open search bar takes text input until it is closed.
+        if self.handle_search_input(&key, &event.physical_key) {
+            return;
+        }
+
+        // Copy mode keeps all other keys away from the PTY.
+        if self.handle_vi_mode_input(&key, &event.physical_key) {
+            return;
+        }
+
         if self.handle_selection_delete_key(&key) {
             return;
         }
@@ -54,6 +65,7 @@ impl FerrumWindow {
         }
 
         if Self::is_modifier_only_key(&key) {
+            self.forward_kitty_key_event(&key, event);
             return;
         }
 
@@ -66,6 +78,6 @@ impl FerrumWindow {
             return;
         }
 
-        self.forward_key_to_pty(&key);
+        self.forward_key_to_pty(&key, event);
     }
 }
@@ -1,8 +1,50 @@
-use crate::gui::input::key_to_bytes_ex;
+use winit::event::KeyEvent;
+use winit::platform::modifier_supplement::KeyEventExtModifierSupplement;
+
+use crate::core::KeyboardFlags;
+use crate::gui::input::{
+    KeyEventKind, KittyKeyEvent, application_keypad_bytes, encode_kitty_key, key_to_bytes_ex,
+};
 use crate::gui::*;
 
+/// Encodes `event` for the Kitty keyboard protocol; `key` is the normalized logical key.
+fn encode_kitty_event(
+    key: &Key,
+    event: &KeyEvent,
+    modifiers: ModifiersState,
+    flags: KeyboardFlags,
+) -> Option<Vec<u8>> {
+    let unmodified_key = event.key_without_modifiers();
+    let kind = match (event.state, event.repeat) {
+        (ElementState::Released, _) => KeyEventKind::Release,
+        (ElementState::Pressed, true) => KeyEventKind::Repeat,
+        (ElementState::Pressed, false) => KeyEventKind::Press,
+    };
+    encode_kitty_key(
+        &KittyKeyEvent {
+            key,
+            unmodified_key: &unmodified_key,
+            physical_key: event.physical_key,
+            text: event.text.as_deref(),
+            kind,
+            modifiers,
+        },
+        flags,
+    )
+}
+
 impl FerrumWindow {
-    pub(in crate::gui::events::keyboard) fn forward_key_to_pty(&mut self, key: &Key) {
+    fn active_keyboard_flags(&self) -> KeyboardFlags {
+        self.active_leaf_ref()
+            .map(|leaf| leaf.terminal.keyboard_flags())
+            .unwrap_or_default()
+    }
+
+    pub(in crate::gui::events::keyboard) fn forward_key_to_pty(
+        &mut self,
+        key: &Key,
+        event: &KeyEvent,
+    ) {
         let should_replace_selection = self
             .active_leaf_ref()
             .is_some_and(|leaf| leaf.selection.is_some())
@@ -17,15 +59,63 @@ impl FerrumWindow {
         }
         self.keyboard_selection_anchor = None;
 
-        let (decckm, modify_other_keys) = self
+        let (decckm, application_keypad, modify_other_keys) = self
             .active_leaf_ref()
-            .map(|l| (l.terminal.decckm, l.terminal.modify_other_keys))
-            .unwrap_or((false, 0));
-        let Some(bytes) = key_to_bytes_ex(key, self.modifiers, decckm, modify_other_keys) else {
+            .map(|l| {
+                (
+                    l.terminal.decckm,
+                    l.terminal.application_keypad,
+                    l.terminal.modify_other_keys,
+                )
+            })
+            .unwrap_or((false, false, 0));
+        let keyboard_flags = self.active_keyboard_flags();
+        let kitty = !keyboard_flags.is_empty();
+        let bytes = if kitty {
+            encode_kitty_event(key, event, self.modifiers, keyboard_flags)
+        } else if application_keypad && self.modifiers.is_empty() {
+            application_keypad_bytes(event.physical_key)
+                .or_else(|| key_to_bytes_ex(key, self.modifiers, decckm, modify_other_keys))
+        } else {
+            key_to_bytes_ex(key, self.modifiers, decckm, modify_other_keys)
+        };
+        let Some(bytes) = bytes else {
             return;
         };
         if let Some(leaf) = self.active_leaf_mut() {
             leaf.write_pty(&bytes);
         }
+        if kitty {
+            self.kitty_pressed_keys.insert(event.physical_key);
+        }
+    }
+
+    /// Reports events the legacy encoding never sends (key releases, bare
+    /// modifier keys) when the application enabled the Kitty keyboard protocol.
+    ///
+    /// A release is reported only when its press was, so keys that opened a
+    /// shortcut or typed into the search bar never show up half-pressed.
+    pub(in crate::gui::events::keyboard) fn forward_kitty_key_event(
+        &mut self,
+        key: &Key,
+        event: &KeyEvent,
+    ) {
+        let pressed = event.state == ElementState::Pressed;
+        if !pressed && !self.kitty_pressed_keys.remove(&event.physical_key) {
+            return;
+        }
+        let keyboard_flags = self.active_keyboard_flags();
+        if keyboard_flags.is_empty() {
+            return;
+        }
+        let Some(bytes) = encode_kitty_event(key, event, self.modifiers, keyboard_flags) else {
+            return;
+        };
+        if let Some(leaf) = self.active_leaf_mut() {
+            leaf.write_pty(&bytes);
+        }
+        if pressed {
+            self.kitty_pressed_keys.insert(event.physical_key);
+        }
     }
 }
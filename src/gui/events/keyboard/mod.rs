@@ -4,9 +4,11 @@ mod entry;
 mod forward;
 mod navigation_shortcuts;
 mod rename;
+mod search;
 mod selection;
 mod shortcuts;
 mod tab_shortcuts;
+mod vi_mode;
 mod word_motion;
 
 use crate::gui::*;
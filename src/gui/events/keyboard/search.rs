@@ -0,0 +1,86 @@
+use crate::gui::interaction::SearchJump;
+use crate::gui::*;
+
+impl FerrumWindow {
+    /// Routes keys to the focused pane's search bar while it is open.
+    ///
+    /// Returns `false` for action-modifier combos so app shortcuts keep
+    /// working with the bar open; every other key is consumed.
+    pub(in crate::gui::events::keyboard) fn handle_search_input(
+        &mut self,
+        key: &Key,
+        physical: &PhysicalKey,
+    ) -> bool {
+        let ctrl = self.is_action_modifier();
+        let alt = self.modifiers.alt_key();
+        let shift = self.modifiers.shift_key();
+
+        if self
+            .active_leaf_ref()
+            .is_none_or(|leaf| leaf.search.is_none())
+            || ctrl
+        {
+            return false;
+        }
+
+        match key {
+            Key::Named(NamedKey::Escape) => {
+                self.close_search();
+                return true;
+            }
+            // In copy mode Enter confirms the search and moves the vi cursor.
+            Key::Named(NamedKey::Enter)
+                if self
+                    .active_leaf_ref()
+                    .is_some_and(|leaf| leaf.vi_mode.is_some()) =>
+            {
+                if let Some(leaf) = self.active_leaf_mut() {
+                    leaf.confirm_vi_search();
+                }
+            }
+            Key::Named(NamedKey::Enter) => {
+                let jump = if shift {
+                    SearchJump::Newer
+                } else {
+                    SearchJump::Older
+                };
+                if let Some(leaf) = self.active_leaf_mut() {
+                    leaf.jump_search(jump);
+                }
+            }
+            _ => {
+                let Some(leaf) = self.active_leaf_mut() else {
+                    return false;
+                };
+                let Some(search) = leaf.search.as_mut() else {
+                    return false;
+                };
+                let changed = match key {
+                    Key::Named(NamedKey::Backspace) => search.query.pop().is_some(),
+                    _ if alt && Self::physical_key_is(physical, KeyCode::KeyC) => {
+                        search.case_sensitive = !search.case_sensitive;
+                        true
+                    }
+                    _ if alt && Self::physical_key_is(physical, KeyCode::KeyR) => {
+                        search.regex = !search.regex;
+                        true
+                    }
+                    Key::Named(NamedKey::Space) if !alt => {
+                        search.query.push(' ');
+                        true
+                    }
+                    Key::Character(text) if !alt && !text.chars().any(char::is_control) => {
+                        search.query.push_str(text);
+                        true
+                    }
+                    _ => false,
+                };
+                if changed {
+                    leaf.run_search();
+                }
+            }
+        }
+        self.window.request_redraw();
+        true
+    }
+}
@@ -59,6 +59,7 @@ impl FerrumWindow {
                 abs_row,
                 col: end_col,
             },
+            kind: SelectionKind::Stream,
         })
     }
 
@@ -1,4 +1,5 @@
 use crate::config::AppConfig;
+use crate::gui::interaction::PromptJump;
 use crate::gui::pane::{NavigateDirection, SplitDirection};
 #[cfg(not(target_os = "macos"))]
 use crate::gui::tabs::create::NewTabParams;
@@ -48,6 +49,10 @@ impl FerrumWindow {
         if let Some(result) = self.handle_tab_management_shortcuts(key, physical, next_tab_id, tx, config) {
             return result;
         }
+        if Self::physical_key_is(physical, KeyCode::KeyF) {
+            self.open_search();
+            return true;
+        }
         if self.handle_super_text_shortcuts(physical) {
             return true;
         }
@@ -138,6 +143,32 @@ impl FerrumWindow {
             return true;
         }
 
+        // ── Jump between shell prompts (OSC 133) ──────────────────────────
+        if Self::physical_key_is(physical, KeyCode::KeyZ) {
+            self.jump_to_prompt(PromptJump::Previous);
+            return true;
+        }
+        if Self::physical_key_is(physical, KeyCode::KeyX) {
+            self.jump_to_prompt(PromptJump::Next);
+            return true;
+        }
+
+        // ── Last command output (OSC 133) ─────────────────────────────────
+        if Self::physical_key_is(physical, KeyCode::KeyO) {
+            self.select_last_command_output();
+            return true;
+        }
+        if Self::physical_key_is(physical, KeyCode::KeyY) {
+            self.copy_last_command_output();
+            return true;
+        }
+
+        // ── Vi copy mode ──────────────────────────────────────────────────
+        if Self::physical_key_is(physical, KeyCode::Space) {
+            self.toggle_vi_mode();
+            return true;
+        }
+
         // ── Close terminal window ─────────────────────────────────────────
         if Self::physical_key_is(physical, KeyCode::KeyW) {
             self.request_close_window();
@@ -0,0 +1,119 @@
+use crate::gui::interaction::ViMotion;
+use crate::gui::state::ViVisual;
+use crate::gui::*;
+
+/// Motion bound to a plain character key in copy mode.
+fn motion_for_char(text: &str) -> Option<ViMotion> {
+    Some(match text {
+        "h" => ViMotion::Left,
+        "j" => ViMotion::Down,
+        "k" => ViMotion::Up,
+        "l" => ViMotion::Right,
+        "w" => ViMotion::WordForward,
+        "b" => ViMotion::WordBackward,
+        "e" => ViMotion::WordEnd,
+        "0" => ViMotion::LineStart,
+        "^" => ViMotion::FirstNonBlank,
+        "$" => ViMotion::LineEnd,
+        "G" => ViMotion::Bottom,
+        _ => return None,
+    })
+}
+
+/// Motion bound to a named key (arrows, Home/End, PageUp/PageDown).
+fn motion_for_named(key: NamedKey) -> Option<ViMotion> {
+    Some(match key {
+        NamedKey::ArrowLeft => ViMotion::Left,
+        NamedKey::ArrowDown => ViMotion::Down,
+        NamedKey::ArrowUp => ViMotion::Up,
+        NamedKey::ArrowRight => ViMotion::Right,
+        NamedKey::Home => ViMotion::LineStart,
+        NamedKey::End => ViMotion::LineEnd,
+        NamedKey::PageUp => ViMotion::HalfPageUp,
+        NamedKey::PageDown => ViMotion::HalfPageDown,
+        _ => return None,
+    })
+}
+
+impl FerrumWindow {
+    /// Handles keys while the focused pane is in vi copy mode.
+    ///
+    /// Nothing reaches the PTY in copy mode. Action-modifier combos other
+    /// than `Ctrl-u`/`Ctrl-d`/`Ctrl-v`, and Alt+digit tab switching, are left
+    /// to the regular shortcut handlers.
+    pub(in crate::gui::events::keyboard) fn handle_vi_mode_input(
+        &mut self,
+        key: &Key,
+        physical: &PhysicalKey,
+    ) -> bool {
+        if self
+            .active_leaf_ref()
+            .is_none_or(|leaf| leaf.vi_mode.is_none())
+        {
+            return false;
+        }
+
+        let ctrl_only = self.modifiers.control_key()
+            && !self.modifiers.super_key()
+            && !self.modifiers.alt_key()
+            && !self.modifiers.shift_key();
+        if ctrl_only && Self::physical_key_is(physical, KeyCode::KeyV) {
+            if let Some(leaf) = self.active_leaf_mut() {
+                leaf.toggle_vi_visual(ViVisual::Block);
+            }
+            self.window.request_redraw();
+            return true;
+        }
+        let ctrl_motion = if !ctrl_only {
+            None
+        } else if Self::physical_key_is(physical, KeyCode::KeyU) {
+            Some(ViMotion::HalfPageUp)
+        } else if Self::physical_key_is(physical, KeyCode::KeyD) {
+            Some(ViMotion::HalfPageDown)
+        } else {
+            None
+        };
+        if ctrl_motion.is_none() && self.is_action_modifier() {
+            return false;
+        }
+        let alt = self.modifiers.alt_key();
+        if alt && Self::physical_digit_index(physical).is_some() {
+            return false;
+        }
+
+        if matches!(key, Key::Character(text) if text.as_str() == "y") {
+            self.vi_yank();
+            return true;
+        }
+
+        let Some(leaf) = self.active_leaf_mut() else {
+            return false;
+        };
+        let motion = ctrl_motion.or(match key {
+            Key::Named(named) => motion_for_named(*named),
+            Key::Character(text) if !alt => motion_for_char(text.as_str()),
+            _ => None,
+        });
+        if let Some(motion) = motion {
+            leaf.vi_motion(motion);
+        } else {
+            match key {
+                Key::Named(NamedKey::Escape) => leaf.vi_escape(),
+                Key::Character(text) if !alt => match text.as_str() {
+                    "g" => leaf.vi_g(),
+                    "v" => leaf.toggle_vi_visual(ViVisual::Char),
+                    "V" => leaf.toggle_vi_visual(ViVisual::Line),
+                    "/" => leaf.start_vi_search(true),
+                    "?" => leaf.start_vi_search(false),
+                    "n" => leaf.vi_jump_match(false),
+                    "N" => leaf.vi_jump_match(true),
+                    "q" | "i" => leaf.exit_vi_mode(),
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        self.window.request_redraw();
+        true
+    }
+}
@@ -51,6 +51,7 @@ impl FerrumWindow {
                             abs_row: last_row,
                             col: last_col,
                         },
+                        kind: SelectionKind::Stream,
                     });
                 }
             }
@@ -61,6 +62,10 @@ impl FerrumWindow {
                 self.selection_anchor = None;
                 self.keyboard_selection_anchor = None;
             }
+            MenuAction::SelectCommandOutput => {
+                self.select_last_command_output();
+            }
+            MenuAction::CopyCommandOutput => self.copy_last_command_output(),
             MenuAction::SplitRight => {
                 self.split_pane(SplitDirection::Horizontal, false, next_tab_id, tx, config);
             }
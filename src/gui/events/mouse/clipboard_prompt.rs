@@ -0,0 +1,58 @@
+use winit::event::ElementState;
+
+use crate::gui::interaction::clipboard_prompt_label;
+use crate::gui::renderer::shared::banner_layout::compute_clipboard_prompt_layout;
+use crate::gui::renderer::shared::tab_math::TabLayoutMetrics;
+use crate::gui::*;
+
+impl FerrumWindow {
+    /// Handles a left-button press or release on the clipboard prompt;
+    /// releasing over [Allow] or [Deny] answers it.
+    ///
+    /// Returns `true` if the click was consumed by the prompt.
+    pub(super) fn handle_clipboard_prompt_click(
+        &mut self,
+        state: ElementState,
+        mx: f64,
+        my: f64,
+    ) -> bool {
+        let Some(label) = self
+            .clipboard_prompt
+            .and_then(|prompt| clipboard_prompt_label(&self.tabs, prompt))
+        else {
+            return false;
+        };
+
+        let size = self.window.inner_size();
+        let m = TabLayoutMetrics {
+            cell_width: self.backend.cell_width(),
+            cell_height: self.backend.cell_height(),
+            ui_scale: self.backend.ui_scale(),
+            tab_bar_height: self.backend.tab_bar_height_px(),
+        };
+        let Some(layout) = compute_clipboard_prompt_layout(&label, &m, size.width, size.height)
+        else {
+            return false;
+        };
+
+        let px = mx as i32;
+        let py = my as i32;
+        let inside = |(x, y, w, h): (i32, i32, u32, u32)| {
+            px >= x && px < x + w as i32 && py >= y && py < y + h as i32
+        };
+        let button = |(x, y, w, h): (u32, u32, u32, u32)| (x as i32, y as i32, w, h);
+
+        if !inside(layout.bg_rect()) {
+            return false;
+        }
+        if state == ElementState::Released {
+            if inside(button(layout.allow_rect())) {
+                self.resolve_clipboard_prompt(true);
+            } else if inside(button(layout.deny_rect())) {
+                self.resolve_clipboard_prompt(false);
+            }
+        }
+        // Clicks on the prompt never reach the terminal below.
+        true
+    }
+}
@@ -89,6 +89,8 @@ impl FerrumWindow {
         self.hovered_tab = self
             .backend
             .hit_test_tab_hover(mx, my, self.tabs.len(), size.width);
+        #[cfg(not(target_os = "macos"))]
+        self.update_command_status_hover(mx, my);
 
         let cursor = if my < tab_bar_height {
             match self.tab_bar_hit(mx, my) {
@@ -110,6 +112,7 @@ impl FerrumWindow {
 
         // Handle rename field drag selection in tab bar.
         if my < tab_bar_height {
+            self.clear_link_hover();
             if self.is_selecting && self.renaming_tab.is_some() {
                 self.handle_rename_field_drag(mx);
             }
@@ -129,6 +132,13 @@ impl FerrumWindow {
 
         let (row, col) = self.pixel_to_grid(mx, my);
 
+        // Detected links are inert while the app tracks the mouse or a drag is on.
+        if self.is_mouse_reporting() || self.is_selecting {
+            self.clear_link_hover();
+        } else if self.update_link_hover(row, col) {
+            self.window.set_cursor(CursorIcon::Pointer);
+        }
+
         if self.handle_mouse_motion_reporting(row, col) {
             return;
         }
@@ -4,6 +4,7 @@ use crate::gui::pane::{DIVIDER_HIT_ZONE, DIVIDER_WIDTH};
 use crate::gui::renderer::TabBarHit;
 #[cfg(not(target_os = "linux"))]
 use crate::gui::state::MenuContext;
+use crate::gui::input::mouse_button_code;
 use crate::gui::*;
 use crate::update::AvailableRelease;
 
@@ -45,14 +46,32 @@ impl FerrumWindow {
         }
     }
 
-    /// Handles Middle and Right mouse button events, which behave identically
-    /// on all platforms.
+    /// Handles Middle, Right and extra mouse button events, which behave
+    /// identically on all platforms.
     fn on_non_left_mouse_input(&mut self, state: ElementState, button: winit::event::MouseButton) {
         match button {
             winit::event::MouseButton::Middle => self.on_middle_mouse_input(state),
             winit::event::MouseButton::Right => self.on_right_mouse_input(state),
-            _ => {}
+            _ => self.report_terminal_mouse_button(button, state),
+        }
+    }
+
+    /// Forwards `button` to the terminal when the pointer is over it and
+    /// mouse reporting is active.
+    fn report_terminal_mouse_button(
+        &mut self,
+        button: winit::event::MouseButton,
+        state: ElementState,
+    ) {
+        let (mx, my) = self.mouse_pos;
+        if my < self.backend.tab_bar_height_px() as f64 || !self.is_mouse_reporting() {
+            return;
         }
+        let Some(code) = mouse_button_code(button) else {
+            return;
+        };
+        let (row, col) = self.pixel_to_grid(mx, my);
+        self.send_mouse_event(code, col, row, state == ElementState::Pressed);
     }
 
     /// Closes the tab identified by `hit` if the hit is a tab.
@@ -67,12 +86,15 @@ impl FerrumWindow {
     }
 
     fn on_middle_mouse_input(&mut self, state: ElementState) {
-        if state != ElementState::Pressed {
-            return;
+        if state == ElementState::Pressed {
+            self.commit_rename();
         }
-        self.commit_rename();
         let (mx, my) = self.mouse_pos;
         if my >= self.backend.tab_bar_height_px() as f64 {
+            self.report_terminal_mouse_button(winit::event::MouseButton::Middle, state);
+            return;
+        }
+        if state != ElementState::Pressed {
             return;
         }
         let hit = self.tab_bar_hit(mx, my);
@@ -140,11 +162,17 @@ impl FerrumWindow {
                         .active_leaf_ref()
                         .and_then(|leaf| leaf.selection)
                         .is_some();
+                    let has_command_output = self
+                        .active_leaf_ref()
+                        .is_some_and(|leaf| leaf.terminal.screen.last_command_output().is_some());
                     let has_multiple_panes = self
                         .active_tab_ref()
                         .is_some_and(|t| t.has_multiple_panes());
-                    let (menu, action_map) =
-                        menus::build_terminal_context_menu(has_selection, has_multiple_panes);
+                    let (menu, action_map) = menus::build_terminal_context_menu(
+                        has_selection,
+                        has_command_output,
+                        has_multiple_panes,
+                    );
                     self.pending_menu_context = Some(MenuContext::Terminal {
                         pane_id: clicked_pane,
                         action_map,
@@ -246,7 +274,10 @@ impl FerrumWindow {
         let (mx, my) = self.mouse_pos;
         let tab_bar_height = self.backend.tab_bar_height_px() as f64;
 
-        // Handle update banner clicks before anything else.
+        // Handle clipboard prompt and update banner clicks before anything else.
+        if self.handle_clipboard_prompt_click(state, mx, my) {
+            return;
+        }
         if self.handle_update_banner_click(state, mx, my, available_release) {
             return;
         }
@@ -275,7 +306,10 @@ impl FerrumWindow {
         let (mx, my) = self.mouse_pos;
         let tab_bar_height = self.backend.tab_bar_height_px() as f64;
 
-        // Handle update banner clicks before anything else.
+        // Handle clipboard prompt and update banner clicks before anything else.
+        if self.handle_clipboard_prompt_click(state, mx, my) {
+            return;
+        }
         if self.handle_update_banner_click(state, mx, my, available_release) {
             return;
         }
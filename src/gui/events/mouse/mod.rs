@@ -1,5 +1,7 @@
+mod clipboard_prompt;
 mod cursor;
 mod input;
+mod links;
 mod tab_bar;
 mod tab_bar_actions;
 mod tab_bar_rename;
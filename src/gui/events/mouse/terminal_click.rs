@@ -1,6 +1,5 @@
 use crate::core::PageCoord;
 use crate::gui::*;
-use super::update_banner::open_url;
 
 impl FerrumWindow {
     fn update_terminal_click_streak(&mut self, pos: Position) -> u8 {
@@ -68,11 +67,8 @@ impl FerrumWindow {
                 self.is_selecting = true;
                 self.keyboard_selection_anchor = None;
 
-                if self.modifiers.control_key() && !self.is_mouse_reporting()
-                    && let Some(leaf) = self.tabs[idx].focused_leaf()
-                    && let Some(cell) = leaf.terminal.screen.viewport_row(row).cells.get(col)
-                    && let Some(url) = leaf.terminal.hyperlink_url(cell.hyperlink_id) {
-                    open_url(url);
+                if self.is_action_modifier() && !self.is_mouse_reporting()
+                    && self.open_link_at(row, col) {
                     self.is_selecting = false;
                     return;
                 }
@@ -93,29 +89,30 @@ impl FerrumWindow {
                         leaf.set_selection(Selection {
                             start: anchor,
                             end: abs_pos,
+                            kind: SelectionKind::Stream,
                         });
                     }
                     return;
                 }
 
                 self.selection_anchor = Some(abs_pos);
-                match self.update_terminal_click_streak(pos) {
-                    1 => {
-                        // Single-click arms char-wise drag; selection starts on movement.
-                        self.selection_drag_mode = SelectionDragMode::Character;
-                        if let Some(leaf) = self.tabs[idx].focused_leaf_mut() {
-                            leaf.clear_selection();
+                let streak = self.update_terminal_click_streak(pos);
+                let modifiers = self.modifiers;
+                let Some(leaf) = self.tabs[idx].focused_leaf_mut() else {
+                    return;
+                };
+                match leaf.press_selection(pos, modifiers, streak) {
+                    Some(mode) => {
+                        if mode == SelectionDragMode::Block {
+                            // A block press never starts a double or triple click.
+                            self.click_streak = 0;
                         }
+                        self.selection_drag_mode = mode;
                     }
-                    2 => {
-                        // Double-click selects word and keeps word-wise drag active.
-                        self.selection_drag_mode = SelectionDragMode::Word;
-                        self.select_word_at(row, col);
-                    }
-                    _ => {
-                        // Triple-click selects line and keeps line-wise drag active.
+                    None => {
                         self.selection_drag_mode = SelectionDragMode::Line;
-                        self.select_line_at(row);
+                        self.is_selecting = false;
+                        self.selection_anchor = None;
                     }
                 }
             }
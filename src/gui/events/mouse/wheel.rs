@@ -1,4 +1,3 @@
-use crate::gui::input::encode_mouse_event;
 use crate::gui::pane::{DIVIDER_WIDTH, PaneId};
 use crate::gui::*;
 
@@ -35,11 +34,12 @@ impl FerrumWindow {
     }
 
     pub(crate) fn on_mouse_wheel(&mut self, delta: MouseScrollDelta) {
-        let raw_lines = match delta {
-            MouseScrollDelta::LineDelta(_, y) => {
-                // Line-based scroll (mouse wheel) -- reset accumulator
+        let (raw_cols, raw_lines) = match delta {
+            MouseScrollDelta::LineDelta(x, y) => {
+                // Line-based scroll (mouse wheel) -- reset accumulators
                 self.scroll_accumulator = 0.0;
-                y as isize
+                self.scroll_accumulator_x = 0.0;
+                (x as isize, y as isize)
             }
             MouseScrollDelta::PixelDelta(pos) => {
                 // Pixel-based scroll (trackpad) -- accumulate small deltas
@@ -49,11 +49,17 @@ impl FerrumWindow {
                 if lines != 0 {
                     self.scroll_accumulator -= lines as f64 * cell_h;
                 }
-                lines
+                self.scroll_accumulator_x += pos.x;
+                let cell_w = self.backend.cell_width() as f64;
+                let cols = (self.scroll_accumulator_x / cell_w) as isize;
+                if cols != 0 {
+                    self.scroll_accumulator_x -= cols as f64 * cell_w;
+                }
+                (cols, lines)
             }
         };
 
-        if raw_lines == 0 {
+        if raw_lines == 0 && raw_cols == 0 {
             return;
         }
 
@@ -61,34 +67,48 @@ impl FerrumWindow {
             return;
         };
 
-        let (mouse_reporting, sgr) = match self
+        let mouse_reporting = match self
             .active_tab_ref()
             .and_then(|tab| tab.pane_tree.find_leaf(target_pane))
         {
-            Some(leaf) => (
-                !self.modifiers.shift_key() && leaf.terminal.mouse_mode != MouseMode::Off,
-                leaf.terminal.sgr_mouse,
-            ),
+            Some(leaf) => !self.modifiers.shift_key() && leaf.terminal.mouse_mode != MouseMode::Off,
             None => return,
         };
 
         // Mouse reporting -- send scroll events to app for pane under cursor.
+        // Buttons 4/5 scroll up/down (64/65), 6/7 scroll left/right (66/67).
         if mouse_reporting {
             let Some((row, col)) = self.wheel_grid_pos_for_pane(target_pane) else {
                 return;
             };
-            let button = if raw_lines > 0 { 64u8 } else { 65u8 };
-            let bytes = encode_mouse_event(button, col, row, true, sgr);
+            let vertical = if raw_lines > 0 { 64u8 } else { 65u8 };
+            let horizontal = if raw_cols > 0 { 66u8 } else { 67u8 };
+            let mut bytes = Vec::new();
+            for (count, button) in [(raw_lines, vertical), (raw_cols, horizontal)] {
+                if count == 0 {
+                    continue;
+                }
+                if let Some(report) =
+                    self.encode_pane_mouse_event(target_pane, button, col, row, true)
+                {
+                    for _ in 0..count.unsigned_abs() {
+                        bytes.extend_from_slice(&report);
+                    }
+                }
+            }
             if let Some(tab) = self.active_tab_mut()
                 && let Some(leaf) = tab.pane_tree.find_leaf_mut(target_pane)
             {
-                for _ in 0..raw_lines.unsigned_abs() {
-                    leaf.write_pty(&bytes);
-                }
+                leaf.write_pty(&bytes);
             }
             return;
         }
 
+        // Horizontal scrolling is only meaningful to mouse-reporting apps.
+        if raw_lines == 0 {
+            return;
+        }
+
         // Scrollback/alt-screen code for pane under cursor.
         if let Some(tab) = self.active_tab_mut()
             && let Some(leaf) = tab.pane_tree.find_leaf_mut(target_pane)
@@ -109,6 +129,9 @@ impl FerrumWindow {
             } else if lines < 0 {
                 leaf.scroll_offset = leaf.scroll_offset.saturating_sub((-lines) as usize);
             }
+            // The pointer now rests on other text; hover is picked up on the next move.
+            leaf.hovered_link = None;
+            leaf.hovered_hyperlink = None;
             leaf.scrollbar.last_activity = std::time::Instant::now();
         }
     }
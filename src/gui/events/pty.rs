@@ -1,5 +1,18 @@
+use crate::core::Notification;
+use crate::gui::notifier::notification_due;
+use crate::gui::pane::PaneId;
 use crate::gui::tabs::normalized_active_index_after_remove;
 use crate::gui::*;
+use std::time::{Duration, Instant};
+use winit::window::UserAttentionType;
+
+/// Shortest time between two signals of the bell.
+const BELL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Whether a bell last signalled at `last` may be signalled again at `now`.
+fn bell_due(last: Option<Instant>, now: Instant) -> bool {
+    last.is_none_or(|last| now.saturating_duration_since(last) >= BELL_INTERVAL)
+}
 
 impl FerrumWindow {
     /// Processes one PTY event.
@@ -14,11 +27,21 @@ impl FerrumWindow {
                     && let Some(leaf) = tab.pane_tree.find_leaf_mut(*pane_id)
                 {
                     leaf.process_and_flush(bytes);
-                    if let Some(text) = leaf.terminal.drain_clipboard_write()
-                        && let Some(ref mut clipboard) = self.clipboard
-                        && let Err(e) = clipboard.set_text(&text)
-                    {
-                        eprintln!("OSC 52: failed to write to clipboard: {e}");
+                    let bells = leaf.terminal.take_bells();
+                    let notifications = leaf.terminal.drain_notifications();
+                    let clipboard_write = leaf.terminal.drain_clipboard_write();
+                    let clipboard_read = leaf.terminal.pending_clipboard_reads().next().is_some();
+                    if let Some((target, text)) = clipboard_write {
+                        self.write_clipboard_target(target, &text);
+                    }
+                    if clipboard_read {
+                        self.request_clipboard_reads(*tab_id, *pane_id);
+                    }
+                    if bells > 0 {
+                        self.ring_bell(*tab_id, *pane_id);
+                    }
+                    if !notifications.is_empty() {
+                        self.post_notifications(*tab_id, *pane_id, &notifications);
                     }
                 }
             }
@@ -66,4 +89,83 @@ impl FerrumWindow {
             }
         }
     }
+    /// Signals BEL from `pane_id` as configured: flashes the pane, badges
+    /// the tab when it is in the background, plays the alert sound and asks
+    /// for attention when the window is unfocused.
+    ///
+    /// Bells closer together than [`BELL_INTERVAL`] are signalled once, so a
+    /// program ringing in a loop cannot strobe the pane or flood the system.
+    fn ring_bell(&mut self, tab_id: u64, pane_id: PaneId) {
+        let bell = self.bell;
+        let now = Instant::now();
+        let Some(index) = self.tabs.iter().position(|t| t.id == tab_id) else {
+            return;
+        };
+        let is_background = index != self.active_tab;
+        let tab = &mut self.tabs[index];
+        if bell.visual_flash
+            && let Some(leaf) = tab.pane_tree.find_leaf_mut(pane_id)
+            && bell_due(leaf.bell_at, now)
+        {
+            leaf.bell_at = Some(now);
+            self.window.request_redraw();
+        }
+        if bell.tab_badge && is_background && !tab.has_bell {
+            tab.has_bell = true;
+            self.window.request_redraw();
+        }
+        if !bell_due(self.last_bell_at, now) {
+            return;
+        }
+        self.last_bell_at = Some(now);
+        if bell.audible {
+            platform::beep();
+        }
+        if bell.urgency_hint && !self.window.has_focus() {
+            self.window
+                .request_user_attention(Some(UserAttentionType::Informational));
+        }
+    }
+
+    /// Hands notifications to the notifier unless they come from the pane the
+    /// user is looking at. Each pane posts at most one notification per
+    /// second; the rest are dropped.
+    fn post_notifications(&mut self, tab_id: u64, pane_id: PaneId, notifications: &[Notification]) {
+        let pane_in_view = self.window.has_focus()
+            && self
+                .active_tab_ref()
+                .is_some_and(|tab| tab.id == tab_id && tab.focused_pane == pane_id);
+        if pane_in_view {
+            return;
+        }
+        let Some(leaf) = self
+            .tabs
+            .iter_mut()
+            .find(|t| t.id == tab_id)
+            .and_then(|tab| tab.pane_tree.find_leaf_mut(pane_id))
+        else {
+            return;
+        };
+        let now = std::time::Instant::now();
+        if !notification_due(leaf.notified_at, now) {
+            return;
+        }
+        if let Some(notification) = notifications.first() {
+            leaf.notified_at = Some(now);
+            self.notifier.notify(notification);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bells_in_quick_succession_are_signalled_once() {
+        let now = Instant::now();
+        assert!(bell_due(None, now));
+        assert!(!bell_due(Some(now), now + BELL_INTERVAL / 2));
+        assert!(bell_due(Some(now), now + BELL_INTERVAL));
+    }
 }
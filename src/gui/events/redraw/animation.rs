@@ -1,4 +1,6 @@
+use crate::core::CellEffects;
 use crate::gui::*;
+use super::super::render_shared::BELL_FLASH_DURATION;
 use std::time::{Duration, Instant};
 
 const BLINK_WAKE_TOLERANCE: Duration = Duration::from_millis(20);
@@ -26,7 +28,10 @@ impl FerrumWindow {
         let scrollbar = self.scrollbar_animation_schedule(now);
         let tab_anim = self.tab_animation_schedule(now);
         let ui_anim = self.ui_animation_schedule(now);
-        let schedules = [cursor, scrollbar, tab_anim, ui_anim];
+        let synchronized = self.synchronized_output_schedule(now);
+        let text_blink = self.text_blink_schedule(now);
+        let bell_flash = self.bell_flash_schedule(now);
+        let schedules = [cursor, scrollbar, tab_anim, ui_anim, synchronized, text_blink, bell_flash];
         let mut result: Option<(Instant, bool)> = None;
         for s in schedules.into_iter().flatten() {
             result = Some(match result {
@@ -82,8 +87,45 @@ impl FerrumWindow {
         {
             return None;
         }
+        self.blink_boundary_schedule(now)
+    }
+
+    /// Redraws blinking text at each phase change while any is on screen.
+    fn text_blink_schedule(&self, now: Instant) -> Option<(Instant, bool)> {
+        if !self.active_tab_shows_blinking_text() {
+            return None;
+        }
+        self.blink_boundary_schedule(now)
+    }
 
+    /// Whether a pane of the active tab displays a cell with the blink attribute.
+    fn active_tab_shows_blinking_text(&self) -> bool {
+        let Some(tab) = self.active_tab_ref() else {
+            return false;
+        };
+        tab.pane_tree.leaf_ids().into_iter().any(|id| {
+            let Some(leaf) = tab.pane_tree.find_leaf(id) else {
+                return false;
+            };
+            let screen = &leaf.terminal.screen;
+            let first = screen.scrollback_len().saturating_sub(leaf.scroll_offset);
+            let last = (first + screen.viewport_rows()).min(screen.total_rows());
+            (first..last).any(|abs_row| {
+                screen
+                    .abs_row(abs_row)
+                    .cells
+                    .iter()
+                    .any(|cell| cell.effects.contains(CellEffects::BLINK))
+            })
+        })
+    }
+
+    /// Next cursor/text blink phase boundary, and whether one just passed.
+    fn blink_boundary_schedule(&self, now: Instant) -> Option<(Instant, bool)> {
         let interval_ms = self.cursor_blink_interval_ms as u128;
+        if interval_ms == 0 {
+            return None;
+        }
         let elapsed_ms = now
             .saturating_duration_since(self.cursor_blink_start)
             .as_millis();
@@ -103,6 +145,28 @@ impl FerrumWindow {
         Some((next_boundary, redraw_now))
     }
 
+    /// Animates the visual bell of the active tab's panes until it has faded.
+    fn bell_flash_schedule(&self, now: Instant) -> Option<(Instant, bool)> {
+        let tab = self.active_tab_ref()?;
+        let flashing = tab.pane_tree.leaf_ids().into_iter().any(|id| {
+            tab.pane_tree
+                .find_leaf(id)
+                .and_then(|leaf| leaf.bell_at)
+                // One extra frame clears the last tint.
+                .is_some_and(|at| now < at + BELL_FLASH_DURATION + ANIMATION_FRAME_INTERVAL)
+        });
+        flashing.then_some((now + ANIMATION_FRAME_INTERVAL, true))
+    }
+
+    /// Presents a frame skipped for a synchronized update once it ends or times out.
+    fn synchronized_output_schedule(&self, now: Instant) -> Option<(Instant, bool)> {
+        if !self.deferred_frame {
+            return None;
+        }
+        let deadline = self.synchronized_output_deadline().unwrap_or(now);
+        Some((deadline, now >= deadline))
+    }
+
     fn scrollbar_animation_schedule(&self, now: Instant) -> Option<(Instant, bool)> {
         let leaf = self.active_leaf_ref()?;
         if leaf.terminal.screen.scrollback_len() == 0 || leaf.scrollbar.hover || leaf.scrollbar.dragging {
@@ -7,6 +7,9 @@ use super::super::*;
 /// Debounce delay before sending SIGWINCH after the last resize event.
 /// Allows the user to finish dragging before the shell redraws its prompt.
 const SIGWINCH_DEBOUNCE_MS: u64 = 80;
+/// Longest a synchronized update (mode `?2026`) may hold back frames, so an
+/// application that never ends it cannot freeze the window.
+const SYNCHRONIZED_OUTPUT_TIMEOUT: Duration = Duration::from_millis(150);
 use std::time::{Duration, Instant};
 
 #[cfg(target_os = "macos")]
@@ -20,6 +23,25 @@ impl FerrumWindow {
         self.window.request_redraw();
     }
 
+    /// When the oldest synchronized update of a visible pane times out.
+    pub(in crate::gui) fn synchronized_output_deadline(&self) -> Option<Instant> {
+        let tab = self.active_tab_ref()?;
+        tab.pane_tree
+            .leaf_ids()
+            .into_iter()
+            .filter_map(|id| tab.pane_tree.find_leaf(id)?.terminal.synchronized_output_since())
+            .min()
+            .map(|since| since + SYNCHRONIZED_OUTPUT_TIMEOUT)
+    }
+
+    /// Whether presenting should wait for a visible pane to finish its update.
+    fn holds_synchronized_output(&self, now: Instant) -> bool {
+        !self.pending_grid_resize
+            && self
+                .synchronized_output_deadline()
+                .is_some_and(|deadline| now < deadline)
+    }
+
     pub(in super::super) fn apply_pending_resize(&mut self) {
         if self.pending_grid_resize {
             self.pending_grid_resize = false;
@@ -70,6 +92,16 @@ impl FerrumWindow {
                 };
             }
         }
+        if self.holds_synchronized_output(Instant::now()) {
+            // Keep the last frame on screen until the update is complete.
+            self.deferred_frame = true;
+            return;
+        }
+        self.deferred_frame = false;
+        // The user is looking at the active tab, so its bell has been seen.
+        if let Some(tab) = self.tabs.get_mut(self.active_tab) {
+            tab.has_bell = false;
+        }
         self.refresh_tab_bar_visibility();
         self.apply_pending_resize();
         self.advance_ui_animations();
@@ -3,13 +3,20 @@
 //! Extracts common frame-preparation logic that was previously duplicated
 //! verbatim in `render_cpu.rs` and `render_gpu.rs`.
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use crate::core::CursorStyle;
+use crate::core::{CursorStyle, UnderlineStyle};
 use pane::{DIVIDER_WIDTH, PaneLeaf, PaneNode, PaneRect, SplitDirection, split_rect};
 use renderer::traits::Renderer;
-use renderer::{RenderTarget, ScrollbarState};
-use renderer::shared::banner_layout::UpdateBannerLayout;
+use renderer::{PaneColors, RenderTarget, ScrollbarState};
+use renderer::shared::banner_layout::{
+    ClipboardPromptLayout, UpdateBannerLayout, compute_clipboard_prompt_layout,
+};
+use renderer::shared::command_status_layout::status_ticks;
+use renderer::shared::link_layout::{hyperlink_underline_bars, link_underline_bars};
+use renderer::shared::search_layout::{
+    SearchBarContent, compute_search_bar_layout, visible_search_highlights,
+};
 use super::super::*;
 
 #[cfg(not(target_os = "macos"))]
@@ -18,6 +25,12 @@ use renderer::TabInfo;
 /// Opacity of the inactive-pane dim overlay.
 const INACTIVE_PANE_DIM_ALPHA: f32 = 0.18;
 
+/// How long the visual bell flash takes to fade out.
+pub(in super::super) const BELL_FLASH_DURATION: Duration = Duration::from_millis(200);
+
+/// Opacity of the visual bell flash when it starts.
+const BELL_FLASH_ALPHA: f32 = 0.25;
+
 /// Pre-computed tab bar state needed by both CPU and GPU render paths.
 ///
 /// Built once per frame via `FerrumWindow::build_tab_bar_state`, then passed
@@ -44,6 +57,7 @@ pub(in super::super) struct TabBarFrameTabInfo {
     pub rename_text: Option<String>,
     pub rename_cursor: usize,
     pub rename_selection: Option<(usize, usize)>,
+    pub has_bell: bool,
 }
 
 #[cfg(not(target_os = "macos"))]
@@ -66,6 +80,8 @@ impl TabBarFrameTabInfo {
             rename_cursor: self.rename_cursor,
             #[cfg(not(target_os = "macos"))]
             rename_selection: self.rename_selection,
+            #[cfg(not(target_os = "macos"))]
+            has_bell: self.has_bell,
         }
     }
 }
@@ -100,8 +116,16 @@ pub(in super) struct FrameParams<'a> {
     pub mouse_pos: (f64, f64),
     #[cfg(not(target_os = "macos"))]
     pub pinned: bool,
+    /// Tooltip for the hovered command status marker (exit code and duration).
+    #[cfg(not(target_os = "macos"))]
+    pub command_status_tooltip: Option<String>,
+    /// URL of the hovered OSC 8 hyperlink, shown before it is opened.
+    #[cfg(not(target_os = "macos"))]
+    pub hyperlink_tooltip: Option<String>,
     /// Pre-computed update banner geometry, or `None` when the banner is not shown.
     pub update_banner: Option<UpdateBannerLayout>,
+    /// Pre-computed clipboard prompt geometry, or `None` when no pane asks.
+    pub clipboard_prompt: Option<ClipboardPromptLayout>,
 }
 
 /// Window-level inputs for [`build_frame_params`], grouping the fields that are
@@ -122,9 +146,12 @@ pub(in super) struct FrameParamsInput<'a> {
     pub mouse_pos: (f64, f64),
     #[cfg(not(target_os = "macos"))]
     pub pinned: bool,
+    #[cfg(not(target_os = "macos"))]
+    pub hovered_command_status: Option<crate::core::CommandStatus>,
     pub update_banner_dismissed: bool,
     pub update_install_state: &'a UpdateInstallState,
     pub pending_update_tag: Option<&'a str>,
+    pub clipboard_prompt: Option<state::ClipboardPrompt>,
 }
 
 /// Constructs a [`FrameParamsInput`] from a `FerrumWindow` reference.
@@ -145,9 +172,12 @@ macro_rules! make_frame_params_input {
             mouse_pos: $self.mouse_pos,
             #[cfg(not(target_os = "macos"))]
             pinned: $self.pinned,
+            #[cfg(not(target_os = "macos"))]
+            hovered_command_status: $self.hovered_command_status,
             update_banner_dismissed: $self.update_banner_dismissed,
             update_install_state: &$self.update_install_state,
             pending_update_tag: $self.pending_update_tag.as_deref(),
+            clipboard_prompt: $self.clipboard_prompt,
         }
     };
 }
@@ -179,6 +209,10 @@ pub(in super) fn build_frame_params<'a>(
         mouse_pos: input.mouse_pos,
         #[cfg(not(target_os = "macos"))]
         pinned: input.pinned,
+        #[cfg(not(target_os = "macos"))]
+        command_status_tooltip: input.hovered_command_status.map(|status| status.to_string()),
+        #[cfg(not(target_os = "macos"))]
+        hyperlink_tooltip: hovered_hyperlink_url(input.tabs.get(input.active_tab)),
         update_banner: compute_banner(
             input.update_banner_dismissed,
             input.update_install_state,
@@ -188,6 +222,10 @@ pub(in super) fn build_frame_params<'a>(
             bw,
             bh,
         ),
+        clipboard_prompt: input
+            .clipboard_prompt
+            .and_then(|prompt| interaction::clipboard_prompt_label(input.tabs, prompt))
+            .and_then(|label| compute_clipboard_prompt_layout(&label, tab_layout_metrics, bw, bh)),
     }
 }
 
@@ -250,6 +288,7 @@ impl FerrumWindow {
                     } else {
                         None
                     },
+                    has_bell: t.has_bell && i != self.active_tab,
                 }
             })
             .collect();
@@ -368,6 +407,10 @@ pub(in super) fn draw_frame_content(
     #[cfg(not(target_os = "macos"))] frame_tab_infos: &[TabInfo<'_>],
 ) {
     let mut target = RenderTarget { buffer, width: bw, height: bh };
+    renderer.set_blink_visible(blink_phase_visible(
+        params.cursor_blink_start,
+        params.cursor_blink_interval_ms,
+    ));
 
     // 1) Draw terminal content — single-pane fast path or multi-pane loop.
     if let Some(tab) = params.tab {
@@ -397,6 +440,7 @@ pub(in super) fn draw_frame_content(
                     let content = rect.inset(pane_pad);
 
                     // Render terminal content into pane area.
+                    renderer.set_pane_colors(pane_colors(leaf));
                     renderer.render_in_rect(
                         &mut target,
                         &leaf.terminal.screen,
@@ -422,6 +466,11 @@ pub(in super) fn draw_frame_content(
                     if let Some(sb) = leaf_scrollbar_state(leaf) {
                         renderer.render_scrollbar_in_rect(&mut target, &sb, rect);
                     }
+
+                    draw_link_hover(renderer, &mut target, leaf, content);
+                    draw_search_overlay(renderer, &mut target, leaf, content);
+                    draw_vi_cursor(renderer, &mut target, leaf, content);
+                    draw_bell_flash(renderer, &mut target, leaf, rect);
                 }
             }
 
@@ -443,6 +492,7 @@ pub(in super) fn draw_frame_content(
         } else {
             // Single-pane: use the original render path (faster, no rect clipping).
             if let Some(leaf) = tab.focused_leaf() {
+                renderer.set_pane_colors(pane_colors(leaf));
                 renderer.render(
                     &mut target,
                     &leaf.terminal.screen,
@@ -465,6 +515,19 @@ pub(in super) fn draw_frame_content(
                 if let Some(sb) = leaf_scrollbar_state(leaf) {
                     renderer.render_scrollbar(&mut target, &sb);
                 }
+
+                let tab_bar_h = renderer.tab_bar_height_px();
+                let padding = renderer.window_padding_px();
+                let grid_rect = PaneRect {
+                    x: padding,
+                    y: tab_bar_h + padding,
+                    width: (bw as u32).saturating_sub(padding * 2),
+                    height: (bh as u32).saturating_sub(tab_bar_h + padding * 2),
+                };
+                draw_link_hover(renderer, &mut target, leaf, grid_rect);
+                draw_search_overlay(renderer, &mut target, leaf, grid_rect);
+                draw_vi_cursor(renderer, &mut target, leaf, grid_rect);
+                draw_bell_flash(renderer, &mut target, leaf, grid_rect);
             }
         }
     }
@@ -506,12 +569,151 @@ pub(in super) fn draw_frame_content(
         && let Some(ref title) = tab_bar.tab_tooltip
     {
         renderer.draw_tab_tooltip(&mut target, params.mouse_pos, title);
+    } else if let Some(ref status) = params.command_status_tooltip {
+        renderer.draw_tab_tooltip(&mut target, params.mouse_pos, status);
+    } else if let Some(ref url) = params.hyperlink_tooltip {
+        renderer.draw_tab_tooltip(&mut target, params.mouse_pos, url);
     }
 
     // 7) Draw update banner (when available).
     if let Some(ref banner) = params.update_banner {
         renderer.draw_update_banner(&mut target, banner);
     }
+
+    // 8) Draw the clipboard read prompt (when a pane asks).
+    if let Some(ref prompt) = params.clipboard_prompt {
+        renderer.draw_clipboard_prompt(&mut target, prompt);
+    }
+}
+
+/// Tints a pane that rang the bell, fading out over `BELL_FLASH_DURATION`.
+fn draw_bell_flash(
+    renderer: &mut dyn Renderer,
+    target: &mut RenderTarget<'_>,
+    leaf: &PaneLeaf,
+    rect: PaneRect,
+) {
+    let Some(rang_at) = leaf.bell_at else {
+        return;
+    };
+    let elapsed = rang_at.elapsed();
+    if elapsed >= BELL_FLASH_DURATION {
+        return;
+    }
+    let remaining = 1.0 - elapsed.as_secs_f32() / BELL_FLASH_DURATION.as_secs_f32();
+    renderer.draw_bell_flash(target, rect, BELL_FLASH_ALPHA * remaining);
+}
+
+/// URL of the OSC 8 hyperlink hovered in the focused pane of `tab`.
+#[cfg(not(target_os = "macos"))]
+fn hovered_hyperlink_url(tab: Option<&TabState>) -> Option<String> {
+    let tab = tab?;
+    let leaf = tab.pane_tree.find_leaf(tab.focused_pane)?;
+    let url = leaf.terminal.hyperlink_url(leaf.hovered_hyperlink?)?;
+    Some(url.to_owned())
+}
+
+/// Underlines the OSC 8 hyperlink or detected link under the pointer.
+fn draw_link_hover(
+    renderer: &mut dyn Renderer,
+    target: &mut RenderTarget<'_>,
+    leaf: &PaneLeaf,
+    grid_rect: PaneRect,
+) {
+    let cell = (renderer.cell_width(), renderer.cell_height());
+    let bars = if let Some(id) = leaf.hovered_hyperlink {
+        hyperlink_underline_bars(
+            &leaf.terminal.screen,
+            leaf.scroll_offset,
+            grid_rect,
+            cell,
+            id,
+            UnderlineStyle::Single,
+        )
+    } else if let Some(link) = leaf.hovered_link.as_ref() {
+        link_underline_bars(
+            &leaf.terminal.screen,
+            leaf.scroll_offset,
+            grid_rect,
+            cell,
+            link,
+            UnderlineStyle::Single,
+        )
+    } else {
+        return;
+    };
+    renderer.draw_link_underline(target, &bars);
+}
+
+/// Draws the match highlights and the search bar of a pane with an open search.
+///
+/// `grid_rect` is where the pane's cells start; highlights are clipped to it
+/// and the bar sits in its top-right corner.
+fn draw_search_overlay(
+    renderer: &mut dyn Renderer,
+    target: &mut RenderTarget<'_>,
+    leaf: &PaneLeaf,
+    grid_rect: PaneRect,
+) {
+    let Some(search) = leaf.search.as_ref() else {
+        return;
+    };
+    let highlights = visible_search_highlights(
+        &leaf.terminal.screen,
+        leaf.scroll_offset,
+        grid_rect,
+        (renderer.cell_width(), renderer.cell_height()),
+        &search.matches,
+        search.current,
+    );
+    renderer.draw_search_highlights(target, &highlights);
+
+    let status = search.status_text();
+    let content = SearchBarContent {
+        query: &search.query,
+        status: &status,
+        status_error: search.status_is_error(),
+        case_sensitive: search.case_sensitive,
+        regex: search.regex,
+    };
+    if let Some(layout) =
+        compute_search_bar_layout(&content, &renderer.tab_layout_metrics(), grid_rect)
+    {
+        renderer.draw_search_bar(target, &layout);
+    }
+}
+
+/// Draws the copy-mode cursor of a pane in vi mode when its row is displayed.
+fn draw_vi_cursor(
+    renderer: &mut dyn Renderer,
+    target: &mut RenderTarget<'_>,
+    leaf: &PaneLeaf,
+    grid_rect: PaneRect,
+) {
+    let Some(vi) = leaf.vi_mode.as_ref() else {
+        return;
+    };
+    let screen = &leaf.terminal.screen;
+    let cursor = vi.cursor.coord();
+    let top = screen.scrollback_len().saturating_sub(leaf.scroll_offset);
+    let Some(row) = cursor.abs_row.checked_sub(top) else {
+        return;
+    };
+    let (cw, ch) = (renderer.cell_width(), renderer.cell_height());
+    let x = grid_rect.x + cursor.col as u32 * cw;
+    let y = grid_rect.y + row as u32 * ch;
+    if row >= screen.viewport_rows()
+        || x + cw > grid_rect.x + grid_rect.width
+        || y + ch > grid_rect.y + grid_rect.height
+    {
+        return;
+    }
+    let glyph = screen
+        .abs_row(cursor.abs_row)
+        .cells
+        .get(cursor.col)
+        .map_or(' ', |cell| cell.first_char());
+    renderer.draw_vi_cursor(target, x, y, glyph);
 }
 
 /// Recursively draws divider lines between split panes.
@@ -618,6 +820,17 @@ pub(in super) fn scrollbar_opacity(
     }
 }
 
+/// Whether blinking content is in its visible phase; the first phase after
+/// `blink_start` is always visible.
+fn blink_phase_visible(blink_start: Instant, interval_ms: u64) -> bool {
+    let interval = interval_ms as u128;
+    if interval == 0 {
+        return true;
+    }
+    let ms = blink_start.elapsed().as_millis();
+    ms < interval || (ms / interval).is_multiple_of(2)
+}
+
 /// Determines whether the cursor should be visible this frame, accounting
 /// for blinking.
 pub(in super) fn should_show_cursor(
@@ -625,15 +838,16 @@ pub(in super) fn should_show_cursor(
     style: CursorStyle,
     interval_ms: u64,
 ) -> bool {
-    if style.is_blinking() {
-        let interval = interval_ms as u128;
-        if interval == 0 {
-            return true;
-        }
-        let ms = blink_start.elapsed().as_millis();
-        ms < interval || (ms / interval).is_multiple_of(2)
-    } else {
-        true
+    !style.is_blinking() || blink_phase_visible(blink_start, interval_ms)
+}
+
+/// Default and cursor colours of `leaf`, including OSC 10/11/12 changes.
+fn pane_colors(leaf: &PaneLeaf) -> PaneColors {
+    let terminal = &leaf.terminal;
+    PaneColors {
+        fg: terminal.default_fg,
+        bg: terminal.default_bg,
+        cursor: terminal.cursor_color().unwrap_or(terminal.default_fg),
     }
 }
 
@@ -641,6 +855,7 @@ pub(in super) fn should_show_cursor(
 fn cursor_should_draw(params: &FrameParams<'_>, leaf: &PaneLeaf, is_focused: bool) -> bool {
     !params.suppress_cursor
         && leaf.scroll_offset == 0
+        && leaf.vi_mode.is_none()
         && leaf.terminal.cursor_visible
         && is_focused
         && should_show_cursor(
@@ -670,6 +885,7 @@ fn leaf_scrollbar_state(leaf: &PaneLeaf) -> Option<ScrollbarState> {
             grid_rows: leaf.terminal.screen.viewport_rows(),
             opacity,
             hover,
+            command_ticks: status_ticks(&leaf.terminal.screen),
         })
     } else {
         None
@@ -48,12 +48,17 @@ impl FerrumWindow {
 
         // Update cursor blink interval.
         self.cursor_blink_interval_ms = config.terminal.cursor_blink_interval_ms;
+        self.editor_command = config.links.editor_command.clone();
+        self.bell = config.bell;
 
-        // Update terminal max scrollback and security config.
+        // Update terminal max scrollback, tab width, cell metrics and security config.
         let sec = config.security.to_runtime();
+        let (cell_w, cell_h) = (self.backend.cell_width(), self.backend.cell_height());
         for tab in &mut self.tabs {
             tab.pane_tree.for_each_leaf_mut(&mut |leaf| {
                 leaf.terminal.max_scrollback = config.terminal.max_scrollback;
+                leaf.terminal.set_tab_width(config.terminal.tab_width);
+                leaf.terminal.set_cell_pixel_size(cell_w, cell_h);
                 leaf.terminal.security_config = sec;
                 leaf.security.config = sec;
             });
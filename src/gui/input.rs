@@ -1,3 +1,4 @@
+use crate::core::KeyboardFlags;
 use crate::gui::*;
 
 /// X10 mouse protocol base offset for button and coordinate encoding.
@@ -8,6 +9,10 @@ const X10_COORD_OFFSET: u8 = 33;
 const X10_BUTTON_RELEASE: u8 = 3;
 /// Maximum coordinate encodable in legacy X10 single-byte format after applying offset.
 const X10_MAX_ENCODED_POS: usize = (u8::MAX - X10_COORD_OFFSET) as usize;
+/// Maximum coordinate encodable as a two-byte UTF-8 character (?1005).
+const UTF8_MAX_ENCODED_POS: usize = 0x7ff - X10_COORD_OFFSET as usize;
+/// Modifier bits of a mouse button code: Shift 4, Meta 8, Control 16.
+const MOUSE_MODIFIER_MASK: u8 = 4 | 8 | 16;
 
 fn csi_modifier_param(modifiers: ModifiersState) -> Option<u8> {
     let mut param = 1;
@@ -96,28 +101,87 @@ fn encode_ss3_key(final_byte: char, modifier_param: Option<u8>) -> Vec<u8> {
     }
 }
 
-/// Encodes mouse events as SGR (?1006) or legacy X10 bytes.
+/// xterm button code for a mouse button: 0-2 for left, middle and right,
+/// 128-131 for buttons 8-11 (back, forward and the two after them).
+/// Wheel buttons 4-7 are reported as 64-67 by the wheel handler.
+pub(super) fn mouse_button_code(button: winit::event::MouseButton) -> Option<u8> {
+    use winit::event::MouseButton;
+    match button {
+        MouseButton::Left => Some(0),
+        MouseButton::Middle => Some(1),
+        MouseButton::Right => Some(2),
+        MouseButton::Back => Some(128),
+        MouseButton::Forward => Some(129),
+        MouseButton::Other(n @ 10..=11) => Some(128 + (n - 8) as u8),
+        MouseButton::Other(_) => None,
+    }
+}
+
+/// Modifier bits added to a mouse button code: Shift 4, Meta (Alt) 8, Control 16.
+pub(super) fn mouse_modifier_bits(modifiers: ModifiersState) -> u8 {
+    let mut bits = 0;
+    if modifiers.shift_key() {
+        bits |= 4;
+    }
+    if modifiers.alt_key() {
+        bits |= 8;
+    }
+    if modifiers.control_key() {
+        bits |= 16;
+    }
+    bits
+}
+
+/// Appends `value` as a UTF-8 character, as the ?1005 encoding does.
+fn push_utf8_mouse_value(bytes: &mut Vec<u8>, value: u32) {
+    let c = char::from_u32(value).unwrap_or(' ');
+    let mut buf = [0u8; 4];
+    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+}
+
+/// Encodes a mouse event in `encoding`.
+///
+/// `button` is the xterm button code including modifier and motion bits.
+/// `col` and `row` are 0-based; with SGR-Pixels they are pixel offsets
+/// inside the pane instead of cells.
 pub(super) fn encode_mouse_event(
     button: u8,
     col: usize,
     row: usize,
     pressed: bool,
-    sgr: bool,
+    encoding: MouseEncoding,
 ) -> Vec<u8> {
-    if sgr {
-        // SGR: \x1b[<button;col;rowM (press) / ...m (release)
-        let suffix = if pressed { 'M' } else { 'm' };
-        format!("\x1b[<{};{};{}{}", button, col + 1, row + 1, suffix).into_bytes()
-    } else {
-        // Legacy X10: \x1b[M{cb}{cx}{cy} with single-byte coordinates.
-        let cb = if pressed {
-            button + X10_MOUSE_BASE_OFFSET
-        } else {
-            X10_BUTTON_RELEASE + X10_MOUSE_BASE_OFFSET
-        };
-        let cx = (col.min(X10_MAX_ENCODED_POS) as u8).saturating_add(X10_COORD_OFFSET);
-        let cy = (row.min(X10_MAX_ENCODED_POS) as u8).saturating_add(X10_COORD_OFFSET);
-        vec![0x1b, b'[', b'M', cb, cx, cy]
+    // Only SGR tells which button was released; the others send button 3.
+    let release_code = X10_BUTTON_RELEASE | (button & MOUSE_MODIFIER_MASK);
+    let code = if pressed { button } else { release_code };
+    match encoding {
+        MouseEncoding::Sgr | MouseEncoding::SgrPixels => {
+            // SGR: \x1b[<button;col;rowM (press) / ...m (release)
+            let suffix = if pressed { 'M' } else { 'm' };
+            format!("\x1b[<{};{};{}{}", button, col + 1, row + 1, suffix).into_bytes()
+        }
+        MouseEncoding::Urxvt => {
+            // urxvt: \x1b[{cb};{col};{row}M with decimal values.
+            let cb = u32::from(code) + u32::from(X10_MOUSE_BASE_OFFSET);
+            format!("\x1b[{};{};{}M", cb, col + 1, row + 1).into_bytes()
+        }
+        MouseEncoding::Utf8 => {
+            // Like X10, but values of 128 and above are UTF-8 encoded.
+            let mut bytes = vec![0x1b, b'[', b'M'];
+            push_utf8_mouse_value(&mut bytes, u32::from(code) + u32::from(X10_MOUSE_BASE_OFFSET));
+            for pos in [col, row] {
+                let value = pos.min(UTF8_MAX_ENCODED_POS) as u32 + u32::from(X10_COORD_OFFSET);
+                push_utf8_mouse_value(&mut bytes, value);
+            }
+            bytes
+        }
+        MouseEncoding::Default => {
+            // Legacy X10: \x1b[M{cb}{cx}{cy} with single-byte coordinates.
+            let cb = code.saturating_add(X10_MOUSE_BASE_OFFSET);
+            let cx = (col.min(X10_MAX_ENCODED_POS) as u8).saturating_add(X10_COORD_OFFSET);
+            let cy = (row.min(X10_MAX_ENCODED_POS) as u8).saturating_add(X10_COORD_OFFSET);
+            vec![0x1b, b'[', b'M', cb, cx, cy]
+        }
     }
 }
 
@@ -246,6 +310,470 @@ pub(super) fn key_to_bytes_ex(
     }
 }
 
+/// Key event type reported by the Kitty keyboard protocol.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum KeyEventKind {
+    Press,
+    Repeat,
+    Release,
+}
+
+impl KeyEventKind {
+    fn code(self) -> u8 {
+        match self {
+            Self::Press => 1,
+            Self::Repeat => 2,
+            Self::Release => 3,
+        }
+    }
+}
+
+/// Key event data consumed by the Kitty keyboard protocol encoder.
+pub(super) struct KittyKeyEvent<'a> {
+    /// Logical key with modifiers applied (e.g. `"A"` for Shift+a).
+    pub key: &'a Key,
+    /// Logical key with modifiers ignored (e.g. `"a"` for Shift+a).
+    pub unmodified_key: &'a Key,
+    pub physical_key: PhysicalKey,
+    /// Text the key produces, if any.
+    pub text: Option<&'a str>,
+    pub kind: KeyEventKind,
+    pub modifiers: ModifiersState,
+}
+
+/// How a functional key is spelled in the Kitty protocol.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum KittyKey {
+    /// `CSI 1 ; mods {letter}` (arrows, Home/End, F1/F2/F4).
+    Letter(char),
+    /// `CSI {number} ; mods ~`.
+    Tilde(u32),
+    /// `CSI {code} ; mods u`.
+    Code(u32),
+}
+
+/// Kitty key codes for keys that have no Unicode code point.
+mod kitty_code {
+    pub const ESCAPE: u32 = 27;
+    pub const ENTER: u32 = 13;
+    pub const TAB: u32 = 9;
+    pub const BACKSPACE: u32 = 127;
+    pub const CAPS_LOCK: u32 = 57358;
+    pub const SCROLL_LOCK: u32 = 57359;
+    pub const NUM_LOCK: u32 = 57360;
+    pub const PRINT_SCREEN: u32 = 57361;
+    pub const PAUSE: u32 = 57362;
+    pub const MENU: u32 = 57363;
+    pub const F13: u32 = 57376;
+    pub const KP_0: u32 = 57399;
+    pub const KP_DECIMAL: u32 = 57409;
+    pub const KP_DIVIDE: u32 = 57410;
+    pub const KP_MULTIPLY: u32 = 57411;
+    pub const KP_SUBTRACT: u32 = 57412;
+    pub const KP_ADD: u32 = 57413;
+    pub const KP_ENTER: u32 = 57414;
+    pub const KP_EQUAL: u32 = 57415;
+    pub const MEDIA_PLAY: u32 = 57428;
+    pub const MEDIA_PAUSE: u32 = 57429;
+    pub const MEDIA_PLAY_PAUSE: u32 = 57430;
+    pub const MEDIA_STOP: u32 = 57432;
+    pub const MEDIA_FAST_FORWARD: u32 = 57433;
+    pub const MEDIA_REWIND: u32 = 57434;
+    pub const MEDIA_TRACK_NEXT: u32 = 57435;
+    pub const MEDIA_TRACK_PREVIOUS: u32 = 57436;
+    pub const MEDIA_RECORD: u32 = 57437;
+    pub const VOLUME_DOWN: u32 = 57438;
+    pub const VOLUME_UP: u32 = 57439;
+    pub const VOLUME_MUTE: u32 = 57440;
+    pub const LEFT_SHIFT: u32 = 57441;
+    pub const LEFT_CONTROL: u32 = 57442;
+    pub const LEFT_ALT: u32 = 57443;
+    pub const LEFT_SUPER: u32 = 57444;
+    pub const RIGHT_SHIFT: u32 = 57447;
+    pub const RIGHT_CONTROL: u32 = 57448;
+    pub const RIGHT_ALT: u32 = 57449;
+    pub const RIGHT_SUPER: u32 = 57450;
+    pub const ISO_LEVEL3_SHIFT: u32 = 57453;
+}
+
+const KITTY_MOD_SHIFT: u8 = 1;
+const KITTY_MOD_ALT: u8 = 2;
+const KITTY_MOD_CTRL: u8 = 4;
+const KITTY_MOD_SUPER: u8 = 8;
+
+fn kitty_modifier_bits(modifiers: ModifiersState) -> u8 {
+    let mut bits = 0;
+    if modifiers.shift_key() {
+        bits |= KITTY_MOD_SHIFT;
+    }
+    if modifiers.alt_key() {
+        bits |= KITTY_MOD_ALT;
+    }
+    if modifiers.control_key() {
+        bits |= KITTY_MOD_CTRL;
+    }
+    if modifiers.super_key() {
+        bits |= KITTY_MOD_SUPER;
+    }
+    bits
+}
+
+/// Modifier keys: their key code and the modifier bit they control.
+fn kitty_modifier_key(physical_key: PhysicalKey) -> Option<(u32, u8)> {
+    let PhysicalKey::Code(code) = physical_key else {
+        return None;
+    };
+    match code {
+        KeyCode::ShiftLeft => Some((kitty_code::LEFT_SHIFT, KITTY_MOD_SHIFT)),
+        KeyCode::ShiftRight => Some((kitty_code::RIGHT_SHIFT, KITTY_MOD_SHIFT)),
+        KeyCode::ControlLeft => Some((kitty_code::LEFT_CONTROL, KITTY_MOD_CTRL)),
+        KeyCode::ControlRight => Some((kitty_code::RIGHT_CONTROL, KITTY_MOD_CTRL)),
+        KeyCode::AltLeft => Some((kitty_code::LEFT_ALT, KITTY_MOD_ALT)),
+        KeyCode::AltRight => Some((kitty_code::RIGHT_ALT, KITTY_MOD_ALT)),
+        KeyCode::SuperLeft => Some((kitty_code::LEFT_SUPER, KITTY_MOD_SUPER)),
+        KeyCode::SuperRight => Some((kitty_code::RIGHT_SUPER, KITTY_MOD_SUPER)),
+        _ => None,
+    }
+}
+
+/// SS3 sequence sent by an unmodified keypad key in application keypad
+/// mode (DECKPAM); `None` for other keys.
+pub(super) fn application_keypad_bytes(physical_key: PhysicalKey) -> Option<Vec<u8>> {
+    let PhysicalKey::Code(code) = physical_key else {
+        return None;
+    };
+    let final_byte = match code {
+        KeyCode::Numpad0 => b'p',
+        KeyCode::Numpad1 => b'q',
+        KeyCode::Numpad2 => b'r',
+        KeyCode::Numpad3 => b's',
+        KeyCode::Numpad4 => b't',
+        KeyCode::Numpad5 => b'u',
+        KeyCode::Numpad6 => b'v',
+        KeyCode::Numpad7 => b'w',
+        KeyCode::Numpad8 => b'x',
+        KeyCode::Numpad9 => b'y',
+        KeyCode::NumpadMultiply => b'j',
+        KeyCode::NumpadAdd => b'k',
+        KeyCode::NumpadComma => b'l',
+        KeyCode::NumpadSubtract => b'm',
+        KeyCode::NumpadDecimal => b'n',
+        KeyCode::NumpadDivide => b'o',
+        KeyCode::NumpadEnter => b'M',
+        KeyCode::NumpadEqual => b'X',
+        _ => return None,
+    };
+    Some(vec![0x1b, b'O', final_byte])
+}
+
+fn kitty_keypad_code(physical_key: PhysicalKey) -> Option<u32> {
+    let PhysicalKey::Code(code) = physical_key else {
+        return None;
+    };
+    let digit = match code {
+        KeyCode::Numpad0 => 0,
+        KeyCode::Numpad1 => 1,
+        KeyCode::Numpad2 => 2,
+        KeyCode::Numpad3 => 3,
+        KeyCode::Numpad4 => 4,
+        KeyCode::Numpad5 => 5,
+        KeyCode::Numpad6 => 6,
+        KeyCode::Numpad7 => 7,
+        KeyCode::Numpad8 => 8,
+        KeyCode::Numpad9 => 9,
+        KeyCode::NumpadDecimal => return Some(kitty_code::KP_DECIMAL),
+        KeyCode::NumpadDivide => return Some(kitty_code::KP_DIVIDE),
+        KeyCode::NumpadMultiply => return Some(kitty_code::KP_MULTIPLY),
+        KeyCode::NumpadSubtract => return Some(kitty_code::KP_SUBTRACT),
+        KeyCode::NumpadAdd => return Some(kitty_code::KP_ADD),
+        KeyCode::NumpadEnter => return Some(kitty_code::KP_ENTER),
+        KeyCode::NumpadEqual => return Some(kitty_code::KP_EQUAL),
+        _ => return None,
+    };
+    Some(kitty_code::KP_0 + digit)
+}
+
+fn kitty_functional_key(named: NamedKey) -> Option<KittyKey> {
+    let key = match named {
+        NamedKey::Escape => KittyKey::Code(kitty_code::ESCAPE),
+        NamedKey::Enter => KittyKey::Code(kitty_code::ENTER),
+        NamedKey::Tab => KittyKey::Code(kitty_code::TAB),
+        NamedKey::Backspace => KittyKey::Code(kitty_code::BACKSPACE),
+        NamedKey::Insert => KittyKey::Tilde(2),
+        NamedKey::Delete => KittyKey::Tilde(3),
+        NamedKey::PageUp => KittyKey::Tilde(5),
+        NamedKey::PageDown => KittyKey::Tilde(6),
+        NamedKey::ArrowUp => KittyKey::Letter('A'),
+        NamedKey::ArrowDown => KittyKey::Letter('B'),
+        NamedKey::ArrowRight => KittyKey::Letter('C'),
+        NamedKey::ArrowLeft => KittyKey::Letter('D'),
+        NamedKey::Home => KittyKey::Letter('H'),
+        NamedKey::End => KittyKey::Letter('F'),
+        NamedKey::F1 => KittyKey::Letter('P'),
+        NamedKey::F2 => KittyKey::Letter('Q'),
+        // F3 uses the tilde form: `CSI 1 ; mods R` collides with cursor position reports.
+        NamedKey::F3 => KittyKey::Tilde(13),
+        NamedKey::F4 => KittyKey::Letter('S'),
+        NamedKey::F5 => KittyKey::Tilde(15),
+        NamedKey::F6 => KittyKey::Tilde(17),
+        NamedKey::F7 => KittyKey::Tilde(18),
+        NamedKey::F8 => KittyKey::Tilde(19),
+        NamedKey::F9 => KittyKey::Tilde(20),
+        NamedKey::F10 => KittyKey::Tilde(21),
+        NamedKey::F11 => KittyKey::Tilde(23),
+        NamedKey::F12 => KittyKey::Tilde(24),
+        NamedKey::F13 => KittyKey::Code(kitty_code::F13),
+        NamedKey::F14 => KittyKey::Code(kitty_code::F13 + 1),
+        NamedKey::F15 => KittyKey::Code(kitty_code::F13 + 2),
+        NamedKey::F16 => KittyKey::Code(kitty_code::F13 + 3),
+        NamedKey::F17 => KittyKey::Code(kitty_code::F13 + 4),
+        NamedKey::F18 => KittyKey::Code(kitty_code::F13 + 5),
+        NamedKey::F19 => KittyKey::Code(kitty_code::F13 + 6),
+        NamedKey::F20 => KittyKey::Code(kitty_code::F13 + 7),
+        NamedKey::F21 => KittyKey::Code(kitty_code::F13 + 8),
+        NamedKey::F22 => KittyKey::Code(kitty_code::F13 + 9),
+        NamedKey::F23 => KittyKey::Code(kitty_code::F13 + 10),
+        NamedKey::F24 => KittyKey::Code(kitty_code::F13 + 11),
+        NamedKey::CapsLock => KittyKey::Code(kitty_code::CAPS_LOCK),
+        NamedKey::ScrollLock => KittyKey::Code(kitty_code::SCROLL_LOCK),
+        NamedKey::NumLock => KittyKey::Code(kitty_code::NUM_LOCK),
+        NamedKey::PrintScreen => KittyKey::Code(kitty_code::PRINT_SCREEN),
+        NamedKey::Pause => KittyKey::Code(kitty_code::PAUSE),
+        NamedKey::ContextMenu => KittyKey::Code(kitty_code::MENU),
+        NamedKey::MediaPlay => KittyKey::Code(kitty_code::MEDIA_PLAY),
+        NamedKey::MediaPause => KittyKey::Code(kitty_code::MEDIA_PAUSE),
+        NamedKey::MediaPlayPause => KittyKey::Code(kitty_code::MEDIA_PLAY_PAUSE),
+        NamedKey::MediaStop => KittyKey::Code(kitty_code::MEDIA_STOP),
+        NamedKey::MediaFastForward => KittyKey::Code(kitty_code::MEDIA_FAST_FORWARD),
+        NamedKey::MediaRewind => KittyKey::Code(kitty_code::MEDIA_REWIND),
+        NamedKey::MediaTrackNext => KittyKey::Code(kitty_code::MEDIA_TRACK_NEXT),
+        NamedKey::MediaTrackPrevious => KittyKey::Code(kitty_code::MEDIA_TRACK_PREVIOUS),
+        NamedKey::MediaRecord => KittyKey::Code(kitty_code::MEDIA_RECORD),
+        NamedKey::AudioVolumeDown => KittyKey::Code(kitty_code::VOLUME_DOWN),
+        NamedKey::AudioVolumeUp => KittyKey::Code(kitty_code::VOLUME_UP),
+        NamedKey::AudioVolumeMute => KittyKey::Code(kitty_code::VOLUME_MUTE),
+        NamedKey::AltGraph => KittyKey::Code(kitty_code::ISO_LEVEL3_SHIFT),
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Character a key produces on a US layout, used for the base-layout alternate key.
+fn us_layout_char(physical_key: PhysicalKey) -> Option<char> {
+    let PhysicalKey::Code(code) = physical_key else {
+        return None;
+    };
+    let ch = match code {
+        KeyCode::KeyA => 'a',
+        KeyCode::KeyB => 'b',
+        KeyCode::KeyC => 'c',
+        KeyCode::KeyD => 'd',
+        KeyCode::KeyE => 'e',
+        KeyCode::KeyF => 'f',
+        KeyCode::KeyG => 'g',
+        KeyCode::KeyH => 'h',
+        KeyCode::KeyI => 'i',
+        KeyCode::KeyJ => 'j',
+        KeyCode::KeyK => 'k',
+        KeyCode::KeyL => 'l',
+        KeyCode::KeyM => 'm',
+        KeyCode::KeyN => 'n',
+        KeyCode::KeyO => 'o',
+        KeyCode::KeyP => 'p',
+        KeyCode::KeyQ => 'q',
+        KeyCode::KeyR => 'r',
+        KeyCode::KeyS => 's',
+        KeyCode::KeyT => 't',
+        KeyCode::KeyU => 'u',
+        KeyCode::KeyV => 'v',
+        KeyCode::KeyW => 'w',
+        KeyCode::KeyX => 'x',
+        KeyCode::KeyY => 'y',
+        KeyCode::KeyZ => 'z',
+        KeyCode::Digit0 => '0',
+        KeyCode::Digit1 => '1',
+        KeyCode::Digit2 => '2',
+        KeyCode::Digit3 => '3',
+        KeyCode::Digit4 => '4',
+        KeyCode::Digit5 => '5',
+        KeyCode::Digit6 => '6',
+        KeyCode::Digit7 => '7',
+        KeyCode::Digit8 => '8',
+        KeyCode::Digit9 => '9',
+        KeyCode::Minus => '-',
+        KeyCode::Equal => '=',
+        KeyCode::BracketLeft => '[',
+        KeyCode::BracketRight => ']',
+        KeyCode::Backslash => '\\',
+        KeyCode::Semicolon => ';',
+        KeyCode::Quote => '\'',
+        KeyCode::Backquote => '`',
+        KeyCode::Comma => ',',
+        KeyCode::Period => '.',
+        KeyCode::Slash => '/',
+        KeyCode::Space => ' ',
+        _ => return None,
+    };
+    Some(ch)
+}
+
+fn key_char(key: &Key) -> Option<char> {
+    match key {
+        Key::Character(c) => {
+            let mut chars = c.chars();
+            let ch = chars.next()?;
+            chars.next().is_none().then_some(ch)
+        }
+        Key::Named(NamedKey::Space) => Some(' '),
+        _ => None,
+    }
+}
+
+/// Serializes `CSI key ; mods:event ; text final`, omitting trailing defaults.
+fn kitty_csi(
+    key: &str,
+    modifiers: u8,
+    kind: KeyEventKind,
+    text: &str,
+    final_byte: char,
+) -> Vec<u8> {
+    let mut out = format!("\x1b[{key}");
+    if modifiers != 0 || kind != KeyEventKind::Press || !text.is_empty() {
+        out.push_str(&format!(";{}", modifiers + 1));
+        if kind != KeyEventKind::Press {
+            out.push_str(&format!(":{}", kind.code()));
+        }
+    }
+    if !text.is_empty() {
+        out.push(';');
+        out.push_str(text);
+    }
+    out.push(final_byte);
+    out.into_bytes()
+}
+
+fn encode_kitty_functional(key: KittyKey, modifiers: u8, kind: KeyEventKind) -> Vec<u8> {
+    match key {
+        KittyKey::Letter(final_byte) => {
+            let number = if modifiers == 0 && kind == KeyEventKind::Press {
+                ""
+            } else {
+                "1"
+            };
+            kitty_csi(number, modifiers, kind, "", final_byte)
+        }
+        KittyKey::Tilde(number) => kitty_csi(&number.to_string(), modifiers, kind, "", '~'),
+        KittyKey::Code(code) => kitty_csi(&code.to_string(), modifiers, kind, "", 'u'),
+    }
+}
+
+/// Encodes a key event for the Kitty keyboard protocol under `flags`.
+///
+/// Returns `None` for events the active flags do not report (e.g. releases
+/// without `REPORT_EVENT_TYPES`, bare modifiers without `REPORT_ALL_KEYS`).
+pub(super) fn encode_kitty_key(event: &KittyKeyEvent<'_>, flags: KeyboardFlags) -> Option<Vec<u8>> {
+    let report_all = flags.contains(KeyboardFlags::REPORT_ALL_KEYS);
+    let disambiguate = report_all || flags.contains(KeyboardFlags::DISAMBIGUATE);
+    let kind = match event.kind {
+        KeyEventKind::Press => KeyEventKind::Press,
+        _ if !flags.contains(KeyboardFlags::REPORT_EVENT_TYPES) => {
+            if event.kind == KeyEventKind::Release {
+                return None;
+            }
+            KeyEventKind::Press
+        }
+        kind => kind,
+    };
+    let mut modifiers = kitty_modifier_bits(event.modifiers);
+
+    if let Some((code, bit)) = kitty_modifier_key(event.physical_key) {
+        if !report_all {
+            return None;
+        }
+        // The modifier's own bit reflects the state after this event.
+        if kind == KeyEventKind::Release {
+            modifiers &= !bit;
+        } else {
+            modifiers |= bit;
+        }
+        return Some(kitty_csi(&code.to_string(), modifiers, kind, "", 'u'));
+    }
+
+    if let Some(code) = kitty_keypad_code(event.physical_key)
+        && (report_all || modifiers & !KITTY_MOD_SHIFT != 0 || code == kitty_code::KP_ENTER)
+    {
+        return Some(kitty_csi(&code.to_string(), modifiers, kind, "", 'u'));
+    }
+
+    if let Key::Named(named) = event.key
+        && let Some(functional) = kitty_functional_key(*named)
+    {
+        let legacy = match functional {
+            KittyKey::Code(kitty_code::ESCAPE) => !disambiguate,
+            KittyKey::Code(kitty_code::ENTER | kitty_code::TAB | kitty_code::BACKSPACE) => true,
+            _ => false,
+        };
+        // Enter, Tab and Backspace keep their legacy bytes unless modified
+        // or all keys are reported; their releases are not reported then.
+        if legacy && !report_all && (modifiers == 0 || !disambiguate) {
+            if kind == KeyEventKind::Release {
+                return None;
+            }
+            return key_to_bytes_ex(event.key, event.modifiers, false, 0);
+        }
+        return Some(encode_kitty_functional(functional, modifiers, kind));
+    }
+
+    let ch = key_char(event.key)?;
+    // Plain and shifted text keys stay text unless all keys are reported;
+    // modified ones too without the disambiguate flag.
+    if !report_all && (modifiers & !KITTY_MOD_SHIFT == 0 || !disambiguate) {
+        if kind == KeyEventKind::Release {
+            return None;
+        }
+        if modifiers & !KITTY_MOD_SHIFT != 0 {
+            return key_to_bytes_ex(event.key, event.modifiers, false, 0);
+        }
+        let text = event.text.map_or_else(|| ch.to_string(), str::to_string);
+        return Some(text.into_bytes());
+    }
+
+    let base = key_char(event.unmodified_key).unwrap_or(ch);
+    let base = base.to_lowercase().next().unwrap_or(base);
+    let mut key_field = (base as u32).to_string();
+    if flags.contains(KeyboardFlags::REPORT_ALTERNATE_KEYS) {
+        let shifted = (modifiers & KITTY_MOD_SHIFT != 0 && ch != base).then_some(ch);
+        let layout = us_layout_char(event.physical_key).filter(|&c| c != base);
+        if shifted.is_some() || layout.is_some() {
+            key_field.push(':');
+            if let Some(shifted) = shifted {
+                key_field.push_str(&(shifted as u32).to_string());
+            }
+            if let Some(layout) = layout {
+                key_field.push(':');
+                key_field.push_str(&(layout as u32).to_string());
+            }
+        }
+    }
+
+    let text = if report_all
+        && flags.contains(KeyboardFlags::REPORT_TEXT)
+        && kind != KeyEventKind::Release
+    {
+        event
+            .text
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| !c.is_control())
+            .map(|c| (c as u32).to_string())
+            .collect::<Vec<_>>()
+            .join(":")
+    } else {
+        String::new()
+    };
+    Some(kitty_csi(&key_field, modifiers, kind, &text, 'u'))
+}
+
 /// Converts logical key input into PTY byte sequences (modifyOtherKeys level 0).
 ///
 /// Used only in tests; production callers use `key_to_bytes_ex` directly.
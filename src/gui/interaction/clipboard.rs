@@ -1,12 +1,17 @@
 use crate::core::terminal::Terminal;
-use crate::core::{PageCoord, Selection};
+use crate::core::{ClipboardTarget, PageCoord, SecurityEventKind, Selection, SelectionKind};
+use crate::gui::pane::PaneId;
+use crate::gui::state::{ClipboardPrompt, TabState};
 use crate::gui::*;
 
 const BRACKETED_PASTE_START: &[u8] = b"\x1b[200~";
 const BRACKETED_PASTE_END: &[u8] = b"\x1b[201~";
 
 impl FerrumWindow {
-    fn selected_text_from_terminal(terminal: &Terminal, selection: Selection) -> String {
+    pub(in crate::gui) fn selected_text_from_terminal(
+        terminal: &Terminal,
+        selection: Selection,
+    ) -> String {
         let cols = terminal.screen.cols();
         if cols == 0 {
             return String::new();
@@ -28,13 +33,20 @@ impl FerrumWindow {
                 abs_row: selection.end.abs_row.min(max_row),
                 col: selection.end.col.min(max_col),
             },
+            kind: selection.kind,
         };
         let (start, end) = clamped.normalized();
+        let block = clamped.block_columns();
 
         let mut text = String::new();
         for row in start.abs_row..=end.abs_row {
-            let col_start = if row == start.abs_row { start.col } else { 0 };
-            let col_end = if row == end.abs_row { end.col } else { max_col };
+            let (col_start, col_end) = match clamped.kind {
+                SelectionKind::Block => block,
+                SelectionKind::Stream => (
+                    if row == start.abs_row { start.col } else { 0 },
+                    if row == end.abs_row { end.col } else { max_col },
+                ),
+            };
 
             for col in col_start..=col_end {
                 let ch = if row < scrollback_len {
@@ -85,6 +97,13 @@ impl FerrumWindow {
         }
     }
 
+    /// Selects and copies the output of the most recent finished command.
+    pub(in crate::gui) fn copy_last_command_output(&mut self) {
+        if self.select_last_command_output() {
+            self.copy_selection();
+        }
+    }
+
     pub(in crate::gui) fn paste_clipboard(&mut self) {
         let text = match self.clipboard.as_mut() {
             Some(cb) => match cb.get_text() {
@@ -122,6 +141,176 @@ impl FerrumWindow {
 
         self.write_pty_bytes(&bytes);
     }
+
+    /// Stores an OSC 52 write in `target`. Without a primary selection
+    /// (outside Linux) the clipboard is used instead.
+    pub(in crate::gui) fn write_clipboard_target(&mut self, target: ClipboardTarget, text: &str) {
+        let Some(clipboard) = self.clipboard.as_mut() else {
+            return;
+        };
+        let result = match target {
+            #[cfg(target_os = "linux")]
+            ClipboardTarget::Primary => {
+                use arboard::{LinuxClipboardKind, SetExtLinux};
+                clipboard
+                    .set()
+                    .clipboard(LinuxClipboardKind::Primary)
+                    .text(text)
+            }
+            _ => clipboard.set_text(text),
+        };
+        if let Err(e) = result {
+            eprintln!("OSC 52: failed to write to clipboard: {e}");
+        }
+    }
+
+    fn read_clipboard_target(&mut self, target: ClipboardTarget) -> Option<String> {
+        let clipboard = self.clipboard.as_mut()?;
+        let result = match target {
+            #[cfg(target_os = "linux")]
+            ClipboardTarget::Primary => {
+                use arboard::{GetExtLinux, LinuxClipboardKind};
+                clipboard
+                    .get()
+                    .clipboard(LinuxClipboardKind::Primary)
+                    .text()
+            }
+            _ => clipboard.get_text(),
+        };
+        result
+            .map_err(|e| eprintln!("OSC 52: failed to read from clipboard: {e}"))
+            .ok()
+    }
+
+    /// Handles the OSC 52 queries pending in `pane_id`. Reads from any pane
+    /// but the focused one are refused; otherwise the pane's remembered
+    /// answer is used, or the clipboard prompt asks the user.
+    pub(in crate::gui) fn request_clipboard_reads(&mut self, tab_id: u64, pane_id: PaneId) {
+        let focused = self
+            .tabs
+            .get(self.active_tab)
+            .is_some_and(|tab| tab.id == tab_id && tab.focused_pane == pane_id);
+        let Some(consent) = self
+            .tabs
+            .iter()
+            .find(|t| t.id == tab_id)
+            .and_then(|tab| tab.pane_tree.find_leaf(pane_id))
+            .map(|leaf| leaf.clipboard_read_consent)
+        else {
+            return;
+        };
+        match consent {
+            _ if !focused => self.answer_clipboard_reads(tab_id, pane_id, false),
+            Some(allowed) => self.answer_clipboard_reads(tab_id, pane_id, allowed),
+            None => {
+                let prompt = ClipboardPrompt { tab_id, pane_id };
+                // A prompt left open for another pane is refused: that pane
+                // has lost the focus since.
+                if let Some(stale) = self.clipboard_prompt.replace(prompt)
+                    && stale != prompt
+                {
+                    self.answer_clipboard_reads(stale.tab_id, stale.pane_id, false);
+                }
+                self.window.request_redraw();
+            }
+        }
+    }
+
+    /// Closes the clipboard prompt with the user's answer, remembering it
+    /// for the pane.
+    pub(in crate::gui) fn resolve_clipboard_prompt(&mut self, allowed: bool) {
+        let Some(prompt) = self.clipboard_prompt.take() else {
+            return;
+        };
+        if let Some(leaf) = self
+            .tabs
+            .iter_mut()
+            .find(|t| t.id == prompt.tab_id)
+            .and_then(|tab| tab.pane_tree.find_leaf_mut(prompt.pane_id))
+        {
+            leaf.clipboard_read_consent = Some(allowed);
+        }
+        self.answer_clipboard_reads(prompt.tab_id, prompt.pane_id, allowed);
+        self.window.request_redraw();
+    }
+
+    /// Answers every OSC 52 query pending in `pane_id`: with the clipboard
+    /// contents when `allowed`, and with an empty selection otherwise.
+    fn answer_clipboard_reads(&mut self, tab_id: u64, pane_id: PaneId, allowed: bool) {
+        let Some(targets) = self
+            .tabs
+            .iter()
+            .find(|t| t.id == tab_id)
+            .and_then(|tab| tab.pane_tree.find_leaf(pane_id))
+            .map(|leaf| {
+                leaf.terminal
+                    .pending_clipboard_reads()
+                    .map(|read| read.target)
+                    .collect::<Vec<_>>()
+            })
+        else {
+            return;
+        };
+        let texts: Vec<Option<String>> = targets
+            .into_iter()
+            .map(|target| {
+                allowed
+                    .then(|| self.read_clipboard_target(target))
+                    .flatten()
+            })
+            .collect();
+
+        let Some(leaf) = self
+            .tabs
+            .iter_mut()
+            .find(|t| t.id == tab_id)
+            .and_then(|tab| tab.pane_tree.find_leaf_mut(pane_id))
+        else {
+            return;
+        };
+        for text in texts {
+            if !allowed {
+                leaf.security.record(SecurityEventKind::ClipboardRead);
+            }
+            leaf.terminal.answer_clipboard_read(text.as_deref());
+        }
+        leaf.flush_responses();
+    }
+}
+
+/// Longest tab title shown in the clipboard prompt before it is cut short.
+const PROMPT_TITLE_MAX_CHARS: usize = 32;
+
+/// Label of the clipboard prompt, naming the tab and, in a split tab, the
+/// pane that asks. `None` once that pane is gone.
+pub(in crate::gui) fn clipboard_prompt_label(
+    tabs: &[TabState],
+    prompt: ClipboardPrompt,
+) -> Option<String> {
+    let t = crate::i18n::t();
+    let tab = tabs.iter().find(|tab| tab.id == prompt.tab_id)?;
+    tab.pane_tree.find_leaf(prompt.pane_id)?;
+    let mut title: String = tab.title.chars().take(PROMPT_TITLE_MAX_CHARS).collect();
+    if title.len() < tab.title.len() {
+        title.push('…');
+    }
+    let name = if tab.has_multiple_panes() {
+        let number = tab
+            .pane_tree
+            .leaf_ids()
+            .iter()
+            .position(|&id| id == prompt.pane_id)
+            .map_or(0, |index| index + 1);
+        // The number goes in first so braces in the title stay as they are.
+        let mut name = t.clipboard_read_prompt_pane.to_owned();
+        if let Some(at) = name.rfind("{}") {
+            name.replace_range(at..at + 2, &number.to_string());
+        }
+        name.replacen("{}", &title, 1)
+    } else {
+        title
+    };
+    Some(t.clipboard_read_prompt.replacen("{}", &name, 1))
 }
 
 #[cfg(test)]
@@ -143,6 +332,7 @@ mod tests {
         let viewport_selection = Selection {
             start: PageCoord { abs_row: scrollback_len, col: 0 },
             end: PageCoord { abs_row: scrollback_len, col: 4 },
+            kind: SelectionKind::Stream,
         };
         let viewport_text = FerrumWindow::selected_text_from_terminal(&terminal, viewport_selection);
         // Viewport first row is readable (non-empty content)
@@ -152,6 +342,7 @@ mod tests {
         let scrollback_selection = Selection {
             start: PageCoord { abs_row: 0, col: 0 },
             end: PageCoord { abs_row: 0, col: 4 },
+            kind: SelectionKind::Stream,
         };
         let scrollback_text =
             FerrumWindow::selected_text_from_terminal(&terminal, scrollback_selection);
@@ -160,4 +351,44 @@ mod tests {
         // Verify that scrollback and viewport content differ
         assert_ne!(viewport_text, scrollback_text);
     }
+
+    #[test]
+    fn selected_text_clips_block_selection_to_its_columns() {
+        let mut terminal = Terminal::new(3, 12);
+        terminal.process(b"PID  CMD\r\n1    init\r\n42   sshd");
+
+        let block = Selection {
+            start: PageCoord { abs_row: 2, col: 7 },
+            end: PageCoord { abs_row: 0, col: 5 },
+            kind: SelectionKind::Block,
+        };
+        let text = FerrumWindow::selected_text_from_terminal(&terminal, block);
+        assert_eq!(text, "CMD\nini\nssh");
+    }
+
+    #[test]
+    fn clipboard_prompt_names_the_tab_and_pane() {
+        use crate::gui::pane::{PaneNode, SplitDirection};
+
+        let t = crate::i18n::t();
+        let mut tab = TabState {
+            id: 7,
+            title: "{build}".to_owned(),
+            pane_tree: PaneNode::new_leaf(1),
+            focused_pane: 1,
+            next_pane_id: 2,
+            is_renamed: false,
+            has_bell: false,
+        };
+        let prompt = |pane_id| ClipboardPrompt { tab_id: 7, pane_id };
+        assert_eq!(
+            clipboard_prompt_label(std::slice::from_ref(&tab), prompt(1)),
+            Some(t.clipboard_read_prompt.replacen("{}", "{build}", 1))
+        );
+
+        tab.pane_tree.split(1, SplitDirection::Vertical, 2);
+        let label = clipboard_prompt_label(std::slice::from_ref(&tab), prompt(2)).unwrap();
+        assert!(label.contains("{build}") && label.contains('2'));
+        assert_eq!(clipboard_prompt_label(&[tab], prompt(3)), None);
+    }
 }
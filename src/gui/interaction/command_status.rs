@@ -0,0 +1,40 @@
+use crate::core::CommandStatus;
+use crate::gui::renderer::STATUS_MARKER_WIDTH;
+use crate::gui::renderer::shared::command_status_layout::visible_status_markers;
+use crate::gui::*;
+
+impl FerrumWindow {
+    /// Status of the finished command whose gutter marker is under `(x, y)`.
+    fn command_status_at(&self, x: f64, y: f64) -> Option<CommandStatus> {
+        let tab = self.active_tab_ref()?;
+        let marker_width = self.backend.scaled_px(STATUS_MARKER_WIDTH);
+        let cell_height = self.backend.cell_height();
+        tab.pane_tree
+            .layout(self.terminal_content_rect(), pane::DIVIDER_WIDTH)
+            .into_iter()
+            .find_map(|(pane_id, _)| {
+                let leaf = tab.pane_tree.find_leaf(pane_id)?;
+                let rect = self.pane_content_rect(pane_id)?;
+                visible_status_markers(
+                    &leaf.terminal.screen,
+                    leaf.scroll_offset,
+                    rect,
+                    cell_height,
+                    marker_width,
+                )
+                .into_iter()
+                .find(|marker| marker.contains(x, y))
+                .map(|marker| marker.status)
+            })
+    }
+
+    /// Tracks the hovered command status marker, redrawing when its tooltip
+    /// has to appear or go away.
+    pub(in crate::gui) fn update_command_status_hover(&mut self, x: f64, y: f64) {
+        let hovered = self.command_status_at(x, y);
+        if hovered != self.hovered_command_status {
+            self.hovered_command_status = hovered;
+            self.window.request_redraw();
+        }
+    }
+}
@@ -1,8 +1,18 @@
 mod clipboard;
+#[cfg(not(target_os = "macos"))]
+mod command_status;
 mod cursor_move;
 mod geometry;
 mod mouse_reporting;
+mod prompt_nav;
+mod search;
 mod selection;
+mod vi_mode;
+
+pub(in crate::gui) use clipboard::clipboard_prompt_label;
+pub(in crate::gui) use prompt_nav::PromptJump;
+pub(in crate::gui) use search::SearchJump;
+pub(in crate::gui) use vi_mode::ViMotion;
 
 use crate::gui::FerrumWindow;
 
@@ -1,4 +1,5 @@
-use crate::gui::input::encode_mouse_event;
+use crate::gui::input::{encode_mouse_event, mouse_modifier_bits};
+use crate::gui::pane::PaneId;
 use crate::gui::*;
 
 impl FerrumWindow {
@@ -10,13 +11,64 @@ impl FerrumWindow {
         row: usize,
         pressed: bool,
     ) {
-        let sgr = self.active_leaf_ref().is_some_and(|l| l.terminal.sgr_mouse);
-        let bytes = encode_mouse_event(button, col, row, pressed, sgr);
+        let Some(pane_id) = self.active_tab_ref().map(|tab| tab.focused_pane) else {
+            return;
+        };
+        let Some(bytes) = self.encode_pane_mouse_event(pane_id, button, col, row, pressed) else {
+            return;
+        };
         if let Some(leaf) = self.active_leaf_mut() {
             leaf.write_pty(&bytes);
         }
     }
 
+    /// Encodes a report for `pane_id` in the pane's mouse mode and encoding,
+    /// adding the held modifiers to `button`.
+    ///
+    /// Returns `None` when the mode does not report the event (X10 mode
+    /// reports presses only).
+    pub(in crate::gui) fn encode_pane_mouse_event(
+        &self,
+        pane_id: PaneId,
+        button: u8,
+        col: usize,
+        row: usize,
+        pressed: bool,
+    ) -> Option<Vec<u8>> {
+        let leaf = self.active_tab_ref()?.pane_tree.find_leaf(pane_id)?;
+        let encoding = leaf.terminal.mouse_encoding;
+        let button = if leaf.terminal.mouse_mode == MouseMode::X10 {
+            if !pressed {
+                return None;
+            }
+            button
+        } else {
+            button | mouse_modifier_bits(self.modifiers)
+        };
+        let (x, y) = if encoding == MouseEncoding::SgrPixels {
+            self.pane_pixel_pos(pane_id)?
+        } else {
+            (col, row)
+        };
+        Some(encode_mouse_event(button, x, y, pressed, encoding))
+    }
+
+    /// Pointer position in pixels relative to the top-left of `pane_id`'s
+    /// grid, clamped to the cells the pane shows.
+    fn pane_pixel_pos(&self, pane_id: PaneId) -> Option<(usize, usize)> {
+        let leaf = self.active_tab_ref()?.pane_tree.find_leaf(pane_id)?;
+        let content = self.pane_content_rect(pane_id)?;
+        let width = leaf.terminal.screen.cols() as u32 * self.backend.cell_width();
+        let height = leaf.terminal.screen.viewport_rows() as u32 * self.backend.cell_height();
+        let x = (self.mouse_pos.0.max(0.0) as u32)
+            .saturating_sub(content.x)
+            .min(width.saturating_sub(1));
+        let y = (self.mouse_pos.1.max(0.0) as u32)
+            .saturating_sub(content.y)
+            .min(height.saturating_sub(1));
+        Some((x as usize, y as usize))
+    }
+
     /// Returns whether terminal mouse tracking is active (Shift forces local selection mode).
     pub(in crate::gui) fn is_mouse_reporting(&self) -> bool {
         let mode = self
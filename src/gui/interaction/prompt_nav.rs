@@ -0,0 +1,76 @@
+use crate::core::PageList;
+use crate::gui::*;
+
+/// Direction of a jump between OSC 133 prompts.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(in crate::gui) enum PromptJump {
+    Previous,
+    Next,
+}
+
+/// Returns the scroll offset that puts the previous/next prompt at the top of
+/// the viewport, or `None` when there is nowhere to go.
+fn prompt_jump_offset(screen: &PageList, scroll_offset: usize, jump: PromptJump) -> Option<usize> {
+    let scrollback_len = screen.scrollback_len();
+    let top = scrollback_len.saturating_sub(scroll_offset);
+    let target = match jump {
+        PromptJump::Previous => screen.prompt_before(top)?,
+        // Past the last prompt, fall back to the live screen.
+        PromptJump::Next => match screen.prompt_after(top) {
+            Some(abs_row) => abs_row,
+            None if scroll_offset > 0 => scrollback_len,
+            None => return None,
+        },
+    };
+    Some(scrollback_len.saturating_sub(target))
+}
+
+impl FerrumWindow {
+    /// Scrolls the focused pane so the previous/next shell prompt is at the top.
+    pub(in crate::gui) fn jump_to_prompt(&mut self, jump: PromptJump) {
+        let Some(leaf) = self.active_leaf_mut() else {
+            return;
+        };
+        if let Some(offset) = prompt_jump_offset(&leaf.terminal.screen, leaf.scroll_offset, jump) {
+            leaf.scroll_offset = offset;
+            self.window.request_redraw();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PromptJump, prompt_jump_offset};
+    use crate::core::terminal::Terminal;
+
+    /// 3-row terminal with prompts at absolute rows 0, 3 and 6 and 5 scrollback rows.
+    fn terminal_with_prompts() -> Terminal {
+        let mut term = Terminal::new(3, 10);
+        for i in 0..3 {
+            term.process(format!("\x1b]133;A\x07$ cmd{i}\r\nout\r\nout\r\n").as_bytes());
+        }
+        term
+    }
+
+    #[test]
+    fn previous_walks_up_through_prompts() {
+        let term = terminal_with_prompts();
+        let screen = &term.screen;
+        assert_eq!(screen.scrollback_len(), 7);
+        // Viewport top is abs row 7; prompt at 6 is the previous one.
+        assert_eq!(prompt_jump_offset(screen, 0, PromptJump::Previous), Some(1));
+        assert_eq!(prompt_jump_offset(screen, 1, PromptJump::Previous), Some(4));
+        assert_eq!(prompt_jump_offset(screen, 4, PromptJump::Previous), Some(7));
+        assert_eq!(prompt_jump_offset(screen, 7, PromptJump::Previous), None);
+    }
+
+    #[test]
+    fn next_walks_down_and_returns_to_live_screen() {
+        let term = terminal_with_prompts();
+        let screen = &term.screen;
+        assert_eq!(prompt_jump_offset(screen, 7, PromptJump::Next), Some(4));
+        assert_eq!(prompt_jump_offset(screen, 4, PromptJump::Next), Some(1));
+        assert_eq!(prompt_jump_offset(screen, 1, PromptJump::Next), Some(0));
+        assert_eq!(prompt_jump_offset(screen, 0, PromptJump::Next), None);
+    }
+}
@@ -0,0 +1,252 @@
+use crate::core::terminal::Terminal;
+use crate::core::{PageList, Regex, SearchMatches, search_screen};
+use crate::gui::pane::PaneLeaf;
+use crate::gui::state::SearchState;
+use crate::gui::*;
+
+/// Direction of a jump between search matches.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(in crate::gui) enum SearchJump {
+    /// Towards the top of the scrollback.
+    Older,
+    /// Towards the live screen.
+    Newer,
+}
+
+/// Replaces the matches of `search` with a fresh search of `terminal`.
+fn find_matches(search: &mut SearchState, terminal: &Terminal) {
+    search.matches = SearchMatches::default();
+    search.current = None;
+    search.invalid = false;
+    search.alt_screen = terminal.is_alt_screen();
+    if search.query.is_empty() {
+        return;
+    }
+    let regex = if search.regex {
+        match Regex::new(&search.query, search.case_sensitive) {
+            Ok(regex) => regex,
+            Err(_) => {
+                search.invalid = true;
+                return;
+            }
+        }
+    } else {
+        Regex::literal(&search.query, search.case_sensitive)
+    };
+    search.matches = search_screen(&terminal.screen, &regex);
+}
+
+/// The last match starting above `bottom` (exclusive), so a new search
+/// lands next to what the user is looking at; otherwise the first match.
+fn nearest_match(matches: &SearchMatches, screen: &PageList, bottom: usize) -> Option<usize> {
+    matches
+        .in_rows(screen, 0..bottom)
+        .filter(|(_, m)| m.start.abs_row < bottom)
+        .last()
+        .map(|(i, _)| i)
+        .or((!matches.is_empty()).then_some(0))
+}
+
+/// Index of the match after `current` in the `jump` direction, wrapping
+/// around at either end.
+fn step_index(current: Option<usize>, len: usize, jump: SearchJump) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    Some(match (current, jump) {
+        (None, _) => len - 1,
+        (Some(i), SearchJump::Older) => (i + len - 1) % len,
+        (Some(i), SearchJump::Newer) => (i + 1) % len,
+    })
+}
+
+impl SearchState {
+    /// Text shown after the query: "3 of 12", "No results" or the regex error.
+    pub(in crate::gui) fn status_text(&self) -> String {
+        let t = crate::i18n::t();
+        if self.invalid {
+            t.search_invalid_pattern.to_owned()
+        } else if self.query.is_empty() {
+            String::new()
+        } else if self.matches.is_empty() {
+            t.search_no_results.to_owned()
+        } else {
+            let position = self.current.map_or(0, |i| i + 1);
+            t.search_match_count
+                .replacen("{}", &position.to_string(), 1)
+                .replacen("{}", &self.matches.len().to_string(), 1)
+        }
+    }
+
+    /// Whether the status text reports a problem rather than a count.
+    pub(in crate::gui) fn status_is_error(&self) -> bool {
+        self.invalid || (!self.query.is_empty() && self.matches.is_empty())
+    }
+}
+
+impl PaneLeaf {
+    /// Absolute row just below the displayed rows.
+    fn view_bottom_abs(&self) -> usize {
+        let screen = &self.terminal.screen;
+        screen.scrollback_len().saturating_sub(self.scroll_offset) + screen.viewport_rows()
+    }
+
+    /// Searches again after the query or its options changed and scrolls
+    /// the match nearest the view into sight.
+    pub(in crate::gui) fn run_search(&mut self) {
+        let bottom = self.view_bottom_abs();
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        find_matches(search, &self.terminal);
+        search.current = nearest_match(&search.matches, &self.terminal.screen, bottom);
+        self.reveal_search_match();
+    }
+
+    /// Keeps matches in step with the buffer after output or a resize.
+    ///
+    /// Matches follow their rows as output scrolls, so usually only evicted
+    /// ones are dropped.  After a resize, a reflow or a switch between the
+    /// main and alternate screen none remain in place, and the query runs
+    /// again.
+    pub(in crate::gui) fn refresh_search(&mut self) {
+        let bottom = self.view_bottom_abs();
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let screen = &self.terminal.screen;
+        if search.alt_screen != self.terminal.is_alt_screen() || search.matches.is_stale(screen) {
+            find_matches(search, &self.terminal);
+            search.current = nearest_match(&search.matches, screen, bottom);
+            return;
+        }
+        let dropped = search.matches.prune(screen);
+        if dropped == 0 {
+            return;
+        }
+        // Keep the current index on the same match, or on the next one
+        // when the current match itself was evicted.
+        let current = search.current.unwrap_or(0);
+        search.current = (!search.matches.is_empty()).then(|| {
+            current
+                .saturating_sub(dropped)
+                .min(search.matches.len() - 1)
+        });
+    }
+
+    /// Moves to the next match in the `jump` direction.
+    pub(in crate::gui) fn jump_search(&mut self, jump: SearchJump) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        search.current = step_index(search.current, search.matches.len(), jump);
+        self.reveal_search_match();
+    }
+
+    /// Scrolls so the current match is displayed, centring it when it was
+    /// off screen.
+    fn reveal_search_match(&mut self) {
+        let Some(abs_row) = self
+            .search
+            .as_ref()
+            .and_then(|search| search.matches.get(search.current?, &self.terminal.screen))
+            .map(|m| m.start.abs_row)
+        else {
+            return;
+        };
+        let scrollback_len = self.terminal.screen.scrollback_len();
+        let rows = self.terminal.screen.viewport_rows();
+        let top = scrollback_len.saturating_sub(self.scroll_offset);
+        if (top..top + rows).contains(&abs_row) {
+            return;
+        }
+        let new_top = abs_row.saturating_sub(rows / 2).min(scrollback_len);
+        self.scroll_offset = scrollback_len - new_top;
+    }
+}
+
+impl FerrumWindow {
+    /// Opens the search bar on the focused pane (Ctrl/Cmd+F).
+    pub(in crate::gui) fn open_search(&mut self) {
+        if let Some(leaf) = self.active_leaf_mut() {
+            leaf.search.get_or_insert_with(SearchState::default);
+            self.window.request_redraw();
+        }
+    }
+
+    /// Closes the focused pane's search bar and drops its highlights.
+    pub(in crate::gui) fn close_search(&mut self) {
+        if let Some(leaf) = self.active_leaf_mut() {
+            leaf.search = None;
+            self.window.request_redraw();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terminal_with_lines(lines: &[&str]) -> Terminal {
+        let mut term = Terminal::new(3, 20);
+        term.process(lines.join("\r\n").as_bytes());
+        term
+    }
+
+    fn search_for(query: &str, regex: bool) -> SearchState {
+        SearchState {
+            query: query.to_owned(),
+            regex,
+            ..SearchState::default()
+        }
+    }
+
+    #[test]
+    fn new_search_starts_at_the_match_nearest_the_view() {
+        let term = terminal_with_lines(&["err 1", "ok", "err 2", "ok", "err 3", "ok"]);
+        let mut search = search_for("ERR", false);
+        find_matches(&mut search, &term);
+        assert_eq!(search.matches.len(), 3);
+        let screen = &term.screen;
+        // Viewport shows rows 3..6; the match on row 4 is the nearest.
+        assert_eq!(nearest_match(&search.matches, screen, 6), Some(2));
+        // Scrolled to the top: only the first match is above the bottom.
+        assert_eq!(nearest_match(&search.matches, screen, 3), Some(1));
+        assert_eq!(nearest_match(&search.matches, screen, 0), Some(0));
+        assert_eq!(nearest_match(&SearchMatches::default(), screen, 6), None);
+    }
+
+    #[test]
+    fn jumps_wrap_around() {
+        assert_eq!(step_index(Some(0), 3, SearchJump::Older), Some(2));
+        assert_eq!(step_index(Some(2), 3, SearchJump::Newer), Some(0));
+        assert_eq!(step_index(Some(1), 3, SearchJump::Older), Some(0));
+        assert_eq!(step_index(None, 3, SearchJump::Newer), Some(2));
+        assert_eq!(step_index(None, 0, SearchJump::Older), None);
+    }
+
+    #[test]
+    fn status_reports_position_and_errors() {
+        let term = terminal_with_lines(&["a1 a2", "a3"]);
+        let mut search = search_for(r"a\d", true);
+        find_matches(&mut search, &term);
+        search.current = Some(1);
+        let t = crate::i18n::t();
+        let expected = t
+            .search_match_count
+            .replacen("{}", "2", 1)
+            .replacen("{}", "3", 1);
+        assert_eq!(search.status_text(), expected);
+        assert!(!search.status_is_error());
+
+        let mut invalid = search_for("a(", true);
+        find_matches(&mut invalid, &term);
+        assert!(invalid.invalid && invalid.status_is_error());
+        assert_eq!(invalid.status_text(), t.search_invalid_pattern);
+
+        let mut missing = search_for("zzz", false);
+        find_matches(&mut missing, &term);
+        assert_eq!(missing.status_text(), t.search_no_results);
+        assert!(search_for("", false).status_text().is_empty());
+    }
+}
@@ -1,8 +1,9 @@
 use crate::core::PageCoord;
+use crate::gui::pane::PaneLeaf;
 use crate::gui::*;
 use std::cmp::Ordering;
 
-impl FerrumWindow {
+impl PaneLeaf {
     fn compare_pos(a: Position, b: Position) -> Ordering {
         match a.row.cmp(&b.row) {
             Ordering::Equal => a.col.cmp(&b.col),
@@ -15,14 +16,13 @@ impl FerrumWindow {
     }
 
     fn word_bounds_at(&self, row: usize, col: usize) -> Option<(Position, Position)> {
-        let leaf = self.active_leaf_ref()?;
-        let vp_rows = leaf.terminal.screen.viewport_rows();
-        let vp_cols = leaf.terminal.screen.cols();
+        let vp_rows = self.terminal.screen.viewport_rows();
+        let vp_cols = self.terminal.screen.cols();
         if row >= vp_rows || col >= vp_cols {
             return None;
         }
 
-        let ch = leaf.terminal.screen.viewport_get(row, col).first_char();
+        let ch = self.terminal.screen.viewport_get(row, col).first_char();
         if !Self::is_word_char(ch) {
             let pos = Position { row, col };
             return Some((pos, pos));
@@ -30,7 +30,11 @@ impl FerrumWindow {
 
         let mut start_col = col;
         while start_col > 0 {
-            let prev_ch = leaf.terminal.screen.viewport_get(row, start_col - 1).first_char();
+            let prev_ch = self
+                .terminal
+                .screen
+                .viewport_get(row, start_col - 1)
+                .first_char();
             if !Self::is_word_char(prev_ch) {
                 break;
             }
@@ -39,7 +43,11 @@ impl FerrumWindow {
 
         let mut end_col = col;
         while end_col + 1 < vp_cols {
-            let next_ch = leaf.terminal.screen.viewport_get(row, end_col + 1).first_char();
+            let next_ch = self
+                .terminal
+                .screen
+                .viewport_get(row, end_col + 1)
+                .first_char();
             if !Self::is_word_char(next_ch) {
                 break;
             }
@@ -56,9 +64,8 @@ impl FerrumWindow {
     }
 
     fn line_bounds_at(&self, row: usize) -> Option<(Position, Position)> {
-        let leaf = self.active_leaf_ref()?;
-        let vp_rows = leaf.terminal.screen.viewport_rows();
-        let vp_cols = leaf.terminal.screen.cols();
+        let vp_rows = self.terminal.screen.viewport_rows();
+        let vp_cols = self.terminal.screen.cols();
         if row >= vp_rows || vp_cols == 0 {
             return None;
         }
@@ -72,14 +79,10 @@ impl FerrumWindow {
     }
 
     fn viewport_start(&self) -> usize {
-        match self.active_leaf_ref() {
-            Some(leaf) => leaf
-                .terminal
-                .screen
-                .scrollback_len()
-                .saturating_sub(leaf.scroll_offset),
-            None => 0,
-        }
+        self.terminal
+            .screen
+            .scrollback_len()
+            .saturating_sub(self.scroll_offset)
     }
 
     pub(in crate::gui) fn screen_to_abs(&self, screen_row: usize) -> usize {
@@ -96,44 +99,99 @@ impl FerrumWindow {
     fn set_selection_from_positions(&mut self, start: Position, end: Position) {
         let abs_start = self.pos_to_abs(start);
         let abs_end = self.pos_to_abs(end);
-        if let Some(leaf) = self.active_leaf_mut() {
-            leaf.set_selection(Selection {
-                start: abs_start,
-                end: abs_end,
-            });
-        }
+        self.set_selection(Selection {
+            start: abs_start,
+            end: abs_end,
+            kind: SelectionKind::Stream,
+        });
     }
 
-    pub(in crate::gui) fn select_word_at(&mut self, row: usize, col: usize) {
+    fn select_word_at(&mut self, row: usize, col: usize) {
         let Some((start, end)) = self.word_bounds_at(row, col) else {
             return;
         };
         self.set_selection_from_positions(start, end);
     }
 
-    pub(in crate::gui) fn select_line_at(&mut self, row: usize) {
+    fn select_line_at(&mut self, row: usize) {
         let Some((start, end)) = self.line_bounds_at(row) else {
             return;
         };
         self.set_selection_from_positions(start, end);
     }
 
-    pub(in crate::gui) fn update_drag_selection(&mut self, row: usize, col: usize) {
-        let (max_row, max_col, existing_selection) = match self.active_leaf_ref() {
-            Some(leaf) => (
-                leaf.terminal.screen.viewport_rows().saturating_sub(1),
-                leaf.terminal.screen.cols().saturating_sub(1),
-                leaf.selection,
-            ),
-            None => return,
+    /// Selects the OSC 133 output block under screen `row`; returns `false`
+    /// when the row is not part of a finished command's output.
+    fn select_command_output_at(&mut self, row: usize) -> bool {
+        let abs_row = self.screen_to_abs(row);
+        let Some((start, end)) = self.terminal.screen.command_output_zone(abs_row) else {
+            return false;
         };
+        self.set_selection(Selection {
+            start,
+            end,
+            kind: SelectionKind::Stream,
+        });
+        true
+    }
 
+    /// Starts a mouse selection with a plain left press at `pos`, the
+    /// `streak`-th click in a row, and returns the drag mode it arms.
+    /// `None` means the press selected a whole command output and no drag
+    /// follows.
+    pub(in crate::gui) fn press_selection(
+        &mut self,
+        pos: Position,
+        modifiers: ModifiersState,
+        streak: u8,
+    ) -> Option<SelectionDragMode> {
+        if modifiers.alt_key() {
+            // Alt+drag selects a rectangle of columns; starts on movement.
+            self.clear_selection();
+            return Some(SelectionDragMode::Block);
+        }
+
+        match streak {
+            1 => {
+                // Single-click arms char-wise drag; selection starts on movement.
+                self.clear_selection();
+                Some(SelectionDragMode::Character)
+            }
+            2 => {
+                // Double-click selects word and keeps word-wise drag active.
+                self.select_word_at(pos.row, pos.col);
+                Some(SelectionDragMode::Word)
+            }
+            _ => {
+                // Triple-click selects line and keeps line-wise drag active.
+                if self.select_command_output_at(pos.row) {
+                    // Inside a command's output: take the whole block, no drag.
+                    return None;
+                }
+                self.select_line_at(pos.row);
+                Some(SelectionDragMode::Line)
+            }
+        }
+    }
+
+    /// Extends the mouse selection armed at `anchor` in `mode` to the
+    /// viewport cell at (`row`, `col`).
+    pub(in crate::gui) fn drag_selection(
+        &mut self,
+        anchor: Option<PageCoord>,
+        mode: SelectionDragMode,
+        row: usize,
+        col: usize,
+    ) {
+        let max_row = self.terminal.screen.viewport_rows().saturating_sub(1);
+        let max_col = self.terminal.screen.cols().saturating_sub(1);
         let vp = self.viewport_start();
 
         // Anchor is already in absolute coords
-        let mut anchor = self
-            .selection_anchor
-            .unwrap_or(PageCoord { abs_row: vp + row, col });
+        let mut anchor = anchor.unwrap_or(PageCoord {
+            abs_row: vp + row,
+            col,
+        });
         // Clamp anchor col (abs_row is absolute, no clamping to screen max_row)
         anchor.col = anchor.col.min(max_col);
 
@@ -142,9 +200,10 @@ impl FerrumWindow {
             abs_row,
             col: col.min(max_col),
         };
-        let mode = self.selection_drag_mode;
 
-        if mode == SelectionDragMode::Character && current == anchor && existing_selection.is_none()
+        if matches!(mode, SelectionDragMode::Character | SelectionDragMode::Block)
+            && current == anchor
+            && self.selection.is_none()
         {
             return;
         }
@@ -157,6 +216,7 @@ impl FerrumWindow {
             SelectionDragMode::Character => Selection {
                 start: anchor,
                 end: current,
+                kind: SelectionKind::Stream,
             },
             SelectionDragMode::Word => {
                 let anchor_screen = Position {
@@ -178,22 +238,96 @@ impl FerrumWindow {
                     Selection {
                         start: self.pos_to_abs(current_start),
                         end: self.pos_to_abs(anchor_end),
+                        kind: SelectionKind::Stream,
                     }
                 } else {
                     Selection {
                         start: self.pos_to_abs(anchor_start),
                         end: self.pos_to_abs(current_end),
+                        kind: SelectionKind::Stream,
                     }
                 }
             }
             SelectionDragMode::Line => Selection {
                 start: PageCoord { abs_row: current.abs_row.min(anchor.abs_row), col: 0 },
                 end: PageCoord { abs_row: current.abs_row.max(anchor.abs_row), col: max_col },
+                kind: SelectionKind::Stream,
+            },
+            SelectionDragMode::Block => Selection {
+                start: anchor,
+                end: current,
+                kind: SelectionKind::Block,
             },
         };
 
+        self.set_selection(selection);
+    }
+}
+
+impl FerrumWindow {
+    pub(in crate::gui) fn screen_to_abs(&self, screen_row: usize) -> usize {
+        self.active_leaf_ref()
+            .map_or(screen_row, |leaf| leaf.screen_to_abs(screen_row))
+    }
+
+    /// Selects the output of the most recent finished command.
+    pub(in crate::gui) fn select_last_command_output(&mut self) -> bool {
+        let Some(leaf) = self.active_leaf_mut() else {
+            return false;
+        };
+        let Some((start, end)) = leaf.terminal.screen.last_command_output() else {
+            return false;
+        };
+        leaf.set_selection(Selection {
+            start,
+            end,
+            kind: SelectionKind::Stream,
+        });
+        true
+    }
+
+    pub(in crate::gui) fn update_drag_selection(&mut self, row: usize, col: usize) {
+        let (anchor, mode) = (self.selection_anchor, self.selection_drag_mode);
         if let Some(leaf) = self.active_leaf_mut() {
-            leaf.set_selection(selection);
+            leaf.drag_selection(anchor, mode, row, col);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::pane::PaneNode;
+
+    fn leaf_with(text: &str) -> PaneLeaf {
+        let PaneNode::Leaf(mut leaf) = PaneNode::new_leaf(1) else {
+            unreachable!("new_leaf builds a leaf");
+        };
+        leaf.terminal.process(text.as_bytes());
+        *leaf
+    }
+
+    fn press_and_drag(modifiers: ModifiersState) -> Option<SelectionKind> {
+        let mut leaf = leaf_with("PID  CMD\r\n1    init\r\n42   sshd");
+        let press = Position { row: 0, col: 5 };
+        let anchor = PageCoord {
+            abs_row: leaf.screen_to_abs(press.row),
+            col: press.col,
+        };
+        let mode = leaf.press_selection(press, modifiers, 1)?;
+        leaf.drag_selection(Some(anchor), mode, 2, 7);
+        leaf.selection.map(|sel| sel.kind)
+    }
+
+    #[test]
+    fn alt_drag_selects_a_block_instead_of_a_span() {
+        assert_eq!(
+            press_and_drag(ModifiersState::ALT),
+            Some(SelectionKind::Block)
+        );
+        assert_eq!(
+            press_and_drag(ModifiersState::empty()),
+            Some(SelectionKind::Stream)
+        );
+    }
+}
@@ -0,0 +1,740 @@
+use crate::core::{PageCoord, PageList, SearchMatches, TrackedPin};
+use crate::gui::pane::PaneLeaf;
+use crate::gui::state::{SearchState, ViModeState, ViVisual};
+use crate::gui::*;
+
+/// Cursor movements of vi copy mode.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(in crate::gui) enum ViMotion {
+    Left,
+    Right,
+    Up,
+    Down,
+    /// `w`: start of the next word.
+    WordForward,
+    /// `b`: start of the current or previous word.
+    WordBackward,
+    /// `e`: end of the current or next word.
+    WordEnd,
+    /// `0`
+    LineStart,
+    /// `^`
+    FirstNonBlank,
+    /// `$`
+    LineEnd,
+    /// `gg`
+    Top,
+    /// `G`
+    Bottom,
+    /// `Ctrl-u`
+    HalfPageUp,
+    /// `Ctrl-d`
+    HalfPageDown,
+}
+
+/// Character classes word motions stop between, as in vi.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum CharClass {
+    Blank,
+    Word,
+    Punct,
+}
+
+fn class_of(ch: char) -> CharClass {
+    if ch.is_whitespace() {
+        CharClass::Blank
+    } else if ch.is_alphanumeric() || ch == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punct
+    }
+}
+
+fn class_at(screen: &PageList, pos: PageCoord) -> CharClass {
+    class_of(screen.abs_row(pos.abs_row).cells[snap_to_char(screen, pos).col].first_char())
+}
+
+/// Moves off the spacer half of a wide char onto the char itself.
+fn snap_to_char(screen: &PageList, pos: PageCoord) -> PageCoord {
+    let cells = &screen.abs_row(pos.abs_row).cells;
+    if pos.col > 0 && cells[pos.col].width == 0 {
+        PageCoord {
+            col: pos.col - 1,
+            ..pos
+        }
+    } else {
+        pos
+    }
+}
+
+/// The neighbouring cell in reading order, and whether a hard line break
+/// (a row end that is not a soft wrap) lies between the two.
+fn step(screen: &PageList, pos: PageCoord, forward: bool) -> Option<(PageCoord, bool)> {
+    let last_col = screen.cols().saturating_sub(1);
+    if forward {
+        if pos.col < last_col {
+            return Some((
+                PageCoord {
+                    col: pos.col + 1,
+                    ..pos
+                },
+                false,
+            ));
+        }
+        let next = pos.abs_row + 1;
+        (next < screen.total_rows()).then(|| {
+            let hard_break = !screen.abs_row(pos.abs_row).wrapped;
+            (
+                PageCoord {
+                    abs_row: next,
+                    col: 0,
+                },
+                hard_break,
+            )
+        })
+    } else {
+        if pos.col > 0 {
+            return Some((
+                PageCoord {
+                    col: pos.col - 1,
+                    ..pos
+                },
+                false,
+            ));
+        }
+        let prev = pos.abs_row.checked_sub(1)?;
+        let hard_break = !screen.abs_row(prev).wrapped;
+        Some((
+            PageCoord {
+                abs_row: prev,
+                col: last_col,
+            },
+            hard_break,
+        ))
+    }
+}
+
+/// `w`: first cell of the next word, treating line breaks as blanks.
+fn word_forward(screen: &PageList, from: PageCoord) -> PageCoord {
+    let start_class = class_at(screen, from);
+    let mut crossed_blank = start_class == CharClass::Blank;
+    let mut pos = from;
+    while let Some((next, hard_break)) = step(screen, pos, true) {
+        pos = next;
+        let class = class_at(screen, pos);
+        if hard_break || class == CharClass::Blank {
+            crossed_blank = true;
+        }
+        if class != CharClass::Blank && (crossed_blank || class != start_class) {
+            return pos;
+        }
+    }
+    from
+}
+
+/// `e` (`forward`) and `b`: the far end of the word the cursor is on, or of
+/// the next word in that direction when it already sits on that end.
+fn word_edge(screen: &PageList, from: PageCoord, forward: bool) -> PageCoord {
+    let mut pos = from;
+    let class = loop {
+        let Some((next, _)) = step(screen, pos, forward) else {
+            return from;
+        };
+        pos = next;
+        let class = class_at(screen, pos);
+        if class != CharClass::Blank {
+            break class;
+        }
+    };
+    while let Some((next, hard_break)) = step(screen, pos, forward) {
+        if hard_break || class_at(screen, next) != class {
+            break;
+        }
+        pos = next;
+    }
+    snap_to_char(screen, pos)
+}
+
+/// Column of the first (`last == false`) or last non-blank cell of a row.
+fn non_blank_col(screen: &PageList, abs_row: usize, last: bool) -> usize {
+    let cells = &screen.abs_row(abs_row).cells;
+    let non_blank = |(_, cell): &(usize, &crate::core::GraphemeCell)| {
+        cell.width > 0 && !cell.first_char().is_whitespace()
+    };
+    let found = if last {
+        cells.iter().enumerate().rfind(non_blank)
+    } else {
+        cells.iter().enumerate().find(non_blank)
+    };
+    found.map_or(0, |(col, _)| col)
+}
+
+/// Where `motion` takes the cursor; `half_page` is the `Ctrl-u`/`Ctrl-d` step.
+fn apply_motion(
+    screen: &PageList,
+    from: PageCoord,
+    motion: ViMotion,
+    half_page: usize,
+) -> PageCoord {
+    let last_row = screen.total_rows().saturating_sub(1);
+    let last_col = screen.cols().saturating_sub(1);
+    let to_row = |abs_row: usize| {
+        snap_to_char(
+            screen,
+            PageCoord {
+                abs_row,
+                col: from.col.min(last_col),
+            },
+        )
+    };
+    let in_row = |col: usize| PageCoord { col, ..from };
+    match motion {
+        ViMotion::Left => snap_to_char(screen, in_row(from.col.saturating_sub(1))),
+        ViMotion::Right => {
+            let cells = &screen.abs_row(from.abs_row).cells;
+            (from.col + 1..=last_col)
+                .find(|&col| cells[col].width > 0)
+                .map_or(from, in_row)
+        }
+        ViMotion::Up => to_row(from.abs_row.saturating_sub(1)),
+        ViMotion::Down => to_row((from.abs_row + 1).min(last_row)),
+        ViMotion::HalfPageUp => to_row(from.abs_row.saturating_sub(half_page)),
+        ViMotion::HalfPageDown => to_row((from.abs_row + half_page).min(last_row)),
+        ViMotion::WordForward => word_forward(screen, from),
+        ViMotion::WordBackward => word_edge(screen, from, false),
+        ViMotion::WordEnd => word_edge(screen, from, true),
+        ViMotion::LineStart => in_row(0),
+        ViMotion::FirstNonBlank => in_row(non_blank_col(screen, from.abs_row, false)),
+        ViMotion::LineEnd => in_row(non_blank_col(screen, from.abs_row, true)),
+        ViMotion::Top => PageCoord { abs_row: 0, col: 0 },
+        ViMotion::Bottom => PageCoord {
+            abs_row: last_row,
+            col: 0,
+        },
+    }
+}
+
+/// Start of the nearest match still in `screen` after (`forward`) or before
+/// `from`, wrapping around the buffer.
+fn next_match_start(
+    matches: &SearchMatches,
+    screen: &PageList,
+    from: PageCoord,
+    forward: bool,
+) -> Option<PageCoord> {
+    let key = |c: PageCoord| (c.abs_row, c.col);
+    let starts: Vec<PageCoord> = matches.iter(screen).map(|(_, m)| m.start).collect();
+    if forward {
+        starts
+            .iter()
+            .find(|s| key(**s) > key(from))
+            .or(starts.first())
+            .copied()
+    } else {
+        starts
+            .iter()
+            .rfind(|s| key(**s) < key(from))
+            .or(starts.last())
+            .copied()
+    }
+}
+
+/// The ends of the selection covering `anchor`..`cursor` in `visual` mode.
+fn visual_selection(
+    visual: ViVisual,
+    anchor: PageCoord,
+    cursor: PageCoord,
+    last_col: usize,
+) -> Selection {
+    match visual {
+        ViVisual::Char => Selection {
+            start: anchor,
+            end: cursor,
+            kind: SelectionKind::Stream,
+        },
+        ViVisual::Line => Selection {
+            start: PageCoord {
+                abs_row: anchor.abs_row.min(cursor.abs_row),
+                col: 0,
+            },
+            end: PageCoord {
+                abs_row: anchor.abs_row.max(cursor.abs_row),
+                col: last_col,
+            },
+            kind: SelectionKind::Stream,
+        },
+        ViVisual::Block => Selection {
+            start: anchor,
+            end: cursor,
+            kind: SelectionKind::Block,
+        },
+    }
+}
+
+impl PaneLeaf {
+    /// Absolute index of the first displayed row.
+    fn view_top_abs(&self) -> usize {
+        self.terminal
+            .screen
+            .scrollback_len()
+            .saturating_sub(self.scroll_offset)
+    }
+
+    /// Pins the first displayed row while the view is scrolled back, so it
+    /// can be restored after output scrolls the buffer. At the bottom the
+    /// view follows the output and nothing is pinned.
+    pub(in crate::gui) fn pin_view_top(&mut self) -> Option<TrackedPin> {
+        if self.scroll_offset == 0 {
+            return None;
+        }
+        let abs_row = self.view_top_abs();
+        Some(
+            self.terminal
+                .screen
+                .track_pin(PageCoord { abs_row, col: 0 }),
+        )
+    }
+
+    /// Enters copy mode with the vi cursor on the terminal cursor, or on the
+    /// top-left cell when the terminal cursor is scrolled out of view.
+    fn enter_vi_mode(&mut self) {
+        let scrollback_len = self.terminal.screen.scrollback_len();
+        let start = if self.scroll_offset == 0 {
+            PageCoord {
+                abs_row: scrollback_len + self.terminal.cursor_row(),
+                col: self.terminal.cursor_col(),
+            }
+        } else {
+            PageCoord {
+                abs_row: self.view_top_abs(),
+                col: 0,
+            }
+        };
+        let cursor = self.terminal.screen.track_pin(start);
+        self.vi_mode = Some(ViModeState {
+            cursor,
+            saved_scroll_offset: self.scroll_offset,
+            visual: None,
+            pending_g: false,
+            search_forward: true,
+            matches: SearchMatches::default(),
+            alt_screen: self.terminal.is_alt_screen(),
+        });
+    }
+
+    /// Leaves copy mode, dropping its selection and restoring the view.
+    pub(in crate::gui) fn exit_vi_mode(&mut self) {
+        let Some(vi) = self.vi_mode.take() else {
+            return;
+        };
+        if vi.visual.is_some() {
+            self.clear_selection();
+        }
+        self.scroll_offset = vi
+            .saved_scroll_offset
+            .min(self.terminal.screen.scrollback_len());
+    }
+
+    /// Moves the vi cursor and scrolls just enough to keep it displayed.
+    pub(in crate::gui) fn vi_motion(&mut self, motion: ViMotion) {
+        let rows = self.terminal.screen.viewport_rows();
+        let half_page = (rows / 2).max(1);
+        let Some(vi) = self.vi_mode.as_mut() else {
+            return;
+        };
+        vi.pending_g = false;
+        let to = apply_motion(&self.terminal.screen, vi.cursor.coord(), motion, half_page);
+        vi.cursor.set_coord(to);
+        // Half-page motions scroll the view along with the cursor.
+        match motion {
+            ViMotion::HalfPageUp => {
+                let max = self.terminal.screen.scrollback_len();
+                self.scroll_offset = (self.scroll_offset + half_page).min(max);
+            }
+            ViMotion::HalfPageDown => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(half_page);
+            }
+            _ => {}
+        }
+        self.after_vi_cursor_move();
+    }
+
+    /// `g`: the second of two in a row jumps to the top (`gg`).
+    pub(in crate::gui) fn vi_g(&mut self) {
+        let Some(vi) = self.vi_mode.as_mut() else {
+            return;
+        };
+        if vi.pending_g {
+            self.vi_motion(ViMotion::Top);
+        } else {
+            vi.pending_g = true;
+        }
+    }
+
+    /// Starts, switches or ends (when `visual` is already active) a visual
+    /// selection anchored at the vi cursor.
+    pub(in crate::gui) fn toggle_vi_visual(&mut self, visual: ViVisual) {
+        let Some(vi) = self.vi_mode.as_mut() else {
+            return;
+        };
+        vi.pending_g = false;
+        match vi.visual.as_mut() {
+            Some((current, _)) if *current == visual => {
+                vi.visual = None;
+                self.clear_selection();
+                return;
+            }
+            Some((current, _)) => *current = visual,
+            None => {
+                let anchor = self.terminal.screen.track_pin(vi.cursor.coord());
+                vi.visual = Some((visual, anchor));
+            }
+        }
+        self.update_vi_selection();
+    }
+
+    /// `Esc`: ends the visual selection, or copy mode when there is none.
+    pub(in crate::gui) fn vi_escape(&mut self) {
+        let Some(vi) = self.vi_mode.as_mut() else {
+            return;
+        };
+        vi.pending_g = false;
+        if vi.visual.take().is_some() {
+            self.clear_selection();
+        } else {
+            self.exit_vi_mode();
+        }
+    }
+
+    /// Opens the search bar for `/` (`forward`) or `?`.
+    pub(in crate::gui) fn start_vi_search(&mut self, forward: bool) {
+        let Some(vi) = self.vi_mode.as_mut() else {
+            return;
+        };
+        vi.pending_g = false;
+        vi.search_forward = forward;
+        self.search = Some(SearchState::default());
+    }
+
+    /// Closes the search bar, keeping its matches for `n`/`N`, and moves the
+    /// vi cursor to the first match in the search direction.
+    pub(in crate::gui) fn confirm_vi_search(&mut self) {
+        let Some(search) = self.search.take() else {
+            return;
+        };
+        if let Some(vi) = self.vi_mode.as_mut() {
+            vi.matches = search.matches;
+        }
+        self.vi_jump_match(false);
+    }
+
+    /// `n` (`reverse == false`) and `N`: moves to the next match of the last
+    /// search.
+    pub(in crate::gui) fn vi_jump_match(&mut self, reverse: bool) {
+        let Some(vi) = self.vi_mode.as_mut() else {
+            return;
+        };
+        vi.pending_g = false;
+        let forward = vi.search_forward != reverse;
+        let screen = &self.terminal.screen;
+        if let Some(to) = next_match_start(&vi.matches, screen, vi.cursor.coord(), forward) {
+            vi.cursor.set_coord(to);
+            self.after_vi_cursor_move();
+        }
+    }
+
+    /// Re-anchors copy mode after output or a resize moved the buffer: a
+    /// cursor whose row was evicted or reflowed goes to the top of the view,
+    /// and a visual selection that lost its anchor is dropped. Switching
+    /// between the main and alternate screen leaves copy mode, as its pins
+    /// point into the other screen.
+    pub(in crate::gui) fn refresh_vi_mode(&mut self) {
+        let top = self.view_top_abs();
+        let alt_screen = self.terminal.is_alt_screen();
+        let Some(vi) = self.vi_mode.as_mut() else {
+            return;
+        };
+        if vi.alt_screen != alt_screen {
+            self.exit_vi_mode();
+            return;
+        }
+        if !vi.cursor.is_valid() {
+            vi.cursor = self.terminal.screen.track_pin(PageCoord {
+                abs_row: top,
+                col: 0,
+            });
+        }
+        if vi
+            .visual
+            .as_ref()
+            .is_some_and(|(_, anchor)| !anchor.is_valid())
+        {
+            vi.visual = None;
+            self.clear_selection();
+        } else {
+            self.update_vi_selection();
+        }
+    }
+
+    fn after_vi_cursor_move(&mut self) {
+        self.scroll_to_vi_cursor();
+        self.update_vi_selection();
+    }
+
+    fn scroll_to_vi_cursor(&mut self) {
+        let Some(abs_row) = self.vi_mode.as_ref().map(|vi| vi.cursor.coord().abs_row) else {
+            return;
+        };
+        let rows = self.terminal.screen.viewport_rows();
+        let top = self.view_top_abs();
+        let new_top = if abs_row < top {
+            abs_row
+        } else if abs_row >= top + rows {
+            abs_row + 1 - rows
+        } else {
+            return;
+        };
+        self.scroll_offset = self
+            .terminal
+            .screen
+            .scrollback_len()
+            .saturating_sub(new_top);
+    }
+
+    /// Mirrors the visual selection into the pane selection.
+    fn update_vi_selection(&mut self) {
+        let last_col = self.terminal.screen.cols().saturating_sub(1);
+        let Some(selection) = self.vi_mode.as_ref().and_then(|vi| {
+            let (visual, anchor) = vi.visual.as_ref()?;
+            Some(visual_selection(
+                *visual,
+                anchor.coord(),
+                vi.cursor.coord(),
+                last_col,
+            ))
+        }) else {
+            return;
+        };
+        self.set_selection(selection);
+    }
+}
+
+impl FerrumWindow {
+    /// Enters or leaves vi copy mode on the focused pane (Ctrl/Cmd+Shift+Space).
+    pub(in crate::gui) fn toggle_vi_mode(&mut self) {
+        let Some(leaf) = self.active_leaf_mut() else {
+            return;
+        };
+        if leaf.vi_mode.is_some() {
+            leaf.exit_vi_mode();
+        } else {
+            leaf.search = None;
+            leaf.enter_vi_mode();
+        }
+        self.window.request_redraw();
+    }
+
+    /// `y`: copies the visual selection and leaves copy mode.
+    pub(in crate::gui) fn vi_yank(&mut self) {
+        if self
+            .active_leaf_ref()
+            .and_then(|leaf| leaf.vi_mode.as_ref())
+            .is_none_or(|vi| vi.visual.is_none())
+        {
+            return;
+        }
+        self.copy_selection();
+        if let Some(leaf) = self.active_leaf_mut() {
+            leaf.exit_vi_mode();
+        }
+        self.window.request_redraw();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::terminal::Terminal;
+    use crate::core::{Regex, SecurityGuard, search_screen};
+    use crate::gui::state::ScrollbarState;
+
+    fn at(abs_row: usize, col: usize) -> PageCoord {
+        PageCoord { abs_row, col }
+    }
+
+    fn screen_with(lines: &[&str], cols: usize) -> PageList {
+        let mut term = Terminal::new(lines.len(), cols);
+        term.process(lines.join("\r\n").as_bytes());
+        term.screen
+    }
+
+    fn leaf_with(lines: &[&str], rows: usize) -> PaneLeaf {
+        let mut terminal = Terminal::new(rows, 20);
+        terminal.process(lines.join("\r\n").as_bytes());
+        PaneLeaf {
+            id: 1,
+            terminal,
+            session: None,
+            pty_writer: Box::new(std::io::sink()),
+            selection: None,
+            scroll_offset: 0,
+            security: SecurityGuard::default(),
+            scrollbar: ScrollbarState::new(),
+            search: None,
+            vi_mode: None,
+            hovered_link: None,
+            hovered_hyperlink: None,
+            bell_at: None,
+            notified_at: None,
+            clipboard_read_consent: None,
+        }
+    }
+
+    #[test]
+    fn word_motions_stop_at_class_changes_and_line_breaks() {
+        let screen = screen_with(&["foo.bar  baz", "qux"], 16);
+        let w = |from| apply_motion(&screen, from, ViMotion::WordForward, 1);
+        assert_eq!(w(at(0, 0)), at(0, 3));
+        assert_eq!(w(at(0, 3)), at(0, 4));
+        assert_eq!(w(at(0, 4)), at(0, 9));
+        assert_eq!(w(at(0, 9)), at(1, 0));
+
+        let e = |from| apply_motion(&screen, from, ViMotion::WordEnd, 1);
+        assert_eq!(e(at(0, 0)), at(0, 2));
+        assert_eq!(e(at(0, 2)), at(0, 3));
+        assert_eq!(e(at(0, 9)), at(0, 11));
+        assert_eq!(e(at(0, 11)), at(1, 2));
+
+        let b = |from| apply_motion(&screen, from, ViMotion::WordBackward, 1);
+        assert_eq!(b(at(1, 0)), at(0, 9));
+        assert_eq!(b(at(0, 10)), at(0, 9));
+        assert_eq!(b(at(0, 4)), at(0, 3));
+        assert_eq!(b(at(0, 0)), at(0, 0));
+    }
+
+    #[test]
+    fn word_motions_follow_soft_wraps() {
+        // "abcdef" wraps after five columns; it is still one word.
+        let screen = screen_with(&["abcdef x"], 5);
+        assert!(screen.abs_row(0).wrapped);
+        assert_eq!(
+            apply_motion(&screen, at(0, 0), ViMotion::WordEnd, 1),
+            at(1, 0)
+        );
+        assert_eq!(
+            apply_motion(&screen, at(0, 1), ViMotion::WordForward, 1),
+            at(1, 2)
+        );
+    }
+
+    #[test]
+    fn line_motions_and_wide_chars() {
+        let screen = screen_with(&["  ab 日x  "], 12);
+        let go = |from, motion| apply_motion(&screen, from, motion, 1);
+        assert_eq!(go(at(0, 6), ViMotion::LineStart), at(0, 0));
+        assert_eq!(go(at(0, 6), ViMotion::FirstNonBlank), at(0, 2));
+        assert_eq!(go(at(0, 0), ViMotion::LineEnd), at(0, 7));
+        // `l` skips the spacer half of 日, `h` lands on its first half.
+        assert_eq!(go(at(0, 5), ViMotion::Right), at(0, 7));
+        assert_eq!(go(at(0, 7), ViMotion::Left), at(0, 5));
+        assert_eq!(go(at(0, 6), ViMotion::Right), at(0, 7));
+    }
+
+    #[test]
+    fn vertical_motions_clamp_to_the_buffer() {
+        let screen = screen_with(&["a", "b", "c", "d"], 4);
+        let go = |from, motion| apply_motion(&screen, from, motion, 2);
+        assert_eq!(go(at(0, 2), ViMotion::Up), at(0, 2));
+        assert_eq!(go(at(3, 2), ViMotion::Down), at(3, 2));
+        assert_eq!(go(at(3, 1), ViMotion::HalfPageUp), at(1, 1));
+        assert_eq!(go(at(2, 1), ViMotion::HalfPageDown), at(3, 1));
+        assert_eq!(go(at(2, 3), ViMotion::Top), at(0, 0));
+        assert_eq!(go(at(0, 3), ViMotion::Bottom), at(3, 0));
+    }
+
+    #[test]
+    fn match_jumps_wrap_in_either_direction() {
+        let screen = screen_with(&["x..x", "..x."], 4);
+        let matches = search_screen(&screen, &Regex::literal("x", true));
+        let next = |from, forward| next_match_start(&matches, &screen, from, forward);
+        assert_eq!(next(at(0, 0), true), Some(at(0, 3)));
+        assert_eq!(next(at(1, 2), true), Some(at(0, 0)));
+        assert_eq!(next(at(0, 3), false), Some(at(0, 0)));
+        assert_eq!(next(at(0, 0), false), Some(at(1, 2)));
+        let none = SearchMatches::default();
+        assert_eq!(next_match_start(&none, &screen, at(0, 0), true), None);
+    }
+
+    #[test]
+    fn visual_line_and_block_selections() {
+        let line = visual_selection(ViVisual::Line, at(4, 3), at(2, 7), 9);
+        assert_eq!((line.start, line.end), (at(2, 0), at(4, 9)));
+        let block = visual_selection(ViVisual::Block, at(4, 3), at(2, 7), 9);
+        assert_eq!(block.kind, SelectionKind::Block);
+        assert!(block.contains(3, 5) && !block.contains(3, 8));
+    }
+
+    #[test]
+    fn copy_mode_freezes_the_view_and_restores_it_on_exit() {
+        let mut leaf = leaf_with(&["one", "two", "three"], 2);
+        leaf.enter_vi_mode();
+        // At the bottom the view follows the output.
+        leaf.process_and_flush(b"\r\nfour");
+        assert_eq!(leaf.scroll_offset, 0);
+
+        leaf.vi_motion(ViMotion::Top);
+        let cursor = leaf.vi_mode.as_ref().unwrap().cursor.clone();
+        let top_text = |leaf: &PaneLeaf| {
+            let top = leaf.view_top_abs();
+            leaf.terminal.screen.abs_row(top).cells[0].first_char()
+        };
+        assert_eq!(leaf.scroll_offset, 2);
+        assert_eq!(top_text(&leaf), 'o');
+
+        leaf.process_and_flush(b"\r\nfive\r\nsix");
+        assert_eq!(leaf.scroll_offset, 4);
+        assert_eq!(top_text(&leaf), 'o');
+        assert_eq!(cursor.coord(), at(0, 0));
+
+        leaf.toggle_vi_visual(ViVisual::Char);
+        leaf.vi_motion(ViMotion::LineEnd);
+        assert!(leaf.selection.is_some());
+        leaf.exit_vi_mode();
+        assert!(leaf.vi_mode.is_none() && leaf.selection.is_none());
+        assert_eq!(leaf.scroll_offset, 0);
+    }
+
+    #[test]
+    fn switching_screens_leaves_copy_mode() {
+        let mut leaf = leaf_with(&["one", "two", "three"], 2);
+        leaf.enter_vi_mode();
+        leaf.vi_motion(ViMotion::Top);
+        leaf.toggle_vi_visual(ViVisual::Line);
+
+        leaf.process_and_flush(b"\x1b[?1049h");
+        assert!(leaf.vi_mode.is_none() && leaf.selection.is_none());
+        assert_eq!(leaf.scroll_offset, 0);
+        // Copy mode entered on the alternate screen works there.
+        leaf.enter_vi_mode();
+        leaf.vi_motion(ViMotion::Bottom);
+        leaf.process_and_flush(b"x");
+        assert!(leaf.vi_mode.is_some());
+        leaf.process_and_flush(b"\x1b[?1049l");
+        assert!(leaf.vi_mode.is_none());
+    }
+
+    #[test]
+    fn search_moves_the_cursor_and_scrolls_it_into_view() {
+        let lines = ["needle", "a", "b", "c", "d", "e"];
+        let mut leaf = leaf_with(&lines, 2);
+        leaf.enter_vi_mode();
+        leaf.start_vi_search(false);
+        leaf.search.as_mut().unwrap().query = "needle".to_owned();
+        leaf.run_search();
+        leaf.confirm_vi_search();
+        assert!(leaf.search.is_none());
+        let vi = leaf.vi_mode.as_ref().unwrap();
+        assert_eq!(vi.cursor.coord(), at(0, 0));
+        assert_eq!(leaf.view_top_abs(), 0);
+    }
+}
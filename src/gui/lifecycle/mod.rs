@@ -168,6 +168,9 @@ impl ApplicationHandler for App {
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         self.drain_pty_events(event_loop);
+        for win in self.windows.values_mut() {
+            win.finish_commit_lookups();
+        }
         self.drain_menu_events();
         self.drain_update_events();
 
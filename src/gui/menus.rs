@@ -10,6 +10,8 @@ pub(super) enum MenuAction {
     Paste,
     SelectAll,
     ClearSelection,
+    SelectCommandOutput,
+    CopyCommandOutput,
     SplitRight,
     SplitDown,
     SplitLeft,
@@ -25,10 +27,12 @@ pub(super) enum MenuAction {
 
 /// Builds the terminal area context menu.
 /// `has_selection`: whether text is currently selected
+/// `has_command_output`: whether a finished command's output (OSC 133) exists
 /// `has_multiple_panes`: whether this tab has >1 pane
 #[cfg(not(target_os = "linux"))]
 pub(super) fn build_terminal_context_menu(
     has_selection: bool,
+    has_command_output: bool,
     has_multiple_panes: bool,
 ) -> (Menu, Vec<(MenuId, MenuAction)>) {
     let t = crate::i18n::t();
@@ -52,6 +56,17 @@ pub(super) fn build_terminal_context_menu(
         &PredefinedMenuItem::separator(),
     ]).ok();
 
+    let select_output = MenuItem::new(t.menu_select_command_output, has_command_output, None);
+    action_map.push((select_output.id().clone(), MenuAction::SelectCommandOutput));
+    let copy_output = MenuItem::new(t.menu_copy_command_output, has_command_output, None);
+    action_map.push((copy_output.id().clone(), MenuAction::CopyCommandOutput));
+
+    menu.append_items(&[
+        &select_output,
+        &copy_output,
+        &PredefinedMenuItem::separator(),
+    ]).ok();
+
     let split_right = MenuItem::new(t.menu_split_right, true, None);
     action_map.push((split_right.id().clone(), MenuAction::SplitRight));
     let split_down = MenuItem::new(t.menu_split_down, true, None);
@@ -3,6 +3,7 @@ mod input;
 mod interaction;
 mod lifecycle;
 mod menus;
+mod notifier;
 mod pane;
 mod platform;
 mod renderer;
@@ -25,7 +26,7 @@ use winit::window::WindowLevel;
 use winit::window::{CursorIcon, ResizeDirection, Window, WindowId};
 
 use crate::core::terminal::Terminal;
-use crate::core::{MouseMode, Position, SecurityGuard, Selection};
+use crate::core::{MouseEncoding, MouseMode, Position, SecurityGuard, Selection, SelectionKind};
 use crate::gui::renderer::{CpuRenderer, Renderer as _, RendererBackend};
 use crate::pty;
 use crate::update;
@@ -54,12 +55,14 @@ impl FerrumWindow {
     ) -> Self {
         let mut backend = RendererBackend::new(window.clone(), context, config);
         backend.set_scale(window.scale_factor());
+        let (commit_lookup_tx, commit_lookup_rx) = mpsc::channel();
 
         FerrumWindow {
             window,
             window_title: "Ferrum".to_string(),
             pending_grid_resize: false,
             sigwinch_deadline: None,
+            deferred_frame: false,
             backend,
             tabs: Vec::new(),
             active_tab: 0,
@@ -74,6 +77,8 @@ impl FerrumWindow {
             keyboard_selection_anchor: None,
             selection_drag_mode: SelectionDragMode::Character,
             hovered_tab: None,
+            #[cfg(not(target_os = "macos"))]
+            hovered_command_status: None,
             #[cfg(not(target_os = "linux"))]
             pending_menu_context: None,
             #[cfg(not(target_os = "macos"))]
@@ -98,16 +103,25 @@ impl FerrumWindow {
             #[cfg(target_os = "macos")]
             next_native_tab_sync_at: None,
             scroll_accumulator: 0.0,
+            scroll_accumulator_x: 0.0,
             pending_requests: Vec::new(),
             pinned: false,
             divider_drag: None,
             last_cwd_poll: std::time::Instant::now(),
             cursor_blink_interval_ms: config.terminal.cursor_blink_interval_ms,
+            editor_command: config.links.editor_command.clone(),
+            bell: config.bell,
+            last_bell_at: None,
+            notifier: notifier::system_notifier(),
             settings_tx: mpsc::channel().0,
             event_proxy: proxy.clone(),
             pending_update_tag: None,
             update_banner_dismissed: false,
             update_install_state: UpdateInstallState::Idle,
+            clipboard_prompt: None,
+            commit_lookup_tx,
+            commit_lookup_rx,
+            kitty_pressed_keys: std::collections::HashSet::new(),
         }
     }
 
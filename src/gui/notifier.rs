@@ -0,0 +1,155 @@
+//! Delivery of desktop notifications posted by terminal programs (OSC 9/777).
+
+use std::time::{Duration, Instant};
+
+use crate::core::Notification;
+
+/// Application name given to the notification service, and the summary of
+/// notifications that carry no title.
+const APP_NAME: &str = "Ferrum";
+
+/// Shortest time between two notifications from the same pane.
+const NOTIFICATION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Notifications waiting for the notification service; more are dropped.
+#[cfg(target_os = "linux")]
+const MAX_QUEUED_NOTIFICATIONS: usize = 8;
+
+/// Whether a pane that last posted at `last` may post again at `now`.
+pub(super) fn notification_due(last: Option<Instant>, now: Instant) -> bool {
+    last.is_none_or(|last| now.saturating_duration_since(last) >= NOTIFICATION_INTERVAL)
+}
+
+/// A desktop notification backend.
+pub(super) trait Notifier {
+    fn notify(&self, notification: &Notification);
+}
+
+/// Writes notifications to stderr; the fallback where no system service is
+/// available.
+pub(super) struct LogNotifier;
+
+impl Notifier for LogNotifier {
+    fn notify(&self, notification: &Notification) {
+        eprintln!(
+            "[ferrum] Notification: {}: {}",
+            summary(notification),
+            notification.body
+        );
+    }
+}
+
+/// Sends notifications to the freedesktop notification service over D-Bus.
+///
+/// The call waits for the service to answer, so a single worker thread
+/// sends them in turn, off the event loop.
+#[cfg(target_os = "linux")]
+pub(super) struct DbusNotifier {
+    tx: std::sync::mpsc::SyncSender<Notification>,
+}
+
+#[cfg(target_os = "linux")]
+impl DbusNotifier {
+    fn new() -> Self {
+        let (tx, rx) = std::sync::mpsc::sync_channel::<Notification>(MAX_QUEUED_NOTIFICATIONS);
+        std::thread::Builder::new()
+            .name("ferrum-notifier".into())
+            .spawn(move || {
+                for notification in rx {
+                    if let Err(e) = send_dbus_notification(&notification) {
+                        eprintln!("[ferrum] Notification service unavailable: {e}");
+                        LogNotifier.notify(&notification);
+                    }
+                }
+            })
+            .ok();
+        Self { tx }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Notifier for DbusNotifier {
+    fn notify(&self, notification: &Notification) {
+        use std::sync::mpsc::TrySendError;
+
+        match self.tx.try_send(notification.clone()) {
+            Ok(()) => {}
+            // The service is slow or stuck; drop rather than pile up.
+            Err(TrySendError::Full(_)) => {
+                eprintln!("[ferrum] Notification queue full; dropping notification");
+            }
+            // The worker thread could not be started.
+            Err(TrySendError::Disconnected(notification)) => LogNotifier.notify(&notification),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn send_dbus_notification(notification: &Notification) -> Result<(), gtk4::glib::Error> {
+    use gtk4::gio;
+    use gtk4::glib::Variant;
+    use gtk4::glib::variant::ToVariant;
+    use std::collections::HashMap;
+
+    let bus = gio::bus_get_sync(gio::BusType::Session, gio::Cancellable::NONE)?;
+    // Notify(app_name, replaces_id, app_icon, summary, body, actions, hints, expire_timeout)
+    let args = (
+        APP_NAME,
+        0u32,
+        "",
+        summary(notification),
+        notification.body.as_str(),
+        Vec::<String>::new(),
+        HashMap::<String, Variant>::new(),
+        -1i32,
+    )
+        .to_variant();
+    bus.call_sync(
+        Some("org.freedesktop.Notifications"),
+        "/org/freedesktop/Notifications",
+        "org.freedesktop.Notifications",
+        "Notify",
+        Some(&args),
+        None,
+        gio::DBusCallFlags::NONE,
+        -1,
+        gio::Cancellable::NONE,
+    )?;
+    Ok(())
+}
+
+/// Returns the notifier for the current platform.
+pub(super) fn system_notifier() -> Box<dyn Notifier> {
+    #[cfg(target_os = "linux")]
+    {
+        Box::new(DbusNotifier::new())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Box::new(LogNotifier)
+    }
+}
+
+fn summary(notification: &Notification) -> &str {
+    if notification.title.is_empty() {
+        APP_NAME
+    } else {
+        &notification.title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifications_from_a_pane_are_spaced_out() {
+        let now = Instant::now();
+        assert!(notification_due(None, now));
+        assert!(!notification_due(
+            Some(now),
+            now + NOTIFICATION_INTERVAL / 2
+        ));
+        assert!(notification_due(Some(now), now + NOTIFICATION_INTERVAL));
+    }
+}
@@ -2,7 +2,7 @@ use std::io::Write;
 
 use crate::core::terminal::Terminal;
 use crate::core::{SecurityGuard, Selection};
-use crate::gui::state::ScrollbarState;
+use crate::gui::state::{ScrollbarState, SearchState, ViModeState};
 use crate::pty;
 
 /// Unique identifier for a pane within a tab.
@@ -126,6 +126,20 @@ pub(super) struct PaneLeaf {
     pub(super) scroll_offset: usize,
     pub(super) security: SecurityGuard,
     pub(super) scrollbar: ScrollbarState,
+    pub(super) search: Option<SearchState>,
+    pub(super) vi_mode: Option<ViModeState>,
+    /// Detected link under the pointer, underlined while hovered.
+    pub(super) hovered_link: Option<crate::core::DetectedLink>,
+    /// OSC 8 hyperlink id under the pointer; every cell of the link is
+    /// underlined while hovered.
+    pub(super) hovered_hyperlink: Option<u16>,
+    /// When the pane last rang the bell, for the visual flash.
+    pub(super) bell_at: Option<std::time::Instant>,
+    /// When a notification from the pane was last delivered, for rate
+    /// limiting.
+    pub(super) notified_at: Option<std::time::Instant>,
+    /// The user's answer to OSC 52 clipboard reads, asked once per pane.
+    pub(super) clipboard_read_consent: Option<bool>,
 }
 
 impl PaneLeaf {
@@ -169,12 +183,34 @@ impl PaneLeaf {
     /// Processes incoming PTY bytes, records any security events they generate,
     /// and writes any terminal responses back to the PTY.
     pub(in crate::gui) fn process_and_flush(&mut self, bytes: &[u8]) {
+        // Copy mode freezes a scrolled-back view: pin its top row so output
+        // scrolling into the scrollback does not move what is displayed.
+        let frozen_top = self
+            .vi_mode
+            .is_some()
+            .then(|| self.pin_view_top())
+            .flatten();
         self.terminal.process(bytes);
+        if let Some(top) = frozen_top {
+            let top_row = if top.is_valid() { top.coord().abs_row } else { 0 };
+            let scrollback_len = self.terminal.screen.scrollback_len();
+            self.scroll_offset = scrollback_len.saturating_sub(top_row);
+        }
+        self.refresh_search();
+        self.refresh_vi_mode();
+        // Output may have rewritten or moved the hovered text.
+        self.hovered_link = None;
+        self.hovered_hyperlink = None;
 
         for event in self.terminal.drain_security_events() {
             self.security.record(event);
         }
 
+        self.flush_responses();
+    }
+
+    /// Writes the terminal's pending replies to the PTY.
+    pub(in crate::gui) fn flush_responses(&mut self) {
         let responses = self.terminal.drain_responses();
         if !responses.is_empty() {
             self.write_pty(&responses);
@@ -207,7 +243,7 @@ pub(super) enum PaneNode {
 impl PaneNode {
     /// Create a test-only leaf with stub PTY handles.
     #[cfg(test)]
-    fn new_leaf(id: PaneId) -> Self {
+    pub(super) fn new_leaf(id: PaneId) -> Self {
         PaneNode::Leaf(Box::new(PaneLeaf {
             id,
             terminal: Terminal::new(24, 80),
@@ -217,6 +253,13 @@ impl PaneNode {
             scroll_offset: 0,
             security: SecurityGuard::default(),
             scrollbar: ScrollbarState::new(),
+            search: None,
+            vi_mode: None,
+            hovered_link: None,
+            hovered_hyperlink: None,
+            bell_at: None,
+            notified_at: None,
+            clipboard_read_consent: None,
         }))
     }
 
@@ -236,6 +279,13 @@ impl PaneNode {
             scroll_offset: 0,
             security: SecurityGuard::default(),
             scrollbar: ScrollbarState::new(),
+            search: None,
+            vi_mode: None,
+            hovered_link: None,
+            hovered_hyperlink: None,
+            bell_at: None,
+            notified_at: None,
+            clipboard_read_consent: None,
         }))
     }
 
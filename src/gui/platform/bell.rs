@@ -0,0 +1,39 @@
+/// Plays the system alert sound for an audible bell.
+pub fn beep() {
+    #[cfg(target_os = "macos")]
+    {
+        #[link(name = "AppKit", kind = "framework")]
+        unsafe extern "C" {
+            fn NSBeep();
+        }
+        // SAFETY: NSBeep takes no arguments and only queues the alert sound.
+        unsafe { NSBeep() };
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        #[allow(non_snake_case)]
+        #[link(name = "user32")]
+        unsafe extern "system" {
+            fn MessageBeep(uType: u32) -> i32;
+        }
+        const MB_OK: u32 = 0;
+        // SAFETY: MessageBeep plays the sound asynchronously and has no
+        // pointer arguments.
+        unsafe { MessageBeep(MB_OK) };
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // libcanberra's player plays the desktop theme's bell sound; desktops
+        // without it simply stay silent.
+        if let Err(e) = std::process::Command::new("canberra-gtk-play")
+            .args(["--id=bell", "--description=Ferrum bell"])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+        {
+            eprintln!("[ferrum] Failed to play the bell sound: {e}");
+        }
+    }
+}
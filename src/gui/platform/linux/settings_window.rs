@@ -131,6 +131,9 @@ fn build_window(config: &AppConfig, tx: mpsc::Sender<AppConfig>, initial_tab: us
         block_title_switch,
         limit_cursor_switch,
         clear_mouse_switch,
+        notifications_switch,
+        clipboard_read_switch,
+        graphics_files_switch,
     ) = build_security_tab(config, t);
     notebook.append_page(&security_box, Some(&Label::new(Some(t.settings_tab_security))));
 
@@ -181,7 +184,11 @@ fn build_window(config: &AppConfig, tx: mpsc::Sender<AppConfig>, initial_tab: us
         block_title: block_title_switch,
         limit_cursor: limit_cursor_switch,
         clear_mouse: clear_mouse_switch,
+        notifications: notifications_switch,
+        clipboard_read: clipboard_read_switch,
+        graphics_files: graphics_files_switch,
         auto_check: auto_check_switch,
+        base: config.clone(),
     });
 
     // Tracks whether we're in a programmatic update (e.g. security sync or reset).
@@ -272,6 +279,18 @@ fn build_window(config: &AppConfig, tx: mpsc::Sender<AppConfig>, initial_tab: us
         });
     }
 
+    // Capability switches are outside the mode presets — just send.
+    for sw in [&controls.notifications, &controls.clipboard_read, &controls.graphics_files] {
+        let suppress = Rc::clone(&suppress);
+        let send = build_and_send.clone();
+        sw.connect_state_set(move |_, _| {
+            if !*suppress.borrow() {
+                send();
+            }
+            gtk4::glib::Propagation::Proceed
+        });
+    }
+
     // Updates auto_check switch.
     {
         let suppress = Rc::clone(&suppress);
@@ -523,7 +542,7 @@ fn build_layout_tab(
 fn build_security_tab(
     config: &AppConfig,
     t: &crate::i18n::Translations,
-) -> (gtk4::Box, DropDown, Switch, Switch, Switch, Switch) {
+) -> (gtk4::Box, DropDown, Switch, Switch, Switch, Switch, Switch, Switch, Switch) {
     let vbox = tab_vbox();
 
     let mode_index = match config.security.mode {
@@ -551,7 +570,25 @@ fn build_security_tab(
         enabled,
     );
 
-    (vbox, mode_combo, paste, block_title, limit_cursor, clear_mouse)
+    // Capabilities are granted by hand; the mode presets leave them alone.
+    let notifications =
+        labeled_switch(&vbox, t.security_allow_notifications_label, config.security.allow_notifications, true);
+    let clipboard_read =
+        labeled_switch(&vbox, t.security_allow_clipboard_read_label, config.security.allow_clipboard_read, true);
+    let graphics_files =
+        labeled_switch(&vbox, t.security_allow_graphics_files_label, config.security.allow_graphics_files, true);
+
+    (
+        vbox,
+        mode_combo,
+        paste,
+        block_title,
+        limit_cursor,
+        clear_mouse,
+        notifications,
+        clipboard_read,
+        graphics_files,
+    )
 }
 
 fn build_updates_tab(
@@ -690,7 +727,12 @@ struct Controls {
     block_title: Switch,
     limit_cursor: Switch,
     clear_mouse: Switch,
+    notifications: Switch,
+    clipboard_read: Switch,
+    graphics_files: Switch,
     auto_check: Switch,
+    /// Config the window opened with; supplies settings that have no control.
+    base: AppConfig,
 }
 
 fn build_config(c: &Controls) -> AppConfig {
@@ -713,6 +755,7 @@ fn build_config(c: &Controls) -> AppConfig {
         terminal: TerminalConfig {
             max_scrollback: c.scrollback.value() as usize,
             cursor_blink_interval_ms: c.cursor_blink.value() as u64,
+            ..c.base.terminal.clone()
         },
         layout: LayoutConfig {
             window_padding: c.win_padding.value() as u32,
@@ -726,9 +769,13 @@ fn build_config(c: &Controls) -> AppConfig {
             block_title_query: c.block_title.is_active(),
             limit_cursor_jumps: c.limit_cursor.is_active(),
             clear_mouse_on_reset: c.clear_mouse.is_active(),
+            allow_notifications: c.notifications.is_active(),
+            allow_clipboard_read: c.clipboard_read.is_active(),
+            allow_graphics_files: c.graphics_files.is_active(),
         },
         language: crate::i18n::Locale::from_index(c.language.selected() as usize),
         updates: UpdatesConfig { auto_check: c.auto_check.is_active() },
+        ..c.base.clone()
     }
 }
 
@@ -776,6 +823,7 @@ fn infer_security_mode(c: &Controls) {
         block_title_query: c.block_title.is_active(),
         limit_cursor_jumps: c.limit_cursor.is_active(),
         clear_mouse_on_reset: c.clear_mouse.is_active(),
+        ..SecuritySettings::default()
     };
     let inferred = settings.inferred_mode();
     let new_index = match inferred {
@@ -819,5 +867,9 @@ fn reset_controls(c: &Controls) {
     c.security_mode.set_selected(mode_idx);
     apply_security_preset(c, Some(mode_idx as usize));
 
+    c.notifications.set_active(d.security.allow_notifications);
+    c.clipboard_read.set_active(d.security.allow_clipboard_read);
+    c.graphics_files.set_active(d.security.allow_graphics_files);
+
     c.auto_check.set_active(d.updates.auto_check);
 }
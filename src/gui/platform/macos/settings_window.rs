@@ -58,6 +58,9 @@ struct NativeSettingsState {
     block_title_query_check: Retained<NSButton>,
     limit_cursor_jumps_check: Retained<NSButton>,
     clear_mouse_on_reset_check: Retained<NSButton>,
+    allow_notifications_check: Retained<NSButton>,
+    allow_clipboard_read_check: Retained<NSButton>,
+    allow_graphics_files_check: Retained<NSButton>,
     // Updates tab
     auto_check_updates_check: Retained<NSButton>,
     // Manual update check UI
@@ -65,6 +68,8 @@ struct NativeSettingsState {
     manual_check_install_button: Retained<NSButton>,
     // Reset (kept alive so ObjC retains the button; never read from Rust).
     _reset_button: Retained<NSButton>,
+    /// Config the window opened with; supplies settings that have no control.
+    base: AppConfig,
 }
 
 // SAFETY: NativeSettingsState is only created and accessed on the main thread.
@@ -275,6 +280,7 @@ fn build_config_from_controls(state: &NativeSettingsState) -> AppConfig {
         terminal: TerminalConfig {
             max_scrollback: state.scrollback_stepper.integerValue() as usize,
             cursor_blink_interval_ms: state.cursor_blink_stepper.integerValue() as u64,
+            ..state.base.terminal.clone()
         },
         layout: LayoutConfig {
             window_padding: state.window_padding_stepper.integerValue() as u32,
@@ -288,6 +294,9 @@ fn build_config_from_controls(state: &NativeSettingsState) -> AppConfig {
             block_title_query: is_checkbox_on(&state.block_title_query_check),
             limit_cursor_jumps: is_checkbox_on(&state.limit_cursor_jumps_check),
             clear_mouse_on_reset: is_checkbox_on(&state.clear_mouse_on_reset_check),
+            allow_notifications: is_checkbox_on(&state.allow_notifications_check),
+            allow_clipboard_read: is_checkbox_on(&state.allow_clipboard_read_check),
+            allow_graphics_files: is_checkbox_on(&state.allow_graphics_files_check),
         },
         language: crate::i18n::Locale::from_index(
             state.language_popup.indexOfSelectedItem() as usize,
@@ -295,6 +304,7 @@ fn build_config_from_controls(state: &NativeSettingsState) -> AppConfig {
         updates: UpdatesConfig {
             auto_check: is_checkbox_on(&state.auto_check_updates_check),
         },
+        ..state.base.clone()
     }
 }
 
@@ -352,6 +362,7 @@ fn sync_security_controls(state: &NativeSettingsState) {
             block_title_query: is_checkbox_on(&state.block_title_query_check),
             limit_cursor_jumps: is_checkbox_on(&state.limit_cursor_jumps_check),
             clear_mouse_on_reset: is_checkbox_on(&state.clear_mouse_on_reset_check),
+            ..SecuritySettings::default()
         };
         let inferred = settings.inferred_mode();
         let new_index = match inferred {
@@ -500,6 +511,10 @@ pub fn reset_controls_to_defaults() {
     state.block_title_query_check.setEnabled(true);
     state.limit_cursor_jumps_check.setEnabled(true);
     state.clear_mouse_on_reset_check.setEnabled(true);
+    // Capabilities: back to their defaults.
+    set_checkbox(&state.allow_notifications_check, defaults.security.allow_notifications);
+    set_checkbox(&state.allow_clipboard_read_check, defaults.security.allow_clipboard_read);
+    set_checkbox(&state.allow_graphics_files_check, defaults.security.allow_graphics_files);
     // Updates: reset to default.
     set_checkbox(&state.auto_check_updates_check, UpdatesConfig::default().auto_check);
 
@@ -923,7 +938,7 @@ pub fn open_settings_window(config: &AppConfig, sender: mpsc::Sender<AppConfig>)
         t.security_paste_protection_label,
         t.security_paste_protection_desc,
         config.security.paste_protection,
-        240.0,
+        244.0,
     );
     let block_title_query_check = create_checkbox_row(
         mtm,
@@ -931,7 +946,7 @@ pub fn open_settings_window(config: &AppConfig, sender: mpsc::Sender<AppConfig>)
         t.security_block_title_query_label,
         t.security_block_title_query_desc,
         config.security.block_title_query,
-        194.0,
+        208.0,
     );
     let limit_cursor_jumps_check = create_checkbox_row(
         mtm,
@@ -939,7 +954,7 @@ pub fn open_settings_window(config: &AppConfig, sender: mpsc::Sender<AppConfig>)
         t.security_limit_cursor_jumps_label,
         t.security_limit_cursor_jumps_desc,
         config.security.limit_cursor_jumps,
-        148.0,
+        172.0,
     );
     let clear_mouse_on_reset_check = create_checkbox_row(
         mtm,
@@ -947,7 +962,31 @@ pub fn open_settings_window(config: &AppConfig, sender: mpsc::Sender<AppConfig>)
         t.security_clear_mouse_on_reset_label,
         t.security_clear_mouse_on_reset_desc,
         config.security.clear_mouse_on_reset,
-        102.0,
+        136.0,
+    );
+    let allow_notifications_check = create_checkbox_row(
+        mtm,
+        &security_view,
+        t.security_allow_notifications_label,
+        t.security_allow_notifications_desc,
+        config.security.allow_notifications,
+        100.0,
+    );
+    let allow_clipboard_read_check = create_checkbox_row(
+        mtm,
+        &security_view,
+        t.security_allow_clipboard_read_label,
+        t.security_allow_clipboard_read_desc,
+        config.security.allow_clipboard_read,
+        64.0,
+    );
+    let allow_graphics_files_check = create_checkbox_row(
+        mtm,
+        &security_view,
+        t.security_allow_graphics_files_label,
+        t.security_allow_graphics_files_desc,
+        config.security.allow_graphics_files,
+        28.0,
     );
 
     security_tab.setView(Some(&security_view));
@@ -1153,6 +1192,12 @@ pub fn open_settings_window(config: &AppConfig, sender: mpsc::Sender<AppConfig>)
         let _: () = msg_send![&limit_cursor_jumps_check, setAction: sel_stepper];
         let _: () = msg_send![&clear_mouse_on_reset_check, setTarget: &*window];
         let _: () = msg_send![&clear_mouse_on_reset_check, setAction: sel_stepper];
+        let _: () = msg_send![&allow_notifications_check, setTarget: &*window];
+        let _: () = msg_send![&allow_notifications_check, setAction: sel_stepper];
+        let _: () = msg_send![&allow_clipboard_read_check, setTarget: &*window];
+        let _: () = msg_send![&allow_clipboard_read_check, setAction: sel_stepper];
+        let _: () = msg_send![&allow_graphics_files_check, setTarget: &*window];
+        let _: () = msg_send![&allow_graphics_files_check, setAction: sel_stepper];
         // Updates checkbox also triggers stepper-changed.
         let _: () = msg_send![&auto_check_updates_check, setTarget: &*window];
         let _: () = msg_send![&auto_check_updates_check, setAction: sel_stepper];
@@ -1229,10 +1274,14 @@ pub fn open_settings_window(config: &AppConfig, sender: mpsc::Sender<AppConfig>)
         block_title_query_check,
         limit_cursor_jumps_check,
         clear_mouse_on_reset_check,
+        allow_notifications_check,
+        allow_clipboard_read_check,
+        allow_graphics_files_check,
         auto_check_updates_check,
         manual_check_status_label,
         manual_check_install_button,
         _reset_button: reset_button,
+        base: config.clone(),
     };
     *SETTINGS_STATE.lock().unwrap_or_else(|e| e.into_inner()) = Some(state);
 }
@@ -7,8 +7,10 @@ pub mod windows;
 #[cfg(target_os = "linux")]
 pub mod linux;
 
+mod bell;
 mod close_dialog;
 
+pub use bell::beep;
 pub use close_dialog::confirm_window_close;
 
 /// Expands to the `request_reopen` function for platform settings windows.
@@ -145,7 +145,7 @@ mod layout {
     pub const MARGIN: i32 = 5;
     pub const TAB_HEADER_H: i32 = 35;
     pub const ROW_SPACING: i32 = 38;
-    pub const MAX_ROWS: i32 = 5;
+    pub const MAX_ROWS: i32 = 8;
     pub const CONTENT_X: i32 = 20;
     pub const CONTENT_Y: i32 = MARGIN + TAB_HEADER_H;
 
@@ -284,6 +284,9 @@ mod id {
     pub const BLOCK_TITLE_CHECK: i32 = 602;
     pub const LIMIT_CURSOR_CHECK: i32 = 603;
     pub const CLEAR_MOUSE_CHECK: i32 = 604;
+    pub const NOTIFICATIONS_CHECK: i32 = 605;
+    pub const CLIPBOARD_READ_CHECK: i32 = 606;
+    pub const GRAPHICS_FILES_CHECK: i32 = 607;
     // Reset (global, not tab-scoped)
     pub const RESET_BUTTON: i32 = 700;
     // Updates
@@ -328,6 +331,9 @@ struct Win32State {
     block_title_check: HWND,
     limit_cursor_check: HWND,
     clear_mouse_check: HWND,
+    notifications_check: HWND,
+    clipboard_read_check: HWND,
+    graphics_files_check: HWND,
     // Updates tab
     auto_check_check: HWND,
     check_now_btn: HWND,
@@ -335,6 +341,8 @@ struct Win32State {
     manual_install_btn: HWND,
     // Tab groups (for show/hide)
     tab_pages: [Vec<HWND>; 6],
+    /// Config the window opened with; supplies settings that have no control.
+    base: AppConfig,
 }
 
 // ── Win32 window ─────────────────────────────────────────────────────
@@ -603,6 +611,10 @@ fn on_command(state: &Win32State, wparam: WPARAM) {
             let config = build_config(state);
             state.tx.send(config).ok();
         }
+        (id::NOTIFICATIONS_CHECK | id::CLIPBOARD_READ_CHECK | id::GRAPHICS_FILES_CHECK, BN_CLICKED) => {
+            let config = build_config(state);
+            state.tx.send(config).ok();
+        }
         (id::AUTO_CHECK_CHECK, BN_CLICKED) => {
             let config = build_config(state);
             state.tx.send(config).ok();
@@ -870,6 +882,25 @@ unsafe fn create_controls(
     });
     security_page.append(&mut ctrls);
 
+    // Capabilities: not touched by the mode presets.
+    let (notifications_check, mut ctrls) = create_checkbox_row(&ctx, &CheckboxRowParams {
+        label_text: t.security_allow_notifications_label, x: x0, y: y0 + sp * 5,
+        checked: config.security.allow_notifications, enabled: true, check_id: id::NOTIFICATIONS_CHECK,
+    });
+    security_page.append(&mut ctrls);
+
+    let (clipboard_read_check, mut ctrls) = create_checkbox_row(&ctx, &CheckboxRowParams {
+        label_text: t.security_allow_clipboard_read_label, x: x0, y: y0 + sp * 6,
+        checked: config.security.allow_clipboard_read, enabled: true, check_id: id::CLIPBOARD_READ_CHECK,
+    });
+    security_page.append(&mut ctrls);
+
+    let (graphics_files_check, mut ctrls) = create_checkbox_row(&ctx, &CheckboxRowParams {
+        label_text: t.security_allow_graphics_files_label, x: x0, y: y0 + sp * 7,
+        checked: config.security.allow_graphics_files, enabled: true, check_id: id::GRAPHICS_FILES_CHECK,
+    });
+    security_page.append(&mut ctrls);
+
     // ── Updates tab controls ─────────────────────────────────────────
     let mut updates_page = Vec::new();
 
@@ -991,11 +1022,15 @@ unsafe fn create_controls(
         block_title_check,
         limit_cursor_check,
         clear_mouse_check,
+        notifications_check,
+        clipboard_read_check,
+        graphics_files_check,
         auto_check_check,
         check_now_btn,
         manual_status_label,
         manual_install_btn,
         tab_pages: [font_page, theme_page, terminal_page, layout_page, security_page, updates_page],
+        base: config.clone(),
     };
 
         update_all_displays(&state);
@@ -1200,6 +1235,9 @@ fn build_config(state: &Win32State) -> AppConfig {
         let block_title = SendMessageW(state.block_title_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize;
         let limit_cursor = SendMessageW(state.limit_cursor_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize;
         let clear_mouse = SendMessageW(state.clear_mouse_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize;
+        let notifications = SendMessageW(state.notifications_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize;
+        let clipboard_read = SendMessageW(state.clipboard_read_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize;
+        let graphics_files = SendMessageW(state.graphics_files_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize;
 
         let auto_check = SendMessageW(state.auto_check_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize;
 
@@ -1216,6 +1254,7 @@ fn build_config(state: &Win32State) -> AppConfig {
             terminal: TerminalConfig {
                 max_scrollback: scrollback,
                 cursor_blink_interval_ms: cursor_blink,
+                ..state.base.terminal.clone()
             },
             layout: LayoutConfig {
                 window_padding: win_padding,
@@ -1229,11 +1268,15 @@ fn build_config(state: &Win32State) -> AppConfig {
                 block_title_query: block_title,
                 limit_cursor_jumps: limit_cursor,
                 clear_mouse_on_reset: clear_mouse,
+                allow_notifications: notifications,
+                allow_clipboard_read: clipboard_read,
+                allow_graphics_files: graphics_files,
             },
             language: crate::i18n::Locale::from_index(
                 SendMessageW(state.language_combo, CB_GETCURSEL, 0, 0) as usize,
             ),
             updates: UpdatesConfig { auto_check },
+            ..state.base.clone()
         }
     }
 }
@@ -1319,6 +1362,7 @@ fn infer_security_mode(state: &Win32State) {
             block_title_query: SendMessageW(state.block_title_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize,
             limit_cursor_jumps: SendMessageW(state.limit_cursor_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize,
             clear_mouse_on_reset: SendMessageW(state.clear_mouse_check, BM_GETCHECK, 0, 0) == BST_CHECKED as isize,
+            ..SecuritySettings::default()
         };
         let inferred = settings.inferred_mode();
         let new_index = match inferred {
@@ -1378,6 +1422,12 @@ fn reset_controls(state: &Win32State) {
         };
         SendMessageW(state.security_mode_combo, CB_SETCURSEL, mode_idx, 0);
         apply_security_preset(state);
+        let notifications_state = if d.security.allow_notifications { BST_CHECKED } else { BST_UNCHECKED };
+        SendMessageW(state.notifications_check, BM_SETCHECK, notifications_state, 0);
+        let clipboard_read_state = if d.security.allow_clipboard_read { BST_CHECKED } else { BST_UNCHECKED };
+        SendMessageW(state.clipboard_read_check, BM_SETCHECK, clipboard_read_state, 0);
+        let graphics_files_state = if d.security.allow_graphics_files { BST_CHECKED } else { BST_UNCHECKED };
+        SendMessageW(state.graphics_files_check, BM_SETCHECK, graphics_files_state, 0);
 
         // Updates
         let auto_check_state = if d.updates.auto_check { BST_CHECKED } else { BST_UNCHECKED };
@@ -1,4 +1,4 @@
-use super::super::shared::banner_layout::UpdateBannerLayout;
+use super::super::shared::banner_layout::{ClipboardPromptLayout, UpdateBannerLayout};
 use super::super::types::RenderTarget;
 use super::CpuRenderer;
 
@@ -9,25 +9,59 @@ impl CpuRenderer {
         target: &mut RenderTarget<'_>,
         layout: &UpdateBannerLayout,
     ) {
-        self.draw_banner_bg(target, layout);
+        self.draw_banner_bg(target, layout.bg_rect(), layout.radius);
         self.draw_banner_labels(target, layout);
     }
 
-    /// Draws the banner background (and border) rounded rect.
+    /// Draws the clipboard read prompt using the CPU renderer.
+    pub fn draw_clipboard_prompt(
+        &mut self,
+        target: &mut RenderTarget<'_>,
+        layout: &ClipboardPromptLayout,
+    ) {
+        self.draw_banner_bg(target, layout.bg_rect(), layout.radius);
+        let (label_text, label_x, label_y) = layout.label();
+        self.draw_text_at(target, label_x, label_y, label_text, self.palette.default_fg);
+
+        let t = crate::i18n::t();
+        let buttons = [
+            (layout.allow_rect(), t.clipboard_read_allow),
+            (layout.deny_rect(), t.clipboard_read_deny),
+        ];
+        for ((bx, by, bw, bh), text) in buttons {
+            #[cfg(not(target_os = "macos"))]
+            self.draw_banner_button_bg(target, bx, by, bw, bh, layout.radius);
+            let (tx, ty) = crate::gui::renderer::shared::centered_button_text_origin(
+                bx,
+                by,
+                bw,
+                bh,
+                text,
+                self.metrics.cell_width,
+                self.metrics.cell_height,
+            );
+            self.draw_text_at(target, tx, ty, text, self.palette.default_fg);
+        }
+    }
+
+    /// Draws the banner background (and border) rounded rect; `bg` is
+    /// `(x, y, w, h)`.
     ///
     /// On non-macOS, uses `draw_rounded_rect` with palette tab colors.
     /// On macOS, writes directly to the pixel buffer using `default_bg`.
-    fn draw_banner_bg(&self, target: &mut RenderTarget<'_>, layout: &UpdateBannerLayout) {
+    fn draw_banner_bg(&self, target: &mut RenderTarget<'_>, bg: (i32, i32, u32, u32), radius: u32) {
+        let (bg_x, bg_y, bg_w, bg_h) = bg;
         #[cfg(not(target_os = "macos"))]
-        self.draw_overlay_box(target, layout.bg_x, layout.bg_y, layout.bg_w, layout.bg_h, layout.radius);
+        self.draw_overlay_box(target, bg_x, bg_y, bg_w, bg_h, radius);
 
         #[cfg(target_os = "macos")]
         {
+            let _ = radius;
             let bg_pixel = self.palette.default_bg.to_pixel();
-            let x0 = layout.bg_x.max(0) as usize;
-            let y0 = layout.bg_y.max(0) as usize;
-            let x1 = (layout.bg_x + layout.bg_w as i32).clamp(0, target.width as i32) as usize;
-            let y1 = (layout.bg_y + layout.bg_h as i32).clamp(0, target.height as i32) as usize;
+            let x0 = bg_x.max(0) as usize;
+            let y0 = bg_y.max(0) as usize;
+            let x1 = (bg_x + bg_w as i32).clamp(0, target.width as i32) as usize;
+            let y1 = (bg_y + bg_h as i32).clamp(0, target.height as i32) as usize;
             for py in y0..y1 {
                 for px in x0..x1 {
                     let idx = py * target.width + px;
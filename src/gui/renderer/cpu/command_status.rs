@@ -0,0 +1,68 @@
+use crate::core::PageList;
+use crate::gui::pane::PaneRect;
+
+use super::super::shared::command_status_layout::{status_color, visible_status_markers};
+use super::super::shared::scrollbar_math;
+use super::super::{
+    RenderTarget, STATUS_MARKER_WIDTH, STATUS_TICK_HEIGHT, ScrollbarState, blend_rgb,
+};
+use super::CpuRenderer;
+
+impl CpuRenderer {
+    /// Draws the exit status markers of the displayed prompt rows, with
+    /// `rect` as the grid origin.
+    pub(in crate::gui::renderer) fn draw_status_markers(
+        &self,
+        target: &mut RenderTarget<'_>,
+        screen: &PageList,
+        scroll_offset: usize,
+        rect: PaneRect,
+    ) {
+        let marker_width = self.scaled_px(STATUS_MARKER_WIDTH);
+        let markers = visible_status_markers(
+            screen,
+            scroll_offset,
+            rect,
+            self.metrics.cell_height,
+            marker_width,
+        );
+        for marker in markers {
+            let pixel = status_color(&self.palette, !marker.status.succeeded()).to_pixel();
+            let x1 = ((marker.x + marker.width) as usize).min(target.width);
+            let y1 = ((marker.y + marker.height) as usize).min(target.height);
+            for py in marker.y as usize..y1 {
+                for px in marker.x as usize..x1 {
+                    target.buffer[py * target.width + px] = pixel;
+                }
+            }
+        }
+    }
+
+    /// Draws the command status ticks of `state` across the scrollbar track,
+    /// spanning `x0..x1` and faded like the thumb.
+    pub(in crate::gui::renderer) fn draw_scrollbar_ticks(
+        &self,
+        target: &mut RenderTarget<'_>,
+        state: &ScrollbarState,
+        (track_top, track_bottom): (f32, f32),
+        (x0, x1): (usize, usize),
+    ) {
+        let alpha = (255.0 * state.opacity).clamp(0.0, 255.0) as u8;
+        let total_rows = state.scrollback_len + state.grid_rows;
+        let tick_height = self.scaled_px(STATUS_TICK_HEIGHT) as f32;
+        let x1 = x1.min(target.width);
+        for &(abs_row, failed) in &state.command_ticks {
+            let center =
+                scrollbar_math::scrollbar_tick_y(track_top, track_bottom, abs_row, total_rows);
+            let y0 = (center - tick_height / 2.0).max(track_top).round() as usize;
+            let y1 = ((y0 as f32 + tick_height) as usize).min(target.height);
+            let pixel = status_color(&self.palette, failed).to_pixel();
+            for py in y0..y1 {
+                for px in x0..x1 {
+                    let idx = py * target.width + px;
+                    target.buffer[idx] = blend_rgb(target.buffer[idx], pixel, alpha);
+                }
+            }
+        }
+    }
+}
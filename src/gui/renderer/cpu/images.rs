@@ -0,0 +1,54 @@
+use crate::core::PageList;
+use crate::gui::pane::PaneRect;
+
+use super::super::shared::image_layout::{ImageBlit, ImageLayers, visible_image_layers};
+use super::super::{RenderTarget, blend_rgb};
+use super::CpuRenderer;
+
+impl CpuRenderer {
+    /// Collects the inline images attached to the displayed rows, with `rect`
+    /// as the grid origin and clip rectangle.
+    pub(in crate::gui::renderer) fn image_layers<'a>(
+        &self,
+        screen: &'a PageList,
+        scroll_offset: usize,
+        rect: PaneRect,
+    ) -> ImageLayers<'a> {
+        visible_image_layers(
+            screen,
+            scroll_offset,
+            rect,
+            self.metrics.cell_width,
+            self.metrics.cell_height,
+        )
+    }
+
+    /// Alpha-blends `blits` (in order) over the already drawn pixels,
+    /// clipped to `clip`.
+    pub(in crate::gui::renderer) fn draw_image_blits(
+        &self,
+        target: &mut RenderTarget<'_>,
+        blits: &[ImageBlit<'_>],
+        clip: PaneRect,
+    ) {
+        let clip_right = ((clip.x + clip.width) as usize).min(target.width);
+        let clip_bottom = ((clip.y + clip.height) as usize).min(target.height);
+        for blit in blits {
+            let x0 = (blit.x as usize).max(clip.x as usize);
+            let y0 = (blit.y as usize).max(clip.y as usize);
+            let x1 = ((blit.x + blit.width) as usize).min(clip_right);
+            let y1 = ((blit.y + blit.height) as usize).min(clip_bottom);
+            for py in y0..y1 {
+                for px in x0..x1 {
+                    let [r, g, b, a] = blit.pixel((px - blit.x as usize) as u32, (py - blit.y as usize) as u32);
+                    if a == 0 {
+                        continue;
+                    }
+                    let idx = py * target.width + px;
+                    let rgb = (r as u32) << 16 | (g as u32) << 8 | b as u32;
+                    target.buffer[idx] = blend_rgb(target.buffer[idx], rgb, a);
+                }
+            }
+        }
+    }
+}
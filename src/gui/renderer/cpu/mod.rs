@@ -1,5 +1,8 @@
 mod banner;
+mod command_status;
+mod images;
 pub(super) mod primitives;
+mod search;
 mod trait_impl;
 
 use std::collections::HashMap;
@@ -8,6 +11,7 @@ use crate::config::{AppConfig, ThemePalette, load_fonts};
 use crate::core::Color;
 use super::rasterizer::{GlyphRasterizer, RasterMode, RasterizedGlyph};
 use super::metrics::FontMetrics;
+use super::PaneColors;
 
 /// CPU-based software renderer using softbuffer pixel buffers.
 pub struct CpuRenderer {
@@ -19,6 +23,10 @@ pub struct CpuRenderer {
     /// Avoids a `powf` call per pixel in the glyph blend inner loop.
     pub(super) linear_to_srgb: [u8; 256],
     pub(super) palette:        ThemePalette,
+    /// Whether blinking text is in its visible phase this frame.
+    pub(super) blink_visible:  bool,
+    /// Default colours of the pane being drawn.
+    pub(super) pane_colors:    PaneColors,
 }
 
 fn build_srgb_lut() -> [f32; 256] {
@@ -55,7 +63,9 @@ impl CpuRenderer {
             glyph_cache: HashMap::new(),
             srgb_to_linear: build_srgb_lut(),
             linear_to_srgb: build_linear_to_srgb_lut(),
+            pane_colors: PaneColors::from_theme(&palette),
             palette,
+            blink_visible: true,
         }
     }
 
@@ -67,6 +77,7 @@ impl CpuRenderer {
         self.metrics.update_bases(config);
         self.recompute_metrics();
         self.palette = config.theme.resolve();
+        self.pane_colors = PaneColors::from_theme(&self.palette);
     }
 
     pub(super) fn recompute_metrics(&mut self) {
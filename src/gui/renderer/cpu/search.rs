@@ -0,0 +1,126 @@
+use super::super::shared::search_layout::{
+    SearchBarLayout, SearchHighlight, search_highlight_style,
+};
+use super::super::{RenderTarget, blend_rgb};
+use super::CpuRenderer;
+
+impl CpuRenderer {
+    /// Blends the search match tints over the already drawn grid.
+    pub(in crate::gui::renderer) fn draw_search_highlights(
+        &self,
+        target: &mut RenderTarget<'_>,
+        highlights: &[SearchHighlight],
+    ) {
+        for highlight in highlights {
+            let (color, alpha) = search_highlight_style(&self.palette, highlight.current);
+            let pixel = color.to_pixel();
+            let x1 = ((highlight.x + highlight.width) as usize).min(target.width);
+            let y1 = ((highlight.y + highlight.height) as usize).min(target.height);
+            for py in highlight.y as usize..y1 {
+                for px in highlight.x as usize..x1 {
+                    let idx = py * target.width + px;
+                    target.buffer[idx] = blend_rgb(target.buffer[idx], pixel, alpha);
+                }
+            }
+        }
+    }
+
+    /// Draws the search bar: query with caret, match count and option toggles.
+    pub(in crate::gui::renderer) fn draw_search_bar(
+        &mut self,
+        target: &mut RenderTarget<'_>,
+        layout: &SearchBarLayout,
+    ) {
+        self.draw_search_bar_bg(target, layout);
+
+        let fg = self.palette.tab_text_active;
+        self.draw_text_at(
+            target,
+            layout.query_x,
+            layout.text_y,
+            &layout.query_text,
+            fg,
+        );
+        self.fill_rect(
+            target,
+            (layout.caret_x, layout.text_y),
+            (layout.caret_w, self.metrics.cell_height),
+            fg.to_pixel(),
+        );
+
+        let status_fg = if layout.status_error {
+            self.palette.ansi[1]
+        } else {
+            self.palette.tab_text_inactive
+        };
+        self.draw_text_at(
+            target,
+            layout.status_x,
+            layout.text_y,
+            &layout.status_text,
+            status_fg,
+        );
+
+        for flag in &layout.flags {
+            if flag.active {
+                self.fill_rect(
+                    target,
+                    (flag.x, flag.y),
+                    (flag.w, flag.h),
+                    self.palette.tab_border.to_pixel(),
+                );
+            }
+            let (tx, ty) = crate::gui::renderer::shared::centered_button_text_origin(
+                flag.x,
+                flag.y,
+                flag.w,
+                flag.h,
+                flag.label,
+                self.metrics.cell_width,
+                self.metrics.cell_height,
+            );
+            let label_fg = if flag.active {
+                fg
+            } else {
+                self.palette.tab_text_inactive
+            };
+            self.draw_text_at(target, tx, ty, flag.label, label_fg);
+        }
+    }
+
+    fn draw_search_bar_bg(&self, target: &mut RenderTarget<'_>, layout: &SearchBarLayout) {
+        #[cfg(not(target_os = "macos"))]
+        self.draw_overlay_box(
+            target,
+            layout.bg_x as i32,
+            layout.bg_y as i32,
+            layout.bg_w,
+            layout.bg_h,
+            layout.radius,
+        );
+
+        #[cfg(target_os = "macos")]
+        self.fill_rect(
+            target,
+            (layout.bg_x, layout.bg_y),
+            (layout.bg_w, layout.bg_h),
+            self.palette.default_bg.to_pixel(),
+        );
+    }
+
+    fn fill_rect(
+        &self,
+        target: &mut RenderTarget<'_>,
+        (x, y): (u32, u32),
+        (w, h): (u32, u32),
+        pixel: u32,
+    ) {
+        let x1 = ((x + w) as usize).min(target.width);
+        let y1 = ((y + h) as usize).min(target.height);
+        for py in y as usize..y1 {
+            for px in x as usize..x1 {
+                target.buffer[py * target.width + px] = pixel;
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use crate::core::{CursorStyle, PageList, Selection};
 use crate::gui::pane::PaneRect;
 
+use super::super::shared::link_layout::UnderlineBar;
+use super::super::shared::search_layout::{SearchBarLayout, SearchHighlight};
 use super::super::traits;
 #[cfg(not(target_os = "macos"))]
 use super::super::types::TabBarDrawParams;
 #[cfg(not(target_os = "macos"))]
 use super::super::TabInfo;
-use super::super::{RenderTarget, ScrollbarState};
+use super::super::{PaneColors, RenderTarget, ScrollbarState};
 use super::CpuRenderer;
 
 impl traits::Renderer for CpuRenderer {
@@ -69,6 +71,14 @@ impl traits::Renderer for CpuRenderer {
         CpuRenderer::render(self, target, screen, selection, scroll_offset);
     }
 
+    fn set_blink_visible(&mut self, visible: bool) {
+        self.blink_visible = visible;
+    }
+
+    fn set_pane_colors(&mut self, colors: PaneColors) {
+        self.pane_colors = colors;
+    }
+
     fn draw_cursor(
         &mut self,
         target: &mut RenderTarget<'_>,
@@ -159,4 +169,32 @@ impl traits::Renderer for CpuRenderer {
     ) {
         CpuRenderer::draw_update_banner(self, target, layout);
     }
+
+    fn draw_link_underline(&mut self, target: &mut RenderTarget<'_>, bars: &[UnderlineBar]) {
+        CpuRenderer::draw_link_underline(self, target, bars);
+    }
+
+    fn draw_search_highlights(&mut self, target: &mut RenderTarget<'_>, highlights: &[SearchHighlight]) {
+        CpuRenderer::draw_search_highlights(self, target, highlights);
+    }
+
+    fn draw_bell_flash(&mut self, target: &mut RenderTarget<'_>, rect: PaneRect, alpha: f32) {
+        CpuRenderer::draw_bell_flash(self, target, rect, alpha);
+    }
+
+    fn draw_search_bar(&mut self, target: &mut RenderTarget<'_>, layout: &SearchBarLayout) {
+        CpuRenderer::draw_search_bar(self, target, layout);
+    }
+
+    fn draw_clipboard_prompt(
+        &mut self,
+        target: &mut RenderTarget<'_>,
+        layout: &super::super::shared::banner_layout::ClipboardPromptLayout,
+    ) {
+        CpuRenderer::draw_clipboard_prompt(self, target, layout);
+    }
+
+    fn draw_vi_cursor(&mut self, target: &mut RenderTarget<'_>, x: u32, y: u32, ch: char) {
+        CpuRenderer::draw_vi_cursor(self, target, x, y, ch);
+    }
 }
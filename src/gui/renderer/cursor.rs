@@ -1,6 +1,6 @@
 use super::*;
 use super::RenderTarget;
-use crate::core::PageList;
+use crate::core::{Color, PageList};
 use super::super::pane::PaneRect;
 
 /// A pixel-space rectangle used as a fill target or clip boundary inside the cursor renderer.
@@ -60,10 +60,10 @@ impl CpuRenderer {
         match style {
             CursorStyle::BlinkingBlock | CursorStyle::SteadyBlock => {
                 // Filled block with inverted foreground/background.
-                self.draw_bg(target, x, y, self.palette.default_fg);
+                self.draw_bg(target, x, y, Color::from_pixel(cursor_pixel));
                 let ch = block_char.unwrap_or(' ');
                 if ch != ' ' {
-                    self.draw_char(target, x, y, ch, self.palette.default_bg);
+                    self.draw_char(target, x, y, ch, self.pane_colors.bg);
                 }
             }
             CursorStyle::BlinkingUnderline | CursorStyle::SteadyUnderline => {
@@ -101,13 +101,20 @@ impl CpuRenderer {
         let y = row as u32 * self.metrics.cell_height
             + self.tab_bar_height_px()
             + self.window_padding_px();
-        let cursor_pixel = self.palette.default_fg.to_pixel();
+        let cursor_pixel = self.pane_colors.cursor.to_pixel();
         let block_char = block_char_at(screen, row, col);
         // No clip rectangle: use the full buffer.
         let clip = PixelRect { x: 0, y: 0, w: target.width, h: target.height };
         self.draw_cursor_shape(target, (x, y), cursor_pixel, clip, block_char, style);
     }
 
+    /// Draws the vi copy-mode cursor: a block over `ch` at pixel `(x, y)`.
+    pub fn draw_vi_cursor(&mut self, target: &mut RenderTarget<'_>, x: u32, y: u32, ch: char) {
+        let cursor_pixel = self.pane_colors.cursor.to_pixel();
+        let clip = PixelRect { x: 0, y: 0, w: target.width, h: target.height };
+        self.draw_cursor_shape(target, (x, y), cursor_pixel, clip, Some(ch), CursorStyle::SteadyBlock);
+    }
+
     /// Draws the cursor at a position offset by a pane rectangle.
     pub fn draw_cursor_in_rect(
         &mut self,
@@ -120,7 +127,7 @@ impl CpuRenderer {
     ) {
         let x = col as u32 * self.metrics.cell_width + rect.x;
         let y = row as u32 * self.metrics.cell_height + rect.y;
-        let cursor_pixel = self.palette.default_fg.to_pixel();
+        let cursor_pixel = self.pane_colors.cursor.to_pixel();
         let block_char = block_char_at(screen, row, col);
         let clip = PixelRect {
             x: rect.x as usize,
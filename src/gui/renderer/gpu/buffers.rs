@@ -3,7 +3,7 @@
 //! All structs are `#[repr(C)]` with `bytemuck::Pod` so they can be
 //! uploaded to wgpu buffers with zero-copy.
 
-/// Packed terminal cell for the grid compute shader (16 bytes).
+/// Packed terminal cell for the grid compute shader (20 bytes).
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct PackedCell {
@@ -20,8 +20,15 @@ pub struct PackedCell {
     ///   bit 3: reverse video
     ///   bit 4: dim
     ///   bit 5: strikethrough
-    ///   bits 6-7: underline style (0=none, 1=single, 2=double, 3=curly)
+    ///   bit 6: overline
+    ///   bit 7: `underline_color` is set
+    ///   bit 8: wide-right spacer
+    ///   bit 9: image underlay
+    ///   bits 10-12: underline style (0=none, 1=single, 2=double, 3=curly,
+    ///               4=dotted, 5=dashed)
     pub attrs: u32,
+    /// Underline color as 0xRRGGBB; the foreground is used when bit 7 is clear.
+    pub underline_color: u32,
 }
 
 /// Grid uniforms uploaded once per frame (48 bytes, 16-byte aligned).
@@ -17,31 +17,16 @@ impl super::GpuRenderer {
         let y = row as f32 * self.metrics.cell_height as f32 + origin_y;
         let cw = self.metrics.cell_width as f32;
         let ch = self.metrics.cell_height as f32;
-        let cursor_color = self.palette.default_fg.to_pixel();
+        let cursor_color = self.pane_colors.cursor.to_pixel();
 
         match style {
             CursorStyle::BlinkingBlock | CursorStyle::SteadyBlock => {
-                self.push_rect(x, y, cw, ch, cursor_color, 1.0);
                 let ch = if row < screen.viewport_rows() && col < screen.cols() {
                     screen.viewport_get(row, col).first_char()
                 } else {
                     ' '
                 };
-                if ch != ' ' {
-                    let cp = ch as u32;
-                    let info = self.get_or_insert_glyph(cp);
-                    if info.w > 0.0 && info.h > 0.0 {
-                        let gx = x + info.offset_x;
-                        let gy = y + info.offset_y;
-                        self.push_glyph(
-                            gx,
-                            gy,
-                            (info.x, info.y, info.w, info.h),
-                            self.palette.default_bg.to_pixel(),
-                            1.0,
-                        );
-                    }
-                }
+                self.draw_block_cursor(x, y, ch);
             }
             CursorStyle::BlinkingUnderline | CursorStyle::SteadyUnderline => {
                 self.push_rect(x, y + ch - 2.0, cw, 2.0, cursor_color, 1.0);
@@ -52,6 +37,25 @@ impl super::GpuRenderer {
         }
     }
 
+    /// Filled block in the cursor colour with `ch` drawn inverted on top.
+    pub(super) fn draw_block_cursor(&mut self, x: f32, y: f32, ch: char) {
+        let cw = self.metrics.cell_width as f32;
+        let cell_h = self.metrics.cell_height as f32;
+        self.push_rect(x, y, cw, cell_h, self.pane_colors.cursor.to_pixel(), 1.0);
+        if ch != ' ' {
+            let info = self.get_or_insert_glyph(ch as u32);
+            if info.w > 0.0 && info.h > 0.0 {
+                self.push_glyph(
+                    x + info.offset_x,
+                    y + info.offset_y,
+                    (info.x, info.y, info.w, info.h),
+                    self.pane_colors.bg.to_pixel(),
+                    1.0,
+                );
+            }
+        }
+    }
+
     pub(super) fn draw_cursor_impl(
         &mut self,
         row: usize,
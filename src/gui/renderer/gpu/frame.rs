@@ -79,6 +79,10 @@ impl super::GpuRenderer {
                 continue;
             }
 
+            for upload in &batch.underlays {
+                self.write_image_upload(upload);
+            }
+
             let needed = batch.cells.len() * size_of::<PackedCell>();
             if needed as u64 > self.grid_cell_buffer.size() {
                 self.grid_cell_buffer =
@@ -103,6 +107,8 @@ impl super::GpuRenderer {
             self.encode_grid_batch_pass(&mut encoder, batch.dispatch_width, batch.dispatch_height);
             self.queue.submit(std::iter::once(encoder.finish()));
         }
+
+        self.upload_images();
     }
 
     /// Uploads UI commands and composite uniforms to the GPU.
@@ -161,6 +167,7 @@ impl super::GpuRenderer {
                         self.commands.clear();
                         self.grid_dirty = false;
                         self.grid_batches.clear();
+                        self.image_uploads.clear();
                         return None;
                     }
                 }
@@ -197,6 +204,7 @@ impl super::GpuRenderer {
         self.commands.clear();
         self.grid_dirty = false;
         self.grid_batches.clear();
+        self.image_uploads.clear();
     }
 
     /// Resizes the surface and internal textures.
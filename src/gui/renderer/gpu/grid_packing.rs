@@ -1,8 +1,9 @@
 //! Packs terminal grid cells into GPU buffer format.
 
-use crate::core::{Color, PageList, Selection, UnderlineStyle};
+use crate::core::{CellEffects, Color, PageList, Selection, UnderlineStyle};
 use crate::gui::pane::PaneRect;
 use crate::gui::renderer::rasterizer::RasterMode;
+use crate::gui::renderer::shared::image_layout::cell_has_underlay;
 
 use super::GridBatch;
 use super::buffers::{GridUniforms, PackedCell};
@@ -14,9 +15,17 @@ const ATTR_UNDERLINE: u32 = 1 << 2;
 const ATTR_REVERSE: u32 = 1 << 3;
 const ATTR_DIM: u32 = 1 << 4;
 const ATTR_STRIKETHROUGH: u32 = 1 << 5;
+const ATTR_OVERLINE: u32 = 1 << 6;
+/// Set when the cell carries its own underline color.
+const ATTR_UNDERLINE_COLOR: u32 = 1 << 7;
 /// Set on spacer cells (right half of a wide char) so the shader can
 /// offset the glyph sample by one cell width.
 const ATTR_WIDE_RIGHT: u32 = 1 << 8;
+/// Set on cells showing a negative z-index image: the shader keeps the
+/// image already in the texture and only blends the text over it.
+const ATTR_IMAGE_UNDERLAY: u32 = 1 << 9;
+/// Underline style (`UnderlineStyle` discriminant) occupies bits 10-12.
+const ATTR_UNDERLINE_STYLE_SHIFT: u32 = 10;
 
 impl super::GpuRenderer {
     /// Returns `1` if the current rasterizer mode is LCD subpixel, `0` for grayscale.
@@ -55,6 +64,7 @@ impl super::GpuRenderer {
                 fg: bg,
                 bg,
                 attrs: 0,
+                underline_color: 0,
             }],
             uniforms: GridUniforms {
                 cols: 1,
@@ -72,6 +82,7 @@ impl super::GpuRenderer {
             },
             dispatch_width: terminal_width,
             dispatch_height: terminal_height,
+            underlays: Vec::new(),
         });
     }
 
@@ -95,12 +106,16 @@ impl super::GpuRenderer {
             for col in 0..cols {
                 let cell = super::super::display_cell(screen, scroll_offset, row, col);
                 let selected = selection.is_some_and(|s| s.contains(abs_row, col));
+                // Concealed text and blinking text in its off phase keep only
+                // their background.
+                let hidden = cell.effects.contains(CellEffects::HIDDEN)
+                    || (cell.effects.contains(CellEffects::BLINK) && !self.blink_visible);
 
                 let (codepoint, attrs_wide) = if cell.width == 0 {
                     // Spacer cell: use the previous cell's codepoint and mark as wide-right.
                     (prev_codepoint, ATTR_WIDE_RIGHT)
                 } else {
-                    let cp = cell.first_char() as u32;
+                    let cp = if hidden { 32 } else { cell.first_char() as u32 };
                     prev_codepoint = cp;
                     (cp, 0u32)
                 };
@@ -113,15 +128,26 @@ impl super::GpuRenderer {
                 let mut attrs = attrs_wide;
                 if cell.bold { attrs |= ATTR_BOLD; }
                 if cell.italic { attrs |= ATTR_ITALIC; }
-                if cell.underline_style != UnderlineStyle::None { attrs |= ATTR_UNDERLINE; }
                 if cell.reverse { attrs |= ATTR_REVERSE; }
                 if cell.dim { attrs |= ATTR_DIM; }
-                if cell.strikethrough { attrs |= ATTR_STRIKETHROUGH; }
-                // Encode underline style in bits 6-7
-                attrs |= (cell.underline_style as u32 & 3) << 6;
+                if !hidden {
+                    if cell.underline_style != UnderlineStyle::None { attrs |= ATTR_UNDERLINE; }
+                    if cell.strikethrough { attrs |= ATTR_STRIKETHROUGH; }
+                    if cell.effects.contains(CellEffects::OVERLINE) { attrs |= ATTR_OVERLINE; }
+                    attrs |= (cell.underline_style as u32) << ATTR_UNDERLINE_STYLE_SHIFT;
+                }
+                let mut underline_color = 0;
+                if let Some(color) = cell.underline_color {
+                    attrs |= ATTR_UNDERLINE_COLOR;
+                    let color = if fg_dim > 0.0 { color.dimmed(fg_dim) } else { color };
+                    underline_color = color.to_pixel();
+                }
+                if cell_has_underlay(screen, scroll_offset, row, col) {
+                    attrs |= ATTR_IMAGE_UNDERLAY;
+                }
 
-                let mut fg = if cell.fg == Color::SENTINEL_FG { self.palette.default_fg } else { cell.fg };
-                let bg_color = if cell.bg == Color::SENTINEL_BG { self.palette.default_bg } else { cell.bg };
+                let mut fg = if cell.fg == Color::SENTINEL_FG { self.pane_colors.fg } else { cell.fg };
+                let bg_color = if cell.bg == Color::SENTINEL_BG { self.pane_colors.bg } else { cell.bg };
                 if cell.bold {
                     fg = fg.bold_bright_with_palette(&self.palette.ansi);
                 }
@@ -142,6 +168,7 @@ impl super::GpuRenderer {
                     fg: fg.to_pixel(),
                     bg,
                     attrs,
+                    underline_color,
                 });
             }
         }
@@ -178,6 +205,8 @@ impl super::GpuRenderer {
 
         let is_lcd = self.is_lcd_flag();
         let cells = self.pack_grid_cells(screen, selection, scroll_offset, fg_dim);
+        let (underlays, overlays) = self.collect_image_uploads(screen, scroll_offset, region);
+        self.image_uploads.extend(overlays);
         self.grid_batches.push(GridBatch {
             cells,
             uniforms: GridUniforms {
@@ -196,6 +225,7 @@ impl super::GpuRenderer {
             },
             dispatch_width,
             dispatch_height,
+            underlays,
         });
     }
 }
@@ -0,0 +1,101 @@
+//! Inline image uploads into the grid texture.
+//!
+//! Images with a non-negative z-index are copied straight into the grid
+//! texture after the grid batches have run, so they cover the cells beneath
+//! them exactly like the CPU path.  Negative z-index images are copied before
+//! their batch; the grid shader then blends text over them.
+
+use wgpu;
+
+use crate::core::PageList;
+use crate::gui::pane::PaneRect;
+
+use super::super::blend_rgb;
+use super::super::shared::image_layout::{ImageBlit, visible_image_layers};
+
+/// Image pixels waiting to be copied into the grid texture this frame.
+pub(super) struct ImageUpload {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    /// Opaque RGBA8 rows, already blended over the default background.
+    rgba: Vec<u8>,
+}
+
+impl super::GpuRenderer {
+    /// Builds uploads for the inline images attached to the displayed rows
+    /// of `screen`, returned as `(underlays, overlays)`.
+    ///
+    /// `region` is in grid-texture coordinates, as for `queue_grid_batch`.
+    /// A texture copy cannot blend, so translucent pixels are composited over
+    /// the theme background here; where strips overlap, the later one in
+    /// z order wins.
+    pub(super) fn collect_image_uploads(
+        &self,
+        screen: &PageList,
+        scroll_offset: usize,
+        region: PaneRect,
+    ) -> (Vec<ImageUpload>, Vec<ImageUpload>) {
+        let clip = PaneRect {
+            x: region.x,
+            y: region.y,
+            width: region.width.min(self.width.saturating_sub(region.x)),
+            height: region.height.min(self.height.saturating_sub(region.y)),
+        };
+        let layers = visible_image_layers(
+            screen,
+            scroll_offset,
+            clip,
+            self.metrics.cell_width,
+            self.metrics.cell_height,
+        );
+        let bg = self.palette.default_bg.to_pixel();
+        let upload = |blit: &ImageBlit<'_>| {
+            let mut rgba = Vec::with_capacity((blit.width * blit.height * 4) as usize);
+            for dy in 0..blit.height {
+                for dx in 0..blit.width {
+                    let [r, g, b, a] = blit.pixel(dx, dy);
+                    let rgb = (r as u32) << 16 | (g as u32) << 8 | b as u32;
+                    let out = blend_rgb(bg, rgb, a);
+                    rgba.extend_from_slice(&[(out >> 16) as u8, (out >> 8) as u8, out as u8, 255]);
+                }
+            }
+            ImageUpload { x: blit.x, y: blit.y, width: blit.width, height: blit.height, rgba }
+        };
+        (
+            layers.under.iter().map(upload).collect(),
+            layers.over.iter().map(upload).collect(),
+        )
+    }
+
+    /// Copies queued overlay image pixels into the grid texture.
+    ///
+    /// Texture writes are applied at the start of the next queue submission,
+    /// i.e. after every grid batch already submitted this frame.
+    pub(super) fn upload_images(&mut self) {
+        let uploads = std::mem::take(&mut self.image_uploads);
+        for upload in &uploads {
+            self.write_image_upload(upload);
+        }
+    }
+
+    /// Schedules one texture copy for the next queue submission.
+    pub(super) fn write_image_upload(&self, upload: &ImageUpload) {
+        self.queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture:   &self.grid_texture,
+                mip_level: 0,
+                origin:    wgpu::Origin3d { x: upload.x, y: upload.y, z: 0 },
+                aspect:    wgpu::TextureAspect::All,
+            },
+            &upload.rgba,
+            wgpu::TexelCopyBufferLayout {
+                offset:         0,
+                bytes_per_row:  Some(upload.width * 4),
+                rows_per_image: Some(upload.height),
+            },
+            wgpu::Extent3d { width: upload.width, height: upload.height, depth_or_array_layers: 1 },
+        );
+    }
+}
@@ -11,6 +11,7 @@ mod cursors;
 mod frame;
 mod gpu_passes;
 mod grid_packing;
+mod images;
 mod overlays;
 pub mod pipelines;
 mod scrollbar;
@@ -30,6 +31,7 @@ use wgpu;
 use crate::config::ThemePalette;
 use super::rasterizer::GlyphRasterizer;
 use super::metrics::FontMetrics;
+use super::PaneColors;
 
 use atlas::GlyphAtlas;
 use buffers::*;
@@ -46,6 +48,8 @@ struct GridBatch {
     uniforms: GridUniforms,
     dispatch_width: u32,
     dispatch_height: u32,
+    /// Negative z-index images, written before the batch so text draws over them.
+    underlays: Vec<images::ImageUpload>,
 }
 
 /// GPU-based renderer using wgpu compute and render pipelines.
@@ -97,6 +101,12 @@ pub struct GpuRenderer {
     // Grid batches for the current frame (single pane = 1 batch + clear).
     grid_batches: Vec<GridBatch>,
     grid_dirty: bool,
+    // Whether blinking text is in its visible phase this frame.
+    blink_visible: bool,
+    // Default colours of the pane being drawn.
+    pane_colors: PaneColors,
+    // Inline image pixels copied into the grid texture after the grid batches.
+    image_uploads: Vec<images::ImageUpload>,
 
     // Window dimensions
     width: u32,
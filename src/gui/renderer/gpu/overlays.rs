@@ -8,8 +8,40 @@ use super::super::types::DragPosition;
 use super::super::types::RoundedRectCmd;
 #[cfg(not(target_os = "macos"))]
 use super::super::TabInfo;
+use super::super::STATUS_MARKER_WIDTH;
+use super::super::shared::command_status_layout::{status_color, visible_status_markers};
+use super::super::shared::link_layout::UnderlineBar;
+use super::super::shared::search_layout::{
+    SearchBarLayout, SearchHighlight, search_highlight_style,
+};
+use crate::core::PageList;
+use crate::gui::pane::PaneRect;
 
 impl super::GpuRenderer {
+    /// Queues the exit status markers of the displayed prompt rows; `rect`
+    /// is the grid rectangle in window coordinates.
+    pub(super) fn draw_status_markers_impl(
+        &mut self,
+        screen: &PageList,
+        scroll_offset: usize,
+        rect: PaneRect,
+    ) {
+        let marker_width = self.metrics.scaled_px(STATUS_MARKER_WIDTH);
+        let markers =
+            visible_status_markers(screen, scroll_offset, rect, self.metrics.cell_height, marker_width);
+        for marker in markers {
+            let color = status_color(&self.palette, !marker.status.succeeded()).to_pixel();
+            self.push_rect(
+                marker.x as f32,
+                marker.y as f32,
+                marker.width as f32,
+                marker.height as f32,
+                color,
+                1.0,
+            );
+        }
+    }
+
     #[cfg(not(target_os = "macos"))]
     pub(super) fn draw_tab_drag_overlay_impl(
         &mut self,
@@ -190,3 +222,158 @@ impl super::GpuRenderer {
         }
     }
 }
+
+impl super::GpuRenderer {
+    pub(super) fn draw_clipboard_prompt_impl(
+        &mut self,
+        layout: &super::super::shared::banner_layout::ClipboardPromptLayout,
+    ) {
+        let (x, y, w, h) = layout.bg_rect();
+        let (x, y, w, h) = (x as f32, y as f32, w as f32, h as f32);
+        let r = layout.radius as f32;
+
+        // Background fill and subtle border, as for the update banner.
+        self.push_rounded_rect_cmd(&RoundedRectCmd {
+            x, y, w, h, radius: r, color: self.palette.active_tab_bg.to_pixel(), opacity: 0.96,
+        });
+        self.push_rounded_rect_cmd(&RoundedRectCmd {
+            x, y, w, h, radius: r, color: self.palette.tab_border.to_pixel(), opacity: 0.31,
+        });
+
+        let (label_text, label_x, label_y) = layout.label();
+        self.push_text(
+            label_x as f32,
+            label_y as f32,
+            label_text,
+            self.palette.tab_text_active.to_pixel(),
+            1.0,
+        );
+
+        let t = crate::i18n::t();
+        let buttons = [
+            (layout.allow_rect(), t.clipboard_read_allow),
+            (layout.deny_rect(), t.clipboard_read_deny),
+        ];
+        for ((bx, by, bw, bh), text) in buttons {
+            self.push_rounded_rect_cmd(&RoundedRectCmd {
+                x: bx as f32,
+                y: by as f32,
+                w: bw as f32,
+                h: bh as f32,
+                radius: r,
+                color: self.palette.tab_border.to_pixel(),
+                opacity: 0.47,
+            });
+            let (tx, ty) = super::super::shared::centered_button_text_origin(
+                bx, by, bw, bh, text, self.metrics.cell_width, self.metrics.cell_height,
+            );
+            self.push_text(
+                tx as f32,
+                ty as f32,
+                text,
+                self.palette.tab_text_active.to_pixel(),
+                1.0,
+            );
+        }
+    }
+}
+
+impl super::GpuRenderer {
+    /// Queues the underline of a hovered link in the default foreground.
+    pub(super) fn draw_link_underline_impl(&mut self, bars: &[UnderlineBar]) {
+        let color = self.palette.default_fg.to_pixel();
+        for bar in bars {
+            self.push_rect(
+                bar.x as f32,
+                bar.y as f32,
+                bar.width as f32,
+                bar.height as f32,
+                color,
+                1.0,
+            );
+        }
+    }
+
+    /// Queues the search match tints over the grid.
+    pub(super) fn draw_search_highlights_impl(&mut self, highlights: &[SearchHighlight]) {
+        for highlight in highlights {
+            let (color, alpha) = search_highlight_style(&self.palette, highlight.current);
+            self.push_rect(
+                highlight.x as f32,
+                highlight.y as f32,
+                highlight.width as f32,
+                highlight.height as f32,
+                color.to_pixel(),
+                alpha as f32 / 255.0,
+            );
+        }
+    }
+
+    /// Queues the visual bell tint over `rect`.
+    pub(super) fn draw_bell_flash_impl(&mut self, rect: PaneRect, alpha: f32) {
+        self.push_rect(
+            rect.x as f32,
+            rect.y as f32,
+            rect.width as f32,
+            rect.height as f32,
+            self.palette.default_fg.to_pixel(),
+            alpha,
+        );
+    }
+
+    /// Queues the search bar: query with caret, match count and option toggles.
+    pub(super) fn draw_search_bar_impl(&mut self, layout: &SearchBarLayout) {
+        let (x, y) = (layout.bg_x as f32, layout.bg_y as f32);
+        let (w, h) = (layout.bg_w as f32, layout.bg_h as f32);
+        let r = layout.radius as f32;
+        self.push_rounded_rect_cmd(&RoundedRectCmd {
+            x, y, w, h, radius: r, color: self.palette.active_tab_bg.to_pixel(), opacity: 0.96,
+        });
+        self.push_rounded_rect_cmd(&RoundedRectCmd {
+            x, y, w, h, radius: r, color: self.palette.tab_border.to_pixel(), opacity: 0.31,
+        });
+
+        let fg = self.palette.tab_text_active.to_pixel();
+        self.push_text(layout.query_x as f32, layout.text_y as f32, &layout.query_text, fg, 1.0);
+        self.push_rect(
+            layout.caret_x as f32,
+            layout.text_y as f32,
+            layout.caret_w as f32,
+            self.metrics.cell_height as f32,
+            fg,
+            1.0,
+        );
+
+        let status_fg = if layout.status_error {
+            self.palette.ansi[1]
+        } else {
+            self.palette.tab_text_inactive
+        };
+        self.push_text(
+            layout.status_x as f32,
+            layout.text_y as f32,
+            &layout.status_text,
+            status_fg.to_pixel(),
+            1.0,
+        );
+
+        for flag in &layout.flags {
+            if flag.active {
+                self.push_rounded_rect_cmd(&RoundedRectCmd {
+                    x: flag.x as f32,
+                    y: flag.y as f32,
+                    w: flag.w as f32,
+                    h: flag.h as f32,
+                    radius: r,
+                    color: self.palette.tab_border.to_pixel(),
+                    opacity: 0.47,
+                });
+            }
+            let (tx, ty) = super::super::shared::centered_button_text_origin(
+                flag.x, flag.y, flag.w, flag.h, flag.label, self.metrics.cell_width, self.metrics.cell_height,
+            );
+            let label_fg = if flag.active { fg } else { self.palette.tab_text_inactive.to_pixel() };
+            self.push_text(tx as f32, ty as f32, flag.label, label_fg, 1.0);
+        }
+    }
+}
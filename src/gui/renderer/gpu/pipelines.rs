@@ -142,13 +142,15 @@ pub fn create_grid_pipeline(
         immediate_size: 0,
     });
 
+    // Cells normally output alpha 1 (opaque); image-underlay cells output
+    // text coverage so the image already in the texture shows through.
     let pipeline = make_render_pipeline(
         device,
         "grid_render_pipeline",
         &shader,
         &pipeline_layout,
         wgpu::TextureFormat::Rgba8Unorm,
-        None,
+        Some(wgpu::BlendState::ALPHA_BLENDING),
     );
 
     (pipeline, bind_group_layout)
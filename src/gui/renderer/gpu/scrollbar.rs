@@ -2,10 +2,31 @@
 
 use super::super::shared::scrollbar_math;
 use super::super::types::{RoundedRectCmd, ScrollbarState};
-use super::super::SCROLLBAR_MIN_THUMB;
+use super::super::shared::command_status_layout::status_color;
+use super::super::{SCROLLBAR_MIN_THUMB, STATUS_TICK_HEIGHT};
 use crate::gui::pane::PaneRect;
 
 impl super::GpuRenderer {
+    /// Queues the command status ticks of `state` across the scrollbar track,
+    /// faded like the thumb.
+    fn push_scrollbar_ticks(
+        &mut self,
+        state: &ScrollbarState,
+        (track_top, track_bottom): (f32, f32),
+        x: f32,
+        width: f32,
+    ) {
+        let total_rows = state.scrollback_len + state.grid_rows;
+        let tick_height = self.metrics.scaled_px(STATUS_TICK_HEIGHT) as f32;
+        for &(abs_row, failed) in &state.command_ticks {
+            let center =
+                scrollbar_math::scrollbar_tick_y(track_top, track_bottom, abs_row, total_rows);
+            let y = (center - tick_height / 2.0).max(track_top);
+            let color = status_color(&self.palette, failed).to_pixel();
+            self.push_rect(x, y, width, tick_height, color, state.opacity);
+        }
+    }
+
     pub(super) fn render_scrollbar_impl(
         &mut self,
         buf_height: usize,
@@ -52,6 +73,7 @@ impl super::GpuRenderer {
             x: thumb_x, y: thumb_y, w: sb_width, h: thumb_height, radius,
             color, opacity: alpha,
         });
+        self.push_scrollbar_ticks(state, (track_top, track_bottom), thumb_x, sb_width);
     }
 
     pub(super) fn render_scrollbar_in_rect_impl(
@@ -97,5 +119,6 @@ impl super::GpuRenderer {
             x: thumb_x, y: thumb_y, w: sb_width, h: thumb_height, radius,
             color, opacity: alpha,
         });
+        self.push_scrollbar_ticks(state, (track_top, track_bottom), thumb_x, sb_width);
     }
 }
@@ -9,6 +9,7 @@ use winit::window::Window;
 use crate::config::{AppConfig, load_fonts};
 use super::super::rasterizer::{GlyphRasterizer, RasterMode};
 use super::super::metrics::FontMetrics;
+use super::super::PaneColors;
 use super::atlas::GlyphAtlas;
 use super::buffers::*;
 use super::pipelines;
@@ -162,10 +163,13 @@ impl super::GpuRenderer {
             atlas,
             rasterizer,
             metrics,
+            pane_colors: PaneColors::from_theme(&palette),
             palette,
             commands: Vec::with_capacity(MAX_UI_COMMANDS),
             grid_batches: Vec::new(),
             grid_dirty: false,
+            blink_visible: true,
+            image_uploads: Vec::new(),
             width,
             height,
         })
@@ -191,7 +195,9 @@ impl super::GpuRenderer {
             sample_count:    1,
             dimension:       wgpu::TextureDimension::D2,
             format,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_DST,
             view_formats: &[],
         });
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -264,6 +270,7 @@ impl super::GpuRenderer {
         self.metrics.recompute(&mut self.rasterizer);
         self.rebuild_atlas();
         self.palette = config.theme.resolve();
+        self.pane_colors = PaneColors::from_theme(&self.palette);
     }
 
     /// Returns glyph info for `codepoint`, lazily inserting it into the atlas.
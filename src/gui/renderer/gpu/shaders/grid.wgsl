@@ -21,7 +21,7 @@ struct GridUniforms {
     _pad2:        u32,
 }
 
-// ---- Per-cell data (16 bytes, tightly packed) ----
+// ---- Per-cell data (20 bytes, tightly packed) ----
 
 struct Cell {
     codepoint: u32,
@@ -34,8 +34,13 @@ struct Cell {
                       // bit 3: reverse video
                       // bit 4: dim
                       // bit 5: strikethrough
-                      // bits 6-7: underline style (0=none, 1=single, 2=double, 3=reserved)
+                      // bit 6: overline
+                      // bit 7: underline_color is set
                       // bit 8: wide-right spacer
+                      // bit 9: image underlay (keep texture, blend text only)
+                      // bits 10-12: underline style (0=none, 1=single, 2=double,
+                      //             3=curly, 4=dotted, 5=dashed)
+    underline_color: u32, // 0xRRGGBB sRGB, used when attrs bit 7 is set
 }
 
 // ---- Glyph lookup entry (32 bytes, 16-byte aligned) ----
@@ -97,6 +102,30 @@ fn linear_to_srgb3(c: vec3<f32>) -> vec3<f32> {
     return vec3<f32>(linear_to_srgb(c.r), linear_to_srgb(c.g), linear_to_srgb(c.b));
 }
 
+/// Whether pixel (`x`, `y`) of a cell lies on an underline of `style`.
+/// Curly, dotted and dashed patterns are phased on the cell so runs of
+/// cells join into one line.
+fn underline_hit(style: u32, x: u32, y: u32) -> bool {
+    let h = uniforms.cell_height;
+    switch style {
+        // Single: 2px at bottom.
+        case 1u: { return y >= h - 2u; }
+        // Double: two 1px lines.
+        case 2u: { return y == h - 1u || y == h - 3u; }
+        // Curly: one wave per cell, 1px thick, up to 2px above the bottom row.
+        case 3u: {
+            let phase = f32(x) / f32(uniforms.cell_width) * 6.2831853;
+            let lift = u32(round(1.0 - cos(phase)));
+            return y == h - 1u - lift;
+        }
+        // Dotted: 2px dots and gaps.
+        case 4u: { return y == h - 2u && (x / 2u) % 2u == 0u; }
+        // Dashed: two thirds of each cell.
+        case 5u: { return y == h - 2u && x < uniforms.cell_width * 2u / 3u; }
+        default: { return false; }
+    }
+}
+
 // ---- Vertex stage: fullscreen triangle ----
 
 @vertex
@@ -166,6 +195,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
     // Start with background.
     var color = bg_lin;
+    // Text coverage, used for cells that show an image beneath the text.
+    var coverage = 0.0;
 
     // Glyph blending — blend in linear space.
     let glyph_count = arrayLength(&glyphs);
@@ -190,28 +221,41 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                         mix(bg_lin.g, fg_lin.g, sample.g),
                         mix(bg_lin.b, fg_lin.b, sample.b),
                     );
+                    coverage = max(sample.r, max(sample.g, sample.b));
                 } else {
                     // Grayscale: single-alpha blend in linear space.
                     color = mix(bg_lin, fg_lin, sample.r);
+                    coverage = sample.r;
                 }
             }
         }
     }
 
-    // Decorations (underline, strikethrough) — use (potentially dimmed) fg.
+    // Decorations (underline, strikethrough, overline) — use (potentially dimmed) fg.
     let decor_lin = fg_lin;
-    let underline_style = (cell.attrs >> 6u) & 3u;
-    if underline_style == 1u && cell_y >= uniforms.cell_height - 2u {
-        // Single underline: 2px at bottom.
-        color = decor_lin;
-    } else if underline_style == 2u &&
-              (cell_y == uniforms.cell_height - 1u || cell_y == uniforms.cell_height - 3u) {
-        // Double underline: two 1px lines.
-        color = decor_lin;
+    var underline_lin = fg_lin;
+    if (cell.attrs & 128u) != 0u {
+        underline_lin = unpack_linear(cell.underline_color);
+    }
+    if underline_hit((cell.attrs >> 10u) & 7u, cell_x, cell_y) {
+        color = underline_lin;
+        coverage = 1.0;
     }
     // Strikethrough: 1px line at vertical center.
     if (cell.attrs & 32u) != 0u && cell_y == uniforms.cell_height / 2u {
         color = decor_lin;
+        coverage = 1.0;
+    }
+    // Overline: 1px line at the top.
+    if (cell.attrs & 64u) != 0u && cell_y == 0u {
+        color = decor_lin;
+        coverage = 1.0;
+    }
+
+    // Image underlay: the image is already in the texture; let the pipeline's
+    // alpha blending draw only the text ink over it.
+    if (cell.attrs & 512u) != 0u {
+        return vec4<f32>(linear_to_srgb3(fg_lin), coverage);
     }
 
     // Encode linear → sRGB explicitly before writing to Rgba8Unorm attachment.
@@ -124,7 +124,7 @@ impl super::GpuRenderer {
             slot.is_hovered,
             slot.tab.hover_progress,
         );
-        let close_reserved = if show_close {
+        let close_reserved = if show_close || slot.tab.has_bell {
             tab_math::close_button_reserved_width(&m)
         } else {
             0
@@ -135,6 +135,8 @@ impl super::GpuRenderer {
 
         if show_close {
             self.draw_close_button_commands(slot.index, tw, slot.tab.close_hover_progress);
+        } else if slot.tab.has_bell {
+            self.bell_badge_commands(tab_x, tw);
         }
     }
 
@@ -152,13 +154,23 @@ impl super::GpuRenderer {
             slot.tab.hover_progress,
         );
 
-        self.tab_title_commands(slot.tab, tab_x, tw, text_y, show_close);
+        self.tab_title_commands(slot.tab, tab_x, tw, text_y, show_close || slot.tab.has_bell);
 
         if show_close {
             self.draw_close_button_commands(slot.index, tw, slot.tab.close_hover_progress);
+        } else if slot.tab.has_bell {
+            self.bell_badge_commands(tab_x, tw);
         }
     }
 
+    /// Draws the bell badge dot in the close button slot.
+    fn bell_badge_commands(&mut self, tab_x: f32, tw: u32) {
+        let m = self.tab_layout_metrics();
+        let (cx, cy) = tab_math::bell_badge_center(&m, tab_x, tw);
+        let r = m.scaled_px(tab_math::BELL_BADGE_RADIUS) as f32;
+        self.push_circle(cx, cy, r, self.palette.active_accent.to_pixel(), 1.0);
+    }
+
     /// Draws the tab title text, truncated to fit the available space.
     fn tab_title_commands(
         &mut self,
@@ -1,8 +1,10 @@
 use crate::core::{CursorStyle, PageList, Selection};
 use crate::gui::pane::PaneRect;
 
+use super::super::shared::link_layout::UnderlineBar;
+use super::super::shared::search_layout::{SearchBarLayout, SearchHighlight};
 use super::super::traits;
-use super::super::{RenderTarget, ScrollbarState};
+use super::super::{PaneColors, RenderTarget, ScrollbarState};
 #[cfg(not(target_os = "macos"))]
 use super::super::TabBarDrawParams;
 #[cfg(not(target_os = "macos"))]
@@ -88,6 +90,17 @@ impl traits::Renderer for GpuRenderer {
             .saturating_sub(self.metrics.tab_bar_height_px() + padding.saturating_mul(2));
         let region = PaneRect { x: 0, y: 0, width: max_width, height: max_height };
         self.queue_grid_batch(screen, selection, scroll_offset, region, 0.0);
+        let top = self.metrics.tab_bar_height_px() + padding;
+        let grid_rect = PaneRect { x: padding, y: top, width: max_width, height: max_height };
+        self.draw_status_markers_impl(screen, scroll_offset, grid_rect);
+    }
+
+    fn set_blink_visible(&mut self, visible: bool) {
+        self.blink_visible = visible;
+    }
+
+    fn set_pane_colors(&mut self, colors: PaneColors) {
+        self.pane_colors = colors;
     }
 
     fn draw_cursor(
@@ -116,6 +129,7 @@ impl traits::Renderer for GpuRenderer {
         let origin_y = rect.y.saturating_sub(top);
         let region = PaneRect { x: origin_x, y: origin_y, width: rect.width, height: rect.height };
         self.queue_grid_batch(screen, selection, scroll_offset, region, fg_dim);
+        self.draw_status_markers_impl(screen, scroll_offset, rect);
     }
 
     fn draw_cursor_in_rect(
@@ -200,4 +214,32 @@ impl traits::Renderer for GpuRenderer {
     ) {
         self.draw_update_banner_impl(layout);
     }
+
+    fn draw_link_underline(&mut self, _target: &mut RenderTarget<'_>, bars: &[UnderlineBar]) {
+        self.draw_link_underline_impl(bars);
+    }
+
+    fn draw_search_highlights(&mut self, _target: &mut RenderTarget<'_>, highlights: &[SearchHighlight]) {
+        self.draw_search_highlights_impl(highlights);
+    }
+
+    fn draw_bell_flash(&mut self, _target: &mut RenderTarget<'_>, rect: PaneRect, alpha: f32) {
+        self.draw_bell_flash_impl(rect, alpha);
+    }
+
+    fn draw_search_bar(&mut self, _target: &mut RenderTarget<'_>, layout: &SearchBarLayout) {
+        self.draw_search_bar_impl(layout);
+    }
+
+    fn draw_clipboard_prompt(
+        &mut self,
+        _target: &mut RenderTarget<'_>,
+        layout: &super::super::shared::banner_layout::ClipboardPromptLayout,
+    ) {
+        self.draw_clipboard_prompt_impl(layout);
+    }
+
+    fn draw_vi_cursor(&mut self, _target: &mut RenderTarget<'_>, x: u32, y: u32, ch: char) {
+        self.draw_block_cursor(x as f32, y as f32, ch);
+    }
 }
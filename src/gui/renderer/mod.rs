@@ -13,7 +13,7 @@ mod terminal;
 pub mod traits;
 pub mod types;
 
-use crate::core::{CursorStyle, GraphemeCell, PageList, Selection};
+use crate::core::{CursorStyle, GraphemeCell, PageList, PageRow, Selection};
 
 pub use backend::RendererBackend;
 pub use cpu::CpuRenderer;
@@ -29,6 +29,12 @@ pub const SCROLLBAR_HIT_ZONE: u32 = 14;
 /// Margin between the thumb right edge and the window right edge.
 pub const SCROLLBAR_MARGIN: u32 = 2;
 
+/// Width of the command status marker drawn left of a prompt row.
+pub const STATUS_MARKER_WIDTH: u32 = 3;
+
+/// Height of a command status tick on the scrollbar track.
+pub const STATUS_TICK_HEIGHT: u32 = 2;
+
 /// Minimum scrollbar thumb height in base UI pixels.
 pub(super) const SCROLLBAR_MIN_THUMB: u32 = 20;
 
@@ -50,6 +56,17 @@ pub(super) fn display_cell(screen: &PageList, scroll_offset: usize, row: usize,
     }
 }
 
+/// Returns the row displayed at `row`, taking `scroll_offset` into account,
+/// or `None` when the row lies above the oldest scrollback entry.
+pub(super) fn display_row(screen: &PageList, scroll_offset: usize, row: usize) -> Option<&PageRow> {
+    if row < scroll_offset {
+        let sb_idx = screen.scrollback_len().saturating_sub(scroll_offset) + row;
+        (sb_idx < screen.scrollback_len()).then(|| screen.scrollback_row(sb_idx))
+    } else {
+        Some(screen.viewport_row(row - scroll_offset))
+    }
+}
+
 /// Sanitizes a DPI scale factor to a safe, finite range.
 ///
 /// Returns `1.0` for non-finite inputs, otherwise clamps to `[0.75, 4.0]`.
@@ -81,6 +81,8 @@ impl CpuRenderer {
                 target.buffer[idx] = (r << 16) | (g << 8) | b;
             }
         }
+
+        self.draw_scrollbar_ticks(target, state, (track_top, track_bottom), (thumb_left, thumb_right));
     }
 
     /// Renders the scrollbar within a pane sub-rectangle.
@@ -150,5 +152,7 @@ impl CpuRenderer {
                 }
             }
         }
+
+        self.draw_scrollbar_ticks(target, state, (track_top, track_bottom), (thumb_left, thumb_right));
     }
 }
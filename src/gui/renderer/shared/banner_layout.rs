@@ -1,9 +1,14 @@
-//! Layout computation for the update-available banner.
+//! Layout computation for the update-available banner and the clipboard
+//! read prompt.
 //!
 //! The banner is a small rounded rect centred horizontally at the top of the
 //! terminal area (just below the tab bar on non-macOS; at the top edge on
 //! macOS). It contains: an "Update vX.Y.Z" label, a [Details] button, an
 //! [Install] button, and a [✕] dismiss button.
+//!
+//! The clipboard read prompt looks the same but sits at the bottom of the
+//! window, so both can be shown at once. It names the pane asking to read
+//! the clipboard and has [Allow] and [Deny] buttons.
 
 use crate::gui::renderer::shared::tab_math::TabLayoutMetrics;
 
@@ -152,6 +157,110 @@ impl UpdateBannerLayout {
     }
 }
 
+/// Pre-computed geometry for the clipboard read prompt.
+#[derive(Debug, Clone)]
+pub(in crate::gui) struct ClipboardPromptLayout {
+    /// Background rectangle.
+    pub bg_x: i32,
+    pub bg_y: i32,
+    pub bg_w: u32,
+    pub bg_h: u32,
+    /// Corner radius.
+    pub radius: u32,
+    /// "… wants to read the clipboard" label position.
+    pub label_x: u32,
+    pub label_y: u32,
+    pub label_text: String,
+    /// Button row (both buttons share `y` and height).
+    pub btn_y: u32,
+    pub btn_h: u32,
+    /// [Allow] button.
+    pub allow_x: u32,
+    pub allow_w: u32,
+    /// [Deny] button.
+    pub deny_x: u32,
+    pub deny_w: u32,
+}
+
+/// Computes the clipboard read prompt layout for `label`.
+///
+/// Returns `None` when the buffer is too small to fit the prompt.
+pub(in crate::gui) fn compute_clipboard_prompt_layout(
+    label: &str,
+    m: &TabLayoutMetrics,
+    buf_width: u32,
+    buf_height: u32,
+) -> Option<ClipboardPromptLayout> {
+    let t = crate::i18n::t();
+    let cell_height = m.cell_height;
+
+    let pad_x = m.scaled_px(10);
+    let pad_y = m.scaled_px(6);
+    let btn_pad_x = m.scaled_px(8);
+    let gap = m.scaled_px(6);
+    let radius = m.scaled_px(6);
+
+    let label_w = label.chars().count() as u32 * m.cell_width;
+    let text_w = |text: &str| text.chars().count() as u32 * m.cell_width;
+    let btn_h = cell_height + pad_y;
+    let allow_w = text_w(t.clipboard_read_allow) + btn_pad_x * 2;
+    let deny_w = text_w(t.clipboard_read_deny) + btn_pad_x * 2;
+
+    let total_w = pad_x * 2 + label_w + gap + allow_w + gap + deny_w;
+    let bg_h = btn_h + pad_y * 2;
+    let margin_bottom = m.scaled_px(6);
+    if total_w > buf_width || bg_h + margin_bottom > buf_height {
+        return None;
+    }
+
+    let bg_x = ((buf_width - total_w) / 2) as i32;
+    let bg_y = (buf_height - bg_h - margin_bottom) as i32;
+    let label_x = bg_x as u32 + pad_x;
+    let btn_y = bg_y as u32 + pad_y;
+    let label_y = btn_y + (btn_h - cell_height) / 2;
+    let allow_x = label_x + label_w + gap;
+    let deny_x = allow_x + allow_w + gap;
+
+    Some(ClipboardPromptLayout {
+        bg_x,
+        bg_y,
+        bg_w: total_w,
+        bg_h,
+        radius,
+        label_x,
+        label_y,
+        label_text: label.to_owned(),
+        btn_y,
+        btn_h,
+        allow_x,
+        allow_w,
+        deny_x,
+        deny_w,
+    })
+}
+
+impl ClipboardPromptLayout {
+    /// Returns the background rectangle as `(x, y, w, h)`.
+    pub(in crate::gui) fn bg_rect(&self) -> (i32, i32, u32, u32) {
+        (self.bg_x, self.bg_y, self.bg_w, self.bg_h)
+    }
+
+    /// Returns the label text and its top-left position.
+    pub(in crate::gui) fn label(&self) -> (&str, u32, u32) {
+        (&self.label_text, self.label_x, self.label_y)
+    }
+
+    /// Returns the [Allow] button rect as `(x, y, w, h)`.
+    pub(in crate::gui) fn allow_rect(&self) -> (u32, u32, u32, u32) {
+        (self.allow_x, self.btn_y, self.allow_w, self.btn_h)
+    }
+
+    /// Returns the [Deny] button rect as `(x, y, w, h)`.
+    pub(in crate::gui) fn deny_rect(&self) -> (u32, u32, u32, u32) {
+        (self.deny_x, self.btn_y, self.deny_w, self.btn_h)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -229,4 +338,16 @@ mod tests {
         let bg_right = (l.bg_x as u32) + l.bg_w;
         assert!(l.dismiss_x + l.dismiss_w <= bg_right);
     }
+
+    #[test]
+    fn clipboard_prompt_sits_at_the_bottom_with_buttons_inside() {
+        let m = metrics_1x();
+        let l = compute_clipboard_prompt_layout("zsh wants to read the clipboard", &m, 800, 600)
+            .expect("layout");
+        assert!(l.bg_y as u32 + l.bg_h <= 600);
+        assert!(l.bg_y > 300);
+        assert!(l.allow_x > l.label_x && l.deny_x > l.allow_x + l.allow_w);
+        assert!(l.deny_x + l.deny_w <= l.bg_x as u32 + l.bg_w);
+        assert!(compute_clipboard_prompt_layout("zsh", &m, 60, 600).is_none());
+    }
 }
@@ -0,0 +1,143 @@
+//! Gutter markers for finished shell commands (OSC 133 `D`), shared by CPU and
+//! GPU renderers.
+
+use crate::config::ThemePalette;
+use crate::core::{Color, CommandStatus, PageList};
+use crate::gui::pane::PaneRect;
+
+/// A coloured bar beside the prompt row of a finished command, in physical pixels.
+pub struct StatusMarker {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub status: CommandStatus,
+}
+
+impl StatusMarker {
+    /// Returns `true` when `(x, y)` falls on the marker or the gap beside it.
+    #[cfg(not(target_os = "macos"))]
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let right = (self.x + self.width * 2) as f64;
+        let bottom = (self.y + self.height) as f64;
+        x >= self.x as f64 && x < right && y >= self.y as f64 && y < bottom
+    }
+}
+
+/// Collects markers for the displayed prompt rows of `screen`.
+///
+/// `rect` is the grid origin and clip rectangle.  The bar sits in the padding
+/// left of the grid, one marker width away from the first column; without
+/// enough padding it is pushed over the first column instead.
+pub fn visible_status_markers(
+    screen: &PageList,
+    scroll_offset: usize,
+    rect: PaneRect,
+    cell_height: u32,
+    marker_width: u32,
+) -> Vec<StatusMarker> {
+    let x = rect.x.saturating_sub(marker_width * 2);
+    let bottom = rect.y + rect.height;
+    let mut markers = Vec::new();
+    for row in 0..screen.viewport_rows() {
+        let y = rect.y + row as u32 * cell_height;
+        if y + cell_height > bottom {
+            break;
+        }
+        let Some(status) =
+            super::super::display_row(screen, scroll_offset, row).and_then(|r| r.marks.status)
+        else {
+            continue;
+        };
+        markers.push(StatusMarker {
+            x,
+            y,
+            width: marker_width,
+            height: cell_height,
+            status,
+        });
+    }
+    markers
+}
+
+/// Theme green for successful commands, theme red for failed ones.
+pub fn status_color(palette: &ThemePalette, failed: bool) -> Color {
+    if failed {
+        palette.ansi[1]
+    } else {
+        palette.ansi[2]
+    }
+}
+
+/// Finished commands as `(abs_row, failed)` for scrollbar ticks.
+pub fn status_ticks(screen: &PageList) -> Vec<(usize, bool)> {
+    screen
+        .command_statuses()
+        .map(|(abs_row, status)| (abs_row, !status.succeeded()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn status(exit_code: i32) -> Option<CommandStatus> {
+        Some(CommandStatus {
+            exit_code: Some(exit_code),
+            duration: Duration::ZERO,
+        })
+    }
+
+    #[test]
+    fn markers_follow_prompt_rows() {
+        let mut screen = PageList::new(4, 10, 100);
+        screen.viewport_row_mut(1).marks.status = status(0);
+        screen.viewport_row_mut(3).marks.status = status(1);
+        let rect = PaneRect {
+            x: 8,
+            y: 20,
+            width: 80,
+            height: 64,
+        };
+        let markers = visible_status_markers(&screen, 0, rect, 16, 3);
+        assert_eq!(markers.len(), 2);
+        assert_eq!((markers[0].x, markers[0].y), (2, 36));
+        assert!(!markers[1].status.succeeded());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn marker_hit_zone_covers_the_gap_before_the_grid() {
+        let mut screen = PageList::new(4, 10, 100);
+        screen.viewport_row_mut(3).marks.status = status(1);
+        let rect = PaneRect {
+            x: 8,
+            y: 20,
+            width: 80,
+            height: 64,
+        };
+        let markers = visible_status_markers(&screen, 0, rect, 16, 3);
+        assert!(markers[0].contains(2.0, 70.0));
+        assert!(markers[0].contains(7.0, 70.0));
+        assert!(!markers[0].contains(8.0, 70.0));
+        assert!(!markers[0].contains(4.0, 60.0));
+    }
+
+    #[test]
+    fn markers_are_clipped_to_rect_and_scroll_with_content() {
+        let mut screen = PageList::new(4, 10, 100);
+        screen.viewport_row_mut(0).marks.status = status(0);
+        screen.viewport_row_mut(3).marks.status = status(0);
+        let rect = PaneRect {
+            x: 0,
+            y: 0,
+            width: 80,
+            height: 60,
+        };
+        let markers = visible_status_markers(&screen, 0, rect, 16, 3);
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].x, 0);
+        assert_eq!(status_ticks(&screen), vec![(0, false), (3, false)]);
+    }
+}
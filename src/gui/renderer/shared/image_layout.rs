@@ -0,0 +1,168 @@
+//! Pixel placement of row-anchored inline images, shared by CPU and GPU renderers.
+
+use crate::core::{Color, ImageSlice, PageList};
+use crate::gui::pane::PaneRect;
+
+/// Images with a z-index below this are hidden by non-default cell
+/// backgrounds; other negative z-indices are drawn over every background.
+const BELOW_BACKGROUND_Z: i32 = i32::MIN / 2;
+
+/// One visible image strip, in physical pixels.
+pub struct ImageBlit<'a> {
+    pub slice: &'a ImageSlice,
+    /// Destination top-left corner.
+    pub x: u32,
+    pub y: u32,
+    /// Size after clipping to the cell row and to `rect`.
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ImageBlit<'_> {
+    /// RGBA of the pixel drawn at offset `(dx, dy)` from the blit origin.
+    pub fn pixel(&self, dx: u32, dy: u32) -> [u8; 4] {
+        self.slice.source_pixel(dx, dy)
+    }
+}
+
+/// Visible image strips split by layer, each in ascending z order.
+pub struct ImageLayers<'a> {
+    /// Negative z-index: drawn over cell backgrounds but beneath the text.
+    pub under: Vec<ImageBlit<'a>>,
+    /// Drawn over the text.
+    pub over: Vec<ImageBlit<'a>>,
+}
+
+/// Collects the image strips attached to the displayed rows of `screen`.
+///
+/// `rect` is the grid origin and clip rectangle.  Each strip is clipped to
+/// its own cell row so that strips of one image never overlap.
+pub fn visible_image_layers<'a>(
+    screen: &'a PageList,
+    scroll_offset: usize,
+    rect: PaneRect,
+    cell_width: u32,
+    cell_height: u32,
+) -> ImageLayers<'a> {
+    let right = rect.x + rect.width;
+    let bottom = rect.y + rect.height;
+    let mut blits = Vec::new();
+    for row in 0..screen.viewport_rows() {
+        let row_y = rect.y + row as u32 * cell_height;
+        if row_y >= bottom {
+            break;
+        }
+        let Some(page_row) = super::super::display_row(screen, scroll_offset, row) else {
+            continue;
+        };
+        for slice in &page_row.images {
+            let x = rect.x + slice.col as u32 * cell_width + slice.x_offset;
+            let y = row_y + slice.y_offset;
+            if x >= right || y >= bottom {
+                continue;
+            }
+            let width = slice.width.min(right - x);
+            let height = slice
+                .height
+                .min(cell_height.saturating_sub(slice.y_offset))
+                .min(bottom - y);
+            if width == 0 || height == 0 {
+                continue;
+            }
+            blits.push(ImageBlit { slice, x, y, width, height });
+        }
+    }
+    blits.sort_by_key(|blit| blit.slice.z_index);
+    let (under, over) = blits.into_iter().partition(|blit| blit.slice.z_index < 0);
+    ImageLayers { under, over }
+}
+
+/// Returns `true` when an underlay image shows through cell `(row, col)`,
+/// i.e. the cell background must give way to the image.
+pub fn cell_has_underlay(screen: &PageList, scroll_offset: usize, row: usize, col: usize) -> bool {
+    let Some(page_row) = super::super::display_row(screen, scroll_offset, row) else {
+        return false;
+    };
+    page_row.images.iter().any(|slice| {
+        slice.z_index < 0
+            && slice.covers_col(col)
+            && (slice.z_index >= BELOW_BACKGROUND_Z
+                || page_row.cells.get(col).is_some_and(|cell| cell.bg == Color::SENTINEL_BG))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{PageRow, TerminalImage};
+    use std::rc::Rc;
+
+    fn screen_with_image(col: usize, width: u32) -> PageList {
+        let mut screen = PageList::new(3, 10, 10);
+        let image = Rc::new(TerminalImage {
+            id: 1,
+            width,
+            height: 8,
+            pixels: vec![0; (width * 8 * 4) as usize],
+        });
+        screen.viewport_row_mut(1).images.push(ImageSlice::new(image, col, 1, 4, 4));
+        screen
+    }
+
+    #[test]
+    fn blit_positions_strip_at_its_cell() {
+        let screen = screen_with_image(2, 5);
+        let rect = PaneRect { x: 10, y: 20, width: 100, height: 60 };
+        let blits = visible_image_layers(&screen, 0, rect, 8, 16).over;
+        assert_eq!(blits.len(), 1);
+        assert_eq!((blits[0].x, blits[0].y), (26, 36));
+        assert_eq!((blits[0].slice.src_y, blits[0].width, blits[0].height), (4, 5, 4));
+    }
+
+    #[test]
+    fn blit_is_clipped_to_rect() {
+        let screen = screen_with_image(9, 20);
+        let rect = PaneRect { x: 0, y: 0, width: 80, height: 20 };
+        let blits = visible_image_layers(&screen, 0, rect, 8, 16).over;
+        assert_eq!(blits[0].width, 8);
+        assert_eq!(blits[0].height, 4);
+    }
+
+    #[test]
+    fn scrolled_view_shows_scrollback_images() {
+        let mut screen = screen_with_image(0, 5);
+        let row = screen.viewport_row(1).clone();
+        screen.push_to_scrollback(row);
+        screen.viewport_row_mut(1).clear();
+        screen.push_to_scrollback(PageRow::new(10));
+        let rect = PaneRect { x: 0, y: 0, width: 80, height: 48 };
+        assert!(visible_image_layers(&screen, 0, rect, 8, 16).over.is_empty());
+        let blits = visible_image_layers(&screen, 2, rect, 8, 16).over;
+        assert_eq!(blits.len(), 1);
+        assert_eq!(blits[0].y, 0);
+    }
+
+    #[test]
+    fn offsets_shift_strip_and_negative_z_goes_under() {
+        let mut screen = screen_with_image(1, 5);
+        let slice = &mut screen.viewport_row_mut(1).images[0];
+        slice.x_offset = 3;
+        slice.y_offset = 2;
+        slice.z_index = -1;
+        let rect = PaneRect { x: 0, y: 0, width: 100, height: 60 };
+        let layers = visible_image_layers(&screen, 0, rect, 8, 16);
+        assert!(layers.over.is_empty());
+        assert_eq!((layers.under[0].x, layers.under[0].y), (11, 18));
+        assert!(cell_has_underlay(&screen, 0, 1, 1));
+        assert!(!cell_has_underlay(&screen, 0, 1, 2));
+    }
+
+    #[test]
+    fn far_negative_z_only_shows_under_default_background() {
+        let mut screen = screen_with_image(0, 5);
+        screen.viewport_row_mut(1).images[0].z_index = i32::MIN;
+        assert!(cell_has_underlay(&screen, 0, 1, 0));
+        screen.viewport_row_mut(1).cells[0].bg = Color { r: 1, g: 2, b: 3 };
+        assert!(!cell_has_underlay(&screen, 0, 1, 0));
+    }
+}
@@ -0,0 +1,176 @@
+//! Geometry for the underline drawn under a hovered link.
+
+use crate::core::{DetectedLink, PageCoord, PageList, UnderlineStyle};
+use crate::gui::pane::PaneRect;
+use crate::gui::renderer::shared::search_layout::displayed_span_rows;
+
+/// One line of a link underline, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnderlineBar {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Offset and thickness of each line of `style` within a cell, matching
+/// the underline decoration of the grid itself.
+fn underline_lines(style: UnderlineStyle, cell_height: u32) -> Vec<(u32, u32)> {
+    match style {
+        UnderlineStyle::None => Vec::new(),
+        // Patterned styles are drawn as a plain line under a hovered link.
+        UnderlineStyle::Single
+        | UnderlineStyle::Curly
+        | UnderlineStyle::Dotted
+        | UnderlineStyle::Dashed => vec![(cell_height.saturating_sub(2), 2)],
+        UnderlineStyle::Double => vec![
+            (cell_height.saturating_sub(3), 1),
+            (cell_height.saturating_sub(1), 1),
+        ],
+    }
+}
+
+/// Underline bars for the displayed rows of `link`.
+///
+/// `rect` is the grid origin and clip rectangle.
+pub fn link_underline_bars(
+    screen: &PageList,
+    scroll_offset: usize,
+    rect: PaneRect,
+    cell: (u32, u32),
+    link: &DetectedLink,
+    style: UnderlineStyle,
+) -> Vec<UnderlineBar> {
+    let spans = displayed_span_rows(screen, scroll_offset, rect, cell, link.start, link.end);
+    underline_spans(spans, style, cell.1)
+}
+
+/// Underline bars for every displayed cell of the OSC 8 hyperlink `id`,
+/// wherever the link's pieces are on screen.
+pub fn hyperlink_underline_bars(
+    screen: &PageList,
+    scroll_offset: usize,
+    rect: PaneRect,
+    cell: (u32, u32),
+    id: u16,
+    style: UnderlineStyle,
+) -> Vec<UnderlineBar> {
+    let top = screen.scrollback_len().saturating_sub(scroll_offset);
+    let bottom = (top + screen.viewport_rows()).min(screen.total_rows());
+    let mut spans = Vec::new();
+    for abs_row in top..bottom {
+        let cells = &screen.abs_row(abs_row).cells;
+        let mut col = 0;
+        while col < cells.len() {
+            if cells[col].hyperlink_id != id {
+                col += 1;
+                continue;
+            }
+            let first = col;
+            while col < cells.len() && cells[col].hyperlink_id == id {
+                col += 1;
+            }
+            spans.extend(displayed_span_rows(
+                screen,
+                scroll_offset,
+                rect,
+                cell,
+                PageCoord {
+                    abs_row,
+                    col: first,
+                },
+                PageCoord {
+                    abs_row,
+                    col: col - 1,
+                },
+            ));
+        }
+    }
+    underline_spans(spans, style, cell.1)
+}
+
+fn underline_spans(
+    spans: Vec<(u32, u32, u32)>,
+    style: UnderlineStyle,
+    cell_height: u32,
+) -> Vec<UnderlineBar> {
+    let lines = underline_lines(style, cell_height);
+    spans
+        .into_iter()
+        .flat_map(|(x, y, width)| {
+            lines.iter().map(move |&(offset, height)| UnderlineBar {
+                x,
+                y: y + offset,
+                width,
+                height,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LinkKind;
+    use crate::core::terminal::Terminal;
+
+    #[test]
+    fn wrapped_link_is_underlined_on_each_displayed_row() {
+        let screen = PageList::new(3, 10, 10);
+        let link = DetectedLink {
+            kind: LinkKind::Url,
+            text: "https://ferrum.dev".into(),
+            start: PageCoord { abs_row: 0, col: 6 },
+            end: PageCoord { abs_row: 1, col: 3 },
+        };
+        let rect = PaneRect {
+            x: 4,
+            y: 30,
+            width: 80,
+            height: 48,
+        };
+        let bars = link_underline_bars(&screen, 0, rect, (8, 16), &link, UnderlineStyle::Single);
+        assert_eq!(
+            bars,
+            vec![
+                UnderlineBar {
+                    x: 52,
+                    y: 44,
+                    width: 32,
+                    height: 2
+                },
+                UnderlineBar {
+                    x: 4,
+                    y: 60,
+                    width: 32,
+                    height: 2
+                },
+            ]
+        );
+
+        let double = link_underline_bars(&screen, 0, rect, (8, 16), &link, UnderlineStyle::Double);
+        assert_eq!(double.len(), 4);
+        assert_eq!((double[0].y, double[1].y), (43, 45));
+        assert!(
+            link_underline_bars(&screen, 0, rect, (8, 16), &link, UnderlineStyle::None).is_empty()
+        );
+    }
+
+    #[test]
+    fn every_piece_of_a_hyperlink_is_underlined() {
+        let mut term = Terminal::new(3, 10);
+        term.process(b"\x1b]8;id=a;https://ferrum.dev\x1b\\ab\x1b]8;;\x1b\\ cd\r\n");
+        term.process(b"\x1b]8;id=a;https://ferrum.dev\x1b\\ef\x1b]8;;\x1b\\");
+        let id = term.screen.viewport_get(0, 0).hyperlink_id;
+        let rect = PaneRect {
+            x: 0,
+            y: 0,
+            width: 80,
+            height: 48,
+        };
+        let bars =
+            hyperlink_underline_bars(&term.screen, 0, rect, (8, 16), id, UnderlineStyle::Single);
+        let spans: Vec<_> = bars.iter().map(|bar| (bar.x, bar.y, bar.width)).collect();
+        assert_eq!(spans, [(0, 14, 16), (0, 30, 16)]);
+    }
+}
@@ -5,9 +5,13 @@
 //! these calculations we eliminate duplication between renderer backends.
 
 pub mod banner_layout;
+pub mod command_status_layout;
+pub mod image_layout;
+pub mod link_layout;
 pub mod overlay_layout;
 pub mod path_display;
 pub mod scrollbar_math;
+pub mod search_layout;
 #[cfg(not(target_os = "macos"))]
 pub mod tab_hit_test;
 pub mod tab_math;
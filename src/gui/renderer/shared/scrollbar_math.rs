@@ -74,6 +74,18 @@ pub(in crate::gui::renderer) fn scrollbar_thumb_geometry(
     Some((thumb_y, thumb_height))
 }
 
+/// Vertical centre of the scrollbar tick for absolute row `abs_row` out of
+/// `total_rows` (scrollback plus viewport), in pixels.
+pub(in crate::gui::renderer) fn scrollbar_tick_y(
+    track_top: f32,
+    track_bottom: f32,
+    abs_row: usize,
+    total_rows: usize,
+) -> f32 {
+    let ratio = (abs_row as f32 + 0.5) / total_rows.max(1) as f32;
+    track_top + ratio.min(1.0) * (track_bottom - track_top)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,4 +195,14 @@ mod tests {
         assert!((bottom - expected_bottom).abs() < 0.01);
         assert!((min_t - expected_min).abs() < 0.01);
     }
+
+    // ── scrollbar_tick_y ──────────────────────────────────────────────
+
+    #[test]
+    fn tick_y_maps_rows_onto_track() {
+        assert!((scrollbar_tick_y(100.0, 200.0, 0, 10) - 105.0).abs() < 0.01);
+        assert!((scrollbar_tick_y(100.0, 200.0, 9, 10) - 195.0).abs() < 0.01);
+        // Out-of-range rows clamp to the track bottom.
+        assert!((scrollbar_tick_y(100.0, 200.0, 50, 10) - 200.0).abs() < 0.01);
+    }
 }
@@ -0,0 +1,311 @@
+//! Geometry for scrollback search: match highlights over the grid and the
+//! search bar in the top-right corner of the pane.
+
+use crate::config::ThemePalette;
+use crate::core::{Color, PageCoord, PageList, SearchMatches};
+use crate::gui::pane::PaneRect;
+use crate::gui::renderer::shared::tab_math::TabLayoutMetrics;
+
+/// Width of the query field in characters, before shrinking to fit the pane.
+const QUERY_FIELD_CHARS: u32 = 24;
+
+/// Narrowest query field worth drawing.
+const MIN_QUERY_FIELD_CHARS: u32 = 4;
+
+/// Highlight opacity of ordinary matches and of the current match.
+const MATCH_ALPHA: u8 = 90;
+const CURRENT_MATCH_ALPHA: u8 = 170;
+
+/// One row-slice of a match, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchHighlight {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// `true` for the match the user last jumped to.
+    pub current: bool,
+}
+
+/// Splits the displayed parts of `matches` into per-row rectangles.
+///
+/// `rect` is the grid origin and clip rectangle; only matches touching the
+/// displayed rows are visited.
+pub fn visible_search_highlights(
+    screen: &PageList,
+    scroll_offset: usize,
+    rect: PaneRect,
+    cell: (u32, u32),
+    matches: &SearchMatches,
+    current: Option<usize>,
+) -> Vec<SearchHighlight> {
+    let top = screen.scrollback_len().saturating_sub(scroll_offset);
+    let mut highlights = Vec::new();
+    for (index, m) in matches.in_rows(screen, top..top + screen.viewport_rows()) {
+        let rows = displayed_span_rows(screen, scroll_offset, rect, cell, m.start, m.end);
+        highlights.extend(rows.into_iter().map(|(x, y, width)| SearchHighlight {
+            x,
+            y,
+            width,
+            height: cell.1,
+            current: current == Some(index),
+        }));
+    }
+    highlights
+}
+
+/// Per-row pixel spans `(x, y, width)` of the displayed cells from `start`
+/// to `end` (inclusive, in reading order), clipped to `rect`.
+pub fn displayed_span_rows(
+    screen: &PageList,
+    scroll_offset: usize,
+    rect: PaneRect,
+    (cell_width, cell_height): (u32, u32),
+    start: PageCoord,
+    end: PageCoord,
+) -> Vec<(u32, u32, u32)> {
+    let cols = screen.cols();
+    let top = screen.scrollback_len().saturating_sub(scroll_offset);
+    let bottom = top + screen.viewport_rows();
+    let mut spans = Vec::new();
+    for abs_row in start.abs_row.max(top)..=end.abs_row.min(bottom.saturating_sub(1)) {
+        let first = if abs_row == start.abs_row {
+            start.col
+        } else {
+            0
+        };
+        let last = if abs_row == end.abs_row {
+            end.col
+        } else {
+            cols.saturating_sub(1)
+        };
+        let row = (abs_row - top) as u32;
+        let x = rect.x + first as u32 * cell_width;
+        let y = rect.y + row * cell_height;
+        let right = (rect.x + (last as u32 + 1) * cell_width).min(rect.x + rect.width);
+        if y + cell_height > rect.y + rect.height || right <= x {
+            continue;
+        }
+        spans.push((x, y, right - x));
+    }
+    spans
+}
+
+/// Theme yellow, more opaque for the current match.
+pub fn search_highlight_style(palette: &ThemePalette, current: bool) -> (Color, u8) {
+    let alpha = if current {
+        CURRENT_MATCH_ALPHA
+    } else {
+        MATCH_ALPHA
+    };
+    (palette.ansi[3], alpha)
+}
+
+/// What the search bar shows.
+pub struct SearchBarContent<'a> {
+    pub query: &'a str,
+    /// "3 of 12", "No results", … (empty while the query is empty).
+    pub status: &'a str,
+    /// Draws `status` in the error colour.
+    pub status_error: bool,
+    pub case_sensitive: bool,
+    pub regex: bool,
+}
+
+/// A toggle shown at the right end of the search bar.
+#[derive(Debug, Clone)]
+pub struct SearchFlagButton {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+    pub label: &'static str,
+    pub active: bool,
+}
+
+/// Pre-computed geometry for the search bar, in physical pixels.
+#[derive(Debug, Clone)]
+pub struct SearchBarLayout {
+    pub bg_x: u32,
+    pub bg_y: u32,
+    pub bg_w: u32,
+    pub bg_h: u32,
+    pub radius: u32,
+    /// Baseline row shared by all texts.
+    pub text_y: u32,
+    pub query_x: u32,
+    /// Tail of the query that fits the field.
+    pub query_text: String,
+    pub caret_x: u32,
+    pub caret_w: u32,
+    pub status_x: u32,
+    pub status_text: String,
+    pub status_error: bool,
+    pub flags: [SearchFlagButton; 2],
+}
+
+/// Lays the search bar out in the top-right corner of `rect`.
+///
+/// Returns `None` when the pane is too narrow for a usable query field.
+pub fn compute_search_bar_layout(
+    content: &SearchBarContent<'_>,
+    m: &TabLayoutMetrics,
+    rect: PaneRect,
+) -> Option<SearchBarLayout> {
+    let pad_x = m.scaled_px(8);
+    let pad_y = m.scaled_px(4);
+    let gap = m.scaled_px(8);
+    let flag_pad = m.scaled_px(4);
+    let margin = m.scaled_px(6);
+
+    let status_chars = (content.status.chars().count() as u32).max(8);
+    let status_w = status_chars * m.cell_width;
+    let flag_w = 2 * m.cell_width + flag_pad * 2;
+    let fixed_w = pad_x * 2 + gap * 2 + status_w + flag_w * 2 + flag_pad;
+
+    let available = rect.width.saturating_sub(margin * 2 + fixed_w) / m.cell_width.max(1);
+    let query_chars = available.min(QUERY_FIELD_CHARS);
+    if query_chars < MIN_QUERY_FIELD_CHARS {
+        return None;
+    }
+    let query_w = query_chars * m.cell_width;
+
+    let bg_w = fixed_w + query_w;
+    let bg_h = m.cell_height + pad_y * 2;
+    let bg_x = rect.x + rect.width - margin - bg_w;
+    let bg_y = rect.y + margin;
+    let text_y = bg_y + pad_y;
+
+    // Keep the end of the query visible, leaving one cell for the caret.
+    let shown = content.query.chars().count().min(query_chars as usize - 1);
+    let skip = content.query.chars().count() - shown;
+    let query_text: String = content.query.chars().skip(skip).collect();
+    let query_x = bg_x + pad_x;
+    let caret_x = query_x + shown as u32 * m.cell_width;
+
+    let status_x = query_x + query_w + gap;
+    let case_x = status_x + status_w + gap;
+    let regex_x = case_x + flag_w + flag_pad;
+    let flag = |x: u32, label: &'static str, active: bool| SearchFlagButton {
+        x,
+        y: bg_y + pad_y / 2,
+        w: flag_w,
+        h: m.cell_height + pad_y,
+        label,
+        active,
+    };
+
+    Some(SearchBarLayout {
+        bg_x,
+        bg_y,
+        bg_w,
+        bg_h,
+        radius: m.scaled_px(6),
+        text_y,
+        query_x,
+        query_text,
+        caret_x,
+        caret_w: m.scaled_px(1).max(1),
+        status_x,
+        status_text: content.status.to_owned(),
+        status_error: content.status_error,
+        flags: [
+            flag(case_x, "Aa", content.case_sensitive),
+            flag(regex_x, ".*", content.regex),
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{GraphemeCell, Regex, search_screen};
+
+    fn metrics() -> TabLayoutMetrics {
+        TabLayoutMetrics {
+            cell_width: 9,
+            cell_height: 20,
+            ui_scale: 1.0,
+            tab_bar_height: 36,
+        }
+    }
+
+    fn rect(width: u32, height: u32) -> PaneRect {
+        PaneRect {
+            x: 10,
+            y: 40,
+            width,
+            height,
+        }
+    }
+
+    fn content(query: &str) -> SearchBarContent<'_> {
+        SearchBarContent {
+            query,
+            status: "1 of 2",
+            status_error: false,
+            case_sensitive: false,
+            regex: true,
+        }
+    }
+
+    #[test]
+    fn highlights_split_wrapped_matches_per_row() {
+        let mut screen = PageList::new(3, 4, 10);
+        for (col, ch) in "abcd".chars().enumerate() {
+            screen.viewport_set(0, col, GraphemeCell::from_char(ch));
+        }
+        screen.viewport_row_mut(0).wrapped = true;
+        screen.viewport_row_mut(0).written_cols = 4;
+        screen.viewport_set(1, 0, GraphemeCell::from_char('e'));
+        let matches = search_screen(&screen, &Regex::literal("cde", true));
+        let highlights =
+            visible_search_highlights(&screen, 0, rect(36, 60), (9, 20), &matches, Some(0));
+        assert_eq!(highlights.len(), 2);
+        assert_eq!(
+            (highlights[0].x, highlights[0].y, highlights[0].width),
+            (28, 40, 18)
+        );
+        assert_eq!(
+            (highlights[1].x, highlights[1].y, highlights[1].width),
+            (10, 60, 9)
+        );
+        assert!(highlights.iter().all(|h| h.current));
+    }
+
+    #[test]
+    fn highlights_outside_the_viewport_are_skipped() {
+        let mut screen = PageList::new(2, 4, 10);
+        screen.viewport_set(1, 0, GraphemeCell::from_char('a'));
+        let matches = search_screen(&screen, &Regex::literal("a", true));
+        assert_eq!(matches.len(), 1);
+        // Rect holds one row only.
+        let highlights =
+            visible_search_highlights(&screen, 0, rect(36, 20), (9, 20), &matches, None);
+        assert!(highlights.is_empty());
+    }
+
+    #[test]
+    fn bar_sits_in_top_right_corner() {
+        let layout = compute_search_bar_layout(&content("needle"), &metrics(), rect(800, 400))
+            .expect("fits");
+        assert_eq!(layout.bg_x + layout.bg_w, 10 + 800 - 6);
+        assert_eq!(layout.bg_y, 46);
+        assert_eq!(layout.query_text, "needle");
+        assert_eq!(layout.caret_x, layout.query_x + 6 * 9);
+        assert!(!layout.flags[0].active && layout.flags[1].active);
+    }
+
+    #[test]
+    fn long_queries_show_their_tail_and_narrow_panes_hide_the_bar() {
+        let query = "x".repeat(40) + "end";
+        let layout =
+            compute_search_bar_layout(&content(&query), &metrics(), rect(800, 400)).expect("fits");
+        assert_eq!(
+            layout.query_text.chars().count(),
+            QUERY_FIELD_CHARS as usize - 1
+        );
+        assert!(layout.query_text.ends_with("end"));
+        assert!(compute_search_bar_layout(&content("a"), &metrics(), rect(120, 400)).is_none());
+    }
+}
@@ -181,6 +181,8 @@ mod tests {
             rename_cursor: 0,
             #[cfg(not(target_os = "macos"))]
             rename_selection: None,
+            #[cfg(not(target_os = "macos"))]
+            has_bell: false,
         }
     }
 
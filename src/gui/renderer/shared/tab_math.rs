@@ -51,6 +51,10 @@ pub const PLUS_BUTTON_GAP: u32 = 4;
 #[cfg(not(target_os = "macos"))]
 pub const TAB_PADDING_H: u32 = 14;
 
+/// Radius of the bell badge dot on background tabs.
+#[cfg(not(target_os = "macos"))]
+pub const BELL_BADGE_RADIUS: u32 = 3;
+
 /// Pin button size in logical pixels (non-macOS).
 #[cfg(not(target_os = "macos"))]
 pub const PIN_BUTTON_SIZE: u32 = 24;
@@ -243,6 +247,19 @@ pub fn gear_button_rect(m: &TabLayoutMetrics) -> Rect {
 
 
 
+/// Returns the centre of the bell badge of a tab starting at `tab_x`.
+///
+/// The badge sits in the close button slot and gives way to the button
+/// when the tab shows it.
+#[cfg(not(target_os = "macos"))]
+pub fn bell_badge_center(m: &TabLayoutMetrics, tab_x: f32, tab_width: u32) -> (f32, f32) {
+    let btn_size = m.scaled_px(CLOSE_BUTTON_SIZE);
+    let cx = tab_x + tab_width as f32
+        - m.scaled_px(CLOSE_BUTTON_MARGIN) as f32
+        - btn_size as f32 / 2.0;
+    (cx, m.tab_bar_height as f32 / 2.0)
+}
+
 #[cfg(not(target_os = "macos"))]
 /// Returns the width reserved by the close button when it is visible.
 pub fn close_button_reserved_width(m: &TabLayoutMetrics) -> u32 {
@@ -524,8 +541,8 @@ mod tests {
     fn title_chars_decrease_with_close_button() {
         let m = default_metrics();
         let tw = 240;
-        let without_close = tab_title_max_chars(&m, tw, false, 0);
-        let with_close = tab_title_max_chars(&m, tw, true, 0);
+        let without_close = tab_title_max_chars(&m, tw, false);
+        let with_close = tab_title_max_chars(&m, tw, true);
         assert!(without_close >= with_close);
     }
 
@@ -534,7 +551,7 @@ mod tests {
     #[test]
     fn title_chars_zero_for_very_narrow_tab() {
         let m = default_metrics();
-        assert_eq!(tab_title_max_chars(&m, 0, true, 0), 0);
+        assert_eq!(tab_title_max_chars(&m, 0, true), 0);
     }
 
     #[cfg(not(target_os = "macos"))]
@@ -25,7 +25,7 @@ impl CpuRenderer {
         let state = self.tab_text_state(slot);
 
         let number_str = (slot.index + 1).to_string();
-        let close_reserved = if state.show_close {
+        let close_reserved = if state.show_close || slot.tab.has_bell {
             tab_math::close_button_reserved_width(&state.m)
         } else {
             0
@@ -37,6 +37,8 @@ impl CpuRenderer {
 
         if state.show_close {
             self.draw_close_button(target, slot.index, slot.width, slot.tab.close_hover_progress);
+        } else if slot.tab.has_bell {
+            self.draw_bell_badge(target, slot, &state.m);
         }
     }
 
@@ -48,16 +50,35 @@ impl CpuRenderer {
     ) {
         let state = self.tab_text_state(slot);
         let tab_padding_h = state.m.scaled_px(tab_math::TAB_PADDING_H);
-        let max_chars = tab_math::tab_title_max_chars(&state.m, slot.width, state.show_close);
+        let max_chars = tab_math::tab_title_max_chars(
+            &state.m,
+            slot.width,
+            state.show_close || slot.tab.has_bell,
+        );
 
         let text_x = slot.x + tab_padding_h;
         self.draw_tab_title(target, slot.tab, text_x, state.text_y, state.fg, max_chars);
 
         if state.show_close {
             self.draw_close_button(target, slot.index, slot.width, slot.tab.close_hover_progress);
+        } else if slot.tab.has_bell {
+            self.draw_bell_badge(target, slot, &state.m);
         }
     }
 
+    /// Draws the bell badge dot in the close button slot.
+    fn draw_bell_badge(&self, target: &mut RenderTarget<'_>, slot: &TabSlot, m: &TabLayoutMetrics) {
+        let (cx, cy) = tab_math::bell_badge_center(m, slot.x as f32, slot.width);
+        Self::draw_filled_circle(
+            target,
+            cx.round() as i32,
+            cy.round() as i32,
+            m.scaled_px(tab_math::BELL_BADGE_RADIUS),
+            self.palette.active_accent.to_pixel(),
+            255,
+        );
+    }
+
     /// Computes shared layout state used by both `draw_tab_number` and `draw_tab_content`.
     fn tab_text_state(&self, slot: &TabSlot) -> TabTextState {
         let m = self.tab_layout_metrics();
@@ -1,6 +1,8 @@
 use super::*;
 use super::RenderTarget;
-use crate::core::{Color, PageList, UnderlineStyle};
+use super::shared::image_layout::cell_has_underlay;
+use super::shared::link_layout::UnderlineBar;
+use crate::core::{CellEffects, Color, GraphemeCell, PageList, UnderlineStyle};
 use crate::gui::pane::PaneRect;
 
 /// Draws a single horizontal line of pixels spanning one cell width.
@@ -32,14 +34,104 @@ fn draw_horizontal_cell_line(
     }
 }
 
+/// Writes one pixel if it lies inside the buffer and left of `max_x`.
+fn put_pixel(target: &mut RenderTarget<'_>, x: usize, y: u32, pixel: u32, max_x: usize) {
+    let y = y as usize;
+    if x < max_x && y < target.height {
+        let idx = y * target.width + x;
+        if idx < target.buffer.len() {
+            target.buffer[idx] = pixel;
+        }
+    }
+}
+
+/// Draws the `style` underline of the cell whose bottom edge is at `bottom`.
+///
+/// Curly, dotted and dashed patterns are phased on the cell, so runs of
+/// underlined cells join into one continuous line.
+fn draw_underline(
+    target: &mut RenderTarget<'_>,
+    style: UnderlineStyle,
+    x: usize,
+    bottom: u32,
+    cell_width: usize,
+    pixel: u32,
+    max_x: usize,
+) {
+    match style {
+        UnderlineStyle::None => {}
+        UnderlineStyle::Single => {
+            draw_horizontal_cell_line(target, x, bottom - 2, cell_width, pixel, max_x);
+        }
+        UnderlineStyle::Double => {
+            draw_horizontal_cell_line(target, x, bottom - 3, cell_width, pixel, max_x);
+            draw_horizontal_cell_line(target, x, bottom - 1, cell_width, pixel, max_x);
+        }
+        UnderlineStyle::Curly => {
+            for dx in 0..cell_width {
+                let phase = dx as f32 / cell_width as f32 * std::f32::consts::TAU;
+                let lift = (1.0 - phase.cos()).round() as u32;
+                put_pixel(target, x + dx, bottom - 1 - lift, pixel, max_x);
+            }
+        }
+        UnderlineStyle::Dotted => {
+            for dx in (0..cell_width).filter(|dx| (dx / 2) % 2 == 0) {
+                put_pixel(target, x + dx, bottom - 2, pixel, max_x);
+            }
+        }
+        UnderlineStyle::Dashed => {
+            let dash = cell_width * 2 / 3;
+            draw_horizontal_cell_line(target, x, bottom - 2, dash, pixel, max_x);
+        }
+    }
+}
+
 impl CpuRenderer {
+    /// Draws the glyph and line decorations of `cell` in the text and
+    /// underline colors, honouring conceal and the blink phase. Spacer
+    /// cells have nothing to draw.
+    fn draw_cell_foreground(
+        &mut self,
+        target: &mut RenderTarget<'_>,
+        cell: &GraphemeCell,
+        x: u32,
+        y: u32,
+        (fg, underline_color): (Color, Color),
+        max_x: usize,
+    ) {
+        let hidden = cell.effects.contains(CellEffects::HIDDEN)
+            || (cell.effects.contains(CellEffects::BLINK) && !self.blink_visible);
+        if cell.width == 0 || hidden {
+            return;
+        }
+        let ch = cell.first_char();
+        if ch != ' ' {
+            self.draw_char(target, x, y, ch, fg);
+        }
+
+        let cell_width = self.metrics.cell_width as usize;
+        let cell_height = self.metrics.cell_height;
+        draw_underline(
+            target, cell.underline_style, x as usize, y + cell_height,
+            cell_width, underline_color.to_pixel(), max_x,
+        );
+        if cell.strikethrough {
+            draw_horizontal_cell_line(
+                target, x as usize, y + cell_height / 2, cell_width, fg.to_pixel(), max_x,
+            );
+        }
+        if cell.effects.contains(CellEffects::OVERLINE) {
+            draw_horizontal_cell_line(target, x as usize, y, cell_width, fg.to_pixel(), max_x);
+        }
+    }
+
     /// Maps sentinel default colors to the current theme palette.
     ///
     /// Cells created by `GraphemeCell::default()` carry compile-time sentinel values.
     /// We remap them so empty cells render with the theme's actual defaults.
     fn remap_defaults(&self, fg: Color, bg: Color) -> (Color, Color) {
-        let fg = if fg == Color::SENTINEL_FG { self.palette.default_fg } else { fg };
-        let bg = if bg == Color::SENTINEL_BG { self.palette.default_bg } else { bg };
+        let fg = if fg == Color::SENTINEL_FG { self.pane_colors.fg } else { fg };
+        let bg = if bg == Color::SENTINEL_BG { self.pane_colors.bg } else { bg };
         (fg, bg)
     }
 
@@ -58,14 +150,18 @@ impl CpuRenderer {
         let rows = screen.viewport_rows();
         let cols = screen.cols();
         let viewport_start = screen.scrollback_len().saturating_sub(scroll_offset);
+        let grid_rect = PaneRect {
+            x: x_offset,
+            y: y_offset,
+            width: (buf_width as u32).saturating_sub(x_offset),
+            height: (buf_height as u32).saturating_sub(y_offset),
+        };
+        let images = self.image_layers(screen, scroll_offset, grid_rect);
 
         for row in 0..rows {
             let abs_row = viewport_start + row;
             for col in 0..cols {
                 let cell = display_cell(screen, scroll_offset, row, col);
-                // Spacer cells (right half of wide char) — skip rendering the glyph,
-                // but still draw the background.
-                let is_spacer = cell.width == 0;
                 let x = col as u32 * self.metrics.cell_width + x_offset;
                 let y = row as u32 * self.metrics.cell_height + y_offset;
 
@@ -101,32 +197,24 @@ impl CpuRenderer {
 
                 self.draw_bg(target, x, y, bg);
 
-                if !is_spacer {
-                    let ch = cell.first_char();
-                    if ch != ' ' {
-                        self.draw_char(target, x, y, ch, fg);
-                    }
-                }
-
-                // Underline
-                if !is_spacer && cell.underline_style != UnderlineStyle::None {
-                    let underline_y = y + self.metrics.cell_height - 2;
-                    draw_horizontal_cell_line(
-                        target, x as usize, underline_y,
-                        self.metrics.cell_width as usize, fg.to_pixel(), buf_width,
-                    );
+                // Negative z-index images sit between the background and the text.
+                if !images.under.is_empty() && cell_has_underlay(screen, scroll_offset, row, col) {
+                    let cell_rect = PaneRect {
+                        x,
+                        y,
+                        width: self.metrics.cell_width,
+                        height: self.metrics.cell_height,
+                    };
+                    self.draw_image_blits(target, &images.under, cell_rect);
                 }
 
-                // Strikethrough
-                if !is_spacer && cell.strikethrough {
-                    let strike_y = y + self.metrics.cell_height / 2;
-                    draw_horizontal_cell_line(
-                        target, x as usize, strike_y,
-                        self.metrics.cell_width as usize, fg.to_pixel(), buf_width,
-                    );
-                }
+                let underline_color = cell.underline_color.unwrap_or(fg);
+                self.draw_cell_foreground(target, &cell, x, y, (fg, underline_color), buf_width);
             }
         }
+
+        self.draw_image_blits(target, &images.over, grid_rect);
+        self.draw_status_markers(target, screen, scroll_offset, grid_rect);
     }
 
     /// Renders terminal cells into a sub-rectangle of the buffer.
@@ -148,12 +236,12 @@ impl CpuRenderer {
         let rows = screen.viewport_rows();
         let cols = screen.cols();
         let viewport_start = screen.scrollback_len().saturating_sub(scroll_offset);
+        let images = self.image_layers(screen, scroll_offset, rect);
 
         for row in 0..rows {
             let abs_row = viewport_start + row;
             for col in 0..cols {
                 let cell = display_cell(screen, scroll_offset, row, col);
-                let is_spacer = cell.width == 0;
                 let x = col as u32 * self.metrics.cell_width + rect.x;
                 let y = row as u32 * self.metrics.cell_height + rect.y;
 
@@ -199,35 +287,52 @@ impl CpuRenderer {
 
                 self.draw_bg(target, x, y, bg);
 
-                if !is_spacer {
-                    let ch = cell.first_char();
-                    if ch != ' ' {
-                        self.draw_char(target, x, y, ch, fg);
-                    }
+                // Negative z-index images sit between the background and the text.
+                if !images.under.is_empty() && cell_has_underlay(screen, scroll_offset, row, col) {
+                    let cell_rect = PaneRect {
+                        x,
+                        y,
+                        width: self.metrics.cell_width,
+                        height: self.metrics.cell_height,
+                    };
+                    self.draw_image_blits(target, &images.under, cell_rect);
                 }
 
-                if !is_spacer && cell.underline_style != UnderlineStyle::None {
-                    let underline_y = y + self.metrics.cell_height - 2;
-                    if (underline_y as usize) < rect_bottom {
-                        draw_horizontal_cell_line(
-                            target, x as usize, underline_y,
-                            self.metrics.cell_width as usize, fg.to_pixel(),
-                            buf_width.min(rect_right),
-                        );
-                    }
+                let mut underline_color = cell.underline_color.unwrap_or(fg);
+                if fg_dim > 0.0 && cell.underline_color.is_some() {
+                    underline_color = underline_color.dimmed(fg_dim);
                 }
+                self.draw_cell_foreground(
+                    target, &cell, x, y, (fg, underline_color), buf_width.min(rect_right),
+                );
+            }
+        }
 
-                // Strikethrough
-                if !is_spacer && cell.strikethrough {
-                    let strike_y = y + self.metrics.cell_height / 2;
-                    if (strike_y as usize) < rect_bottom {
-                        draw_horizontal_cell_line(
-                            target, x as usize, strike_y,
-                            self.metrics.cell_width as usize, fg.to_pixel(),
-                            buf_width.min(rect_right),
-                        );
-                    }
-                }
+        self.draw_image_blits(target, &images.over, rect);
+        self.draw_status_markers(target, screen, scroll_offset, rect);
+    }
+
+    /// Draws the underline of a hovered link in the default foreground.
+    pub fn draw_link_underline(&self, target: &mut RenderTarget<'_>, bars: &[UnderlineBar]) {
+        let pixel = self.palette.default_fg.to_pixel();
+        let max_x = target.width;
+        for bar in bars {
+            for y in bar.y..bar.y + bar.height {
+                draw_horizontal_cell_line(target, bar.x as usize, y, bar.width as usize, pixel, max_x);
+            }
+        }
+    }
+
+    /// Blends the visual bell tint over `rect`.
+    pub fn draw_bell_flash(&self, target: &mut RenderTarget<'_>, rect: PaneRect, alpha: f32) {
+        let pixel = self.palette.default_fg.to_pixel();
+        let alpha = (alpha * 255.0).round().clamp(0.0, 255.0) as u8;
+        let x1 = ((rect.x + rect.width) as usize).min(target.width);
+        let y1 = ((rect.y + rect.height) as usize).min(target.height);
+        for py in rect.y as usize..y1 {
+            for px in rect.x as usize..x1 {
+                let idx = py * target.width + px;
+                target.buffer[idx] = blend_rgb(target.buffer[idx], pixel, alpha);
             }
         }
     }
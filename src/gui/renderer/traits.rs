@@ -1,15 +1,17 @@
 use crate::core::{CursorStyle, PageList, Selection};
 use crate::gui::pane::PaneRect;
 
-use super::shared::banner_layout::UpdateBannerLayout;
+use super::shared::banner_layout::{ClipboardPromptLayout, UpdateBannerLayout};
+use super::shared::link_layout::UnderlineBar;
 use super::shared::scrollbar_math;
+use super::shared::search_layout::{SearchBarLayout, SearchHighlight};
 #[cfg(not(target_os = "macos"))]
 use super::shared::tab_hit_test;
 use super::shared::tab_math::{self, TabLayoutMetrics};
 #[cfg(not(target_os = "macos"))]
 use super::TabInfo;
 use super::{SCROLLBAR_MIN_THUMB, TabBarHit};
-use super::{RenderTarget, ScrollbarState};
+use super::{PaneColors, RenderTarget, ScrollbarState};
 
 /// Trait defining the full renderer interface used by the GUI layer.
 ///
@@ -63,6 +65,12 @@ pub trait Renderer {
         scroll_offset: usize,
     );
 
+    /// Sets whether text with the blink attribute is drawn this frame.
+    fn set_blink_visible(&mut self, visible: bool);
+
+    /// Sets the default colours used by the following grid and cursor draws.
+    fn set_pane_colors(&mut self, colors: PaneColors);
+
     fn draw_cursor(
         &mut self,
         target: &mut RenderTarget<'_>,
@@ -88,6 +96,25 @@ pub trait Renderer {
     ) {
     }
 
+    /// Underlines the hovered link; drawn over the grid.
+    fn draw_link_underline(&mut self, target: &mut RenderTarget<'_>, bars: &[UnderlineBar]);
+
+    /// Tints scrollback search matches; drawn over the grid, below the cursor.
+    fn draw_search_highlights(&mut self, target: &mut RenderTarget<'_>, highlights: &[SearchHighlight]);
+
+    /// Tints `rect` in the foreground colour for the visual bell; drawn over the grid.
+    fn draw_bell_flash(&mut self, target: &mut RenderTarget<'_>, rect: PaneRect, alpha: f32);
+
+    /// Draws the search bar of a pane with an open search.
+    fn draw_search_bar(&mut self, target: &mut RenderTarget<'_>, layout: &SearchBarLayout);
+
+    /// Draws the prompt asking whether a pane may read the clipboard.
+    fn draw_clipboard_prompt(&mut self, target: &mut RenderTarget<'_>, layout: &ClipboardPromptLayout);
+
+    /// Draws the vi copy-mode cursor as an inverted block over `ch`, with
+    /// the top-left corner of its cell at pixel `(x, y)`.
+    fn draw_vi_cursor(&mut self, target: &mut RenderTarget<'_>, x: u32, y: u32, ch: char);
+
     /// Draws the cursor at a position offset by a pane rectangle.
     ///
     /// Default implementation is a no-op (GPU renderer overrides separately).
@@ -1,3 +1,6 @@
+use crate::config::ThemePalette;
+use crate::core::Color;
+
 /// Render-time tab metadata.
 #[cfg(not(target_os = "macos"))]
 pub struct TabInfo<'a> {
@@ -18,6 +21,9 @@ pub struct TabInfo<'a> {
     pub rename_cursor: usize,
     #[cfg(not(target_os = "macos"))]
     pub rename_selection: Option<(usize, usize)>, // Byte range within rename_text.
+    /// A background pane rang the bell since the tab was last active.
+    #[cfg(not(target_os = "macos"))]
+    pub has_bell: bool,
 }
 
 // ── Layout structs ──────────────────────────────────────────────────
@@ -140,6 +146,28 @@ pub struct ScrollbarState {
     pub grid_rows: usize,
     pub opacity: f32,
     pub hover: bool,
+    /// Finished commands as `(abs_row, failed)`, drawn as ticks on the track.
+    pub command_ticks: Vec<(usize, bool)>,
+}
+
+/// Default colours of the pane being drawn, which OSC 10/11/12 can change
+/// per pane. Cells with sentinel colours and the cursor use these.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaneColors {
+    pub fg: Color,
+    pub bg: Color,
+    pub cursor: Color,
+}
+
+impl PaneColors {
+    /// Colours of a pane that has not changed the theme defaults.
+    pub fn from_theme(palette: &ThemePalette) -> Self {
+        Self {
+            fg: palette.default_fg,
+            bg: palette.default_bg,
+            cursor: palette.default_fg,
+        }
+    }
 }
 #[cfg(not(target_os = "macos"))]
 /// Bundled parameters for tab bar drawing.
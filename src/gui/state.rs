@@ -4,7 +4,7 @@ use std::time::Instant;
 #[cfg(not(target_os = "linux"))]
 use muda::MenuId;
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, BellConfig};
 use crate::core::PageCoord;
 use super::*;
 
@@ -61,6 +61,8 @@ pub(super) struct TabState {
     /// `true` when the user has explicitly renamed this tab.
     /// When `false`, the title auto-updates from the focused pane's CWD.
     pub(super) is_renamed: bool,
+    /// A pane rang the bell while the tab was in the background.
+    pub(super) has_bell: bool,
 }
 
 impl TabState {
@@ -140,11 +142,71 @@ pub(super) struct RenameState {
     pub(super) selection_anchor: Option<usize>, // Byte index for selection anchor.
 }
 
+/// Scrollback search of one pane, open while the search bar is shown.
+#[derive(Default)]
+pub(super) struct SearchState {
+    pub(super) query: String,
+    pub(super) case_sensitive: bool,
+    pub(super) regex: bool,
+    /// Matches in buffer order (oldest first).
+    pub(super) matches: crate::core::SearchMatches,
+    /// Index into `matches` of the match last jumped to.
+    pub(super) current: Option<usize>,
+    /// `true` when `query` does not compile as a regex.
+    pub(super) invalid: bool,
+    /// Whether `matches` were found on the alternate screen.
+    pub(super) alt_screen: bool,
+}
+
+/// Visual selection flavour in vi copy mode (`v`, `V`, `Ctrl-v`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum ViVisual {
+    Char,
+    Line,
+    Block,
+}
+
+/// Vi copy mode of one pane: a keyboard cursor that roams the scrollback
+/// while the view stays put.
+pub(super) struct ViModeState {
+    /// Copy-mode cursor; follows its text as output scrolls.
+    pub(super) cursor: crate::core::TrackedPin,
+    /// `scroll_offset` to restore on exit.
+    pub(super) saved_scroll_offset: usize,
+    /// Active visual selection and the end it was started from.
+    pub(super) visual: Option<(ViVisual, crate::core::TrackedPin)>,
+    /// The first `g` of `gg` has been typed.
+    pub(super) pending_g: bool,
+    /// `true` after `/`, `false` after `?`; `n` repeats in this direction.
+    pub(super) search_forward: bool,
+    /// Matches of the last confirmed search, for `n` and `N`.
+    pub(super) matches: crate::core::SearchMatches,
+    /// Whether copy mode was entered on the alternate screen.
+    pub(super) alt_screen: bool,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub(super) enum SelectionDragMode {
     Character,
     Word,
     Line,
+    /// Alt+drag: a rectangle of columns across the dragged rows.
+    Block,
+}
+
+/// A pane whose OSC 52 clipboard reads wait for the user's answer in the
+/// clipboard prompt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct ClipboardPrompt {
+    pub(super) tab_id: u64,
+    pub(super) pane_id: pane::PaneId,
+}
+
+/// A clicked commit hash and the web page of the commit, found by looking
+/// up the repository's `origin` remote off the event loop.
+pub(super) struct CommitLookup {
+    pub(super) hash: String,
+    pub(super) url: Option<String>,
 }
 
 /// Install state for the in-app update banner.
@@ -197,6 +259,8 @@ pub(super) struct FerrumWindow {
     /// When set, SIGWINCH is sent to all panes once this instant is reached.
     /// Reset on every resize event so SIGWINCH fires only after the drag settles.
     pub(super) sigwinch_deadline: Option<Instant>,
+    /// A redraw was skipped while a pane held back output (mode `?2026`).
+    pub(super) deferred_frame: bool,
     pub(super) backend: RendererBackend,
     pub(super) tabs: Vec<TabState>,
     pub(super) active_tab: usize,
@@ -211,6 +275,9 @@ pub(super) struct FerrumWindow {
     pub(super) keyboard_selection_anchor: Option<PageCoord>,
     pub(super) selection_drag_mode: SelectionDragMode,
     pub(super) hovered_tab: Option<usize>,
+    /// Finished command whose gutter marker is under the pointer.
+    #[cfg(not(target_os = "macos"))]
+    pub(super) hovered_command_status: Option<crate::core::CommandStatus>,
     #[cfg(not(target_os = "linux"))]
     pub(super) pending_menu_context: Option<MenuContext>,
     #[cfg(not(target_os = "macos"))]
@@ -236,6 +303,8 @@ pub(super) struct FerrumWindow {
     pub(super) next_native_tab_sync_at: Option<Instant>,
     /// Accumulates fractional pixel scroll for trackpad (PixelDelta).
     pub(super) scroll_accumulator: f64,
+    /// Horizontal counterpart of `scroll_accumulator`, in cell widths.
+    pub(super) scroll_accumulator_x: f64,
     /// Pending requests from this window to the App (detach, close, etc.).
     pub(super) pending_requests: Vec<WindowRequest>,
     /// Whether this window is pinned (always-on-top).
@@ -246,6 +315,14 @@ pub(super) struct FerrumWindow {
     pub(super) last_cwd_poll: Instant,
     /// Cursor blink interval from config.
     pub(super) cursor_blink_interval_ms: u64,
+    /// Editor command template for opening detected file locations.
+    pub(super) editor_command: String,
+    /// How BEL from a pane is signalled.
+    pub(super) bell: BellConfig,
+    /// When the bell last sounded or asked for attention, for throttling.
+    pub(super) last_bell_at: Option<Instant>,
+    /// Delivers OSC 9/777 notifications from panes the user is not looking at.
+    pub(super) notifier: Box<dyn super::notifier::Notifier>,
     /// Sender for native settings window config updates.
     pub(super) settings_tx: mpsc::Sender<AppConfig>,
     /// Proxy to wake the event loop from PTY reader threads.
@@ -256,6 +333,14 @@ pub(super) struct FerrumWindow {
     pub(super) update_banner_dismissed: bool,
     /// Current state of the background install operation.
     pub(super) update_install_state: UpdateInstallState,
+    /// Pane asking to read the clipboard, shown in the clipboard prompt.
+    pub(super) clipboard_prompt: Option<ClipboardPrompt>,
+    /// Finished commit link lookups, sent by their worker threads.
+    pub(super) commit_lookup_tx: mpsc::Sender<CommitLookup>,
+    pub(super) commit_lookup_rx: mpsc::Receiver<CommitLookup>,
+    /// Keys whose press was reported with the Kitty keyboard protocol; only
+    /// their releases are reported.
+    pub(super) kitty_pressed_keys: std::collections::HashSet<PhysicalKey>,
 }
 
 /// App is now a window manager holding multiple FerrumWindows.
@@ -66,6 +66,8 @@ impl FerrumWindow {
             palette.default_bg,
             palette.ansi,
         );
+        terminal.set_tab_width(params.config.terminal.tab_width);
+        terminal.set_cell_pixel_size(self.backend.cell_width(), self.backend.cell_height());
 
         // Show "Last login" greeting with local time.
         {
@@ -82,6 +84,13 @@ impl FerrumWindow {
             scroll_offset: 0,
             security: SecurityGuard::new(),
             scrollbar: ScrollbarState::new(),
+            search: None,
+            vi_mode: None,
+            hovered_link: None,
+            hovered_hyperlink: None,
+            bell_at: None,
+            notified_at: None,
+            clipboard_read_consent: None,
         };
 
         let shell_name = std::path::Path::new(&shell)
@@ -104,6 +113,7 @@ impl FerrumWindow {
             focused_pane: pane_id,
             next_pane_id: 1,
             is_renamed,
+            has_bell: false,
         })
     }
 }
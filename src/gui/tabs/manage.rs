@@ -243,7 +243,7 @@ impl FerrumWindow {
         }
 
         let palette = config.theme.resolve();
-        let terminal = Terminal::with_config(
+        let mut terminal = Terminal::with_config(
             rows,
             cols,
             config.terminal.max_scrollback,
@@ -251,6 +251,8 @@ impl FerrumWindow {
             palette.default_bg,
             palette.ansi,
         );
+        terminal.set_tab_width(config.terminal.tab_width);
+        terminal.set_cell_pixel_size(self.backend.cell_width(), self.backend.cell_height());
 
         let new_leaf = PaneNode::Leaf(Box::new(PaneLeaf {
             id: pane_id,
@@ -261,6 +263,13 @@ impl FerrumWindow {
             scroll_offset: 0,
             security: SecurityGuard::new(),
             scrollbar: ScrollbarState::new(),
+            search: None,
+            vi_mode: None,
+            hovered_link: None,
+            hovered_hyperlink: None,
+            bell_at: None,
+            notified_at: None,
+            clipboard_read_consent: None,
         }));
 
         // Re-borrow tab after the reader thread was spawned.
@@ -333,8 +342,11 @@ impl FerrumWindow {
                     let content = rect.inset(pane_pad);
                     let cols = (content.width / cw).max(1) as usize;
                     let rows = (content.height / ch).max(1) as usize;
+                    leaf.terminal.set_cell_pixel_size(cw, ch);
                     leaf.terminal.resize(rows, cols);
                     leaf.scroll_offset = leaf.scroll_offset.min(leaf.terminal.screen.scrollback_len());
+                    leaf.refresh_search();
+                    leaf.refresh_vi_mode();
                 }
             }
         }
@@ -6,6 +6,8 @@ static EN: Translations = Translations {
     menu_paste: "Paste",
     menu_select_all: "Select All",
     menu_clear_selection: "Clear Selection",
+    menu_select_command_output: "Select Last Command Output",
+    menu_copy_command_output: "Copy Last Command Output",
     menu_split_right: "Split Right",
     menu_split_down: "Split Down",
     menu_split_left: "Split Left",
@@ -23,6 +25,12 @@ static EN: Translations = Translations {
     close_dialog_confirm: "Close",
     close_dialog_cancel: "Cancel",
 
+    // --- Clipboard read prompt ---
+    clipboard_read_prompt: "{} wants to read the clipboard",
+    clipboard_read_prompt_pane: "{}, pane {}",
+    clipboard_read_allow: "Allow",
+    clipboard_read_deny: "Deny",
+
     // --- Settings window ---
     settings_title: "Ferrum Settings",
     settings_tab_font: "Font",
@@ -64,12 +72,19 @@ static EN: Translations = Translations {
     security_limit_cursor_jumps_desc: "Restrict how far escape sequences can move the cursor",
     security_clear_mouse_on_reset_label: "Clear Mouse on Reset",
     security_clear_mouse_on_reset_desc: "Disable mouse tracking modes when the terminal resets",
+    security_allow_notifications_label: "Allow Notifications",
+    security_allow_notifications_desc: "Let programs post desktop notifications (OSC 9, OSC 777)",
+    security_allow_clipboard_read_label: "Allow Clipboard Reading",
+    security_allow_clipboard_read_desc: "Let programs read the clipboard (OSC 52) once you agree for the pane",
+    security_allow_graphics_files_label: "Allow Image Files",
+    security_allow_graphics_files_desc: "Let programs show local image files with the Kitty graphics protocol",
 
     // --- Security popup ---
     security_event_paste_newlines: "Paste with newlines detected",
     security_event_title_query_blocked: "OSC/CSI title query blocked",
     security_event_cursor_rewrite: "Cursor rewrite detected",
     security_event_mouse_leak_prevented: "Mouse reporting leak prevented",
+    security_event_clipboard_read_denied: "Clipboard read denied",
 
     // --- macOS pin button ---
     macos_pin_window: "Pin Window",
@@ -89,6 +104,11 @@ static EN: Translations = Translations {
     update_auto_check: "Auto-check for updates",
     update_checking: "Checking…",
     update_up_to_date: "You're up to date",
+
+    // --- Search bar ---
+    search_match_count: "{} of {}",
+    search_no_results: "No results",
+    search_invalid_pattern: "Invalid pattern",
 };
 
 pub fn translations() -> &'static Translations {
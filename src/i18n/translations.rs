@@ -8,6 +8,8 @@ pub struct Translations {
     pub menu_paste: &'static str,
     pub menu_select_all: &'static str,
     pub menu_clear_selection: &'static str,
+    pub menu_select_command_output: &'static str,
+    pub menu_copy_command_output: &'static str,
     pub menu_split_right: &'static str,
     pub menu_split_down: &'static str,
     pub menu_split_left: &'static str,
@@ -25,6 +27,14 @@ pub struct Translations {
     pub close_dialog_confirm: &'static str,
     pub close_dialog_cancel: &'static str,
 
+    // --- Clipboard read prompt ---
+    /// Format string — `{}` placeholder for the pane asking to read.
+    pub clipboard_read_prompt: &'static str,
+    /// Format string — `{}` placeholders for the tab title and the pane number.
+    pub clipboard_read_prompt_pane: &'static str,
+    pub clipboard_read_allow: &'static str,
+    pub clipboard_read_deny: &'static str,
+
     // --- Settings window ---
     pub settings_title: &'static str,
     pub settings_tab_font: &'static str,
@@ -66,12 +76,19 @@ pub struct Translations {
     pub security_limit_cursor_jumps_desc: &'static str,
     pub security_clear_mouse_on_reset_label: &'static str,
     pub security_clear_mouse_on_reset_desc: &'static str,
+    pub security_allow_notifications_label: &'static str,
+    pub security_allow_notifications_desc: &'static str,
+    pub security_allow_clipboard_read_label: &'static str,
+    pub security_allow_clipboard_read_desc: &'static str,
+    pub security_allow_graphics_files_label: &'static str,
+    pub security_allow_graphics_files_desc: &'static str,
 
     // --- Security popup ---
     pub security_event_paste_newlines: &'static str,
     pub security_event_title_query_blocked: &'static str,
     pub security_event_cursor_rewrite: &'static str,
     pub security_event_mouse_leak_prevented: &'static str,
+    pub security_event_clipboard_read_denied: &'static str,
 
     // --- macOS pin button ---
     pub macos_pin_window: &'static str,
@@ -101,6 +118,12 @@ pub struct Translations {
     pub update_checking: &'static str,
     /// Status shown when the manual check found no newer version.
     pub update_up_to_date: &'static str,
+
+    // --- Search bar ---
+    /// Format string — `{}` placeholders for the current match and the match count.
+    pub search_match_count: &'static str,
+    pub search_no_results: &'static str,
+    pub search_invalid_pattern: &'static str,
 }
 
 impl Translations {
@@ -115,6 +138,8 @@ impl Translations {
             self.menu_paste,
             self.menu_select_all,
             self.menu_clear_selection,
+            self.menu_select_command_output,
+            self.menu_copy_command_output,
             self.menu_split_right,
             self.menu_split_down,
             self.menu_split_left,
@@ -129,6 +154,10 @@ impl Translations {
             self.close_dialog_body,
             self.close_dialog_confirm,
             self.close_dialog_cancel,
+            self.clipboard_read_prompt,
+            self.clipboard_read_prompt_pane,
+            self.clipboard_read_allow,
+            self.clipboard_read_deny,
             self.settings_title,
             self.settings_tab_font,
             self.settings_tab_theme,
@@ -159,10 +188,17 @@ impl Translations {
             self.security_limit_cursor_jumps_desc,
             self.security_clear_mouse_on_reset_label,
             self.security_clear_mouse_on_reset_desc,
+            self.security_allow_notifications_label,
+            self.security_allow_notifications_desc,
+            self.security_allow_clipboard_read_label,
+            self.security_allow_clipboard_read_desc,
+            self.security_allow_graphics_files_label,
+            self.security_allow_graphics_files_desc,
             self.security_event_paste_newlines,
             self.security_event_title_query_blocked,
             self.security_event_cursor_rewrite,
             self.security_event_mouse_leak_prevented,
+            self.security_event_clipboard_read_denied,
             self.macos_pin_window,
             self.macos_unpin_window,
             self.macos_pin_tooltip,
@@ -178,6 +214,9 @@ impl Translations {
             self.update_auto_check,
             self.update_checking,
             self.update_up_to_date,
+            self.search_match_count,
+            self.search_no_results,
+            self.search_invalid_pattern,
         ];
         fields.iter().all(|s| !s.is_empty())
     }
@@ -6,6 +6,8 @@ static UK: Translations = Translations {
     menu_paste: "Вставити",
     menu_select_all: "Вибрати все",
     menu_clear_selection: "Зняти виділення",
+    menu_select_command_output: "Виділити вивід останньої команди",
+    menu_copy_command_output: "Копіювати вивід останньої команди",
     menu_split_right: "Розділити праворуч",
     menu_split_down: "Розділити донизу",
     menu_split_left: "Розділити ліворуч",
@@ -23,6 +25,12 @@ static UK: Translations = Translations {
     close_dialog_confirm: "Закрити",
     close_dialog_cancel: "Скасувати",
 
+    // --- Clipboard read prompt ---
+    clipboard_read_prompt: "{} хоче прочитати буфер обміну",
+    clipboard_read_prompt_pane: "{}, панель {}",
+    clipboard_read_allow: "Дозволити",
+    clipboard_read_deny: "Заборонити",
+
     // --- Settings window ---
     settings_title: "Налаштування Ferrum",
     settings_tab_font: "Шрифт",
@@ -64,12 +72,19 @@ static UK: Translations = Translations {
     security_limit_cursor_jumps_desc: "Обмежити відстань переміщення курсора керуючими послідовностями",
     security_clear_mouse_on_reset_label: "Очищати мишу при скиданні",
     security_clear_mouse_on_reset_desc: "Вимикати режими відстеження миші при скиданні термінала",
+    security_allow_notifications_label: "Дозволити сповіщення",
+    security_allow_notifications_desc: "Дозволяти програмам надсилати сповіщення на робочий стіл (OSC 9, OSC 777)",
+    security_allow_clipboard_read_label: "Дозволити читання буфера обміну",
+    security_allow_clipboard_read_desc: "Дозволяти програмам читати буфер обміну (OSC 52) після вашої згоди для панелі",
+    security_allow_graphics_files_label: "Дозволити файли зображень",
+    security_allow_graphics_files_desc: "Дозволяти програмам показувати локальні файли зображень через протокол графіки Kitty",
 
     // --- Security popup ---
     security_event_paste_newlines: "Виявлено вставку з новими рядками",
     security_event_title_query_blocked: "Заблоковано запит заголовка OSC/CSI",
     security_event_cursor_rewrite: "Виявлено перезапис курсора",
     security_event_mouse_leak_prevented: "Запобігнено витоку звітування миші",
+    security_event_clipboard_read_denied: "Читання буфера обміну заборонено",
 
     // --- macOS pin button ---
     macos_pin_window: "Закріпити вікно",
@@ -89,6 +104,11 @@ static UK: Translations = Translations {
     update_auto_check: "Автоперевірка оновлень",
     update_checking: "Перевіряємо…",
     update_up_to_date: "У вас остання версія",
+
+    // --- Search bar ---
+    search_match_count: "{} з {}",
+    search_no_results: "Немає збігів",
+    search_invalid_pattern: "Некоректний шаблон",
 };
 
 pub fn translations() -> &'static Translations {
@@ -1,5 +1,6 @@
 # Ferrum terminal shell integration for bash.
-# Sends OSC 7 with the current working directory on every prompt.
+# Sends OSC 7 with the current working directory on every prompt and
+# OSC 133 semantic prompt marks around every command.
 
 # Guard: only run inside Ferrum.
 [[ -n "$FERRUM_SHELL_INTEGRATION" ]] || return
@@ -14,3 +15,30 @@ _ferrum_report_cwd() {
 }
 
 PROMPT_COMMAND="_ferrum_report_cwd${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+
+# Prompt marks only make sense for interactive shells.
+[[ $- == *i* ]] || return
+
+_ferrum_command_running=""
+
+# D (with exit status) for the command that just finished, then A.
+# Runs first in PROMPT_COMMAND so `$?` is still the command's status.
+_ferrum_mark_prompt() {
+  local ret=$?
+  if [[ -n "$_ferrum_command_running" ]]; then
+    builtin printf '\e]133;D;%s\a' "$ret"
+    _ferrum_command_running=""
+  fi
+  builtin printf '\e]133;A\a'
+  # B after the prompt text; re-added if the prompt was rebuilt.
+  if [[ "$PS1" != *'\e]133;B\a'* ]]; then
+    PS1="$PS1"'\[\e]133;B\a\]'
+  fi
+  return $ret
+}
+
+PROMPT_COMMAND="_ferrum_mark_prompt${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+
+# C before the command runs (PS0, bash 4.4+).  The zero-length substring
+# expansion marks the command as running without printing anything.
+PS0="${PS0}"'\e]133;C\a${_ferrum_command_running:0:$((_ferrum_command_running=1,0))}'
@@ -1,5 +1,6 @@
 # Ferrum terminal shell integration for fish.
-# Sends OSC 7 with the current working directory on every directory change.
+# Sends OSC 7 with the current working directory on every directory change
+# and OSC 133 semantic prompt marks around every command.
 
 # Guard: only run inside Ferrum.
 if not set -q FERRUM_SHELL_INTEGRATION
@@ -12,3 +13,26 @@ end
 
 # Report initial CWD.
 __ferrum_report_cwd
+
+# A before every prompt.  The user's fish_prompt is wrapped on first use
+# (config.fish runs after vendor_conf.d) so B follows the prompt text.
+function __ferrum_mark_prompt --on-event fish_prompt
+    printf '\e]133;A\a'
+    if not functions -q __ferrum_original_fish_prompt
+        functions -c fish_prompt __ferrum_original_fish_prompt
+        function fish_prompt
+            __ferrum_original_fish_prompt
+            printf '\e]133;B\a'
+        end
+    end
+end
+
+# C when a command line is accepted.
+function __ferrum_mark_output --on-event fish_preexec
+    printf '\e]133;C\a'
+end
+
+# D with the exit status once the command finishes.
+function __ferrum_mark_command_end --on-event fish_postexec
+    printf '\e]133;D;%s\a' $status
+end
@@ -1,10 +1,34 @@
 if ($env:FERRUM_SHELL_INTEGRATION -ne "1") { return }
 
+$global:__FerrumCommandRunning = $false
+
 function prompt {
+    # Capture the last command's status before anything else overwrites it.
+    $exitCode = if ($?) { 0 } elseif ($LASTEXITCODE) { $LASTEXITCODE } else { 1 }
+    # OSC 133 D with the exit status of the command that just finished.
+    if ($global:__FerrumCommandRunning) {
+        [Console]::Write("`e]133;D;$exitCode`a")
+        $global:__FerrumCommandRunning = $false
+    }
+
     # Emit OSC 7 with current directory (forward slashes for URI)
     $path = $PWD.Path -replace '\\', '/'
     $host_name = [System.Net.Dns]::GetHostName()
     [Console]::Write("`e]7;file://$host_name/$path`e\")
-    # Standard PS prompt
-    "PS $($PWD.Path)> "
+    # Standard PS prompt between OSC 133 A (prompt start) and B (input start)
+    "`e]133;A`aPS $($PWD.Path)> `e]133;B`a"
+}
+
+# OSC 133 C when a non-empty command line is accepted.
+if (Get-Module -Name PSReadLine) {
+    Set-PSReadLineKeyHandler -Chord Enter -ScriptBlock {
+        $line = $null
+        $cursor = $null
+        [Microsoft.PowerShell.PSConsoleReadLine]::GetBufferState([ref]$line, [ref]$cursor)
+        [Microsoft.PowerShell.PSConsoleReadLine]::AcceptLine()
+        if ($line.Trim()) {
+            [Console]::Write("`e]133;C`a")
+            $global:__FerrumCommandRunning = $true
+        }
+    }
 }
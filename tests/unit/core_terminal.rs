@@ -1,6 +1,6 @@
 use super::Terminal;
 use crate::config::ThemeChoice;
-use crate::core::Color;
+use crate::core::{ClipboardTarget, Color};
 
 fn get_char(term: &Terminal, row: usize, col: usize) -> char {
     term.screen.viewport_get(row, col).first_char()
@@ -52,7 +52,7 @@ fn reports_cursor_position_response_for_private_query() {
 
     term.process(b"\x1b[?6n");
 
-    assert_eq!(term.drain_responses(), b"\x1b[3;5R".to_vec());
+    assert_eq!(term.drain_responses(), b"\x1b[?3;5;1R".to_vec());
 }
 
 // ── Perform trait: print ──
@@ -162,6 +162,20 @@ fn execute_tab() {
     assert_eq!(term.cursor_col(), 9);
 }
 
+#[test]
+fn execute_bel_queues_bell() {
+    let mut term = Terminal::new(4, 80);
+    term.process(b"A\x07B\x07");
+
+    assert_eq!(term.take_bells(), 2);
+    assert_eq!(term.take_bells(), 0);
+    assert_eq!(get_char(&term, 0, 1), 'B');
+
+    // BEL terminating an OSC string is not a bell.
+    term.process(b"\x1b]2;title\x07");
+    assert_eq!(term.take_bells(), 0);
+}
+
 // ── Perform trait: esc_dispatch ──
 
 #[test]
@@ -585,11 +599,14 @@ fn osc52_writes_decoded_text_to_pending_clipboard() {
     let mut term = Terminal::new(24, 80);
     // "hello" in base64 is "aGVsbG8="
     term.process(b"\x1b]52;c;aGVsbG8=\x07");
-    assert_eq!(term.pending_clipboard_write.as_deref(), Some("hello"));
+    assert_eq!(
+        term.pending_clipboard_write,
+        Some((ClipboardTarget::Clipboard, "hello".to_owned()))
+    );
 }
 
 #[test]
-fn osc52_query_is_ignored() {
+fn osc52_query_does_not_write() {
     let mut term = Terminal::new(24, 80);
     term.process(b"\x1b]52;c;?\x07");
     assert!(term.pending_clipboard_write.is_none());
@@ -692,3 +709,138 @@ fn full_reset_clears_modify_other_keys() {
 
     assert_eq!(term.modify_other_keys, 0, "modifyOtherKeys should be 0 after full_reset");
 }
+
+// ── DECOM, DECAWM, IRM and LNM ──
+
+#[test]
+fn origin_mode_addresses_relative_to_scroll_region() {
+    let mut term = Terminal::new(10, 10);
+    term.process(b"\x1b[3;7r\x1b[?6h");
+    assert_eq!((term.cursor_row(), term.cursor_col()), (2, 0));
+
+    term.process(b"\x1b[2;4H");
+    assert_eq!((term.cursor_row(), term.cursor_col()), (3, 3));
+    term.process(b"\x1b[6n");
+    assert_eq!(term.drain_responses(), b"\x1b[2;4R".to_vec());
+
+    // Addressing is clamped to the region.
+    term.process(b"\x1b[20;1H");
+    assert_eq!(term.cursor_row(), 6);
+    term.process(b"\x1b[1d");
+    assert_eq!(term.cursor_row(), 2);
+
+    term.process(b"\x1b[?6l");
+    assert_eq!((term.cursor_row(), term.cursor_col()), (0, 0));
+    term.process(b"\x1b[9;1H");
+    assert_eq!(term.cursor_row(), 8);
+}
+
+#[test]
+fn cursor_up_and_down_stop_at_margins() {
+    let mut term = Terminal::new(10, 10);
+    term.process(b"\x1b[3;7r\x1b[5;1H\x1b[9A");
+    assert_eq!(term.cursor_row(), 2);
+    term.process(b"\x1b[9B");
+    assert_eq!(term.cursor_row(), 6);
+
+    // Outside the region only the screen edges limit the movement.
+    term.process(b"\x1b[9;1H\x1b[9B");
+    assert_eq!(term.cursor_row(), 9);
+}
+
+#[test]
+fn autowrap_off_overwrites_last_column() {
+    let mut term = Terminal::new(3, 5);
+    term.process(b"\x1b[?7labcdefg");
+    assert_eq!(get_char(&term, 0, 3), 'd');
+    assert_eq!(get_char(&term, 0, 4), 'g');
+    assert_eq!(get_char(&term, 1, 0), ' ');
+    assert_eq!((term.cursor_row(), term.cursor_col()), (0, 4));
+
+    term.process(b"\x1b[?7h\rabcdefg");
+    assert_eq!(get_char(&term, 1, 0), 'f');
+    assert!(term.screen.viewport_is_wrapped(0));
+}
+
+#[test]
+fn insert_mode_shifts_line_right() {
+    let mut term = Terminal::new(2, 6);
+    term.process(b"abcdef\r\x1b[4hXY");
+    assert_eq!(get_char(&term, 0, 0), 'X');
+    assert_eq!(get_char(&term, 0, 1), 'Y');
+    assert_eq!(get_char(&term, 0, 2), 'a');
+    assert_eq!(get_char(&term, 0, 5), 'd');
+
+    term.process(b"\x1b[4lZ");
+    assert_eq!(get_char(&term, 0, 2), 'Z');
+    assert_eq!(get_char(&term, 0, 3), 'b');
+}
+
+#[test]
+fn newline_mode_returns_carriage_on_line_feed() {
+    let mut term = Terminal::new(4, 10);
+    term.process(b"ab\ncd");
+    assert_eq!(get_char(&term, 1, 2), 'c');
+
+    term.process(b"\x1b[20h\nef");
+    assert_eq!(get_char(&term, 2, 0), 'e');
+    term.process(b"\x1b[20l");
+    assert!(!term.linefeed_mode);
+}
+
+#[test]
+fn decsc_decrc_save_origin_and_autowrap_modes() {
+    let mut term = Terminal::new(10, 10);
+    term.process(b"\x1b[2;8r\x1b[?6h\x1b[?7l\x1b[3;4H\x1b7");
+    term.process(b"\x1b[?6l\x1b[?7h\x1b[H");
+    assert!(!term.origin_mode);
+
+    term.process(b"\x1b8");
+    assert!(term.origin_mode);
+    assert!(!term.autowrap);
+    assert_eq!((term.cursor_row(), term.cursor_col()), (3, 3));
+}
+
+#[test]
+fn full_reset_restores_default_modes() {
+    let mut term = Terminal::new(4, 10);
+    term.process(b"\x1b[?6h\x1b[?7l\x1b[4;20h\x1bc");
+    assert!(!term.origin_mode);
+    assert!(term.autowrap);
+    assert!(!term.insert_mode);
+    assert!(!term.linefeed_mode);
+}
+
+// ── Character sets ──
+
+#[test]
+fn dec_special_graphics_draws_lines() {
+    let mut term = Terminal::new(2, 10);
+    term.process(b"\x1b(0lqk\x1b(Bq");
+    assert_eq!(get_char(&term, 0, 0), '┌');
+    assert_eq!(get_char(&term, 0, 1), '─');
+    assert_eq!(get_char(&term, 0, 2), '┐');
+    assert_eq!(get_char(&term, 0, 3), 'q');
+}
+
+#[test]
+fn shift_out_uses_g1_until_shift_in() {
+    let mut term = Terminal::new(2, 10);
+    term.process(b"\x1b)0x\x0ex\x0fx\x1b*A\x1bN##");
+    assert_eq!(get_char(&term, 0, 0), 'x');
+    assert_eq!(get_char(&term, 0, 1), '│');
+    assert_eq!(get_char(&term, 0, 2), 'x');
+    assert_eq!(get_char(&term, 0, 3), '£');
+    assert_eq!(get_char(&term, 0, 4), '#');
+}
+
+#[test]
+fn decsc_decrc_save_charsets() {
+    let mut term = Terminal::new(2, 10);
+    term.process(b"\x1b(0\x1b7\x1b(Bq\x1b8\x1b[2Gq");
+    assert_eq!(get_char(&term, 0, 0), 'q');
+    assert_eq!(get_char(&term, 0, 1), '─');
+
+    term.process(b"\x1bcq");
+    assert_eq!(get_char(&term, 0, 0), 'q');
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::core::KeyboardFlags;
 
 fn mods(ctrl: bool, shift: bool, alt: bool) -> ModifiersState {
     let mut state = ModifiersState::empty();
@@ -162,10 +163,44 @@ fn alt_character_is_escaped() {
 
 #[test]
 fn legacy_mouse_encoding_clamps_large_coordinates() {
-    let bytes = encode_mouse_event(0, 300, 500, true, false);
+    let bytes = encode_mouse_event(0, 300, 500, true, MouseEncoding::Default);
     assert_eq!(bytes, vec![0x1b, b'[', b'M', 32, 255, 255]);
 }
 
+#[test]
+fn utf8_mouse_encoding_extends_coordinates() {
+    let bytes = encode_mouse_event(0, 300, 5, true, MouseEncoding::Utf8);
+    let mut expected = b"\x1b[M ".to_vec();
+    expected.extend_from_slice("\u{14d}&".as_bytes());
+    assert_eq!(bytes, expected);
+    // Release reports button 3 and keeps the modifier bits.
+    let bytes = encode_mouse_event(16, 0, 0, false, MouseEncoding::Utf8);
+    assert_eq!(bytes, b"\x1b[M3!!");
+}
+
+#[test]
+fn urxvt_and_sgr_mouse_encodings_use_decimal_values() {
+    assert_eq!(encode_mouse_event(2, 299, 9, true, MouseEncoding::Urxvt), b"\x1b[34;300;10M");
+    assert_eq!(encode_mouse_event(2, 299, 9, false, MouseEncoding::Urxvt), b"\x1b[35;300;10M");
+    assert_eq!(encode_mouse_event(2, 299, 9, false, MouseEncoding::Sgr), b"\x1b[<2;300;10m");
+    assert_eq!(encode_mouse_event(66, 1234, 56, true, MouseEncoding::SgrPixels), b"\x1b[<66;1235;57M");
+}
+
+#[test]
+fn mouse_buttons_and_modifiers_map_to_xterm_codes() {
+    use winit::event::MouseButton;
+    assert_eq!(mouse_button_code(MouseButton::Left), Some(0));
+    assert_eq!(mouse_button_code(MouseButton::Right), Some(2));
+    assert_eq!(mouse_button_code(MouseButton::Back), Some(128));
+    assert_eq!(mouse_button_code(MouseButton::Forward), Some(129));
+    assert_eq!(mouse_button_code(MouseButton::Other(11)), Some(131));
+    assert_eq!(mouse_button_code(MouseButton::Other(12)), None);
+    assert_eq!(mouse_modifier_bits(mods(true, true, true)), 28);
+    assert_eq!(mouse_modifier_bits(mods(false, false, true)), 8);
+    // Back (button 8) with Control in the legacy encoding.
+    assert_eq!(encode_mouse_event(128 | 16, 0, 0, true, MouseEncoding::Default), vec![0x1b, b'[', b'M', 176, 33, 33]);
+}
+
 #[test]
 fn f1_through_f4_use_ss3_sequences() {
     use winit::keyboard::NamedKey;
@@ -251,3 +286,200 @@ fn shift_enter_without_modify_other_keys_still_produces_cr() {
     .expect("Shift+Enter without mode should be encoded");
     assert_eq!(bytes, vec![b'\r']);
 }
+
+fn kitty_event<'a>(
+    key: &'a Key,
+    unmodified_key: &'a Key,
+    code: KeyCode,
+    text: Option<&'a str>,
+    modifiers: ModifiersState,
+) -> KittyKeyEvent<'a> {
+    KittyKeyEvent {
+        key,
+        unmodified_key,
+        physical_key: PhysicalKey::Code(code),
+        text,
+        kind: KeyEventKind::Press,
+        modifiers,
+    }
+}
+
+fn flags(bits: u16) -> KeyboardFlags {
+    KeyboardFlags::from_bits(bits)
+}
+
+#[test]
+fn kitty_disambiguates_ctrl_i_from_tab() {
+    let key = Key::Character("i".into());
+    let ctrl_i = kitty_event(&key, &key, KeyCode::KeyI, None, mods(true, false, false));
+    assert_eq!(encode_kitty_key(&ctrl_i, flags(1)).unwrap(), b"\x1b[105;5u");
+
+    let tab = Key::Named(NamedKey::Tab);
+    let plain_tab = kitty_event(&tab, &tab, KeyCode::Tab, None, ModifiersState::empty());
+    assert_eq!(encode_kitty_key(&plain_tab, flags(1)).unwrap(), b"\t");
+    let shift_tab = kitty_event(&tab, &tab, KeyCode::Tab, None, mods(false, true, false));
+    assert_eq!(
+        encode_kitty_key(&shift_tab, flags(1)).unwrap(),
+        b"\x1b[9;2u"
+    );
+}
+
+#[test]
+fn kitty_plain_text_stays_text_without_report_all_keys() {
+    let key = Key::Character("A".into());
+    let unmodified = Key::Character("a".into());
+    let event = kitty_event(
+        &key,
+        &unmodified,
+        KeyCode::KeyA,
+        Some("A"),
+        mods(false, true, false),
+    );
+    assert_eq!(encode_kitty_key(&event, flags(1)).unwrap(), b"A");
+}
+
+#[test]
+fn kitty_escape_uses_csi_u_when_disambiguating() {
+    let key = Key::Named(NamedKey::Escape);
+    let event = kitty_event(&key, &key, KeyCode::Escape, None, ModifiersState::empty());
+    assert_eq!(encode_kitty_key(&event, flags(1)).unwrap(), b"\x1b[27u");
+}
+
+#[test]
+fn kitty_functional_keys_use_legacy_csi_forms() {
+    let up = Key::Named(NamedKey::ArrowUp);
+    let plain = kitty_event(&up, &up, KeyCode::ArrowUp, None, ModifiersState::empty());
+    assert_eq!(encode_kitty_key(&plain, flags(1)).unwrap(), b"\x1b[A");
+    let ctrl = kitty_event(&up, &up, KeyCode::ArrowUp, None, mods(true, false, false));
+    assert_eq!(encode_kitty_key(&ctrl, flags(1)).unwrap(), b"\x1b[1;5A");
+
+    let f3 = Key::Named(NamedKey::F3);
+    let event = kitty_event(&f3, &f3, KeyCode::F3, None, ModifiersState::empty());
+    assert_eq!(encode_kitty_key(&event, flags(1)).unwrap(), b"\x1b[13~");
+}
+
+#[test]
+fn kitty_releases_are_reported_only_with_event_types() {
+    let key = Key::Character("a".into());
+    let mut event = kitty_event(
+        &key,
+        &key,
+        KeyCode::KeyA,
+        Some("a"),
+        mods(true, false, false),
+    );
+    event.kind = KeyEventKind::Release;
+    assert_eq!(encode_kitty_key(&event, flags(1)), None);
+    assert_eq!(encode_kitty_key(&event, flags(3)).unwrap(), b"\x1b[97;5:3u");
+
+    event.kind = KeyEventKind::Repeat;
+    assert_eq!(encode_kitty_key(&event, flags(3)).unwrap(), b"\x1b[97;5:2u");
+    assert_eq!(encode_kitty_key(&event, flags(1)).unwrap(), b"\x1b[97;5u");
+
+    let enter = Key::Named(NamedKey::Enter);
+    let mut release = kitty_event(
+        &enter,
+        &enter,
+        KeyCode::Enter,
+        None,
+        ModifiersState::empty(),
+    );
+    release.kind = KeyEventKind::Release;
+    assert_eq!(encode_kitty_key(&release, flags(3)), None);
+    assert_eq!(
+        encode_kitty_key(&release, flags(11)).unwrap(),
+        b"\x1b[13;1:3u"
+    );
+}
+
+#[test]
+fn kitty_report_all_keys_encodes_text_and_modifiers() {
+    let key = Key::Character("a".into());
+    let event = kitty_event(
+        &key,
+        &key,
+        KeyCode::KeyA,
+        Some("a"),
+        ModifiersState::empty(),
+    );
+    assert_eq!(encode_kitty_key(&event, flags(8)).unwrap(), b"\x1b[97u");
+    assert_eq!(
+        encode_kitty_key(&event, flags(24)).unwrap(),
+        b"\x1b[97;1;97u"
+    );
+
+    let shift = Key::Named(NamedKey::Shift);
+    let mut press = kitty_event(
+        &shift,
+        &shift,
+        KeyCode::ShiftLeft,
+        None,
+        ModifiersState::empty(),
+    );
+    assert_eq!(encode_kitty_key(&press, flags(1)), None);
+    assert_eq!(
+        encode_kitty_key(&press, flags(8)).unwrap(),
+        b"\x1b[57441;2u"
+    );
+    press.kind = KeyEventKind::Release;
+    press.modifiers = mods(false, true, false);
+    assert_eq!(
+        encode_kitty_key(&press, flags(10)).unwrap(),
+        b"\x1b[57441;1:3u"
+    );
+}
+
+#[test]
+fn kitty_alternate_keys_report_shifted_and_base_layout_keys() {
+    let key = Key::Character("A".into());
+    let unmodified = Key::Character("a".into());
+    let event = kitty_event(
+        &key,
+        &unmodified,
+        KeyCode::KeyA,
+        Some("A"),
+        mods(true, true, false),
+    );
+    assert_eq!(
+        encode_kitty_key(&event, flags(5)).unwrap(),
+        b"\x1b[97:65;6u"
+    );
+
+    // Cyrillic layout: the physical key is US `f`.
+    let key = Key::Character("а".into());
+    let event = kitty_event(
+        &key,
+        &key,
+        KeyCode::KeyF,
+        Some("а"),
+        mods(true, false, false),
+    );
+    assert_eq!(
+        encode_kitty_key(&event, flags(5)).unwrap(),
+        b"\x1b[1072::102;5u"
+    );
+}
+
+#[test]
+fn kitty_keypad_enter_has_its_own_code() {
+    let enter = Key::Named(NamedKey::Enter);
+    let event = kitty_event(
+        &enter,
+        &enter,
+        KeyCode::NumpadEnter,
+        None,
+        ModifiersState::empty(),
+    );
+    assert_eq!(encode_kitty_key(&event, flags(1)).unwrap(), b"\x1b[57414u");
+}
+
+#[test]
+fn application_keypad_uses_ss3_sequences() {
+    use winit::keyboard::{KeyCode, PhysicalKey};
+    let keypad = |code| application_keypad_bytes(PhysicalKey::Code(code));
+    assert_eq!(keypad(KeyCode::Numpad0), Some(b"\x1bOp".to_vec()));
+    assert_eq!(keypad(KeyCode::Numpad9), Some(b"\x1bOy".to_vec()));
+    assert_eq!(keypad(KeyCode::NumpadEnter), Some(b"\x1bOM".to_vec()));
+    assert_eq!(keypad(KeyCode::NumpadAdd), Some(b"\x1bOk".to_vec()));
+    assert_eq!(keypad(KeyCode::Digit1), None);
+}